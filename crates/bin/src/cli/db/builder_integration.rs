@@ -0,0 +1,397 @@
+use alloy_primitives::Address;
+use brontes_types::{
+    db::{
+        builder::BuilderInfo,
+        traits::{DBWriter, LibmdbxReader},
+    },
+    mev::{Bundle, MevType},
+    FastHashMap, FastHashSet,
+};
+use clap::Parser;
+use comfy_table::{presets::ASCII_MARKDOWN, Table as ComfyTable};
+use itertools::Itertools;
+
+use crate::{
+    cli::{load_libmdbx, static_object},
+    runner::CliContext,
+};
+
+/// Bundles landing at or below this tx index count as top of block
+const TOP_OF_BLOCK_INDEX: u64 = 2;
+/// A bribe below this fraction of the median bribe for its mev type is sub
+/// market
+const SUB_MARKET_BRIBE_FRACTION: f64 = 0.5;
+
+#[derive(Debug, Parser)]
+pub struct BuilderIntegration {
+    /// Start Block
+    #[arg(long, short)]
+    pub start_block:    u64,
+    /// End Block (inclusive)
+    #[arg(long, short)]
+    pub end_block:      u64,
+    /// Minimum number of bundles a searcher needs in the range to be scored
+    #[arg(long, default_value_t = 20)]
+    pub min_bundles:    usize,
+    /// Minimum confidence for a builder / searcher pair to be reported
+    #[arg(long, default_value_t = 0.75)]
+    pub min_confidence: f64,
+    /// Adds the reported searchers to the searchers of their builder in the
+    /// `Builder` table. Builders without an entry in the table are skipped
+    #[arg(long, default_value_t = false)]
+    pub update:         bool,
+}
+
+impl BuilderIntegration {
+    pub async fn execute(self, brontes_db_path: String, ctx: CliContext) -> eyre::Result<()> {
+        let libmdbx = static_object(load_libmdbx(&ctx.task_executor, brontes_db_path)?);
+
+        let mut history = BuilderHistory::default();
+        for mev_block in libmdbx.try_fetch_mev_blocks(Some(self.start_block), self.end_block)? {
+            history.add_block(mev_block.block.builder_address, &mev_block.mev);
+        }
+
+        let pairs = history
+            .score_pairs(self.min_bundles)
+            .into_iter()
+            .filter(|pair| pair.confidence >= self.min_confidence)
+            .collect_vec();
+
+        let mut table = ComfyTable::new();
+        table.load_preset(ASCII_MARKDOWN);
+        table.set_header(vec![
+            "Builder",
+            "Searcher EOA",
+            "Bundles",
+            "Exclusivity",
+            "Builder Block Share",
+            "Sub Market Bribes",
+            "Top Of Block",
+            "Confidence",
+        ]);
+        for pair in &pairs {
+            let builder = libmdbx
+                .try_fetch_builder_info(pair.builder)?
+                .and_then(|info| info.name)
+                .unwrap_or_else(|| format!("{:?}", pair.builder));
+
+            table.add_row(vec![
+                builder,
+                format!("{:?}", pair.eoa),
+                format!("{}/{}", pair.bundles_with_builder, pair.total_bundles),
+                format!("{:.2}", pair.exclusivity),
+                format!("{:.2}", pair.builder_block_share),
+                format!("{:.2}", pair.sub_market_bribes),
+                format!("{:.2}", pair.top_of_block),
+                format!("{:.2}", pair.confidence),
+            ]);
+        }
+        println!("{table}");
+
+        if !self.update {
+            return Ok(())
+        }
+
+        for (builder, info) in updated_builder_infos(libmdbx, pairs)? {
+            libmdbx.write_builder_info(builder, info).await?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Adds the searchers of each pair to the info of their builder. Builders
+/// without an entry in the builder table are skipped, so unknown builders
+/// don't get an otherwise empty entry
+fn updated_builder_infos<DB: LibmdbxReader>(
+    db: &DB,
+    pairs: Vec<IntegrationEvidence>,
+) -> eyre::Result<Vec<(Address, BuilderInfo)>> {
+    let mut updates = Vec::new();
+    for (builder, pairs) in &pairs.into_iter().group_by(|pair| pair.builder) {
+        let Some(mut info) = db.try_fetch_builder_info(builder)? else {
+            tracing::warn!(?builder, "builder not in the builder table, skipping update");
+            continue
+        };
+        for pair in pairs {
+            if !info.searchers_eoas.contains(&pair.eoa) {
+                info.searchers_eoas.push(pair.eoa);
+            }
+            for contract in pair.contracts {
+                if !info.searchers_contracts.contains(&contract) {
+                    info.searchers_contracts.push(contract);
+                }
+            }
+        }
+        updates.push((builder, info));
+    }
+
+    Ok(updates)
+}
+
+#[derive(Debug, Default)]
+struct SearcherHistory {
+    contracts:     FastHashSet<Address>,
+    /// builder -> bundles landed in its blocks
+    bundles:       FastHashMap<Address, usize>,
+    /// builder -> bundles with revenue, the only ones a bribe share is known
+    /// for
+    priced:        FastHashMap<Address, usize>,
+    /// builder -> bundles with a sub market bribe
+    sub_market:    FastHashMap<Address, usize>,
+    /// builder -> bundles placed at the top of the block
+    top_of_block:  FastHashMap<Address, usize>,
+    total:         usize,
+    /// (builder, mev type, bribe share of revenue) of each bundle with revenue
+    bribe_samples: Vec<(Address, MevType, f64)>,
+}
+
+#[derive(Debug, Default)]
+struct BuilderHistory {
+    blocks_per_builder: FastHashMap<Address, usize>,
+    total_blocks:       usize,
+    searchers:          FastHashMap<Address, SearcherHistory>,
+    bribe_shares:       FastHashMap<MevType, Vec<f64>>,
+}
+
+impl BuilderHistory {
+    fn add_block(&mut self, builder: Address, bundles: &[Bundle]) {
+        *self.blocks_per_builder.entry(builder).or_default() += 1;
+        self.total_blocks += 1;
+
+        for bundle in bundles {
+            let header = &bundle.header;
+            let searcher = self.searchers.entry(header.eoa).or_default();
+            searcher.contracts.extend(header.mev_contract);
+            searcher.total += 1;
+            *searcher.bundles.entry(builder).or_default() += 1;
+            if header.tx_index <= TOP_OF_BLOCK_INDEX {
+                *searcher.top_of_block.entry(builder).or_default() += 1;
+            }
+
+            // without revenue, e.g. unpriced bundles, there is no market bribe to
+            // compare against
+            let revenue = header.profit_usd + header.bribe_usd;
+            if revenue <= 0.0 {
+                continue
+            }
+            let bribe_share = header.bribe_usd / revenue;
+            *searcher.priced.entry(builder).or_default() += 1;
+            searcher
+                .bribe_samples
+                .push((builder, header.mev_type, bribe_share));
+            self.bribe_shares
+                .entry(header.mev_type)
+                .or_default()
+                .push(bribe_share);
+        }
+    }
+
+    fn score_pairs(mut self, min_bundles: usize) -> Vec<IntegrationEvidence> {
+        let median_bribe_share = self
+            .bribe_shares
+            .iter_mut()
+            .map(|(mev_type, shares)| {
+                shares.sort_by(f64::total_cmp);
+                (*mev_type, shares[shares.len() / 2])
+            })
+            .collect::<FastHashMap<_, _>>();
+
+        let mut pairs = Vec::new();
+        for (eoa, mut searcher) in self.searchers {
+            if searcher.total < min_bundles {
+                continue
+            }
+
+            for (builder, mev_type, share) in &searcher.bribe_samples {
+                let median = median_bribe_share[mev_type];
+                if *share == 0.0 || *share < median * SUB_MARKET_BRIBE_FRACTION {
+                    *searcher.sub_market.entry(*builder).or_default() += 1;
+                }
+            }
+
+            // the builder the searcher lands most of its bundles with
+            let Some((&builder, &bundles_with_builder)) =
+                searcher.bundles.iter().max_by_key(|(_, count)| **count)
+            else {
+                continue
+            };
+
+            let exclusivity = bundles_with_builder as f64 / searcher.total as f64;
+            let builder_block_share =
+                self.blocks_per_builder[&builder] as f64 / self.total_blocks as f64;
+            // how much more the searcher favours the builder than the market does
+            let exclusivity_score = if builder_block_share < 1.0 {
+                ((exclusivity - builder_block_share) / (1.0 - builder_block_share)).clamp(0.0, 1.0)
+            } else {
+                0.0
+            };
+            let priced = searcher.priced.get(&builder).copied().unwrap_or_default();
+            let sub_market_bribes = if priced == 0 {
+                0.0
+            } else {
+                searcher
+                    .sub_market
+                    .get(&builder)
+                    .copied()
+                    .unwrap_or_default() as f64
+                    / priced as f64
+            };
+            let top_of_block = searcher
+                .top_of_block
+                .get(&builder)
+                .copied()
+                .unwrap_or_default() as f64
+                / bundles_with_builder as f64;
+
+            pairs.push(IntegrationEvidence {
+                builder,
+                eoa,
+                contracts: searcher.contracts.into_iter().collect(),
+                total_bundles: searcher.total,
+                bundles_with_builder,
+                exclusivity,
+                builder_block_share,
+                sub_market_bribes,
+                top_of_block,
+                confidence: 0.5 * exclusivity_score
+                    + 0.25 * sub_market_bribes
+                    + 0.25 * top_of_block,
+            });
+        }
+
+        pairs.sort_by(|a, b| {
+            a.builder
+                .cmp(&b.builder)
+                .then(b.confidence.total_cmp(&a.confidence))
+        });
+        pairs
+    }
+}
+
+/// Why a searcher is believed to be integrated with a builder
+#[derive(Debug)]
+struct IntegrationEvidence {
+    builder:              Address,
+    eoa:                  Address,
+    contracts:            Vec<Address>,
+    total_bundles:        usize,
+    bundles_with_builder: usize,
+    /// fraction of the searcher's bundles landing in the builder's blocks
+    exclusivity:          f64,
+    /// fraction of all blocks built by the builder
+    builder_block_share:  f64,
+    /// fraction of the bundles with revenue in the builder's blocks paying a
+    /// sub market or zero bribe
+    sub_market_bribes:    f64,
+    /// fraction of the bundles in the builder's blocks placed at the top of
+    /// the block
+    top_of_block:         f64,
+    confidence:           f64,
+}
+
+#[cfg(test)]
+mod tests {
+    use brontes_database::libmdbx::{tables::Builder, LibmdbxReadWriter};
+    use brontes_types::mev::BundleHeader;
+    use reth_db::DatabaseError;
+
+    use super::*;
+
+    fn bundle(eoa: Address, tx_index: u64, profit_usd: f64, bribe_usd: f64) -> Bundle {
+        Bundle {
+            header: BundleHeader {
+                eoa,
+                tx_index,
+                profit_usd,
+                bribe_usd,
+                mev_type: MevType::AtomicArb,
+                ..Default::default()
+            },
+            data:   Default::default(),
+        }
+    }
+
+    /// Builder `a` builds half the blocks, `searcher` lands all its bundles at
+    /// the top of `a`s blocks. The market searcher bribes half its revenue
+    fn history(searcher_bundle: impl Fn() -> Bundle) -> BuilderHistory {
+        let (a, b, market) =
+            (Address::with_last_byte(1), Address::with_last_byte(2), Address::with_last_byte(20));
+        let mut history = BuilderHistory::default();
+        for _ in 0..10 {
+            history.add_block(a, &[searcher_bundle()]);
+            history.add_block(b, &[bundle(market, 5, 50.0, 50.0)]);
+        }
+
+        history
+    }
+
+    #[test]
+    fn test_integrated_searcher() {
+        let searcher = Address::with_last_byte(10);
+        let pairs = history(|| bundle(searcher, 0, 100.0, 0.0)).score_pairs(5);
+        let pair = pairs.iter().find(|pair| pair.eoa == searcher).unwrap();
+
+        assert_eq!(pair.builder, Address::with_last_byte(1));
+        assert_eq!(pair.exclusivity, 1.0);
+        assert_eq!(pair.builder_block_share, 0.5);
+        assert_eq!(pair.sub_market_bribes, 1.0);
+        assert_eq!(pair.top_of_block, 1.0);
+        assert_eq!(pair.confidence, 1.0);
+    }
+
+    #[test]
+    fn test_zero_revenue_bundles_are_not_sub_market() {
+        let searcher = Address::with_last_byte(10);
+        let pairs = history(|| bundle(searcher, 0, 0.0, 0.0)).score_pairs(5);
+        let pair = pairs.iter().find(|pair| pair.eoa == searcher).unwrap();
+
+        assert_eq!(pair.sub_market_bribes, 0.0);
+        assert_eq!(pair.confidence, 0.75);
+    }
+
+    #[tokio::test]
+    async fn test_update_skips_unknown_builders() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = LibmdbxReadWriter::init_db_tests(dir.path()).unwrap();
+        let (known, unknown) = (Address::with_last_byte(1), Address::with_last_byte(2));
+        db.db
+            .update_db(|tx| {
+                tx.put::<Builder>(
+                    known,
+                    BuilderInfo { name: Some("known".to_string()), ..Default::default() },
+                )?;
+                Ok::<_, DatabaseError>(())
+            })
+            .unwrap()
+            .unwrap();
+
+        let pair = |builder, eoa| IntegrationEvidence {
+            builder,
+            eoa,
+            contracts: vec![Address::with_last_byte(30)],
+            total_bundles: 1,
+            bundles_with_builder: 1,
+            exclusivity: 1.0,
+            builder_block_share: 0.0,
+            sub_market_bribes: 1.0,
+            top_of_block: 1.0,
+            confidence: 1.0,
+        };
+        let updates = updated_builder_infos(
+            &db,
+            vec![
+                pair(known, Address::with_last_byte(10)),
+                pair(unknown, Address::with_last_byte(11)),
+            ],
+        )
+        .unwrap();
+
+        assert_eq!(updates.len(), 1);
+        let (builder, info) = &updates[0];
+        assert_eq!(*builder, known);
+        assert_eq!(info.name.as_deref(), Some("known"));
+        assert_eq!(info.searchers_eoas, vec![Address::with_last_byte(10)]);
+        assert_eq!(info.searchers_contracts, vec![Address::with_last_byte(30)]);
+    }
+}
@@ -0,0 +1,239 @@
+use alloy_primitives::Address;
+use brontes_types::{
+    constants::{ETH_ADDRESS, USDT_ADDRESS_STRING, WETH_ADDRESS},
+    db::{
+        cex_dex_inventory::{DailyCexDexInventory, SECONDS_PER_DAY},
+        metadata::Metadata,
+        traits::{DBWriter, LibmdbxReader},
+    },
+    init_thread_pools,
+    mev::{Bundle, MevType},
+    pair::Pair,
+    utils::ToFloatNearest,
+    FastHashMap, FastHashSet,
+};
+use clap::Parser;
+use comfy_table::{presets::ASCII_MARKDOWN, Table as ComfyTable};
+use itertools::Itertools;
+
+use crate::{
+    cli::{load_libmdbx, static_object},
+    runner::CliContext,
+};
+
+#[derive(Debug, Parser)]
+pub struct CexDexInventory {
+    /// Start Block
+    #[arg(long, short)]
+    pub start_block:   u64,
+    /// End Block (inclusive)
+    #[arg(long, short)]
+    pub end_block:     u64,
+    /// Quote asset the positions are valued in
+    #[arg(long, short, default_value = USDT_ADDRESS_STRING)]
+    pub quote_asset:   String,
+    /// Open positions are marked to the cex mid price every this many blocks,
+    /// on top of the blocks the searchers trade in
+    #[arg(long, default_value_t = 300)]
+    pub mark_interval: u64,
+}
+
+impl CexDexInventory {
+    pub async fn execute(self, brontes_db_path: String, ctx: CliContext) -> eyre::Result<()> {
+        init_thread_pools(10);
+        let libmdbx = static_object(load_libmdbx(&ctx.task_executor, brontes_db_path)?);
+        let quote_asset: Address = self.quote_asset.parse()?;
+
+        // the ledger carries on from the last day accounted before the range
+        let previous = libmdbx.try_fetch_cex_dex_inventories(None, u64::MAX)?;
+        if let Some(overlap) = previous
+            .iter()
+            .find(|day| day.last_block >= self.start_block)
+        {
+            eyre::bail!(
+                "the inventory of day {} already accounts for block {}, start after it",
+                overlap.day,
+                overlap.last_block
+            )
+        }
+        let mut current = previous.last().cloned();
+        if let Some(last) = &current {
+            if last.last_block + 1 != self.start_block {
+                tracing::warn!(
+                    last_block = last.last_block,
+                    start_block = self.start_block,
+                    "blocks between the last accounted block and the start are skipped"
+                );
+            }
+        }
+
+        let mut bundles: FastHashMap<u64, Vec<Bundle>> = FastHashMap::default();
+        // a searcher is cex-dex labelled through its eoa or its contract, so the
+        // lookup is cached per pair
+        let mut labels: FastHashMap<(Address, Option<Address>), bool> = FastHashMap::default();
+        for mev_block in libmdbx.try_fetch_mev_blocks(Some(self.start_block), self.end_block)? {
+            for bundle in mev_block.mev {
+                if !is_cex_dex(bundle.header.mev_type) {
+                    continue
+                }
+
+                let key = (bundle.header.eoa, bundle.header.mev_contract);
+                let labelled = match labels.get(&key) {
+                    Some(labelled) => *labelled,
+                    None => {
+                        let (eoa_info, contract_info) =
+                            libmdbx.try_fetch_searcher_info(key.0, key.1)?;
+                        let labelled = eoa_info
+                            .iter()
+                            .chain(contract_info.iter())
+                            .any(|info| info.config_labels.iter().copied().any(is_cex_dex));
+                        labels.insert(key, labelled);
+                        labelled
+                    }
+                };
+
+                if labelled {
+                    bundles
+                        .entry(bundle.header.block_number)
+                        .or_default()
+                        .push(bundle);
+                }
+            }
+        }
+
+        let mut days = vec![];
+        for block in self.start_block..=self.end_block {
+            let block_bundles = bundles.remove(&block).unwrap_or_default();
+            let mark = (block - self.start_block) % self.mark_interval.max(1) == 0
+                || block == self.end_block;
+            if block_bundles.is_empty() && !mark {
+                continue
+            }
+
+            let metadata = match libmdbx.get_metadata_no_dex_price(block, quote_asset) {
+                Ok(metadata) => metadata,
+                Err(e) => {
+                    tracing::error!(%block, err=%e, "failed to load metadata");
+                    continue
+                }
+            };
+
+            let day = metadata.block_timestamp / SECONDS_PER_DAY;
+            let inventory = match current.take() {
+                Some(inventory) if inventory.day == day => inventory,
+                Some(inventory) => {
+                    let next = inventory.roll_over(day);
+                    libmdbx.write_cex_dex_inventory(inventory.clone()).await?;
+                    days.push(inventory);
+                    next
+                }
+                None => DailyCexDexInventory { day, ..Default::default() },
+            };
+            let inventory = current.insert(inventory);
+
+            let pricer = Pricer { metadata: &metadata, quote_asset };
+            let mut seen_txs = FastHashSet::default();
+            for bundle in block_bundles {
+                let header = bundle.header;
+                let searcher = inventory.searcher_mut(header.mev_contract.unwrap_or(header.eoa));
+
+                if header.mev_type == MevType::CexDexTrades {
+                    searcher.markout_pnl_usd += header.profit_usd;
+                }
+
+                for tx in header.balance_deltas {
+                    // a transaction can be flagged by more than one cex-dex inspector
+                    if !seen_txs.insert(tx.tx_hash) {
+                        continue
+                    }
+                    searcher.tx_count += 1;
+
+                    for deltas in tx.address_deltas.into_iter().filter(|deltas| {
+                        deltas.address == header.eoa || Some(deltas.address) == header.mev_contract
+                    }) {
+                        for delta in deltas.token_deltas {
+                            let Some(price) = pricer.price(delta.token.address).or_else(|| {
+                                (delta.amount != 0.0).then(|| delta.usd_value / delta.amount)
+                            }) else {
+                                continue
+                            };
+                            searcher.apply_delta(delta.token.address, delta.amount, price.abs());
+                        }
+                    }
+                }
+            }
+
+            for searcher in &mut inventory.searchers {
+                let tokens = searcher.positions.iter().map(|p| p.token).collect_vec();
+                for token in tokens {
+                    if let Some(price) = pricer.price(token) {
+                        searcher.mark(token, price);
+                    }
+                }
+            }
+            inventory.last_block = block;
+        }
+
+        if let Some(inventory) = current {
+            libmdbx.write_cex_dex_inventory(inventory.clone()).await?;
+            days.push(inventory);
+        }
+
+        let mut table = ComfyTable::new();
+        table.load_preset(ASCII_MARKDOWN);
+        table.set_header(vec![
+            "Epoch Day",
+            "Last Block",
+            "Searcher",
+            "Txs",
+            "Markout PnL",
+            "Realized PnL",
+            "Unrealized PnL",
+            "Open Positions",
+        ]);
+        for day in &days {
+            for searcher in &day.searchers {
+                table.add_row(vec![
+                    day.day.to_string(),
+                    day.last_block.to_string(),
+                    format!("{:?}", searcher.searcher),
+                    searcher.tx_count.to_string(),
+                    format!("${:.2}", searcher.markout_pnl_usd),
+                    format!("${:.2}", searcher.realized_pnl_usd),
+                    format!("${:.2}", searcher.unrealized_pnl_usd),
+                    searcher.positions.len().to_string(),
+                ]);
+            }
+        }
+        println!("{table}");
+
+        Ok(())
+    }
+}
+
+fn is_cex_dex(mev_type: MevType) -> bool {
+    matches!(
+        mev_type,
+        MevType::CexDexTrades | MevType::CexDexQuotes | MevType::CexDexRfq | MevType::JitCexDex
+    )
+}
+
+/// Prices tokens at the cex mid price of the block
+struct Pricer<'a> {
+    metadata:    &'a Metadata,
+    quote_asset: Address,
+}
+
+impl Pricer<'_> {
+    fn price(&self, token: Address) -> Option<f64> {
+        let token = if token == ETH_ADDRESS { WETH_ADDRESS } else { token };
+
+        self.metadata
+            .cex_quotes
+            .get_mid_price(
+                &Pair(token, self.quote_asset),
+                self.metadata.microseconds_block_timestamp(),
+            )
+            .map(|price| price.to_float())
+    }
+}
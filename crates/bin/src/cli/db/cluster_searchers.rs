@@ -0,0 +1,596 @@
+use std::path::PathBuf;
+
+use alloy_primitives::Address;
+use brontes_database::libmdbx::LibmdbxReadWriter;
+use brontes_types::{
+    db::{
+        searcher::{Fund, SearcherInfo},
+        traits::{DBWriter, LibmdbxReader},
+    },
+    mev::Bundle,
+    structured_trace::{TraceActions, TxTrace},
+    FastHashMap, FastHashSet,
+};
+use clap::Parser;
+use comfy_table::{presets::ASCII_MARKDOWN, Table as ComfyTable};
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    cli::{confirm, load_libmdbx, static_object},
+    runner::CliContext,
+};
+
+#[derive(Debug, Parser)]
+pub struct ClusterSearchers {
+    /// Start Block
+    #[arg(long, short)]
+    pub start_block:    Option<u64>,
+    /// End Block (inclusive)
+    #[arg(long, short)]
+    pub end_block:      Option<u64>,
+    /// File the proposed clusters are written to, and read from when applying
+    #[arg(long, default_value = "searcher_clusters.json")]
+    pub proposals:      PathBuf,
+    /// Minimum confidence for a link between two searchers to be used
+    #[arg(long, default_value_t = 0.7)]
+    pub min_confidence: f64,
+    /// Funders, payers and deployers linking more addresses than this are
+    /// assumed to be shared infrastructure (exchanges, routers, factories)
+    #[arg(long, default_value_t = 20)]
+    pub max_hub_size:   usize,
+    /// Merge the reviewed proposals file into the searcher tables instead of
+    /// generating new proposals
+    #[arg(long, default_value_t = false)]
+    pub apply:          bool,
+    /// Apply the proposals without asking for confirmation
+    #[arg(long, default_value_t = false)]
+    pub yes:            bool,
+}
+
+impl ClusterSearchers {
+    pub async fn execute(self, brontes_db_path: String, ctx: CliContext) -> eyre::Result<()> {
+        let libmdbx = static_object(load_libmdbx(&ctx.task_executor, brontes_db_path)?);
+
+        if self.apply {
+            let proposals: Vec<ClusterProposal> =
+                serde_json::from_str(&std::fs::read_to_string(&self.proposals)?)?;
+            let proposals = proposals
+                .into_iter()
+                .filter(|proposal| proposal.confidence >= self.min_confidence)
+                .collect_vec();
+
+            let updates = proposed_updates(libmdbx, &proposals)?;
+            if updates.is_empty() {
+                println!("the searcher tables already contain every proposal");
+                return Ok(())
+            }
+            println!("{}", review_diff(&updates));
+            if !self.yes && !confirm(&format!("write {} searcher updates?", updates.len()))? {
+                println!("aborted, nothing was written");
+                return Ok(())
+            }
+
+            for update in updates {
+                match update.kind {
+                    SearcherKind::Eoa => {
+                        libmdbx
+                            .write_searcher_eoa_info(update.address, update.proposed)
+                            .await?
+                    }
+                    SearcherKind::Contract => {
+                        libmdbx
+                            .write_searcher_contract_info(update.address, update.proposed)
+                            .await?
+                    }
+                }
+            }
+
+            return Ok(())
+        }
+
+        let (Some(start_block), Some(end_block)) = (self.start_block, self.end_block) else {
+            eyre::bail!("--start-block and --end-block are required to generate proposals");
+        };
+
+        let mut signals = SearcherSignals::default();
+        for mev_block in libmdbx.try_fetch_mev_blocks(Some(start_block), end_block)? {
+            signals
+                .coinbases
+                .insert(mev_block.block.block_number, mev_block.block.builder_address);
+            signals.add_bundles(&mev_block.mev);
+        }
+        for block in start_block..=end_block {
+            let Ok(traces) = libmdbx.load_trace(block) else {
+                tracing::warn!(block, "no traces found, skipping block");
+                continue
+            };
+            let coinbase = signals.coinbases.get(&block).copied();
+            traces
+                .iter()
+                .for_each(|trace| signals.add_tx_trace(trace, coinbase));
+        }
+
+        let proposals = signals.into_proposals(libmdbx, self.min_confidence, self.max_hub_size)?;
+        std::fs::write(&self.proposals, serde_json::to_string_pretty(&proposals)?)?;
+
+        println!("{}", review_diff(&proposed_updates(libmdbx, &proposals)?));
+        println!(
+            "wrote {} proposed clusters to {}, review them and rerun with --apply to merge",
+            proposals.len(),
+            self.proposals.display()
+        );
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum SearcherKind {
+    Eoa,
+    Contract,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ClusterSignal {
+    /// EOAs calling the same mev contract, and the contract itself
+    SharedMevContract,
+    /// contracts deployed by the same deployer, or by a searcher EOA
+    Deployer,
+    /// coinbase payments made through the same payer
+    CoinbasePayer,
+    /// EOAs funded by the same address
+    SharedFunding,
+}
+
+impl ClusterSignal {
+    const fn confidence(&self) -> f64 {
+        match self {
+            ClusterSignal::SharedMevContract => 0.9,
+            ClusterSignal::Deployer => 0.8,
+            ClusterSignal::CoinbasePayer => 0.6,
+            ClusterSignal::SharedFunding => 0.5,
+        }
+    }
+}
+
+/// An address that links a group of searcher addresses through one signal
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClusterEvidence {
+    pub signal:  ClusterSignal,
+    pub hub:     Address,
+    pub members: Vec<Address>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClusterProposal {
+    /// confidence of the weakest link holding the cluster together
+    pub confidence: f64,
+    /// fund already assigned to one of the members, if they all agree
+    pub fund:       Fund,
+    pub eoas:       Vec<Address>,
+    pub contracts:  Vec<Address>,
+    pub evidence:   Vec<ClusterEvidence>,
+}
+
+impl ClusterProposal {
+    fn members(&self) -> impl Iterator<Item = (Address, SearcherKind)> + '_ {
+        self.eoas.iter().map(|eoa| (*eoa, SearcherKind::Eoa)).chain(
+            self.contracts
+                .iter()
+                .map(|contract| (*contract, SearcherKind::Contract)),
+        )
+    }
+}
+
+/// Links between searcher addresses collected from the mev blocks and traces
+#[derive(Debug, Default)]
+struct SearcherSignals {
+    eoas:      FastHashSet<Address>,
+    contracts: FastHashSet<Address>,
+    coinbases: FastHashMap<u64, Address>,
+    /// hub -> searcher addresses it links, per signal
+    hubs:      FastHashMap<(ClusterSignal, Address), FastHashSet<Address>>,
+}
+
+impl SearcherSignals {
+    fn add_bundles(&mut self, bundles: &[Bundle]) {
+        for bundle in bundles {
+            self.eoas.insert(bundle.header.eoa);
+
+            if let Some(contract) = bundle.header.mev_contract {
+                self.contracts.insert(contract);
+                let members = self
+                    .hubs
+                    .entry((ClusterSignal::SharedMevContract, contract))
+                    .or_default();
+                members.insert(contract);
+                members.insert(bundle.header.eoa);
+            }
+        }
+    }
+
+    fn add_tx_trace(&mut self, tx: &TxTrace, coinbase: Option<Address>) {
+        let Some(sender) = tx.trace.first().map(|root| root.get_from_addr()) else { return };
+
+        for trace in &tx.trace {
+            if trace.is_create() {
+                let created = trace.get_create_output();
+                if !self.contracts.contains(&created) {
+                    continue
+                }
+                // the factory and the EOA that sent the deployment both link the
+                // contract
+                for deployer in [trace.get_from_addr(), sender] {
+                    let members = self
+                        .hubs
+                        .entry((ClusterSignal::Deployer, deployer))
+                        .or_default();
+                    members.insert(created);
+                    if self.eoas.contains(&deployer) {
+                        members.insert(deployer);
+                    }
+                }
+                continue
+            }
+
+            if trace.is_delegate_call() || trace.get_msg_value().is_zero() {
+                continue
+            }
+            let (from, to) = (trace.get_from_addr(), trace.get_to_address());
+
+            if Some(to) == coinbase && self.eoas.contains(&sender) {
+                self.hubs
+                    .entry((ClusterSignal::CoinbasePayer, from))
+                    .or_default()
+                    .insert(sender);
+            } else if self.eoas.contains(&to) && from != to && !self.eoas.contains(&from) {
+                self.hubs
+                    .entry((ClusterSignal::SharedFunding, from))
+                    .or_default()
+                    .insert(to);
+            }
+        }
+    }
+
+    fn into_proposals<DB: LibmdbxReader>(
+        self,
+        db: &DB,
+        min_confidence: f64,
+        max_hub_size: usize,
+    ) -> eyre::Result<Vec<ClusterProposal>> {
+        let evidence = self
+            .hubs
+            .into_iter()
+            .filter(|(_, members)| members.len() > 1 && members.len() <= max_hub_size)
+            .filter(|((signal, hub), _)| {
+                // known entities such as exchanges fund and pay for many unrelated
+                // searchers
+                *signal == ClusterSignal::SharedMevContract
+                    || !db
+                        .try_fetch_address_metadata(*hub)
+                        .ok()
+                        .flatten()
+                        .is_some_and(|metadata| metadata.entity_name.is_some())
+            })
+            .map(|((signal, hub), members)| ClusterEvidence {
+                signal,
+                hub,
+                members: members.into_iter().sorted().collect(),
+            })
+            .collect_vec();
+
+        // combine the signals linking the same two addresses. every member of a hub
+        // is linked to every other member, members are sorted so each pair has a
+        // single key
+        let mut links: FastHashMap<(Address, Address), f64> = FastHashMap::default();
+        for ev in &evidence {
+            for (a, b) in ev.members.iter().tuple_combinations() {
+                let miss = links.entry((*a, *b)).or_insert(1.0);
+                *miss *= 1.0 - ev.signal.confidence();
+            }
+        }
+
+        let mut clusters = UnionFind::default();
+        links
+            .into_iter()
+            .map(|(pair, miss)| (pair, 1.0 - miss))
+            .filter(|(_, confidence)| *confidence >= min_confidence)
+            .sorted_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)))
+            .for_each(|((a, b), confidence)| clusters.union(a, b, confidence));
+
+        let mut proposals = Vec::new();
+        for (members, confidence) in clusters.into_clusters() {
+            let eoas = members
+                .iter()
+                .copied()
+                .filter(|member| self.eoas.contains(member))
+                .collect_vec();
+            let contracts = members
+                .iter()
+                .copied()
+                .filter(|member| self.contracts.contains(member))
+                .collect_vec();
+            if eoas.len() + contracts.len() < 2 {
+                continue
+            }
+
+            let mut funds = FastHashSet::default();
+            for member in &eoas {
+                if let Some(info) = db.try_fetch_searcher_eoa_info(*member)? {
+                    funds.insert(info.fund);
+                }
+            }
+            for member in &contracts {
+                if let Some(info) = db.try_fetch_searcher_contract_info(*member)? {
+                    funds.insert(info.fund);
+                }
+            }
+            funds.remove(&Fund::None);
+            let fund =
+                if funds.len() == 1 { funds.into_iter().next().unwrap() } else { Fund::None };
+
+            let evidence = evidence
+                .iter()
+                .filter(|ev| ev.members.iter().any(|member| members.contains(member)))
+                .cloned()
+                .collect_vec();
+
+            proposals.push(ClusterProposal { confidence, fund, eoas, contracts, evidence });
+        }
+
+        proposals.sort_by(|a, b| b.confidence.total_cmp(&a.confidence));
+        Ok(proposals)
+    }
+}
+
+#[derive(Debug, Default)]
+struct UnionFind {
+    parent:     FastHashMap<Address, Address>,
+    /// weakest link used to join each cluster, keyed by root
+    confidence: FastHashMap<Address, f64>,
+}
+
+impl UnionFind {
+    fn find(&mut self, address: Address) -> Address {
+        let parent = *self.parent.entry(address).or_insert(address);
+        if parent == address {
+            return address
+        }
+        let root = self.find(parent);
+        self.parent.insert(address, root);
+        root
+    }
+
+    fn union(&mut self, a: Address, b: Address, confidence: f64) {
+        let (root_a, root_b) = (self.find(a), self.find(b));
+        if root_a == root_b {
+            return
+        }
+        let joined = [self.confidence.remove(&root_a), self.confidence.remove(&root_b)]
+            .into_iter()
+            .flatten()
+            .fold(confidence, f64::min);
+
+        self.parent.insert(root_b, root_a);
+        self.confidence.insert(root_a, joined);
+    }
+
+    fn into_clusters(mut self) -> Vec<(FastHashSet<Address>, f64)> {
+        let mut clusters: FastHashMap<Address, FastHashSet<Address>> = FastHashMap::default();
+        for address in self.parent.keys().copied().collect_vec() {
+            let root = self.find(address);
+            clusters.entry(root).or_default().insert(address);
+        }
+
+        clusters
+            .into_iter()
+            .map(|(root, members)| (members, self.confidence[&root]))
+            .collect()
+    }
+}
+
+struct SearcherUpdate {
+    address:  Address,
+    kind:     SearcherKind,
+    current:  Option<SearcherInfo>,
+    proposed: SearcherInfo,
+}
+
+/// Merges the proposals into the current searcher info, keeping only the
+/// entries that change
+fn proposed_updates(
+    db: &LibmdbxReadWriter,
+    proposals: &[ClusterProposal],
+) -> eyre::Result<Vec<SearcherUpdate>> {
+    let mut updates = Vec::new();
+
+    for proposal in proposals {
+        let members = proposal.members().collect_vec();
+        for &(address, kind) in &members {
+            let current = match kind {
+                SearcherKind::Eoa => db.try_fetch_searcher_eoa_info(address)?,
+                SearcherKind::Contract => db.try_fetch_searcher_contract_info(address)?,
+            };
+            let mut proposed = current.clone().unwrap_or_default();
+
+            for (sibling, _) in &members {
+                if *sibling != address && !proposed.sibling_searchers.contains(sibling) {
+                    proposed.sibling_searchers.push(*sibling);
+                }
+            }
+            if proposed.fund == Fund::None {
+                proposed.fund = proposal.fund;
+            }
+
+            if current.as_ref() != Some(&proposed) {
+                updates.push(SearcherUpdate { address, kind, current, proposed });
+            }
+        }
+    }
+
+    Ok(updates)
+}
+
+fn review_diff(updates: &[SearcherUpdate]) -> ComfyTable {
+    let mut table = ComfyTable::new();
+    table.load_preset(ASCII_MARKDOWN);
+    table.set_header(vec!["Address", "Kind", "Fund", "Added Siblings"]);
+
+    for update in updates {
+        let current_fund = update
+            .current
+            .as_ref()
+            .map(|info| info.fund)
+            .unwrap_or_default();
+        let fund = if current_fund == update.proposed.fund {
+            current_fund.to_string()
+        } else {
+            format!("{current_fund} -> {}", update.proposed.fund)
+        };
+        let added = update
+            .proposed
+            .sibling_searchers
+            .iter()
+            .filter(|sibling| {
+                !update
+                    .current
+                    .as_ref()
+                    .is_some_and(|info| info.sibling_searchers.contains(sibling))
+            })
+            .map(|sibling| format!("{sibling:?}"))
+            .join("\n");
+
+        table.add_row(vec![
+            format!("{:?}", update.address),
+            format!("{:?}", update.kind),
+            fund,
+            added,
+        ]);
+    }
+
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use brontes_database::libmdbx::tables::SearcherEOAs;
+    use brontes_types::mev::BundleHeader;
+    use reth_db::DatabaseError;
+
+    use super::*;
+
+    fn addr(byte: u8) -> Address {
+        Address::with_last_byte(byte)
+    }
+
+    fn db_with_searchers(dir: &Path, searchers: &[(Address, SearcherInfo)]) -> LibmdbxReadWriter {
+        let db = LibmdbxReadWriter::init_db_tests(dir).unwrap();
+        db.db
+            .update_db(|tx| {
+                for (address, info) in searchers {
+                    tx.put::<SearcherEOAs>(*address, info.clone())?;
+                }
+                Ok::<_, DatabaseError>(())
+            })
+            .unwrap()
+            .unwrap();
+
+        db
+    }
+
+    fn signals(eoas: &[Address], hubs: &[(ClusterSignal, Address, &[Address])]) -> SearcherSignals {
+        let mut signals = SearcherSignals::default();
+        signals.eoas.extend(eoas);
+        for (signal, hub, members) in hubs {
+            signals
+                .hubs
+                .entry((*signal, *hub))
+                .or_default()
+                .extend(members.iter().copied());
+        }
+
+        signals
+    }
+
+    #[tokio::test]
+    async fn test_signals_link_every_member_of_a_hub() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = db_with_searchers(dir.path(), &[]);
+        let (a, b, c) = (addr(1), addr(2), addr(3));
+
+        // a and c are only linked strongly enough when the funding link between
+        // them is counted, which isn't the case if only neighbours in the sorted
+        // member list are linked
+        let signals = signals(
+            &[a, b, c],
+            &[
+                (ClusterSignal::SharedFunding, addr(100), &[a, b, c]),
+                (ClusterSignal::CoinbasePayer, addr(101), &[a, c]),
+            ],
+        );
+        let proposals = signals.into_proposals(&db, 0.7, 20).unwrap();
+
+        assert_eq!(proposals.len(), 1);
+        assert_eq!(proposals[0].eoas.iter().copied().sorted().collect_vec(), vec![a, c]);
+        assert!((proposals[0].confidence - 0.8).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn test_hubs_above_max_size_are_ignored() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = db_with_searchers(dir.path(), &[]);
+        let members = (1..=5).map(addr).collect_vec();
+
+        let signals = signals(&members, &[(ClusterSignal::SharedMevContract, addr(100), &members)]);
+        assert!(signals.into_proposals(&db, 0.7, 4).unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_shared_mev_contract_proposal() {
+        let dir = tempfile::tempdir().unwrap();
+        let (eoa_a, eoa_b, contract) = (addr(1), addr(2), addr(3));
+        let db = db_with_searchers(
+            dir.path(),
+            &[(eoa_a, SearcherInfo { fund: Fund::Wintermute, ..Default::default() })],
+        );
+
+        let mut signals = SearcherSignals::default();
+        let bundles = [eoa_a, eoa_b]
+            .into_iter()
+            .map(|eoa| Bundle {
+                header: BundleHeader { eoa, mev_contract: Some(contract), ..Default::default() },
+                data:   Default::default(),
+            })
+            .collect_vec();
+        signals.add_bundles(&bundles);
+
+        let proposals = signals.into_proposals(&db, 0.7, 20).unwrap();
+        assert_eq!(proposals.len(), 1);
+        let proposal = &proposals[0];
+        assert_eq!(proposal.eoas.iter().copied().sorted().collect_vec(), vec![eoa_a, eoa_b]);
+        assert_eq!(proposal.contracts, vec![contract]);
+        assert_eq!(proposal.fund, Fund::Wintermute);
+
+        let updates = proposed_updates(&db, &proposals).unwrap();
+        assert_eq!(updates.len(), 3);
+        let update_b = updates
+            .iter()
+            .find(|update| update.address == eoa_b)
+            .unwrap();
+        assert!(update_b.current.is_none());
+        assert_eq!(update_b.proposed.fund, Fund::Wintermute);
+        assert_eq!(
+            update_b
+                .proposed
+                .sibling_searchers
+                .iter()
+                .copied()
+                .sorted()
+                .collect_vec(),
+            vec![eoa_a, contract]
+        );
+    }
+}
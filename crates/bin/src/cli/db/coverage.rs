@@ -0,0 +1,402 @@
+use std::path::Path;
+
+use alloy_primitives::{hex, Address, B256};
+use brontes_classifier::Classifier;
+use brontes_core::decoding::Parser as DParser;
+use brontes_database::libmdbx::LibmdbxReadWriter;
+use brontes_metrics::ParserMetricsListener;
+use brontes_types::{
+    constants::USDT_ADDRESS_STRING,
+    db::{dex::BlockPrice, traits::LibmdbxReader},
+    init_thread_pools,
+    normalized_actions::{Action, NormalizedAction},
+    pair::Pair,
+    tree::{BlockTree, Node, NodeData},
+    FastHashMap, FastHashSet, ToFloatNearest, UnboundedYapperReceiver,
+};
+use clap::{Parser, ValueEnum};
+use comfy_table::{presets::ASCII_MARKDOWN, Table as ComfyTable};
+use futures::StreamExt;
+use itertools::Itertools;
+use tokio::sync::mpsc::unbounded_channel;
+
+use crate::{
+    cli::{determine_max_tasks, get_env_vars, get_tracing_provider, load_libmdbx, static_object},
+    runner::CliContext,
+};
+
+#[derive(Debug, Parser)]
+pub struct Coverage {
+    /// Start Block
+    #[arg(long, short)]
+    pub start_block: u64,
+    /// End Block (inclusive)
+    #[arg(long, short)]
+    pub end_block:   u64,
+    /// Number of entries to display per ranking
+    #[arg(long, default_value_t = 50)]
+    pub top:         usize,
+    /// Metric used to rank the unclassified calls
+    #[arg(long, value_enum, default_value_t = SortBy::Calls)]
+    pub sort_by:     SortBy,
+    /// Quote asset the dex prices in the db are denominated in, used to value
+    /// the erc20 transfers
+    #[arg(long, short, default_value = USDT_ADDRESS_STRING)]
+    pub quote_asset: String,
+    /// Max number of blocks to classify concurrently
+    #[arg(long, short)]
+    pub max_tasks:   Option<u64>,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum SortBy {
+    Calls,
+    Value,
+    PossibleMev,
+}
+
+impl Coverage {
+    pub async fn execute(self, brontes_db_path: String, ctx: CliContext) -> eyre::Result<()> {
+        let db_path = get_env_vars()?;
+        let quote_asset: Address = self.quote_asset.parse()?;
+
+        let max_tasks = determine_max_tasks(self.max_tasks);
+        init_thread_pools(max_tasks as usize);
+
+        let (metrics_tx, metrics_rx) = unbounded_channel();
+        let metrics_listener = ParserMetricsListener::new(UnboundedYapperReceiver::new(
+            metrics_rx,
+            10_000,
+            "metrics".to_string(),
+        ));
+        ctx.task_executor
+            .spawn_critical("metrics", metrics_listener);
+
+        let libmdbx = static_object(load_libmdbx(&ctx.task_executor, brontes_db_path)?);
+        let tracer =
+            get_tracing_provider(Path::new(&db_path), max_tasks, ctx.task_executor.clone());
+        let parser = static_object(DParser::new(metrics_tx, libmdbx, tracer.clone()).await);
+
+        // the classifier sends pool updates for pricing which we don't run here
+        let (pricing_tx, mut pricing_rx) = unbounded_channel();
+        tokio::spawn(async move { while pricing_rx.recv().await.is_some() {} });
+        let classifier = static_object(
+            Classifier::new(libmdbx, pricing_tx, parser.get_tracer()).without_persisting_pools(),
+        );
+
+        let possible_mev = libmdbx
+            .try_fetch_mev_blocks(Some(self.start_block), self.end_block)?
+            .into_iter()
+            .flat_map(|mev_block| mev_block.block.possible_mev.0)
+            .map(|possible| possible.tx_hash)
+            .collect::<FastHashSet<_>>();
+
+        let mut report = CoverageReport::default();
+        let mut trees = futures::stream::iter(self.start_block..=self.end_block)
+            .map(|block| async move {
+                let (_, traces, header) = parser.execute(block, 0, None).await?;
+                Some(classifier.build_block_tree(traces, header, false).await)
+            })
+            .buffer_unordered(max_tasks as usize);
+
+        while let Some(tree) = trees.next().await {
+            let Some(tree) = tree else { continue };
+            report.add_block(&tree, libmdbx, quote_asset, &possible_mev);
+        }
+
+        println!("{}", report.address_table(libmdbx, self.sort_by, self.top));
+        println!("{}", report.selector_table(self.sort_by, self.top));
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Default)]
+struct CoverageStats {
+    calls:        u64,
+    erc20_value:  f64,
+    txes:         FastHashSet<B256>,
+    possible_mev: FastHashSet<B256>,
+    callees:      FastHashSet<Address>,
+}
+
+impl CoverageStats {
+    fn sort_key(&self, sort_by: SortBy) -> f64 {
+        match sort_by {
+            SortBy::Calls => self.calls as f64,
+            SortBy::Value => self.erc20_value,
+            SortBy::PossibleMev => self.possible_mev.len() as f64,
+        }
+    }
+}
+
+/// Unclassified calls that emit logs, grouped by callee address and by
+/// function selector
+#[derive(Debug, Default)]
+struct CoverageReport {
+    by_address:  FastHashMap<Address, CoverageStats>,
+    by_selector: FastHashMap<String, CoverageStats>,
+}
+
+impl CoverageReport {
+    fn add_block(
+        &mut self,
+        tree: &BlockTree<Action>,
+        libmdbx: &LibmdbxReadWriter,
+        quote_asset: Address,
+        possible_mev: &FastHashSet<B256>,
+    ) {
+        let dex_quotes = libmdbx.get_dex_quotes(tree.header.number).ok();
+
+        for root in &tree.tx_roots {
+            let mut unclassified = Vec::new();
+            collect_unclassified(&root.head, &root.data_store, false, &mut unclassified);
+
+            for (action, node, nested) in unclassified {
+                let callee = action.get_to_address();
+                let selector = action
+                    .get_calldata()
+                    .filter(|calldata| calldata.len() >= 4)
+                    .map(|calldata| format!("0x{}", hex::encode(&calldata[..4])))
+                    .unwrap_or_else(|| "fallback".to_string());
+
+                // the transfers below a nested call are already counted by its
+                // outermost unclassified ancestor
+                let erc20_value = (!nested)
+                    .then(|| node.get_all_sub_actions_exclusive())
+                    .unwrap_or_default()
+                    .into_iter()
+                    .filter_map(|idx| root.data_store.get_ref(idx))
+                    .flatten()
+                    .filter_map(|action| match action {
+                        Action::Transfer(transfer) => {
+                            if transfer.token.address == quote_asset {
+                                return Some(transfer.amount.clone().to_float())
+                            }
+                            let price = dex_quotes.as_ref()?.price_for_block(
+                                Pair(transfer.token.address, quote_asset),
+                                BlockPrice::Average,
+                            )?;
+                            Some((&transfer.amount * price).to_float())
+                        }
+                        _ => None,
+                    })
+                    .sum::<f64>();
+
+                let is_possible_mev = possible_mev.contains(&root.tx_hash);
+                for stats in [
+                    self.by_address.entry(callee).or_default(),
+                    self.by_selector.entry(selector).or_default(),
+                ] {
+                    stats.calls += 1;
+                    stats.erc20_value += erc20_value;
+                    stats.txes.insert(root.tx_hash);
+                    stats.callees.insert(callee);
+                    if is_possible_mev {
+                        stats.possible_mev.insert(root.tx_hash);
+                    }
+                }
+            }
+        }
+    }
+
+    fn address_table(
+        &self,
+        libmdbx: &LibmdbxReadWriter,
+        sort_by: SortBy,
+        top: usize,
+    ) -> ComfyTable {
+        let mut table = ComfyTable::new();
+        table.load_preset(ASCII_MARKDOWN);
+        table.set_header([
+            "Address",
+            "Label",
+            "Calls",
+            "Txes",
+            "ERC20 Value Moved",
+            "Possible MEV Txes",
+        ]);
+
+        for (address, stats) in ranked(&self.by_address, sort_by, top) {
+            let label = libmdbx
+                .try_fetch_address_metadata(*address)
+                .ok()
+                .flatten()
+                .and_then(|meta| meta.describe())
+                .unwrap_or_default();
+
+            table.add_row(vec![
+                format!("{:?}", address),
+                label,
+                stats.calls.to_string(),
+                stats.txes.len().to_string(),
+                format!("{:.2}", stats.erc20_value),
+                stats.possible_mev.len().to_string(),
+            ]);
+        }
+
+        table
+    }
+
+    fn selector_table(&self, sort_by: SortBy, top: usize) -> ComfyTable {
+        let mut table = ComfyTable::new();
+        table.load_preset(ASCII_MARKDOWN);
+        table.set_header([
+            "Selector",
+            "Callees",
+            "Calls",
+            "Txes",
+            "ERC20 Value Moved",
+            "Possible MEV Txes",
+        ]);
+
+        for (selector, stats) in ranked(&self.by_selector, sort_by, top) {
+            table.add_row(vec![
+                selector.clone(),
+                stats.callees.len().to_string(),
+                stats.calls.to_string(),
+                stats.txes.len().to_string(),
+                format!("{:.2}", stats.erc20_value),
+                stats.possible_mev.len().to_string(),
+            ]);
+        }
+
+        table
+    }
+}
+
+fn ranked<K>(
+    stats: &FastHashMap<K, CoverageStats>,
+    sort_by: SortBy,
+    top: usize,
+) -> Vec<(&K, &CoverageStats)> {
+    stats
+        .iter()
+        .sorted_by(|(_, a), (_, b)| b.sort_key(sort_by).total_cmp(&a.sort_key(sort_by)))
+        .take(top)
+        .collect()
+}
+
+/// Collects all unclassified actions that emitted logs along with the node
+/// they were found at and whether they are nested below another collected
+/// action.
+fn collect_unclassified<'a>(
+    node: &'a Node,
+    data: &'a NodeData<Action>,
+    nested: bool,
+    result: &mut Vec<(&'a Action, &'a Node, bool)>,
+) {
+    let len = result.len();
+    if let Some(actions) = data.get_ref(node.data) {
+        result.extend(
+            actions
+                .iter()
+                .filter(|action| action.is_unclassified() && action.emitted_logs())
+                .map(|action| (action, node, nested)),
+        );
+    }
+
+    let nested = nested || result.len() > len;
+    node.inner
+        .iter()
+        .for_each(|inner| collect_unclassified(inner, data, nested, result));
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::{Bytes, Log, U256};
+    use brontes_types::{
+        normalized_actions::NormalizedTransfer, structured_trace::TransactionTraceWithLogs,
+    };
+    use reth_rpc_types::trace::parity::{
+        Action as TraceAction, CallAction, CallType, TransactionTrace,
+    };
+
+    use super::*;
+
+    fn unclassified(to: Address, logs: usize) -> Action {
+        Action::Unclassified(TransactionTraceWithLogs {
+            trace:        TransactionTrace {
+                action:        TraceAction::Call(CallAction {
+                    from: Address::ZERO,
+                    gas: Default::default(),
+                    value: U256::ZERO,
+                    call_type: CallType::Call,
+                    input: Bytes::from_static(&[0xde, 0xad, 0xbe, 0xef]),
+                    to,
+                }),
+                error:         None,
+                result:        None,
+                subtraces:     0,
+                trace_address: vec![],
+            },
+            logs:         vec![Log::default(); logs],
+            msg_sender:   Address::ZERO,
+            trace_idx:    0,
+            decoded_data: None,
+        })
+    }
+
+    fn node(data: &mut NodeData<Action>, actions: Vec<Action>, inner: Vec<Node>) -> Node {
+        let mut node = Node::new(0, Address::ZERO, vec![]);
+        node.data = data.add(actions);
+        node.inner = inner;
+        node
+    }
+
+    #[test]
+    fn test_collect_unclassified_with_logs() {
+        let (emits, silent, nested) =
+            (Address::with_last_byte(1), Address::with_last_byte(2), Address::with_last_byte(3));
+        let mut data = NodeData(vec![]);
+        let leaf = node(
+            &mut data,
+            vec![unclassified(nested, 2), Action::Transfer(NormalizedTransfer::default())],
+            vec![],
+        );
+        let child = node(&mut data, vec![unclassified(silent, 0)], vec![leaf]);
+        let root = node(&mut data, vec![unclassified(emits, 1)], vec![child]);
+
+        let mut result = Vec::new();
+        collect_unclassified(&root, &data, false, &mut result);
+
+        let callees = result
+            .iter()
+            .map(|(action, _, is_nested)| (action.get_to_address(), *is_nested))
+            .collect_vec();
+        assert_eq!(callees, vec![(emits, false), (nested, true)]);
+
+        // without an emitting ancestor the leaf is the outermost call
+        let mut result = Vec::new();
+        collect_unclassified(&root.inner[0], &data, false, &mut result);
+        assert!(matches!(result.as_slice(), [(_, _, false)]));
+    }
+
+    #[test]
+    fn test_ranked() {
+        let stats = |calls, erc20_value, possible_mev: &[u8]| CoverageStats {
+            calls,
+            erc20_value,
+            possible_mev: possible_mev
+                .iter()
+                .map(|b| B256::with_last_byte(*b))
+                .collect(),
+            ..Default::default()
+        };
+        let by_address = FastHashMap::from_iter([
+            ("a", stats(10, 1.0, &[])),
+            ("b", stats(5, 100.0, &[1])),
+            ("c", stats(1, 10.0, &[1, 2])),
+        ]);
+
+        let keys = |sort_by, top| {
+            ranked(&by_address, sort_by, top)
+                .into_iter()
+                .map(|(key, _)| *key)
+                .collect_vec()
+        };
+        assert_eq!(keys(SortBy::Calls, 3), vec!["a", "b", "c"]);
+        assert_eq!(keys(SortBy::Value, 3), vec!["b", "c", "a"]);
+        assert_eq!(keys(SortBy::PossibleMev, 2), vec!["c", "b"]);
+    }
+}
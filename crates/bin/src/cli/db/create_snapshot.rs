@@ -0,0 +1,203 @@
+use std::{
+    fs::File,
+    io::Write,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use brontes_database::libmdbx::{
+    rclone_wrapper::BlockRangeList, LibmdbxInit, LibmdbxPartitioner, LibmdbxReadWriter,
+    FULL_RANGE_NAME, PARTITION_FILE_NAME,
+};
+use clap::Parser;
+use flate2::{write::GzEncoder, Compression};
+use futures::StreamExt;
+use itertools::Itertools;
+
+use super::snapshot::{sha256_file, CHECKSUM_PATH, RANGES_AVAILABLE, SIZE_PATH};
+use crate::runner::CliContext;
+
+#[derive(Debug, Parser)]
+pub struct CreateSnapshot {
+    /// Directory the snapshot is written to. Serve it over http or download
+    /// from it directly with `db download-snapshot --endpoint file://<dir>/`
+    #[arg(long, short)]
+    pub output_dir:  PathBuf,
+    /// Block to start partitioning from, defaults to the first block of the db
+    #[arg(long, short)]
+    pub start_block: Option<u64>,
+    /// Also snapshot the complete db as a single tarball
+    #[arg(long, default_value_t = false)]
+    pub full_db:     bool,
+    /// the amount of dbs to partition and compress at a time
+    #[clap(short, long, default_value_t = 10)]
+    rayon_tasks:     usize,
+}
+
+impl CreateSnapshot {
+    pub async fn execute(self, brontes_db_path: String, ctx: CliContext) -> eyre::Result<()> {
+        fs_extra::dir::create_all(&self.output_dir, false)?;
+
+        let db = LibmdbxReadWriter::init_db(&brontes_db_path, None, &ctx.task_executor, false)?;
+        let start_block = match self.start_block {
+            Some(block) => block,
+            None => db.get_db_range()?.0,
+        };
+
+        if self.full_db {
+            tracing::info!("compressing full database");
+            let (db_path, output_dir) = (PathBuf::from(&brontes_db_path), self.output_dir.clone());
+            tokio::task::spawn_blocking(move || pack(&db_path, FULL_RANGE_NAME, &output_dir))
+                .await??;
+        }
+
+        // unique per run so concurrent snapshots can't clobber each others
+        // partitions. removed on drop, including when partitioning fails
+        let tmp_dir = tempfile::Builder::new()
+            .prefix("brontes-snapshot-partitions")
+            .tempdir()?;
+        let partition_dir = tmp_dir.path().to_path_buf();
+
+        tracing::info!(%start_block, "partitioning db");
+        let (rayon_tasks, executor, folder) =
+            (self.rayon_tasks, ctx.task_executor.clone(), partition_dir.clone());
+        tokio::task::spawn_blocking(move || {
+            LibmdbxPartitioner::new(db, folder, start_block, executor).execute(rayon_tasks)
+        })
+        .await??;
+
+        let partitions = std::fs::read_dir(&partition_dir)?
+            .map(|entry| Ok(entry?.file_name().to_string_lossy().to_string()))
+            .collect::<eyre::Result<Vec<_>>>()?;
+
+        tracing::info!(partitions = partitions.len(), "compressing partitions");
+        let packed = futures::stream::iter(partitions.iter().cloned())
+            .map(|name| {
+                let mut dir = partition_dir.clone();
+                dir.push(&name);
+                let output_dir = self.output_dir.clone();
+                tokio::task::spawn_blocking(move || pack(&dir, &name, &output_dir))
+            })
+            .buffer_unordered(self.rayon_tasks)
+            .collect::<Vec<_>>()
+            .await;
+        for res in packed {
+            res??;
+        }
+
+        self.write_available_ranges(&partitions)?;
+        tmp_dir.close()?;
+        tracing::info!(output_dir = ?self.output_dir, "snapshot created");
+
+        Ok(())
+    }
+
+    /// Adds the new partitions to the ranges already in the output dir so
+    /// snapshots can be extended over time
+    fn write_available_ranges(&self, partitions: &[String]) -> eyre::Result<()> {
+        let mut manifest = self.output_dir.clone();
+        manifest.push(RANGES_AVAILABLE);
+
+        let mut ranges: Vec<BlockRangeList> = if manifest.exists() {
+            serde_json::from_str(&std::fs::read_to_string(&manifest)?)?
+        } else {
+            vec![]
+        };
+        ranges.extend(partitions.iter().filter_map(|name| {
+            let mut range = name
+                .strip_prefix(PARTITION_FILE_NAME)?
+                .trim_start_matches('-')
+                .split('-');
+            let start_block = u64::from_str(range.next()?).ok()?;
+            let end_block = u64::from_str(range.next()?).ok()?;
+
+            Some(BlockRangeList { start_block, end_block })
+        }));
+
+        let ranges = ranges
+            .into_iter()
+            .unique()
+            .sorted_by_key(|range| range.start_block)
+            .collect_vec();
+        std::fs::write(manifest, serde_json::to_string(&ranges)?)?;
+
+        Ok(())
+    }
+}
+
+/// Compresses the db dir into `<name>.tar.gz` and writes the byte count and
+/// checksum the downloader expects next to it
+fn pack(dir: &Path, name: &str, output_dir: &Path) -> eyre::Result<()> {
+    let mut tarball = output_dir.to_path_buf();
+    tarball.push(format!("{name}.tar.gz"));
+
+    let enc = GzEncoder::new(File::create(&tarball)?, Compression::default());
+    let mut tar = tar::Builder::new(enc);
+    tar.append_dir_all(name, dir)?;
+    tar.into_inner()?.finish()?;
+
+    let mut size = output_dir.to_path_buf();
+    size.push(format!("{name}-{SIZE_PATH}"));
+    write!(File::create(size)?, "{}", filesize::file_real_size(&tarball)?)?;
+
+    let mut checksum = output_dir.to_path_buf();
+    checksum.push(format!("{name}-{CHECKSUM_PATH}"));
+    write!(File::create(checksum)?, "{}", sha256_file(&tarball)?)?;
+
+    tracing::info!(%name, "compressed");
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ranges(output_dir: &Path) -> Vec<BlockRangeList> {
+        serde_json::from_str(&std::fs::read_to_string(output_dir.join(RANGES_AVAILABLE)).unwrap())
+            .unwrap()
+    }
+
+    #[test]
+    fn test_write_available_ranges() {
+        let dir = tempfile::tempdir().unwrap();
+        let snapshot = CreateSnapshot {
+            output_dir:  dir.path().to_path_buf(),
+            start_block: None,
+            full_db:     false,
+            rayon_tasks: 1,
+        };
+
+        snapshot
+            .write_available_ranges(&[
+                format!("{PARTITION_FILE_NAME}-20-29"),
+                format!("{PARTITION_FILE_NAME}-10-19"),
+                // the fixed tables aren't a block range
+                format!("{PARTITION_FILE_NAME}-full-range-tables"),
+            ])
+            .unwrap();
+        assert_eq!(
+            ranges(dir.path()),
+            vec![
+                BlockRangeList { start_block: 10, end_block: 19 },
+                BlockRangeList { start_block: 20, end_block: 29 },
+            ]
+        );
+
+        // extending an existing snapshot keeps the old ranges and drops duplicates
+        snapshot
+            .write_available_ranges(&[
+                format!("{PARTITION_FILE_NAME}-20-29"),
+                format!("{PARTITION_FILE_NAME}-30-39"),
+            ])
+            .unwrap();
+        assert_eq!(
+            ranges(dir.path()),
+            vec![
+                BlockRangeList { start_block: 10, end_block: 19 },
+                BlockRangeList { start_block: 20, end_block: 29 },
+                BlockRangeList { start_block: 30, end_block: 39 },
+            ]
+        );
+    }
+}
@@ -66,7 +66,14 @@ impl Clear {
                 AddressMeta,
                 SearcherEOAs,
                 SearcherContracts,
-                TxTraces
+                TxTraces,
+                BlockAnalyses,
+                ClassifiedBlocks,
+                BlockLvrs,
+                FailedMevAttempts,
+                CexDexInventory,
+                RunCheckpoints,
+                SchemaVersions
             )
         });
 
@@ -53,6 +53,13 @@ impl Insert {
             SearcherEOAs,
             SearcherContracts,
             InitializedState,
+            BlockAnalyses,
+            ClassifiedBlocks,
+            BlockLvrs,
+            FailedMevAttempts,
+            CexDexInventory,
+            RunCheckpoints,
+            SchemaVersions,
             PoolCreationBlocks = &self.key,
             &self.value
         );
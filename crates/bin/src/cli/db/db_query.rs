@@ -77,7 +77,14 @@ impl DatabaseQuery {
                     AddressMeta,
                     SearcherEOAs,
                     SearcherContracts,
-                    TxTraces
+                    TxTraces,
+                    BlockAnalyses,
+                    ClassifiedBlocks,
+                    BlockLvrs,
+                    FailedMevAttempts,
+                    CexDexInventory,
+                    RunCheckpoints,
+                    SchemaVersions
                 );
             } else {
                 match_table!(
@@ -97,6 +104,13 @@ impl DatabaseQuery {
                     SearcherEOAs,
                     SearcherContracts,
                     TxTraces,
+                    BlockAnalyses,
+                    ClassifiedBlocks,
+                    BlockLvrs,
+                    FailedMevAttempts,
+                    CexDexInventory,
+                    RunCheckpoints,
+                    SchemaVersions,
                     PoolCreationBlocks = &self.key
                 );
             }
@@ -1,37 +1,66 @@
-use std::sync::Arc;
+use std::{path::Path, sync::Arc};
 
-use brontes_database::{parquet::ParquetExporter, Tables};
+use brontes_classifier::Classifier;
+use brontes_database::{
+    libmdbx::{LibmdbxInit, LibmdbxReadWriter},
+    parquet::{ParquetExporter, DEFAULT_PARTITION_SIZE},
+    Tables,
+};
+use brontes_types::init_thread_pools;
 use clap::Parser;
-use futures::future::join_all;
-use tokio::task::spawn;
-use tracing::error;
+use futures::{future::join_all, StreamExt};
+use tokio::{sync::mpsc::unbounded_channel, task::spawn};
+use tracing::{error, info, warn};
 
 use crate::{
-    cli::{load_libmdbx, static_object},
+    cli::{
+        determine_max_tasks, get_tracing_provider, load_libmdbx, load_stored_block, reth_db_path,
+        static_object,
+    },
     runner::CliContext,
 };
 #[derive(Debug, Parser)]
 pub struct Export {
     /// Optional tables to exports, if omitted will export all supported tables
     #[arg(long, short, default_values = &["MevBlocks", "AddressMeta", "SearcherContracts", "Builder"], value_delimiter = ',', ignore_case=true)]
-    pub tables:      Vec<Tables>,
+    pub tables:             Vec<Tables>,
     /// Optional Start Block, if omitted it will export the entire range to
     /// parquet
     #[arg(long, short)]
-    pub start_block: Option<u64>,
+    pub start_block:        Option<u64>,
     /// Optional End Block
     #[arg(long, short)]
-    pub end_block:   Option<u64>,
+    pub end_block:          Option<u64>,
     /// Optional path, will default to "data_exports/"
     #[arg(long, short)]
-    pub path:        Option<String>,
+    pub path:               Option<String>,
+    /// Amount of blocks per file for the tables keyed by block
+    #[arg(long, default_value_t = DEFAULT_PARTITION_SIZE)]
+    pub partition_size:     u64,
+    /// Also export the normalized actions, re-classified from the stored
+    /// traces. Defaults to the range of the db when no blocks are given
+    #[arg(long, default_value_t = false)]
+    pub normalized_actions: bool,
+    /// Max number of blocks to classify concurrently when exporting the
+    /// normalized actions
+    #[arg(long)]
+    pub max_tasks:          Option<u64>,
 }
 
 impl Export {
     pub async fn execute(self, brontes_db_path: String, ctx: CliContext) -> eyre::Result<()> {
         let libmdbx = static_object(load_libmdbx(&ctx.task_executor, brontes_db_path)?);
-        let exporter =
-            Arc::new(ParquetExporter::new(self.start_block, self.end_block, self.path, libmdbx));
+        let exporter = Arc::new(ParquetExporter::new(
+            self.start_block,
+            self.end_block,
+            self.path,
+            self.partition_size,
+            libmdbx,
+        ));
+
+        if self.normalized_actions {
+            Self::export_normalized_actions(&exporter, libmdbx, self.max_tasks, &ctx).await?;
+        }
 
         let futures = self.tables.into_iter().map(|t| {
             let exporter = exporter.clone();
@@ -56,4 +85,143 @@ impl Export {
 
         Ok(())
     }
+
+    /// Re-classifies the stored traces one partition at a time and writes the
+    /// resulting normalized actions. Blocks are never re-traced, only their
+    /// headers are fetched from the node, and discovered pools are not written
+    /// back to the db
+    async fn export_normalized_actions(
+        exporter: &ParquetExporter<LibmdbxReadWriter>,
+        libmdbx: &'static LibmdbxReadWriter,
+        max_tasks: Option<u64>,
+        ctx: &CliContext,
+    ) -> eyre::Result<()> {
+        let max_tasks = determine_max_tasks(max_tasks);
+        init_thread_pools(max_tasks as usize);
+
+        // the reth db is only opened with a local reth, otherwise the headers come
+        // from the node's rpc
+        let db_path = reth_db_path()?;
+        let tracer = Arc::new(get_tracing_provider(
+            Path::new(&db_path),
+            max_tasks,
+            ctx.task_executor.clone(),
+        ));
+
+        // the classifier sends pool updates for pricing which we don't run here
+        let (pricing_tx, mut pricing_rx) = unbounded_channel();
+        tokio::spawn(async move { while pricing_rx.recv().await.is_some() {} });
+        let classifier = static_object(
+            Classifier::new(libmdbx, pricing_tx, tracer.clone()).without_persisting_pools(),
+        );
+        let tracer = static_object(tracer);
+
+        let (start_block, end_block) =
+            normalized_actions_range(libmdbx, exporter.start_block, exporter.end_block)?;
+
+        for (start_block, end_block) in exporter.partitions_in(start_block, end_block) {
+            let results = futures::stream::iter(start_block..=end_block)
+                .map(|block| async move {
+                    let (traces, header) = load_stored_block(libmdbx, &**tracer, block)
+                        .await
+                        .map_err(|e| (block, e))?;
+                    Ok(Arc::new(classifier.build_block_tree(traces, header, false).await))
+                })
+                .buffer_unordered(max_tasks as usize)
+                .collect::<Vec<Result<_, (u64, eyre::Report)>>>()
+                .await;
+
+            let mut trees = Vec::with_capacity(results.len());
+            let mut failed = 0usize;
+            for result in results {
+                match result {
+                    Ok(tree) => trees.push(tree),
+                    Err((block, e)) => {
+                        warn!(block, err = %e, "skipping block in normalized actions export");
+                        failed += 1;
+                    }
+                }
+            }
+
+            info!(
+                start_block,
+                end_block,
+                blocks = trees.len(),
+                failed,
+                "exporting normalized actions"
+            );
+            exporter
+                .export_normalized_actions(trees, start_block, end_block)
+                .await?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The block range to re-classify, the unset bounds default to the range of
+/// the db
+fn normalized_actions_range<DB: LibmdbxInit>(
+    libmdbx: &DB,
+    start_block: Option<u64>,
+    end_block: Option<u64>,
+) -> eyre::Result<(u64, u64)> {
+    let (start_block, end_block) = match (start_block, end_block) {
+        (Some(start_block), Some(end_block)) => (start_block, end_block),
+        (start_block, end_block) => {
+            let (first, last) = libmdbx.get_db_range()?;
+            (start_block.unwrap_or(first), end_block.unwrap_or(last))
+        }
+    };
+
+    if start_block > end_block {
+        eyre::bail!("start block {start_block} is after end block {end_block}");
+    }
+
+    Ok((start_block, end_block))
+}
+
+#[cfg(test)]
+mod tests {
+    use brontes_database::libmdbx::{tables::BlockInfo, LibmdbxReadWriter};
+    use brontes_types::db::metadata::BlockMetadataInner;
+    use reth_db::DatabaseError;
+
+    use super::*;
+
+    fn db_with_blocks(dir: &Path, blocks: &[u64]) -> LibmdbxReadWriter {
+        let db = LibmdbxReadWriter::init_db_tests(dir).unwrap();
+        db.db
+            .update_db(|tx| {
+                for block in blocks {
+                    tx.put::<BlockInfo>(*block, BlockMetadataInner::default())?;
+                }
+                Ok::<_, DatabaseError>(())
+            })
+            .unwrap()
+            .unwrap();
+
+        db
+    }
+
+    #[tokio::test]
+    async fn test_normalized_actions_range_defaults_to_db_range() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = db_with_blocks(dir.path(), &[10, 15, 20]);
+
+        assert_eq!(normalized_actions_range(&db, None, None).unwrap(), (10, 20));
+        assert_eq!(normalized_actions_range(&db, Some(15), None).unwrap(), (15, 20));
+        assert_eq!(normalized_actions_range(&db, None, Some(15)).unwrap(), (10, 15));
+        assert_eq!(normalized_actions_range(&db, Some(1), Some(2)).unwrap(), (1, 2));
+        assert!(normalized_actions_range(&db, Some(21), None).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_normalized_actions_range_empty_db() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = db_with_blocks(dir.path(), &[]);
+
+        assert!(normalized_actions_range(&db, None, None).is_err());
+        assert_eq!(normalized_actions_range(&db, Some(1), Some(2)).unwrap(), (1, 2));
+    }
 }
@@ -71,7 +71,7 @@ impl Init {
         let task_executor = ctx.task_executor;
 
         let libmdbx =
-            static_object(load_database(&task_executor, brontes_db_path, None, None).await?);
+            static_object(load_database(&task_executor, brontes_db_path, None, None, false).await?);
         let clickhouse = static_object(load_clickhouse(Default::default(), None).await?);
 
         let tracer = Arc::new(get_tracing_provider(Path::new(&db_path), 10, task_executor.clone()));
@@ -0,0 +1,79 @@
+use brontes_database::libmdbx::{Libmdbx, TableSchema};
+use clap::Parser;
+use comfy_table::{Cell, Row, Table as ComfyTable};
+use indicatif::MultiProgress;
+
+/// Converts the tables stored in an older schema version to the layout this
+/// build of brontes reads. No other brontes process may have the db open
+/// while it runs
+#[derive(Debug, Parser)]
+pub struct Migrate {
+    /// Only list the pending migrations
+    #[arg(long, default_value_t = false)]
+    pub dry_run: bool,
+}
+
+impl Migrate {
+    pub fn execute(self, brontes_db_path: String) -> eyre::Result<()> {
+        let db = Libmdbx::init_db_unchecked(brontes_db_path, None)?;
+        let schemas = db.table_schemas()?;
+
+        if let Some(ahead) = schemas.iter().find(|schema| schema.is_ahead()) {
+            eyre::bail!(
+                "{} is stored in schema version {} which is newer than this build of brontes \
+                 ({}), upgrade brontes instead",
+                ahead.table,
+                ahead.stored_version,
+                ahead.current_version
+            );
+        }
+
+        let outdated = schemas
+            .into_iter()
+            .filter(TableSchema::is_outdated)
+            .collect::<Vec<_>>();
+        if outdated.is_empty() {
+            println!("all tables are at their current schema version");
+            return Ok(())
+        }
+        print_pending(&outdated);
+
+        if self.dry_run {
+            return Ok(())
+        }
+
+        let multi = MultiProgress::default();
+        for schema in &outdated {
+            let migrations = schema.pending_migrations().count();
+            let pb = schema
+                .table
+                .build_init_state_progress_bar(&multi, (schema.entries * migrations) as u64);
+            let rows = db.migrate_table(schema, Some(&pb))?;
+            pb.finish_and_clear();
+
+            println!(
+                "migrated {} from schema version {} to {}, rewriting {rows} rows",
+                schema.table, schema.stored_version, schema.current_version
+            );
+        }
+
+        Ok(())
+    }
+}
+
+fn print_pending(outdated: &[TableSchema]) {
+    let mut table = ComfyTable::new();
+    table.load_preset(comfy_table::presets::ASCII_MARKDOWN);
+    table.set_header(["Table Name", "From Version", "Description", "Rows"]);
+    for schema in outdated {
+        for migration in schema.pending_migrations() {
+            let mut row = Row::new();
+            row.add_cell(Cell::new(schema.table.name()))
+                .add_cell(Cell::new(migration.from_version))
+                .add_cell(Cell::new(migration.description))
+                .add_cell(Cell::new(schema.entries));
+            table.add_row(row);
+        }
+    }
+    println!("{table}");
+}
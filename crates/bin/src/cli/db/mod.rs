@@ -2,9 +2,14 @@ use clap::{Parser, Subcommand};
 mod r2_uploader;
 mod snapshot;
 use crate::runner::CliContext;
+mod builder_integration;
 mod cex_data;
+mod cex_dex_inventory;
 #[cfg(feature = "local-clickhouse")]
 mod clickhouse_download;
+mod cluster_searchers;
+mod coverage;
+mod create_snapshot;
 mod db_clear;
 mod db_insert;
 mod db_query;
@@ -14,11 +19,15 @@ mod discovery;
 mod ensure_test_traces;
 mod export;
 mod init;
+mod migrate;
+mod prune;
 mod table_stats;
 #[cfg(feature = "local-clickhouse")]
 mod tip_tracer;
 mod trace_range;
 pub mod utils;
+mod verify;
+mod victim_loss;
 
 #[derive(Debug, Parser)]
 pub struct Database {
@@ -51,6 +60,42 @@ pub enum DatabaseCommands {
     /// Libmbdx Table Stats
     #[command(name = "table-stats")]
     TableStats(table_stats::Stats),
+    /// Prunes the traces of old blocks or of the transactions that aren't
+    /// mev, then compacts the db file
+    #[command(name = "prune")]
+    Prune(prune::Prune),
+    /// Ranks unclassified calls that emit logs over a block range to find
+    /// missing classifiers
+    #[command(name = "coverage")]
+    Coverage(coverage::Coverage),
+    /// Clusters searcher EOAs and contracts into sibling searchers and funds,
+    /// writing proposals to review before merging them into the searcher
+    /// tables
+    #[command(name = "cluster-searchers")]
+    ClusterSearchers(cluster_searchers::ClusterSearchers),
+    /// Infers the searchers vertically integrated with each builder from the
+    /// mev history, optionally adding them to the builder table
+    #[command(name = "builder-integration")]
+    BuilderIntegration(builder_integration::BuilderIntegration),
+    /// Re-executes the victims of the stored sandwiches without their
+    /// frontruns and stores the counterfactual loss on each sandwich. The
+    /// run pipeline leaves the losses empty, this has to be run after it
+    #[command(name = "victim-loss")]
+    VictimLoss(victim_loss::VictimLoss),
+    /// Tracks the inventory of the labelled cex-dex searchers across blocks,
+    /// storing their daily realized and unrealized pnl in the
+    /// `CexDexInventory` table
+    #[command(name = "cex-dex-inventory")]
+    CexDexInventory(cex_dex_inventory::CexDexInventory),
+    /// Checks a block range for missing, truncated or inconsistent data,
+    /// optionally downloading the broken blocks again. Rows are only checked
+    /// on their zstd frame and length, not on the stored value itself
+    #[command(name = "verify")]
+    Verify(verify::Verify),
+    /// Converts the tables stored in an older schema version to the layout
+    /// this build reads
+    #[command(name = "migrate")]
+    Migrate(migrate::Migrate),
     /// Export libmbdx data to parquet
     #[command(name = "export")]
     Export(export::Export),
@@ -59,6 +104,11 @@ pub enum DatabaseCommands {
     /// merges it into the current database.
     #[command(name = "download-snapshot")]
     DownloadSnapshot(snapshot::Snapshot),
+    /// Partitions the local db into the range tarballs and manifests
+    /// `download-snapshot` reads, so it can be shared over http or the file
+    /// system
+    #[command(name = "create-snapshot")]
+    CreateSnapshot(create_snapshot::CreateSnapshot),
     #[cfg(feature = "local-clickhouse")]
     /// Downloads the db data from clickhouse
     #[command(name = "download-clickhouse")]
@@ -93,7 +143,16 @@ impl Database {
             DatabaseCommands::UploadSnapshot(cmd) => cmd.execute(brontes_db_path, ctx).await,
             DatabaseCommands::Export(cmd) => cmd.execute(brontes_db_path, ctx).await,
             DatabaseCommands::TableStats(cmd) => cmd.execute(brontes_db_path),
+            DatabaseCommands::Prune(cmd) => cmd.execute(brontes_db_path).await,
+            DatabaseCommands::Verify(cmd) => cmd.execute(brontes_db_path, ctx).await,
+            DatabaseCommands::Migrate(cmd) => cmd.execute(brontes_db_path),
+            DatabaseCommands::Coverage(cmd) => cmd.execute(brontes_db_path, ctx).await,
+            DatabaseCommands::ClusterSearchers(cmd) => cmd.execute(brontes_db_path, ctx).await,
+            DatabaseCommands::BuilderIntegration(cmd) => cmd.execute(brontes_db_path, ctx).await,
+            DatabaseCommands::VictimLoss(cmd) => cmd.execute(brontes_db_path, ctx).await,
+            DatabaseCommands::CexDexInventory(cmd) => cmd.execute(brontes_db_path, ctx).await,
             DatabaseCommands::DownloadSnapshot(cmd) => cmd.execute(brontes_db_path, ctx).await,
+            DatabaseCommands::CreateSnapshot(cmd) => cmd.execute(brontes_db_path, ctx).await,
             DatabaseCommands::CexData(cmd) => cmd.execute(brontes_db_path, ctx).await,
             #[cfg(feature = "local-clickhouse")]
             DatabaseCommands::DownloadClickhouse(cmd) => cmd.execute(brontes_db_path, ctx).await,
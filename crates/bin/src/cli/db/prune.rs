@@ -0,0 +1,108 @@
+use std::{path::PathBuf, sync::Arc};
+
+use brontes_database::{
+    libmdbx::{Libmdbx, TracePruneMode, TracePruneStats},
+    Tables,
+};
+use clap::Parser;
+use indicatif::MultiProgress;
+
+const DB_FILE: &str = "mdbx.dat";
+
+/// Prunes the `TxTraces` table. No other brontes process may have the db open
+/// while it runs, as the db file is swapped for its compacted copy at the end
+#[derive(Debug, Parser)]
+pub struct Prune {
+    /// Keep the traces of the last N traced blocks, pruning every block before
+    #[arg(long, conflicts_with = "before")]
+    pub keep_last:  Option<u64>,
+    /// Prune the traces of every block before this one
+    #[arg(long)]
+    pub before:     Option<u64>,
+    /// Keep the traces of the transactions that are part of a bundle or
+    /// flagged as possible mev instead of dropping the whole block. Without
+    /// `--keep-last` or `--before` every block is pruned this way. Note that
+    /// brontes can't be rerun over these blocks without tracing them again
+    #[arg(long, default_value_t = false)]
+    pub mev_only:   bool,
+    /// Don't compact the db file after pruning, the freed pages are then only
+    /// reused by later writes
+    #[arg(long, default_value_t = false)]
+    pub no_compact: bool,
+}
+
+impl Prune {
+    pub async fn execute(self, brontes_db_path: String) -> eyre::Result<()> {
+        if self.keep_last.is_none() && self.before.is_none() && !self.mev_only {
+            eyre::bail!("set a retention policy with --keep-last, --before or --mev-only");
+        }
+
+        // the env is owned here rather than leaked so it can be closed before
+        // the db file is swapped for its compacted copy
+        let libmdbx = Arc::new(Libmdbx::init_db(&brontes_db_path, None)?);
+        let Some((first, last)) = libmdbx.trace_range()? else {
+            tracing::info!("no traces stored, nothing to prune");
+            return Ok(())
+        };
+
+        let before = match (self.keep_last, self.before) {
+            (Some(keep), _) => Some((last + 1).saturating_sub(keep)),
+            (_, before) => before,
+        };
+        let mode = if self.mev_only { TracePruneMode::KeepMev } else { TracePruneMode::Drop };
+        tracing::info!(?before, ?mode, "pruning traces");
+
+        let multi = MultiProgress::default();
+        let pb = Tables::TxTraces.build_init_state_progress_bar(
+            &multi,
+            before.unwrap_or(last + 1).saturating_sub(first),
+        );
+        let db = libmdbx.clone();
+        let TracePruneStats { blocks_pruned, traces_removed, blocks_skipped } =
+            tokio::task::spawn_blocking(move || {
+                let stats = db.prune_traces(before, mode, Some(&pb));
+                pb.finish_and_clear();
+                stats
+            })
+            .await??;
+
+        println!("pruned {traces_removed} traces over {blocks_pruned} blocks");
+        if blocks_skipped != 0 {
+            println!("kept {blocks_skipped} blocks whole as brontes hasn't run over them yet");
+        }
+
+        if self.no_compact || blocks_pruned == 0 {
+            return Ok(())
+        }
+
+        let mut db_file = PathBuf::from(&brontes_db_path);
+        db_file.push(DB_FILE);
+        let compacted_dir = PathBuf::from(format!("{brontes_db_path}-compacted"));
+        if compacted_dir.exists() {
+            fs_extra::dir::remove(&compacted_dir)?;
+        }
+        fs_extra::dir::create_all(&compacted_dir, false)?;
+        let compacted = compacted_dir.join(DB_FILE);
+
+        tracing::info!("compacting db, this can take a while");
+        let size_before = filesize::file_real_size(&db_file)?;
+        let dest = compacted.clone();
+        let db = libmdbx.clone();
+        tokio::task::spawn_blocking(move || db.write_compacted_copy(&dest)).await??;
+
+        // the env has to be closed before its file is replaced, otherwise it keeps
+        // the old file mapped and writes through it on close
+        Arc::try_unwrap(libmdbx)
+            .map_err(|_| eyre::eyre!("db is still in use, can't swap in the compacted copy"))
+            .map(drop)?;
+        std::fs::rename(&compacted, &db_file)?;
+        fs_extra::dir::remove(compacted_dir)?;
+        println!(
+            "compacted db from {} to {}",
+            human_bytes::human_bytes(size_before as f64),
+            human_bytes::human_bytes(filesize::file_real_size(&db_file)? as f64)
+        );
+
+        Ok(())
+    }
+}
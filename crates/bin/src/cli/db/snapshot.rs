@@ -1,5 +1,11 @@
-use std::{env::temp_dir, path::PathBuf, str::FromStr};
+use std::{
+    env::temp_dir,
+    io::Read,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
 
+use alloy_primitives::hex;
 use brontes_database::libmdbx::{
     merge_libmdbx_dbs, rclone_wrapper::BlockRangeList, LibmdbxReadWriter, FULL_RANGE_NAME,
 };
@@ -14,19 +20,22 @@ use futures::{stream::StreamExt, Stream};
 use indicatif::MultiProgress;
 use itertools::Itertools;
 use reqwest::Url;
+use sha2::{Digest, Sha256};
 use tar::Archive;
 
 use crate::runner::CliContext;
 
 const NAME: &str = "brontes-db-partition";
 const FIXED_DB: &str = "full-range-tables";
-const SIZE_PATH: &str = "byte-count.txt";
-const RANGES_AVAILABLE: &str = "brontes-available-ranges.json";
+pub(super) const SIZE_PATH: &str = "byte-count.txt";
+pub(super) const CHECKSUM_PATH: &str = "sha256.txt";
+pub(super) const RANGES_AVAILABLE: &str = "brontes-available-ranges.json";
 const BYTES_TO_MB: u64 = 1_000_000;
 
 #[derive(Debug, Parser)]
 pub struct Snapshot {
-    /// Snapshot endpoint
+    /// Snapshot endpoint, either a http url or a `file://` directory written
+    /// by `db create-snapshot`
     #[arg(long, default_value = "https://data.brontes.xyz/")]
     pub endpoint:         Url,
     /// Optional start block
@@ -38,6 +47,10 @@ pub struct Snapshot {
     /// the amount of dbs to merge at a time
     #[clap(short, long, default_value_t = 10)]
     rayon_tasks_db_merge: usize,
+    /// Don't verify downloads against the checksums published by the endpoint.
+    /// Needed for endpoints that don't publish checksums
+    #[arg(long, default_value_t = false)]
+    pub skip_checksum:    bool,
 }
 
 impl Snapshot {
@@ -70,6 +83,8 @@ impl Snapshot {
         download_dir.push(format!("{}s", NAME));
         let mut cloned_download_dir = download_dir.clone();
         fs_extra::dir::create_all(&download_dir, false)?;
+        let endpoint = self.endpoint.clone();
+        let skip_checksum = self.skip_checksum;
 
         ctx.task_executor
             .spawn_critical("download_streams", async move {
@@ -77,21 +92,36 @@ impl Snapshot {
                     .map(|DbRequestWithBytes { url, size_bytes, file_name }| {
                         let client = client.clone();
                         let mb = multi_bar.clone();
+                        let endpoint = endpoint.clone();
                         tracing::info!(?url, ?size_bytes, ?file_name);
                         let mut download_dir = download_dir.clone();
                         async move {
-                            download_dir.push(file_name);
-
-                            tracing::info!("creating file");
-                            let file = tokio::fs::File::create(&download_dir).await?;
-
-                            let stream = client.get(url).send().await?.bytes_stream();
-                            DownloadBufWriterWithProgress::new(
-                                Some(size_bytes),
-                                stream,
-                                file,
-                                40 * 1024 * 1024,
-                                &mb,
+                            download_dir.push(&file_name);
+
+                            if endpoint.scheme() == "file" {
+                                tracing::info!("copying file");
+                                tokio::fs::copy(local_path(&endpoint, &file_name)?, &download_dir)
+                                    .await?;
+                            } else {
+                                tracing::info!("creating file");
+                                let file = tokio::fs::File::create(&download_dir).await?;
+
+                                let stream = client.get(url).send().await?.bytes_stream();
+                                DownloadBufWriterWithProgress::new(
+                                    Some(size_bytes),
+                                    stream,
+                                    file,
+                                    40 * 1024 * 1024,
+                                    &mb,
+                                )
+                                .await?;
+                            }
+                            Self::verify_checksum(
+                                &client,
+                                &endpoint,
+                                &file_name,
+                                &download_dir,
+                                skip_checksum,
                             )
                             .await?;
                             tracing::info!("download of file complete, decompressing");
@@ -222,12 +252,44 @@ impl Snapshot {
         &self,
         client: &reqwest::Client,
     ) -> eyre::Result<Vec<BlockRangeList>> {
-        Ok(client
-            .get(format!("{}{}", self.endpoint, RANGES_AVAILABLE))
-            .send()
-            .await?
-            .json()
-            .await?)
+        Ok(serde_json::from_str(&fetch_text(client, &self.endpoint, RANGES_AVAILABLE).await?)?)
+    }
+
+    /// Checks the downloaded tarball against the checksum published next to
+    /// it. A missing checksum is an error as well, downloads are only trusted
+    /// without one when `--skip-checksum` is passed
+    async fn verify_checksum(
+        client: &reqwest::Client,
+        endpoint: &Url,
+        file_name: &str,
+        downloaded: &Path,
+        skip_checksum: bool,
+    ) -> eyre::Result<()> {
+        if skip_checksum {
+            tracing::warn!(%file_name, "skipping checksum verification");
+            return Ok(())
+        }
+
+        let stem = file_name.trim_end_matches(".tar.gz");
+        let Some(expected) =
+            fetch_optional_text(client, endpoint, &format!("{stem}-{CHECKSUM_PATH}")).await?
+        else {
+            eyre::bail!(
+                "endpoint publishes no checksum for {file_name}, pass --skip-checksum to use it \
+                 without verification"
+            );
+        };
+
+        let downloaded = downloaded.to_path_buf();
+        let checksum = tokio::task::spawn_blocking(move || sha256_file(&downloaded)).await??;
+        if checksum != expected.trim() {
+            eyre::bail!(
+                "checksum mismatch for {file_name}, expected {} got {checksum}",
+                expected.trim()
+            );
+        }
+
+        Ok(())
     }
 
     /// returns a error if there is not enough space remaining. If the overwrite
@@ -243,8 +305,12 @@ impl Snapshot {
         let mut res = vec![];
         match ranges {
             RangeOrFull::Full => {
-                let url = format!("{}{}-{}", self.endpoint, FULL_RANGE_NAME, SIZE_PATH);
-                let size = client.get(url).send().await?.text().await?;
+                let size = fetch_text(
+                    client,
+                    &self.endpoint,
+                    &format!("{}-{}", FULL_RANGE_NAME, SIZE_PATH),
+                )
+                .await?;
                 let size = u64::from_str(&size)?;
                 res.push(DbRequestWithBytes {
                     url:        format!("{}{}.tar.gz", self.endpoint, FULL_RANGE_NAME),
@@ -256,11 +322,15 @@ impl Snapshot {
             }
             RangeOrFull::Range(ranges) => {
                 for range in ranges {
-                    let url = format!(
-                        "{}{}-{}-{}-{}",
-                        self.endpoint, NAME, range.start_block, range.end_block, SIZE_PATH
-                    );
-                    let size = client.get(url).send().await?.text().await?;
+                    let size = fetch_text(
+                        client,
+                        &self.endpoint,
+                        &format!(
+                            "{}-{}-{}-{}",
+                            NAME, range.start_block, range.end_block, SIZE_PATH
+                        ),
+                    )
+                    .await?;
                     let size = u64::from_str(&size)?;
                     res.push(DbRequestWithBytes {
                         url:        format!(
@@ -278,8 +348,12 @@ impl Snapshot {
                 }
 
                 // query 1 off table
-                let url = format!("{}{}-{}-{}", self.endpoint, NAME, FIXED_DB, SIZE_PATH);
-                let size = client.get(url).send().await?.text().await?;
+                let size = fetch_text(
+                    client,
+                    &self.endpoint,
+                    &format!("{}-{}-{}", NAME, FIXED_DB, SIZE_PATH),
+                )
+                .await?;
                 let size = u64::from_str(&size)?;
 
                 res.push(DbRequestWithBytes {
@@ -324,6 +398,70 @@ impl Snapshot {
     }
 }
 
+/// Reads a file from the endpoint, which is either served over http or a
+/// local directory
+async fn fetch_text(client: &reqwest::Client, endpoint: &Url, file: &str) -> eyre::Result<String> {
+    if endpoint.scheme() == "file" {
+        return Ok(tokio::fs::read_to_string(local_path(endpoint, file)?).await?)
+    }
+
+    Ok(client
+        .get(format!("{}{}", endpoint, file))
+        .send()
+        .await?
+        .error_for_status()?
+        .text()
+        .await?)
+}
+
+/// Same as [`fetch_text`] but returns `None` if the file doesn't exist on the
+/// endpoint instead of erroring
+async fn fetch_optional_text(
+    client: &reqwest::Client,
+    endpoint: &Url,
+    file: &str,
+) -> eyre::Result<Option<String>> {
+    if endpoint.scheme() == "file" {
+        return match tokio::fs::read_to_string(local_path(endpoint, file)?).await {
+            Ok(text) => Ok(Some(text)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    let res = client.get(format!("{}{}", endpoint, file)).send().await?;
+    if res.status() == reqwest::StatusCode::NOT_FOUND {
+        return Ok(None)
+    }
+
+    Ok(Some(res.error_for_status()?.text().await?))
+}
+
+fn local_path(endpoint: &Url, file: &str) -> eyre::Result<PathBuf> {
+    let mut path = endpoint
+        .to_file_path()
+        .map_err(|_| eyre::eyre!("invalid file endpoint: {endpoint}"))?;
+    path.push(file);
+
+    Ok(path)
+}
+
+/// Hex encoded sha256 of the file
+pub(super) fn sha256_file(path: &Path) -> eyre::Result<String> {
+    let mut file = std::fs::File::open(path)?;
+    let mut hasher = Sha256::new();
+    let mut buf = vec![0u8; 1024 * 1024];
+    loop {
+        let read = file.read(&mut buf)?;
+        if read == 0 {
+            break
+        }
+        hasher.update(&buf[..read]);
+    }
+
+    Ok(hex::encode(hasher.finalize()))
+}
+
 pub enum RangeOrFull {
     Full,
     Range(Vec<BlockRangeList>),
@@ -351,3 +489,67 @@ trait AsyncFlatten: Stream {
         Ok(Ok(res))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TARBALL: &str = "brontes-db-partition-0-10.tar.gz";
+
+    fn file_endpoint(dir: &Path) -> Url {
+        Url::from_directory_path(dir).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_verify_checksum_mismatch() {
+        let dir = tempfile::tempdir().unwrap();
+        let tarball = dir.path().join(TARBALL);
+        std::fs::write(&tarball, b"partition").unwrap();
+        std::fs::write(
+            dir.path()
+                .join(format!("brontes-db-partition-0-10-{CHECKSUM_PATH}")),
+            "00",
+        )
+        .unwrap();
+
+        let client = reqwest::Client::new();
+        let endpoint = file_endpoint(dir.path());
+        let err = Snapshot::verify_checksum(&client, &endpoint, TARBALL, &tarball, false)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("checksum mismatch"), "{err}");
+
+        Snapshot::verify_checksum(&client, &endpoint, TARBALL, &tarball, true)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_verify_checksum() {
+        let dir = tempfile::tempdir().unwrap();
+        let tarball = dir.path().join(TARBALL);
+        std::fs::write(&tarball, b"partition").unwrap();
+
+        let client = reqwest::Client::new();
+        let endpoint = file_endpoint(dir.path());
+
+        // no checksum published
+        let err = Snapshot::verify_checksum(&client, &endpoint, TARBALL, &tarball, false)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("publishes no checksum"), "{err}");
+        Snapshot::verify_checksum(&client, &endpoint, TARBALL, &tarball, true)
+            .await
+            .unwrap();
+
+        std::fs::write(
+            dir.path()
+                .join(format!("brontes-db-partition-0-10-{CHECKSUM_PATH}")),
+            format!("{}\n", sha256_file(&tarball).unwrap()),
+        )
+        .unwrap();
+        Snapshot::verify_checksum(&client, &endpoint, TARBALL, &tarball, false)
+            .await
+            .unwrap();
+    }
+}
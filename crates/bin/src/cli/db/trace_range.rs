@@ -41,8 +41,9 @@ impl TraceArgs {
         ctx.task_executor
             .spawn_critical("metrics", metrics_listener);
 
-        let libmdbx =
-            static_object(load_database(&ctx.task_executor, brontes_db_path, None, None).await?);
+        let libmdbx = static_object(
+            load_database(&ctx.task_executor, brontes_db_path, None, None, false).await?,
+        );
 
         let tracer =
             get_tracing_provider(Path::new(&db_path), max_tasks, ctx.task_executor.clone());
@@ -0,0 +1,208 @@
+use std::{path::Path, sync::Arc};
+
+use brontes_core::missing_token_info::load_missing_token_info;
+use brontes_database::{
+    libmdbx::{LibmdbxInit, LibmdbxReadWriter, VerifyReport},
+    Tables,
+};
+use brontes_types::{traits::TracingProvider, FastHashMap, FastHashSet};
+use clap::Parser;
+use comfy_table::{Cell, Row, Table as ComfyTable};
+use futures::{StreamExt, TryStreamExt};
+use indicatif::MultiProgress;
+use itertools::Itertools;
+use reth_primitives::BlockNumberOrTag;
+
+use crate::{
+    cli::{get_env_vars, get_tracing_provider, load_clickhouse, load_libmdbx, static_object},
+    runner::CliContext,
+};
+
+/// Tables that can be downloaded again for the blocks they are broken at
+const REFETCHABLE_TABLES: [Tables; 4] =
+    [Tables::BlockInfo, Tables::TxTraces, Tables::DexPrice, Tables::CexTrades];
+
+#[derive(Debug, Parser)]
+pub struct Verify {
+    /// Start Block
+    #[arg(long, short)]
+    pub start_block: u64,
+    /// End Block (inclusive)
+    #[arg(long, short)]
+    pub end_block:   u64,
+    /// Download the broken blocks of `BlockInfo`, `TxTraces`, `DexPrice` and
+    /// `CexTrades` again and query the missing token decimals from the node.
+    /// `MevBlocks` can only be regenerated by running brontes over the range
+    #[arg(long, default_value_t = false)]
+    pub repair:      bool,
+}
+
+impl Verify {
+    pub async fn execute(self, brontes_db_path: String, ctx: CliContext) -> eyre::Result<()> {
+        if self.start_block > self.end_block {
+            eyre::bail!("start block is after the end block");
+        }
+
+        let libmdbx = static_object(load_libmdbx(&ctx.task_executor, brontes_db_path)?);
+        let db_path = get_env_vars()?;
+        let tracer =
+            Arc::new(get_tracing_provider(Path::new(&db_path), 10, ctx.task_executor.clone()));
+
+        let mut report = self.verify(libmdbx, &tracer).await?;
+        print_report(&report);
+
+        if self.repair && !report.is_clean() {
+            self.repair(libmdbx, tracer.clone(), &report).await?;
+            tracing::info!("repair finished, verifying again");
+            report = self.verify(libmdbx, &tracer).await?;
+            print_report(&report);
+        }
+
+        if !report.is_clean() {
+            eyre::bail!("db failed verification for {}..={}", self.start_block, self.end_block);
+        }
+
+        Ok(())
+    }
+
+    async fn verify<T: TracingProvider>(
+        &self,
+        libmdbx: &'static LibmdbxReadWriter,
+        tracer: &Arc<T>,
+    ) -> eyre::Result<VerifyReport> {
+        let (start_block, end_block) = (self.start_block, self.end_block);
+
+        // the stored traces are complete when there is one per transaction of
+        // the block
+        let tx_counts = futures::stream::iter(start_block..=end_block)
+            .map(|block| async move {
+                let receipts = tracer
+                    .block_receipts(BlockNumberOrTag::Number(block))
+                    .await?;
+                eyre::Ok(receipts.map(|receipts| (block, receipts.len())))
+            })
+            .buffer_unordered(100)
+            .try_collect::<Vec<_>>()
+            .await?
+            .into_iter()
+            .flatten()
+            .collect::<FastHashMap<_, _>>();
+
+        let multi = MultiProgress::default();
+        let pb =
+            Tables::BlockInfo.build_init_state_progress_bar(&multi, end_block - start_block + 1);
+        pb.set_message("verifying blocks");
+
+        tokio::task::spawn_blocking(move || {
+            let report = libmdbx.verify_block_range(start_block, end_block, &tx_counts, Some(&pb));
+            pb.finish_and_clear();
+            report
+        })
+        .await?
+    }
+
+    async fn repair<T: TracingProvider>(
+        &self,
+        libmdbx: &'static LibmdbxReadWriter,
+        tracer: Arc<T>,
+        report: &VerifyReport,
+    ) -> eyre::Result<()> {
+        let clickhouse = static_object(load_clickhouse(Default::default(), None).await?);
+
+        let multi = MultiProgress::default();
+        let tables_with_progress = Arc::new(
+            REFETCHABLE_TABLES
+                .iter()
+                .map(|table| (*table, table.build_init_state_progress_bar(&multi, 0)))
+                .collect_vec(),
+        );
+
+        for table in REFETCHABLE_TABLES {
+            let blocks = report.broken_blocks(table);
+            if blocks.is_empty() {
+                continue
+            }
+            tracing::info!(?table, blocks = blocks.len(), "downloading broken blocks");
+            libmdbx
+                .initialize_table_arbitrary(
+                    clickhouse,
+                    tracer.clone(),
+                    table,
+                    blocks,
+                    tables_with_progress.clone(),
+                    false,
+                )
+                .await?;
+        }
+
+        let mut queried = FastHashSet::default();
+        for (_, token, init_block) in &report.missing_token_decimals {
+            if queried.insert(*token) {
+                load_missing_token_info(&tracer, libmdbx, *init_block, *token).await;
+            }
+        }
+
+        let mev_blocks = report.broken_blocks(Tables::MevBlocks);
+        if let (Some(first), Some(last)) = (mev_blocks.first(), mev_blocks.last()) {
+            tracing::warn!(
+                blocks = mev_blocks.len(),
+                "mev blocks can't be downloaded, regenerate them with `brontes run --ranges \
+                 {first}-{last}`"
+            );
+        }
+
+        Ok(())
+    }
+}
+
+fn print_report(report: &VerifyReport) {
+    if report.is_clean() {
+        println!("no issues found");
+        return
+    }
+
+    let mut table = ComfyTable::new();
+    table.load_preset(comfy_table::presets::ASCII_MARKDOWN);
+    table.set_header(["Table Name", "Missing Blocks", "Corrupt Blocks", "First Broken Block"]);
+    for db_table in [
+        Tables::BlockInfo,
+        Tables::TxTraces,
+        Tables::DexPrice,
+        Tables::CexTrades,
+        Tables::MevBlocks,
+    ] {
+        let missing = report
+            .missing
+            .get(&db_table)
+            .map(Vec::len)
+            .unwrap_or_default();
+        let undecodable = report
+            .undecodable
+            .get(&db_table)
+            .map(Vec::len)
+            .unwrap_or_default();
+        let first = report.broken_blocks(db_table).first().copied();
+
+        let mut row = Row::new();
+        row.add_cell(Cell::new(db_table.name()))
+            .add_cell(Cell::new(missing))
+            .add_cell(Cell::new(undecodable))
+            .add_cell(Cell::new(first.map(|b| b.to_string()).unwrap_or_default()));
+        table.add_row(row);
+    }
+    println!("{table}");
+
+    for (block, inconsistency) in &report.inconsistent_traces {
+        println!("traces of block {block}: {inconsistency}");
+    }
+    if report.undecodable_protocols != 0 {
+        println!("{} AddressToProtocolInfo rows are corrupt", report.undecodable_protocols);
+    }
+    println!(
+        "corrupt rows have a broken zstd frame or are too short for the stored value, other \
+         corruption isn't detected"
+    );
+    for (pool, token, _) in &report.missing_token_decimals {
+        println!("token {token:?} of pool {pool:?} has no TokenDecimals entry");
+    }
+}
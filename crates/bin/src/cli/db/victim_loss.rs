@@ -0,0 +1,146 @@
+use std::{path::Path, sync::Arc};
+
+use alloy_primitives::Address;
+use brontes_inspect::victim_loss::VictimLossCalculator;
+use brontes_types::{
+    constants::USDT_ADDRESS_STRING,
+    db::{
+        mev_block::MevBlockWithClassified,
+        traits::{DBWriter, LibmdbxReader},
+    },
+    mev::BundleData,
+    traits::TracingProvider,
+};
+use clap::Parser;
+use futures::StreamExt;
+
+use crate::{
+    cli::{
+        confirm, determine_max_tasks, get_env_vars, get_tracing_provider, load_libmdbx,
+        static_object,
+    },
+    runner::CliContext,
+};
+
+#[derive(Debug, Parser)]
+pub struct VictimLoss {
+    /// Start Block
+    #[arg(long, short)]
+    pub start_block: u64,
+    /// End Block (inclusive)
+    #[arg(long, short)]
+    pub end_block:   u64,
+    /// Quote asset the dex prices in the db are denominated in
+    #[arg(long, short, default_value = USDT_ADDRESS_STRING)]
+    pub quote_asset: String,
+    /// Max number of blocks to simulate concurrently
+    #[arg(long)]
+    pub max_tasks:   Option<u64>,
+    /// Overwrite the stored mev blocks without asking for confirmation
+    #[arg(long, default_value_t = false)]
+    pub yes:         bool,
+}
+
+impl VictimLoss {
+    pub async fn execute(self, brontes_db_path: String, ctx: CliContext) -> eyre::Result<()> {
+        let db_path = get_env_vars()?;
+        let quote_asset: Address = self.quote_asset.parse()?;
+        let max_tasks = determine_max_tasks(self.max_tasks);
+
+        let libmdbx = static_object(load_libmdbx(&ctx.task_executor, brontes_db_path)?);
+        let tracer = static_object(get_tracing_provider(
+            Path::new(&db_path),
+            max_tasks,
+            ctx.task_executor.clone(),
+        ));
+        let calculator = static_object(VictimLossCalculator::new(quote_asset, libmdbx));
+
+        let mev_blocks = libmdbx.try_fetch_mev_blocks(Some(self.start_block), self.end_block)?;
+        let mut blocks = futures::stream::iter(mev_blocks)
+            .map(|mev_block| async move {
+                let block = mev_block.block.block_number;
+                simulate_block(mev_block, quote_asset, libmdbx, tracer, calculator)
+                    .await
+                    .map_err(|e| tracing::error!(%block, err=%e, "failed to simulate victims"))
+                    .ok()
+                    .flatten()
+            })
+            .buffer_unordered(max_tasks as usize);
+
+        let mut sandwiches = 0usize;
+        let mut loss_usd = 0.0;
+        let mut updated = Vec::new();
+        while let Some(result) = blocks.next().await {
+            let Some(mev_block) = result else { continue };
+
+            for bundle in &mev_block.mev {
+                if let BundleData::Sandwich(sandwich) = &bundle.data {
+                    sandwiches += 1;
+                    loss_usd += sandwich
+                        .victim_losses
+                        .iter()
+                        .map(|loss| loss.amount_lost_usd)
+                        .sum::<f64>();
+                }
+            }
+            updated.push(mev_block);
+        }
+
+        println!(
+            "simulated the victims of {sandwiches} sandwiches between {} and {}, total loss \
+             ${loss_usd:.2}",
+            self.start_block, self.end_block
+        );
+        if updated.is_empty() {
+            return Ok(())
+        }
+        if !self.yes && !confirm(&format!("overwrite the stored mev of {} blocks?", updated.len()))?
+        {
+            println!("aborted, nothing was written");
+            return Ok(())
+        }
+
+        for mev_block in updated {
+            libmdbx
+                .save_mev_blocks(mev_block.block.block_number, mev_block.block, mev_block.mev)
+                .await?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Simulates the victims of every sandwich in the block, returns the block
+/// with the victim losses set or `None` if it has no sandwiches
+async fn simulate_block<DB: LibmdbxReader, T: TracingProvider>(
+    mut mev_block: MevBlockWithClassified,
+    quote_asset: Address,
+    libmdbx: &'static DB,
+    tracer: &'static T,
+    calculator: &'static VictimLossCalculator<'static, DB>,
+) -> eyre::Result<Option<MevBlockWithClassified>> {
+    let block = mev_block.block.block_number;
+    if !mev_block
+        .mev
+        .iter()
+        .any(|bundle| matches!(bundle.data, BundleData::Sandwich(_)))
+    {
+        return Ok(None)
+    }
+    let metadata = Arc::new(libmdbx.get_metadata(block, quote_asset)?);
+
+    for bundle in &mut mev_block.mev {
+        let BundleData::Sandwich(sandwich) = &mut bundle.data else { continue };
+        let (skip, victims) = VictimLossCalculator::<DB>::replayed_txs(sandwich);
+        let simulated = tracer.replay_txs_without(block, skip, victims).await?;
+
+        sandwich.victim_losses = calculator.calculate_victim_losses(
+            sandwich,
+            bundle.header.tx_index as usize,
+            &simulated,
+            &metadata,
+        );
+    }
+
+    Ok(Some(mev_block))
+}
@@ -0,0 +1,551 @@
+use std::{
+    convert::Infallible,
+    env::temp_dir,
+    net::SocketAddr,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use brontes_database::libmdbx::{merge_libmdbx_dbs, LibmdbxReadWriter};
+use clap::Parser;
+use eyre::WrapErr;
+use flate2::read::GzDecoder;
+use hyper::{
+    body::HttpBody,
+    service::{make_service_fn, service_fn},
+    Body, Method, Request, Response, Server, StatusCode,
+};
+use itertools::Itertools;
+use tar::Archive;
+use tokio::{io::AsyncWriteExt, sync::Notify};
+
+use super::{Chunk, ChunkRequest, ChunkResponse};
+use crate::runner::CliContext;
+
+const DEFAULT_PARTITION_DIR: &str = "brontes-distributed-partitions";
+
+#[derive(Debug, Parser)]
+pub struct Coordinator {
+    /// Start Block
+    #[arg(long, short)]
+    pub start_block:      u64,
+    /// End Block (inclusive)
+    #[arg(long, short)]
+    pub end_block:        u64,
+    /// Amount of blocks handed to a worker at a time
+    #[arg(long, default_value_t = 10_000)]
+    pub chunk_size:       u64,
+    /// Address the coordinator listens on for workers. Only listens locally by
+    /// default, pass a public address to accept remote workers
+    #[arg(long, default_value = "127.0.0.1:7700")]
+    pub listen:           SocketAddr,
+    /// Shared secret workers have to send with every request
+    #[arg(long, env = "BRONTES_DISTRIBUTED_TOKEN", hide_env_values = true)]
+    pub token:            String,
+    /// Seconds a worker has to return its chunk before the chunk is handed
+    /// out again
+    #[arg(long, default_value_t = 21_600)]
+    pub lease_timeout:    u64,
+    /// Directory the returned partitions are unpacked into before they are
+    /// merged, defaults to a folder in the tmp dir
+    #[arg(long)]
+    pub partition_dir:    Option<PathBuf>,
+    /// the amount of dbs to merge at a time
+    #[clap(short, long, default_value_t = 10)]
+    rayon_tasks_db_merge: usize,
+}
+
+impl Coordinator {
+    pub async fn execute(self, brontes_db_path: String, ctx: CliContext) -> eyre::Result<()> {
+        if self.chunk_size == 0 || self.start_block > self.end_block {
+            eyre::bail!("invalid range or chunk size");
+        }
+        if self.token.is_empty() {
+            eyre::bail!("the worker token can't be empty");
+        }
+
+        let partition_dir = self.partition_dir.clone().unwrap_or_else(|| {
+            let mut dir = temp_dir();
+            dir.push(DEFAULT_PARTITION_DIR);
+            dir
+        });
+        fs_extra::dir::create_all(&partition_dir, false)?;
+        let upload_dir = upload_dir(&partition_dir);
+        fs_extra::dir::create_all(&upload_dir, false)?;
+
+        let chunks = split_range(self.start_block, self.end_block, self.chunk_size);
+        tracing::info!(chunks = chunks.len(), listen = %self.listen, "starting coordinator");
+
+        let queue = Arc::new(ChunkQueue::new(
+            chunks,
+            partition_dir.clone(),
+            upload_dir.clone(),
+            self.token.clone(),
+            Duration::from_secs(self.lease_timeout),
+        ));
+
+        let make_svc = make_service_fn(|_| {
+            let queue = queue.clone();
+            async move {
+                Ok::<_, Infallible>(service_fn(move |req: Request<Body>| {
+                    let queue = queue.clone();
+                    async move { Ok::<_, Infallible>(queue.handle(req).await) }
+                }))
+            }
+        });
+
+        let finished = queue.finished.clone();
+        Server::try_bind(&self.listen)
+            .wrap_err("Could not bind to address")?
+            .serve(make_svc)
+            .with_graceful_shutdown(async move { finished.notified().await })
+            .await?;
+
+        tracing::info!(
+            "all chunks returned, merging partitions into the db at: {}",
+            brontes_db_path
+        );
+        let final_db =
+            LibmdbxReadWriter::init_db(brontes_db_path, None, &ctx.task_executor, false)?;
+
+        let db = partition_dir.clone();
+        let ex = ctx.task_executor.clone();
+        ctx.task_executor
+            .spawn_blocking(async move {
+                merge_libmdbx_dbs(final_db, &db, ex, self.rayon_tasks_db_merge).unwrap();
+            })
+            .await?;
+
+        tracing::info!("cleaning up tmp libmdbx partitions");
+        fs_extra::dir::remove(partition_dir)?;
+        fs_extra::dir::remove(upload_dir)?;
+
+        Ok(())
+    }
+}
+
+/// Splits the inclusive range into chunks of at most `chunk_size` blocks
+fn split_range(start_block: u64, end_block: u64, chunk_size: u64) -> Vec<Chunk> {
+    (start_block..=end_block)
+        .step_by(chunk_size as usize)
+        .enumerate()
+        .map(|(id, start_block)| Chunk {
+            id,
+            start_block,
+            end_block: (start_block + chunk_size - 1).min(end_block),
+        })
+        .collect_vec()
+}
+
+/// Uploads are unpacked next to the partition dir, as every directory in the
+/// partition dir is merged as a partition
+fn upload_dir(partition_dir: &Path) -> PathBuf {
+    let mut name = partition_dir.file_name().unwrap_or_default().to_os_string();
+    name.push("-uploads");
+    partition_dir.with_file_name(name)
+}
+
+#[derive(Debug, Clone)]
+enum ChunkStatus {
+    Pending,
+    Leased { worker: String, until: Instant },
+    Done,
+}
+
+struct ChunkQueue {
+    chunks:        Mutex<Vec<(Chunk, ChunkStatus)>>,
+    partition_dir: PathBuf,
+    upload_dir:    PathBuf,
+    token:         String,
+    lease_timeout: Duration,
+    finished:      Arc<Notify>,
+}
+
+impl ChunkQueue {
+    fn new(
+        chunks: Vec<Chunk>,
+        partition_dir: PathBuf,
+        upload_dir: PathBuf,
+        token: String,
+        lease_timeout: Duration,
+    ) -> Self {
+        Self {
+            chunks: Mutex::new(
+                chunks
+                    .into_iter()
+                    .map(|chunk| (chunk, ChunkStatus::Pending))
+                    .collect(),
+            ),
+            partition_dir,
+            upload_dir,
+            token,
+            lease_timeout,
+            finished: Arc::new(Notify::new()),
+        }
+    }
+
+    async fn handle(&self, req: Request<Body>) -> Response<Body> {
+        if !self.is_authorized(&req) {
+            return status(StatusCode::UNAUTHORIZED)
+        }
+
+        let path = req.uri().path().trim_matches('/').to_string();
+        let parts = path.split('/').collect_vec();
+
+        let res = match (req.method(), parts.as_slice()) {
+            (&Method::POST, ["chunk"]) => self.lease(req.into_body()).await,
+            (&Method::POST, ["chunk", id]) => match id.parse() {
+                Ok(id) => self.receive_partition(id, req.into_body()).await,
+                Err(_) => Ok(status(StatusCode::BAD_REQUEST)),
+            },
+            (&Method::POST, ["chunk", id, "failed"]) => match id.parse() {
+                Ok(id) => Ok(self.release(id)),
+                Err(_) => Ok(status(StatusCode::BAD_REQUEST)),
+            },
+            _ => Ok(status(StatusCode::NOT_FOUND)),
+        };
+
+        res.unwrap_or_else(|e| {
+            tracing::error!(%path, err=%e, "failed to handle worker request");
+            Response::builder()
+                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                .body(Body::from(e.to_string()))
+                .unwrap()
+        })
+    }
+
+    /// Hands out the first chunk that is pending or whose lease ran out
+    async fn lease(&self, body: Body) -> eyre::Result<Response<Body>> {
+        let ChunkRequest { worker } = serde_json::from_slice(&hyper::body::to_bytes(body).await?)?;
+        let now = Instant::now();
+
+        let res = {
+            let mut chunks = self.chunks.lock().unwrap();
+            if let Some((chunk, status)) = chunks.iter_mut().find(|(_, status)| match status {
+                ChunkStatus::Pending => true,
+                ChunkStatus::Leased { until, .. } => *until < now,
+                ChunkStatus::Done => false,
+            }) {
+                if let ChunkStatus::Leased { worker: prev, .. } = status {
+                    tracing::warn!(id = chunk.id, worker = %prev, "lease expired, handing out again");
+                }
+                *status = ChunkStatus::Leased {
+                    worker: worker.clone(),
+                    until:  now + self.lease_timeout,
+                };
+                tracing::info!(
+                    id = chunk.id,
+                    start_block = chunk.start_block,
+                    end_block = chunk.end_block,
+                    %worker,
+                    "leased chunk"
+                );
+                ChunkResponse::Assigned(*chunk)
+            } else if chunks
+                .iter()
+                .all(|(_, status)| matches!(status, ChunkStatus::Done))
+            {
+                ChunkResponse::Done
+            } else {
+                ChunkResponse::Wait
+            }
+        };
+
+        Ok(Response::new(Body::from(serde_json::to_vec(&res)?)))
+    }
+
+    fn is_authorized(&self, req: &Request<Body>) -> bool {
+        req.headers()
+            .get(hyper::header::AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .is_some_and(|token| constant_time_eq(token.as_bytes(), self.token.as_bytes()))
+    }
+
+    /// Streams the uploaded partition tarball to disk and unpacks it into a
+    /// directory of its own, so uploads of the same chunk can't interleave. The
+    /// first upload to finish is moved into the partition dir
+    async fn receive_partition(&self, id: usize, body: Body) -> eyre::Result<Response<Body>> {
+        let Some(chunk) = self.chunk(id) else { return Ok(status(StatusCode::NOT_FOUND)) };
+        if self.is_done(id) {
+            return Ok(status(StatusCode::CONFLICT))
+        }
+
+        let mut upload = self.upload_dir.clone();
+        upload.push(format!("{}-{:x}", chunk.partition_name(), rand::random::<u64>()));
+        let res = self.unpack_upload(chunk, body, &upload).await;
+
+        let accepted = res.and_then(|unpacked| {
+            let mut chunks = self.chunks.lock().unwrap();
+            if matches!(chunks[id].1, ChunkStatus::Done) {
+                return Ok(false)
+            }
+
+            let mut partition = self.partition_dir.clone();
+            partition.push(chunk.partition_name());
+            // left over from an earlier coordinator run on the same dir
+            if partition.exists() {
+                fs_extra::dir::remove(&partition)?;
+            }
+            std::fs::rename(unpacked, partition)?;
+
+            chunks[id].1 = ChunkStatus::Done;
+            let done = chunks
+                .iter()
+                .filter(|(_, status)| matches!(status, ChunkStatus::Done))
+                .count();
+            tracing::info!(id, "received partition, {}/{} chunks done", done, chunks.len());
+            if done == chunks.len() {
+                self.finished.notify_one();
+            }
+
+            Ok(true)
+        });
+        fs_extra::dir::remove(&upload)?;
+
+        if accepted? {
+            Ok(status(StatusCode::OK))
+        } else {
+            Ok(status(StatusCode::CONFLICT))
+        }
+    }
+
+    /// Writes the tarball into the upload dir and unpacks it there, returns
+    /// the unpacked partition
+    async fn unpack_upload(
+        &self,
+        chunk: Chunk,
+        mut body: Body,
+        upload: &Path,
+    ) -> eyre::Result<PathBuf> {
+        tokio::fs::create_dir_all(upload).await?;
+
+        let mut tarball = upload.to_path_buf();
+        tarball.push(format!("{}.tar.gz", chunk.partition_name()));
+        let mut file = tokio::fs::File::create(&tarball).await?;
+        while let Some(bytes) = body.data().await {
+            file.write_all(&bytes?).await?;
+        }
+        file.flush().await?;
+        drop(file);
+
+        let mut unpacked = upload.to_path_buf();
+        unpacked.push(chunk.partition_name());
+        let upload = upload.to_path_buf();
+        tokio::task::spawn_blocking(move || {
+            let tar = GzDecoder::new(std::fs::File::open(&tarball)?);
+            Archive::new(tar).unpack(&upload)?;
+            if !unpacked.is_dir() {
+                eyre::bail!("upload doesn't contain {}", unpacked.display());
+            }
+
+            Ok(unpacked)
+        })
+        .await?
+    }
+
+    /// Puts a chunk a worker failed to run back in the queue
+    fn release(&self, id: usize) -> Response<Body> {
+        let mut chunks = self.chunks.lock().unwrap();
+        let Some((chunk, status)) = chunks.get_mut(id) else {
+            return self::status(StatusCode::NOT_FOUND)
+        };
+
+        if let ChunkStatus::Leased { worker, .. } = status {
+            tracing::warn!(id = chunk.id, %worker, "worker failed chunk, requeueing");
+            *status = ChunkStatus::Pending;
+        }
+
+        self::status(StatusCode::OK)
+    }
+
+    fn chunk(&self, id: usize) -> Option<Chunk> {
+        self.chunks.lock().unwrap().get(id).map(|(chunk, _)| *chunk)
+    }
+
+    fn is_done(&self, id: usize) -> bool {
+        matches!(self.chunks.lock().unwrap().get(id), Some((_, ChunkStatus::Done)))
+    }
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |acc, (a, b)| acc | (a ^ b)) == 0
+}
+
+fn status(code: StatusCode) -> Response<Body> {
+    Response::builder()
+        .status(code)
+        .body(Body::empty())
+        .unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::distributed::worker::Worker;
+
+    const TOKEN: &str = "secret";
+
+    fn queue(chunks: Vec<Chunk>, dir: &Path, lease_timeout: Duration) -> ChunkQueue {
+        let partition_dir = dir.join("partitions");
+        let upload_dir = upload_dir(&partition_dir);
+        std::fs::create_dir_all(&partition_dir).unwrap();
+        std::fs::create_dir_all(&upload_dir).unwrap();
+
+        ChunkQueue::new(chunks, partition_dir, upload_dir, TOKEN.to_string(), lease_timeout)
+    }
+
+    fn request(path: &str, token: Option<&str>, body: Body) -> Request<Body> {
+        let mut req = Request::builder().method(Method::POST).uri(path);
+        if let Some(token) = token {
+            req = req.header(hyper::header::AUTHORIZATION, format!("Bearer {token}"));
+        }
+        req.body(body).unwrap()
+    }
+
+    async fn lease(queue: &ChunkQueue) -> ChunkResponse {
+        let body = serde_json::to_vec(&ChunkRequest { worker: "worker".to_string() }).unwrap();
+        let res = queue
+            .handle(request("/chunk", Some(TOKEN), Body::from(body)))
+            .await;
+        assert_eq!(res.status(), StatusCode::OK);
+
+        serde_json::from_slice(&hyper::body::to_bytes(res.into_body()).await.unwrap()).unwrap()
+    }
+
+    fn leased_id(res: ChunkResponse) -> usize {
+        match res {
+            ChunkResponse::Assigned(chunk) => chunk.id,
+            res => panic!("expected a chunk, got {res:?}"),
+        }
+    }
+
+    /// A packed partition the way a worker uploads it
+    fn tarball(dir: &Path, chunk: Chunk) -> Vec<u8> {
+        let partition = dir.join(format!("worker-{}", chunk.id));
+        std::fs::create_dir_all(&partition).unwrap();
+        std::fs::write(partition.join("mdbx.dat"), [chunk.id as u8; 64]).unwrap();
+
+        let tarball = dir.join(format!("{}.tar.gz", chunk.partition_name()));
+        Worker::pack(&partition, &tarball, chunk).unwrap();
+        std::fs::read(tarball).unwrap()
+    }
+
+    async fn upload(queue: &ChunkQueue, id: usize, tarball: Vec<u8>) -> StatusCode {
+        queue
+            .handle(request(&format!("/chunk/{id}"), Some(TOKEN), Body::from(tarball)))
+            .await
+            .status()
+    }
+
+    fn partitions(queue: &ChunkQueue) -> Vec<String> {
+        std::fs::read_dir(&queue.partition_dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().to_string())
+            .sorted()
+            .collect()
+    }
+
+    #[test]
+    fn test_split_range() {
+        let chunks = split_range(0, 24, 10);
+        assert_eq!(
+            chunks
+                .iter()
+                .map(|chunk| (chunk.id, chunk.start_block, chunk.end_block))
+                .collect_vec(),
+            vec![(0, 0, 9), (1, 10, 19), (2, 20, 24)]
+        );
+
+        assert_eq!(split_range(5, 5, 10).len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_requests_need_the_token() {
+        let dir = tempfile::tempdir().unwrap();
+        let queue = queue(split_range(0, 9, 10), dir.path(), Duration::from_secs(60));
+
+        for token in [None, Some("wrong"), Some("")] {
+            let res = queue.handle(request("/chunk", token, Body::empty())).await;
+            assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_chunk_assignment() {
+        let dir = tempfile::tempdir().unwrap();
+        let queue = queue(split_range(0, 29, 10), dir.path(), Duration::from_secs(60));
+
+        assert_eq!(leased_id(lease(&queue).await), 0);
+        assert_eq!(leased_id(lease(&queue).await), 1);
+        assert_eq!(leased_id(lease(&queue).await), 2);
+        assert!(matches!(lease(&queue).await, ChunkResponse::Wait));
+
+        // a failed chunk is handed out again
+        let res = queue
+            .handle(request("/chunk/1/failed", Some(TOKEN), Body::empty()))
+            .await;
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(leased_id(lease(&queue).await), 1);
+
+        for id in 0..3 {
+            let chunk = queue.chunk(id).unwrap();
+            assert_eq!(upload(&queue, id, tarball(dir.path(), chunk)).await, StatusCode::OK);
+        }
+        assert!(matches!(lease(&queue).await, ChunkResponse::Done));
+        tokio::time::timeout(Duration::from_secs(1), queue.finished.notified())
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_expired_lease_is_handed_out_again() {
+        let dir = tempfile::tempdir().unwrap();
+        let queue = queue(split_range(0, 9, 10), dir.path(), Duration::ZERO);
+
+        assert_eq!(leased_id(lease(&queue).await), 0);
+        tokio::time::sleep(Duration::from_millis(5)).await;
+        assert_eq!(leased_id(lease(&queue).await), 0);
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_uploads_of_a_chunk() {
+        let dir = tempfile::tempdir().unwrap();
+        let queue = queue(split_range(0, 19, 10), dir.path(), Duration::ZERO);
+        let chunk = queue.chunk(0).unwrap();
+        let tarball = tarball(dir.path(), chunk);
+
+        // the lease ran out, so two workers finished the same chunk
+        let (a, b) =
+            tokio::join!(upload(&queue, 0, tarball.clone()), upload(&queue, 0, tarball.clone()));
+        let mut statuses = vec![a, b];
+        statuses.sort();
+        assert_eq!(statuses, vec![StatusCode::OK, StatusCode::CONFLICT]);
+        assert_eq!(upload(&queue, 0, tarball).await, StatusCode::CONFLICT);
+
+        // only the accepted upload is left to merge
+        assert_eq!(partitions(&queue), vec![chunk.partition_name()]);
+        let merged = queue
+            .partition_dir
+            .join(chunk.partition_name())
+            .join("mdbx.dat");
+        assert_eq!(std::fs::read(merged).unwrap(), vec![0; 64]);
+        assert_eq!(std::fs::read_dir(&queue.upload_dir).unwrap().count(), 0);
+        assert!(!queue.is_done(1));
+    }
+
+    #[tokio::test]
+    async fn test_upload_without_partition_is_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let queue = queue(split_range(0, 19, 10), dir.path(), Duration::from_secs(60));
+
+        // a partition of another chunk
+        let tarball = tarball(dir.path(), queue.chunk(1).unwrap());
+        assert_eq!(upload(&queue, 0, tarball).await, StatusCode::INTERNAL_SERVER_ERROR);
+
+        assert!(!queue.is_done(0));
+        assert!(partitions(&queue).is_empty());
+        assert_eq!(std::fs::read_dir(&queue.upload_dir).unwrap().count(), 0);
+    }
+}
@@ -0,0 +1,70 @@
+use clap::{Parser, Subcommand};
+use serde::{Deserialize, Serialize};
+
+use crate::runner::CliContext;
+
+mod coordinator;
+mod worker;
+
+/// Spreads a historical range over multiple machines. The coordinator splits
+/// the range into chunks and hands them out over http, each worker runs brontes
+/// on its chunk against its own node and db and uploads the resulting libmdbx
+/// partition, which the coordinator merges into its db once every chunk is done
+#[derive(Debug, Parser)]
+pub struct Distributed {
+    #[clap(subcommand)]
+    pub command: DistributedCommands,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum DistributedCommands {
+    /// Hands out block chunks to workers and merges the partitions they return
+    #[command(name = "coordinator")]
+    Coordinator(coordinator::Coordinator),
+    /// Runs the chunks handed out by a coordinator
+    #[command(name = "worker")]
+    Worker(worker::Worker),
+}
+
+impl Distributed {
+    pub async fn execute(self, brontes_db_path: String, ctx: CliContext) -> eyre::Result<()> {
+        match self.command {
+            DistributedCommands::Coordinator(cmd) => cmd.execute(brontes_db_path, ctx).await,
+            DistributedCommands::Worker(cmd) => cmd.execute(brontes_db_path, ctx).await,
+        }
+    }
+}
+
+/// A range of blocks, both ends inclusive
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Chunk {
+    pub id:          usize,
+    pub start_block: u64,
+    pub end_block:   u64,
+}
+
+impl Chunk {
+    pub fn partition_name(&self) -> String {
+        format!(
+            "{}-{}-{}",
+            brontes_database::libmdbx::PARTITION_FILE_NAME,
+            self.start_block,
+            self.end_block
+        )
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ChunkRequest {
+    pub worker: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum ChunkResponse {
+    Assigned(Chunk),
+    /// every chunk is either done or leased, ask again later
+    Wait,
+    /// every chunk is done
+    Done,
+}
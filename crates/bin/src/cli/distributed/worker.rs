@@ -0,0 +1,166 @@
+use std::{env::temp_dir, path::Path, time::Duration};
+
+use brontes_database::libmdbx::LibmdbxReadWriter;
+use clap::Parser;
+use flate2::{write::GzEncoder, Compression};
+use reqwest::Url;
+
+use super::{Chunk, ChunkRequest, ChunkResponse};
+use crate::runner::CliContext;
+
+#[derive(Debug, Parser)]
+pub struct Worker {
+    /// Url of the coordinator
+    #[arg(long, default_value = "http://127.0.0.1:7700/")]
+    pub coordinator:    Url,
+    /// Shared secret set on the coordinator
+    #[arg(long, env = "BRONTES_DISTRIBUTED_TOKEN", hide_env_values = true)]
+    pub token:          String,
+    /// Name the worker reports to the coordinator, defaults to a random id
+    #[arg(long)]
+    pub name:           Option<String>,
+    /// Also ship the tx traces of the chunk back to the coordinator
+    #[arg(long, default_value_t = false)]
+    pub include_traces: bool,
+    /// Seconds to wait before asking for work again while every remaining
+    /// chunk is leased to other workers
+    #[arg(long, default_value_t = 30)]
+    pub poll_interval:  u64,
+    /// Arguments passed through to `brontes run` for every chunk, e.g.
+    /// `brontes distributed worker -- --max-tasks 20`
+    #[arg(last = true)]
+    pub run_args:       Vec<String>,
+}
+
+impl Worker {
+    pub async fn execute(self, brontes_db_path: String, ctx: CliContext) -> eyre::Result<()> {
+        let client = reqwest::Client::new();
+        let name = self
+            .name
+            .clone()
+            .unwrap_or_else(|| format!("worker-{:x}", rand::random::<u32>()));
+        tracing::info!(%name, coordinator = %self.coordinator, "starting worker");
+
+        loop {
+            let res: ChunkResponse = client
+                .post(self.coordinator.join("chunk")?)
+                .bearer_auth(&self.token)
+                .json(&ChunkRequest { worker: name.clone() })
+                .send()
+                .await?
+                .error_for_status()?
+                .json()
+                .await?;
+
+            match res {
+                ChunkResponse::Assigned(chunk) => {
+                    if let Err(e) = self.run_chunk(chunk, &brontes_db_path, &client, &ctx).await {
+                        tracing::error!(id = chunk.id, err=%e, "failed to run chunk");
+                        let _ = client
+                            .post(
+                                self.coordinator
+                                    .join(&format!("chunk/{}/failed", chunk.id))?,
+                            )
+                            .bearer_auth(&self.token)
+                            .send()
+                            .await
+                            .inspect_err(|e| tracing::error!(err=%e, "failed to release chunk"));
+                    }
+                }
+                ChunkResponse::Wait => {
+                    tokio::time::sleep(Duration::from_secs(self.poll_interval)).await
+                }
+                ChunkResponse::Done => break,
+            }
+        }
+
+        tracing::info!("no chunks left, shutting down");
+        Ok(())
+    }
+
+    /// Runs brontes over the chunk, then packs the chunk out of the local db
+    /// and uploads it to the coordinator
+    async fn run_chunk(
+        &self,
+        chunk: Chunk,
+        brontes_db_path: &str,
+        client: &reqwest::Client,
+        ctx: &CliContext,
+    ) -> eyre::Result<()> {
+        tracing::info!(
+            id = chunk.id,
+            start_block = chunk.start_block,
+            end_block = chunk.end_block,
+            "running chunk"
+        );
+
+        // brontes run in its own process so each chunk starts from a clean state
+        let status = tokio::process::Command::new(std::env::current_exe()?)
+            .arg("run")
+            .arg("--ranges")
+            .arg(format!("{}-{}", chunk.start_block, chunk.end_block))
+            .arg("--brontes-db-path")
+            .arg(brontes_db_path)
+            .args(&self.run_args)
+            .kill_on_drop(true)
+            .status()
+            .await?;
+        if !status.success() {
+            eyre::bail!("brontes run exited with {status}");
+        }
+
+        let mut dir = temp_dir();
+        dir.push(format!("brontes-distributed-{}", chunk.partition_name()));
+        if dir.exists() {
+            fs_extra::dir::remove(&dir)?;
+        }
+        let mut partition = dir.clone();
+        partition.push(chunk.partition_name());
+        fs_extra::dir::create_all(&partition, false)?;
+
+        let db = LibmdbxReadWriter::init_db(brontes_db_path, None, &ctx.task_executor, false)?;
+        let partition_db = LibmdbxReadWriter::init_db(&partition, None, &ctx.task_executor, false)?;
+        let include_traces = self.include_traces;
+        tokio::task::spawn_blocking(move || {
+            db.write_block_range_partition(
+                chunk.start_block,
+                chunk.end_block + 1,
+                &partition_db,
+                include_traces,
+            )?;
+            // the local db keeps the searcher totals of every chunk this worker ran, so
+            // only the activity of this chunk is shipped
+            db.write_range_searcher_activity(chunk.start_block, chunk.end_block + 1, &partition_db)
+        })
+        .await??;
+
+        let mut tarball = dir.clone();
+        tarball.push(format!("{}.tar.gz", chunk.partition_name()));
+        let tar_path = tarball.clone();
+        tokio::task::spawn_blocking(move || Self::pack(&partition, &tar_path, chunk)).await??;
+
+        tracing::info!(id = chunk.id, "uploading partition");
+        let file = tokio::fs::File::open(&tarball).await?;
+        client
+            .post(self.coordinator.join(&format!("chunk/{}", chunk.id))?)
+            .bearer_auth(&self.token)
+            .body(file)
+            .send()
+            .await?
+            .error_for_status()?;
+
+        fs_extra::dir::remove(dir)?;
+        tracing::info!(id = chunk.id, "chunk complete");
+
+        Ok(())
+    }
+
+    pub(super) fn pack(partition: &Path, tarball: &Path, chunk: Chunk) -> eyre::Result<()> {
+        let enc = GzEncoder::new(std::fs::File::create(tarball)?, Compression::default());
+        let mut tar = tar::Builder::new(enc);
+        tar.append_dir_all(chunk.partition_name(), partition)?;
+        tar.into_inner()?.finish()?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,343 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use alloy_primitives::{Address, B256};
+use brontes_classifier::Classifier;
+use brontes_database::libmdbx::LibmdbxReadWriter;
+use brontes_inspect::{composer::explain_block_inspection, Inspectors};
+use brontes_types::{
+    constants::USDT_ADDRESS_STRING,
+    db::{
+        cex::{CexExchange, CexFeeSchedule},
+        traits::LibmdbxReader,
+    },
+    init_thread_pools,
+    mev::Mev,
+    normalized_actions::{Action, ActionAccounting},
+    traits::TracingProvider,
+    tree::{Node, Root},
+    BlockData, MultiBlockData, ToFloatNearest,
+};
+use clap::Parser;
+use itertools::Itertools;
+use tokio::sync::mpsc::unbounded_channel;
+
+use super::run::TimeWindowArgs;
+use crate::{
+    cli::{
+        determine_max_tasks, get_tracing_provider, init_inspectors, load_libmdbx,
+        load_stored_block, reth_db_path, static_object,
+    },
+    runner::CliContext,
+};
+
+#[derive(Debug, Parser)]
+pub struct Explain {
+    /// Hash of the transaction to explain
+    #[arg(long)]
+    pub tx:                 B256,
+    /// Block the transaction is in, looked up from the node if omitted
+    #[arg(long, short)]
+    pub block:              Option<u64>,
+    /// Quote asset used to value the mev, if omitted it will default to USDT
+    #[arg(long, short, default_value = USDT_ADDRESS_STRING)]
+    pub quote_asset:        String,
+    /// Additional quote assets that profits are also denominated in, e.g. WETH.
+    /// None by default. Apart from WETH, which uses the block's ETH price, an
+    /// asset is only resolved in blocks where the pricer priced it against the
+    /// quote asset
+    #[arg(long, value_delimiter = ',')]
+    pub extra_quote_assets: Vec<String>,
+    /// Inspectors to run. If omitted it defaults to running all inspectors
+    #[arg(long, short, value_delimiter = ',')]
+    pub inspectors:         Option<Vec<Inspectors>>,
+    /// Time window arguments for cex data
+    #[clap(flatten)]
+    pub time_window_args:   TimeWindowArgs,
+    /// CEX exchanges to consider for cex-dex analysis. The perpetual venues
+    /// (BinanceUsdm, BybitLinear, OkexSwap) are opt in
+    #[arg(
+        long,
+        short,
+        default_value = "Binance,Coinbase,Okex,BybitSpot,Kucoin",
+        value_delimiter = ','
+    )]
+    pub cex_exchanges:      Vec<CexExchange>,
+    /// Maker & taker fee schedule of the cex exchanges, defaults to
+    /// `config/cex_fee_config.toml` of the current directory
+    #[arg(long, env = "BRONTES_CEX_FEE_CONFIG")]
+    pub cex_fee_config:     Option<PathBuf>,
+    /// Optional Max Tasks, if omitted it will default to 80% of the number of
+    /// physical cores on your machine
+    #[arg(long)]
+    pub max_tasks:          Option<u64>,
+}
+
+impl Explain {
+    pub async fn execute(self, brontes_db_path: String, ctx: CliContext) -> eyre::Result<()> {
+        if let Some(cex_fee_config) = &self.cex_fee_config {
+            CexFeeSchedule::init(cex_fee_config)?;
+        }
+        let db_path = reth_db_path()?;
+        let quote_asset: Address = self.quote_asset.parse()?;
+        let extra_quote_assets = self
+            .extra_quote_assets
+            .iter()
+            .map(|asset| asset.parse())
+            .collect::<Result<Vec<Address>, _>>()?;
+
+        let max_tasks = determine_max_tasks(self.max_tasks);
+        init_thread_pools(max_tasks as usize);
+
+        let libmdbx = static_object(load_libmdbx(&ctx.task_executor, brontes_db_path)?);
+        let tracer = Arc::new(get_tracing_provider(
+            Path::new(&db_path),
+            max_tasks,
+            ctx.task_executor.clone(),
+        ));
+
+        // the classifier sends pool updates for pricing which we don't run here
+        let (pricing_tx, mut pricing_rx) = unbounded_channel();
+        tokio::spawn(async move { while pricing_rx.recv().await.is_some() {} });
+        let classifier =
+            Classifier::new(libmdbx, pricing_tx, tracer.clone()).without_persisting_pools();
+
+        let block = match self.block {
+            Some(block) => block,
+            None => tracer.block_and_tx_index(self.tx).await?.0,
+        };
+
+        let inspectors = init_inspectors(
+            quote_asset,
+            &extra_quote_assets,
+            libmdbx,
+            self.inspectors,
+            self.cex_exchanges,
+            self.time_window_args.trade_config(),
+            false,
+        );
+        let window = inspectors
+            .iter()
+            .map(|inspector| inspector.block_window())
+            .max()
+            .unwrap_or(1);
+
+        // load the stored blocks needed for the largest inspector window, the
+        // block of the transaction being the most recent one
+        let mut per_block_data = Vec::new();
+        let mut merges = Vec::new();
+        for number in block.saturating_sub(window as u64 - 1)..=block {
+            let (traces, header) = load_stored_block(libmdbx, &*tracer, number).await?;
+            let (mut tree, block_merges) = classifier
+                .build_block_tree_with_merges(traces, header, false)
+                .await;
+            let metadata = libmdbx.get_metadata(number, quote_asset)?;
+            tree.label_private_txes(&metadata);
+
+            if number == block {
+                merges = block_merges;
+            }
+            per_block_data
+                .push(BlockData { metadata: Arc::new(metadata), tree: Arc::new(tree) });
+        }
+        let data = MultiBlockData { blocks: per_block_data.len(), per_block_data };
+        let BlockData { metadata, tree } = data.get_most_recent_block().clone();
+
+        let roots = tree
+            .tx_roots
+            .iter()
+            .filter(|root| root.tx_hash == self.tx)
+            .collect_vec();
+        if roots.is_empty() {
+            eyre::bail!("transaction {:?} not found in block {block}", self.tx);
+        }
+
+        println!("Transaction {:?} in block {block}", self.tx);
+
+        println!("\n== Classified call tree ==");
+        for root in &roots {
+            match root.user_op {
+                Some(op) => println!("position {} user op {:?}", root.position, op.user_op_hash),
+                None => println!("position {}", root.position),
+            }
+            print_node(root, &root.head);
+        }
+
+        println!("\n== Multi call frame classification merges ==");
+        let tx_merges = roots
+            .iter()
+            .flat_map(|root| {
+                merges
+                    .iter()
+                    .filter(move |(tx_idx, _)| *tx_idx == root.position)
+                    .flat_map(|(_, requests)| requests)
+            })
+            .collect_vec();
+        if tx_merges.is_empty() {
+            println!("none");
+        }
+        for request in tx_merges {
+            println!(
+                "{:?} {} at trace {} merged its child frames",
+                request.call_type, request.protocol, request.trace_idx
+            );
+        }
+
+        println!("\n== Token deltas ==");
+        let deltas = roots
+            .iter()
+            .flat_map(|root| root_actions(root))
+            .filter(|action| action.is_transfer() || action.is_eth_transfer())
+            .account_for_actions();
+        for (address, token_deltas) in deltas.into_iter().sorted_by_key(|(address, _)| *address) {
+            println!("{address:?}");
+            for (token, delta) in token_deltas {
+                println!("    {:>24.6} {}", delta.to_float(), token_symbol(libmdbx, token));
+            }
+        }
+
+        println!("\n== Dex quotes ==");
+        let quotes = metadata
+            .dex_quotes
+            .as_ref()
+            .map(|quotes| {
+                roots
+                    .iter()
+                    .filter_map(|root| quotes.0.get(root.position).cloned().flatten())
+                    .flatten()
+                    .collect_vec()
+            })
+            .unwrap_or_default();
+        if quotes.is_empty() {
+            println!("no dex quotes for this transaction");
+        }
+        for (pair, price) in quotes {
+            println!(
+                "{}/{} pre: {:.8} post: {:.8} goes through {}/{}",
+                token_symbol(libmdbx, pair.0),
+                token_symbol(libmdbx, pair.1),
+                price.pre_state.to_float(),
+                price.post_state.to_float(),
+                token_symbol(libmdbx, price.goes_through.0),
+                token_symbol(libmdbx, price.goes_through.1),
+            );
+        }
+
+        println!("\n== Inspector verdicts ==");
+        let tx = self.tx;
+        let explanation = explain_block_inspection(inspectors, data.clone(), libmdbx);
+        for (inspector, (id, result)) in inspectors.iter().zip(&explanation.inspector_results) {
+            let Some(bundles) = result else {
+                println!("{id}: skipped, not enough blocks loaded for the inspector window");
+                continue
+            };
+
+            let matched = bundles
+                .iter()
+                .filter(|bundle| bundle.data.mev_transaction_hashes().contains(&tx))
+                .collect_vec();
+            if !matched.is_empty() {
+                println!("{id}: accepted");
+                matched.iter().for_each(|bundle| println!("{bundle}"));
+                continue
+            }
+
+            match inspector.reject_reason(data.split_to_size(inspector.block_window()), tx) {
+                Some(reason) => println!("{id}: rejected, {reason}"),
+                None => println!("{id}: rejected, the inspector doesn't report its reasons"),
+            }
+        }
+
+        println!("\n== Composer deduplication ==");
+        let decisions = explanation
+            .dedup_decisions
+            .iter()
+            .filter(|decision| decision.dominant_tx == tx || decision.removed_tx == tx)
+            .collect_vec();
+        if decisions.is_empty() {
+            println!("no deduplication involved this transaction");
+        }
+        for decision in decisions {
+            println!(
+                "{} bundle {:?} removed in favour of {} bundle {:?}",
+                decision.removed_type,
+                decision.removed_tx,
+                decision.dominant_type,
+                decision.dominant_tx
+            );
+        }
+
+        let finals = explanation
+            .mev_details
+            .iter()
+            .filter(|bundle| bundle.data.mev_transaction_hashes().contains(&tx))
+            .map(|bundle| bundle.header.mev_type.to_string())
+            .collect_vec();
+        if finals.is_empty() {
+            println!("\nfinal result: not classified as mev");
+        } else {
+            println!("\nfinal result: {}", finals.join(", "));
+        }
+
+        Ok(())
+    }
+}
+
+fn print_node(root: &Root<Action>, node: &Node) {
+    let indent = "    ".repeat(node.trace_address.len() + 1);
+    let actions = root
+        .data_store
+        .get_ref(node.data)
+        .map(|actions| actions.iter().map(describe_action).join(", "))
+        .unwrap_or_default();
+    println!("{indent}[{}] {:?} {actions}", node.index, node.address);
+
+    node.inner.iter().for_each(|child| print_node(root, child));
+}
+
+fn root_actions(root: &Root<Action>) -> impl Iterator<Item = Action> + '_ {
+    root.data_store.0.iter().flatten().flatten().cloned()
+}
+
+fn describe_action(action: &Action) -> String {
+    match action {
+        Action::Swap(swap) => swap.to_string(),
+        Action::SwapWithFee(swap) => swap.swap.to_string(),
+        Action::Mint(mint) => mint.to_string(),
+        Action::Burn(burn) => burn.to_string(),
+        Action::Collect(collect) => collect.to_string(),
+        Action::Liquidation(liquidation) => liquidation.to_string(),
+        Action::Transfer(transfer) => format!(
+            "Transfer {:.4} {} from {:?} to {:?}",
+            transfer.amount.clone().to_float(),
+            transfer.token.symbol,
+            transfer.from,
+            transfer.to
+        ),
+        Action::EthTransfer(transfer) => {
+            format!(
+                "Eth transfer {} wei from {:?} to {:?}",
+                transfer.value, transfer.from, transfer.to
+            )
+        }
+        Action::NftTransfer(transfer) => transfer.to_string(),
+        Action::NftTrade(trade) => trade.to_string(),
+        Action::FlashLoan(flash_loan) => format!("FlashLoan via {}", flash_loan.protocol),
+        Action::Batch(batch) => format!("Batch via {}", batch.protocol),
+        Action::Aggregator(aggregator) => format!("Aggregator via {}", aggregator.protocol),
+        Action::NewPool(pool) => format!("NewPool {:?} on {}", pool.pool_address, pool.protocol),
+        Action::PoolConfigUpdate(update) => format!("PoolConfigUpdate on {}", update.protocol),
+        Action::SelfDestruct(_) => "SelfDestruct".to_string(),
+        Action::Unclassified(_) => "Unclassified".to_string(),
+        Action::Revert => "Revert".to_string(),
+    }
+}
+
+fn token_symbol(libmdbx: &LibmdbxReadWriter, token: Address) -> String {
+    libmdbx
+        .try_fetch_token_info(token)
+        .map(|info| info.symbol.clone())
+        .unwrap_or_else(|_| format!("{token:?}"))
+}
@@ -1,8 +1,11 @@
 use clap::{Parser, Subcommand};
 
 mod db;
+mod distributed;
+mod explain;
 mod misc;
 mod run;
+mod serve;
 mod utils;
 mod version_data;
 pub use utils::*;
@@ -38,4 +41,13 @@ pub enum Commands {
     /// Brontes database commands
     #[command(name = "db")]
     Database(db::Database),
+    /// Explain how a transaction was classified and inspected
+    #[command(name = "explain")]
+    Explain(explain::Explain),
+    /// Split a historical range over multiple machines
+    #[command(name = "distributed")]
+    Distributed(distributed::Distributed),
+    /// Serve the results in the libmdbx db over a read only json api
+    #[command(name = "serve")]
+    Serve(serve::Serve),
 }
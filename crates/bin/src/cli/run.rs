@@ -1,14 +1,26 @@
-use std::{path::Path, time::Duration};
+use std::{
+    path::{Path, PathBuf},
+    time::Duration,
+};
 
+use alloy_primitives::Address;
 use brontes_core::decoding::Parser as DParser;
-use brontes_database::clickhouse::cex_config::CexDownloadConfig;
-use brontes_inspect::Inspectors;
+use brontes_database::{
+    clickhouse::cex_config::CexDownloadConfig,
+    sqlite::{SqliteMiddleware, SqliteSink},
+};
+use brontes_inspect::{lvr::LvrCalculator, Inspectors};
 use brontes_metrics::ParserMetricsListener;
 use brontes_types::{
     constants::USDT_ADDRESS_STRING,
-    db::cex::{trades::CexDexTradeConfig, CexExchange},
+    db::{
+        cex::{trades::CexDexTradeConfig, CexExchange, CexFeeSchedule},
+        traits::LibmdbxReader,
+    },
     db_write_trigger::{backup_server_heartbeat, start_hr_monitor, HeartRateMonitor},
-    init_thread_pools, UnboundedYapperReceiver,
+    init_thread_pools,
+    uniswap_forks::UniswapForkConfig,
+    UnboundedYapperReceiver,
 };
 use clap::Parser;
 use tokio::sync::mpsc::unbounded_channel;
@@ -27,76 +39,116 @@ const SECONDS_TO_US_FLOAT: f64 = 1_000_000.0;
 pub struct RunArgs {
     /// Optional Start Block, if omitted it will run at tip until killed
     #[arg(long, short)]
-    pub start_block:          Option<u64>,
+    pub start_block:           Option<u64>,
     /// Optional End Block, if omitted it will run historically & at tip until
     /// killed
     #[arg(long, short)]
-    pub end_block:            Option<u64>,
+    pub end_block:             Option<u64>,
     /// starts running at tip from where brontes was last left at.
     #[arg(long, default_value_t = false)]
-    pub from_db_tip:          bool,
+    pub from_db_tip:           bool,
     /// Optional Multiple Ranges, format: "start1-end1 start2-end2 ..."
     /// Use this if you want to specify the exact, non continuous block ranges
     /// you want to run
     #[arg(long, num_args = 1.., value_delimiter = ' ')]
-    pub ranges:               Option<Vec<String>>,
+    pub ranges:                Option<Vec<String>>,
     /// Optional Max Tasks, if omitted it will default to 80% of the number of
     /// physical cores on your machine
     #[arg(long, short)]
-    pub max_tasks:            Option<u64>,
+    pub max_tasks:             Option<u64>,
     /// Optional minimum batch size
     #[arg(long, default_value = "500")]
-    pub min_batch_size:       u64,
+    pub min_batch_size:        u64,
     /// Optional quote asset, if omitted it will default to USDT
     #[arg(long, short, default_value = USDT_ADDRESS_STRING)]
-    pub quote_asset:          String,
+    pub quote_asset:           String,
+    /// Additional quote assets that profits are also denominated in, e.g. WETH.
+    /// None by default. Apart from WETH, which uses the block's ETH price, an
+    /// asset is only resolved in blocks where the pricer priced it against the
+    /// quote asset
+    #[arg(long, value_delimiter = ',')]
+    pub extra_quote_assets:    Vec<String>,
     /// Inspectors to run. If omitted it defaults to running all inspectors
     #[arg(long, short, value_delimiter = ',')]
-    pub inspectors:           Option<Vec<Inspectors>>,
+    pub inspectors:            Option<Vec<Inspectors>>,
     /// Time window arguments for cex data downloads
     #[clap(flatten)]
-    pub time_window_args:     TimeWindowArgs,
-    /// CEX exchanges to consider for cex-dex analysis
+    pub time_window_args:      TimeWindowArgs,
+    /// CEX exchanges to consider for cex-dex analysis. The perpetual venues
+    /// (BinanceUsdm, BybitLinear, OkexSwap) are opt in
     #[arg(
         long,
         short,
         default_value = "Binance,Coinbase,Okex,BybitSpot,Kucoin",
         value_delimiter = ','
     )]
-    pub cex_exchanges:        Vec<CexExchange>,
+    pub cex_exchanges:         Vec<CexExchange>,
+    /// Maker & taker fee schedule of the cex exchanges, defaults to
+    /// `config/cex_fee_config.toml` of the current directory
+    #[arg(long, env = "BRONTES_CEX_FEE_CONFIG")]
+    pub cex_fee_config:        Option<PathBuf>,
+    /// Fees of the discovered Uniswap V2 forks, defaults to
+    /// `config/uniswap_fork_config.toml` of the current directory
+    #[arg(long, env = "BRONTES_UNISWAP_FORK_CONFIG")]
+    pub uniswap_fork_config:   Option<PathBuf>,
+    /// Classifiers decoded at runtime, defaults to
+    /// `config/dyn_classifier_config.toml` of the current directory
+    #[cfg(feature = "dyn-decode")]
+    #[arg(long, env = "BRONTES_DYN_CLASSIFIER_CONFIG")]
+    pub dyn_classifier_config: Option<PathBuf>,
     /// Force DEX price calculation for every block, ignoring existing database
     /// values.
     #[arg(long, short, default_value = "false")]
-    pub force_dex_pricing:    bool,
+    pub force_dex_pricing:     bool,
     /// Disables DEX pricing. Inspectors needing DEX prices will only calculate
     /// token PnL, not USD PnL, if DEX pricing is unavailable in the
     /// database.
     #[arg(long, default_value = "false")]
-    pub force_no_dex_pricing: bool,
+    pub force_no_dex_pricing:  bool,
     /// Number of blocks to lag behind the chain tip when processing.
     #[arg(long, default_value = "10")]
-    pub behind_tip:           u64,
+    pub behind_tip:            u64,
     /// Legacy, run in CLI only mode (no TUI) - will output progress bars to
     /// stdout
     #[arg(long, default_value = "true")]
-    pub cli_only:             bool,
+    pub cli_only:              bool,
     /// Export metrics
     #[arg(long, default_value = "false")]
-    pub with_metrics:         bool,
+    pub with_metrics:          bool,
     /// Wether or not to use a fallback server.
     #[arg(long, default_value_t = false)]
-    pub enable_fallback:      bool,
+    pub enable_fallback:       bool,
     /// Address of the fallback server.
     /// Triggers database writes if the main connection fails, preventing data
     /// loss.
     #[arg(long)]
-    pub fallback_server:      Option<String>,
+    pub fallback_server:       Option<String>,
     /// Set a custom run ID used when inserting data into the Clickhouse
     ///
     /// If omitted, the ID will be automatically incremented from the last run
     /// stored in the Clickhouse database.
     #[arg(long, short)]
-    pub run_id:               Option<u64>,
+    pub run_id:                Option<u64>,
+    /// Continue a crashed range run from the last block every one of its
+    /// chunks committed. The id is logged when the run starts, the remaining
+    /// arguments should match the original run
+    #[arg(long, conflicts_with_all = ["start_block", "end_block", "ranges", "from_db_tip"])]
+    pub resume:                Option<u64>,
+    /// Also write the results brontes sends to ClickHouse into this sqlite
+    /// file, with tables matching the ClickHouse ones, so they can be queried
+    /// without a ClickHouse server
+    #[arg(long)]
+    pub sqlite_sink:           Option<PathBuf>,
+    /// Store the classified transaction trees of each block in libmdbx so
+    /// they can be exported later. They take up a lot of space, so this is
+    /// off by default
+    #[arg(long, default_value_t = false)]
+    pub persist_trees:         bool,
+    /// Measure the loss versus rebalancing of every pool swapped against,
+    /// rebalanced at the cex prices of the time window, and store it in the
+    /// `BlockLvrs` table
+    #[arg(long, default_value_t = false)]
+    pub lvr:                   bool,
 
     /// shows a cool display at startup
     #[arg(long, short, default_value_t = false)]
@@ -111,6 +163,17 @@ impl RunArgs {
             rain();
         }
 
+        if let Some(cex_fee_config) = &self.cex_fee_config {
+            CexFeeSchedule::init(cex_fee_config)?;
+        }
+        if let Some(uniswap_fork_config) = &self.uniswap_fork_config {
+            UniswapForkConfig::init(uniswap_fork_config)?;
+        }
+        #[cfg(feature = "dyn-decode")]
+        if let Some(dyn_classifier_config) = &self.dyn_classifier_config {
+            brontes_classifier::config_classifier::ConfigClassifiers::init(dyn_classifier_config)?;
+        }
+
         let snapshot_mode = !cfg!(feature = "local-clickhouse");
         tracing::info!(%snapshot_mode);
 
@@ -118,7 +181,12 @@ impl RunArgs {
         let reth_db_path = get_env_vars()?;
         tracing::info!(target: "brontes", "got env vars");
         let quote_asset = self.quote_asset.parse()?;
-        tracing::info!(target: "brontes", "parsed quote asset");
+        let extra_quote_assets = self
+            .extra_quote_assets
+            .iter()
+            .map(|asset| asset.parse())
+            .collect::<Result<Vec<Address>, _>>()?;
+        tracing::info!(target: "brontes", "parsed quote assets");
         let task_executor = ctx.task_executor;
 
         let max_tasks = determine_max_tasks(self.max_tasks);
@@ -136,10 +204,21 @@ impl RunArgs {
         let hr = self.try_start_fallback_server().await;
 
         tracing::info!(target: "brontes", "starting database initialization at: '{}'", brontes_db_path);
-        let libmdbx =
-            static_object(load_database(&task_executor, brontes_db_path, hr, self.run_id).await?);
+        let db =
+            load_database(&task_executor, brontes_db_path, hr, self.run_id, self.persist_trees)
+                .await?;
+        let tip = load_tip_database(&db)?;
 
-        let tip = static_object(load_tip_database(libmdbx)?);
+        let sqlite = self
+            .sqlite_sink
+            .as_ref()
+            .map(|path| SqliteSink::spawn(path, self.run_id, task_executor.get_graceful_shutdown()))
+            .transpose()?;
+        if let Some(sink) = &sqlite {
+            tracing::info!(target: "brontes", run_id = sink.run_id, "writing results to sqlite");
+        }
+        let libmdbx = static_object(SqliteMiddleware::new(sqlite.clone(), db));
+        let tip = static_object(SqliteMiddleware::new(sqlite, tip));
         tracing::info!(target: "brontes", "initialized libmdbx database");
 
         let load_window = self.load_time_window();
@@ -150,7 +229,27 @@ impl RunArgs {
             self.cex_exchanges.clone(),
         );
 
-        let range_type = self.get_range_type()?;
+        let resume = self
+            .resume
+            .map(|run_id| {
+                libmdbx
+                    .try_fetch_run_checkpoint(run_id)?
+                    .ok_or_else(|| eyre::eyre!("no checkpoint stored for run {run_id}"))
+            })
+            .transpose()?;
+        let range_type = match &resume {
+            Some(checkpoint) if checkpoint.is_finished() => {
+                tracing::info!(run_id = checkpoint.run_id, "run already finished");
+                return Ok(())
+            }
+            Some(checkpoint) => RangeType::MultipleRanges(
+                checkpoint
+                    .remaining()
+                    .map(|(_, chunk)| (chunk.next_block, chunk.end_block))
+                    .collect(),
+            ),
+            None => self.get_range_type()?,
+        };
         let clickhouse = static_object(load_clickhouse(cex_download_config, self.run_id).await?);
         tracing::info!(target: "brontes", "Databases initialized");
 
@@ -169,8 +268,18 @@ impl RunArgs {
 
         let trade_config = self.time_window_args.trade_config();
 
+        let lvr = self.lvr.then(|| {
+            static_object(LvrCalculator::new(
+                quote_asset,
+                libmdbx,
+                &self.cex_exchanges,
+                trade_config,
+            ))
+        });
+
         let inspectors = init_inspectors(
             quote_asset,
+            &extra_quote_assets,
             libmdbx,
             self.inspectors,
             self.cex_exchanges,
@@ -186,7 +295,7 @@ impl RunArgs {
         let result = executor
             .clone()
             .spawn_critical_with_graceful_shutdown_signal("run init", |shutdown| async move {
-                if let Ok(brontes) = BrontesRunConfig::<_, _, _, MevProcessor>::new(
+                let config = BrontesRunConfig::<_, _, _, MevProcessor>::new(
                     range_type,
                     max_tasks,
                     self.min_batch_size,
@@ -202,10 +311,17 @@ impl RunArgs {
                     self.with_metrics,
                     snapshot_mode,
                     load_window,
-                )
-                .build(task_executor, shutdown)
-                .await
-                .map_err(|e| {
+                );
+                let config = match resume {
+                    Some(checkpoint) => config.with_resume(checkpoint),
+                    None => config,
+                };
+                let config = match lvr {
+                    Some(lvr) => config.with_lvr(lvr),
+                    None => config,
+                };
+
+                if let Ok(brontes) = config.build(task_executor, shutdown).await.map_err(|e| {
                     tracing::error!(%e);
                     e
                 }) {
@@ -254,11 +370,7 @@ impl RunArgs {
 
     /// the time window in seconds for downloading
     fn load_time_window(&self) -> usize {
-        self.time_window_args
-            .max_vwap_pre
-            .max(self.time_window_args.max_vwap_post)
-            .max(self.time_window_args.max_optimistic_pre)
-            .max(self.time_window_args.max_optimistic_post) as usize
+        self.time_window_args.load_time_window()
     }
 
     fn check_proper_range(&self) -> eyre::Result<()> {
@@ -389,7 +501,15 @@ pub struct TimeWindowArgs {
 }
 
 impl TimeWindowArgs {
-    fn trade_config(&self) -> CexDexTradeConfig {
+    /// the time window in seconds for downloading
+    pub fn load_time_window(&self) -> usize {
+        self.max_vwap_pre
+            .max(self.max_vwap_post)
+            .max(self.max_optimistic_pre)
+            .max(self.max_optimistic_post) as usize
+    }
+
+    pub fn trade_config(&self) -> CexDexTradeConfig {
         CexDexTradeConfig {
             initial_vwap_pre_block_us:  (self.initial_vwap_pre * SECONDS_TO_US_FLOAT) as u64,
             initial_vwap_post_block_us: (self.initial_vwap_post * SECONDS_TO_US_FLOAT) as u64,
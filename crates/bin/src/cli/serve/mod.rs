@@ -0,0 +1,403 @@
+use std::{convert::Infallible, net::SocketAddr, str::FromStr, sync::Arc};
+
+use alloy_primitives::{Address, B256};
+use brontes_database::libmdbx::{
+    tables::{
+        AddressToProtocolInfo, Builder, MevBlocks, SearcherContracts, SearcherEOAs, TokenDecimals,
+    },
+    LibmdbxReadWriter, LibmdbxReader,
+};
+use brontes_types::{
+    constants::{ETH_ADDRESS, WETH_ADDRESS},
+    db::token_info::TokenInfoWithAddress,
+    mev::{Bundle, Mev, MevType},
+};
+use clap::{Parser, ValueEnum};
+use eyre::WrapErr;
+use hyper::{
+    service::{make_service_fn, service_fn},
+    Body, Method, Request, Response, Server, StatusCode,
+};
+use itertools::Itertools;
+use serde::Serialize;
+
+use self::types::{BundleResponse, MevBlockResponse, SearcherResponse, TokenResponse};
+use crate::runner::CliContext;
+
+mod types;
+
+/// Serves the results in the libmdbx db as json. The db is opened read only,
+/// so the api can run next to a brontes process that is writing to it.
+///
+/// Endpoints:
+/// - `/bundles?start_block=&end_block=&tx_hash=&searcher=&mev_type=` every
+///   parameter is optional, without a range the latest `--max-range` blocks are
+///   searched. As bundles aren't indexed by tx hash or searcher, these lookups
+///   need a `start_block` or `end_block`
+/// - `/mev-block/<block>`
+/// - `/searcher/<address>`
+/// - `/builder/<address>`
+/// - `/token/<address>`
+/// - `/protocol/<address>`
+#[derive(Debug, Parser)]
+pub struct Serve {
+    /// Address the api listens on
+    #[arg(long, default_value = "127.0.0.1:7800")]
+    pub listen:    SocketAddr,
+    /// Most blocks a single bundle query may search
+    #[arg(long, default_value_t = 10_000)]
+    pub max_range: u64,
+}
+
+impl Serve {
+    pub async fn execute(self, brontes_db_path: String, _ctx: CliContext) -> eyre::Result<()> {
+        let api = Arc::new(Api {
+            db:        LibmdbxReadWriter::init_db_read_only(&brontes_db_path, None)?,
+            max_range: self.max_range,
+        });
+
+        let make_svc = make_service_fn(|_| {
+            let api = api.clone();
+            async move {
+                Ok::<_, Infallible>(service_fn(move |req: Request<Body>| {
+                    let api = api.clone();
+                    async move { Ok::<_, Infallible>(api.handle(req).await) }
+                }))
+            }
+        });
+
+        tracing::info!(listen = %self.listen, db = %brontes_db_path, "serving libmdbx");
+        Server::try_bind(&self.listen)
+            .wrap_err("Could not bind to address")?
+            .serve(make_svc)
+            .await?;
+
+        Ok(())
+    }
+}
+
+struct Api {
+    db:        LibmdbxReadWriter,
+    max_range: u64,
+}
+
+impl Api {
+    async fn handle(self: Arc<Self>, req: Request<Body>) -> Response<Body> {
+        if req.method() != Method::GET {
+            return status(StatusCode::METHOD_NOT_ALLOWED, String::new())
+        }
+
+        let path = req.uri().path().trim_matches('/').to_string();
+        let query = req.uri().query().unwrap_or_default().to_string();
+
+        // libmdbx reads block, keep them off the runtime
+        let res = tokio::task::spawn_blocking(move || self.route(&path, &query))
+            .await
+            .unwrap_or_else(|e| Err(ApiError::Internal(e.into())));
+
+        res.unwrap_or_else(|e| match e {
+            ApiError::BadRequest(msg) => status(StatusCode::BAD_REQUEST, msg),
+            ApiError::NotFound(msg) => status(StatusCode::NOT_FOUND, msg),
+            ApiError::Internal(e) => {
+                tracing::error!(uri = %req.uri(), err = %e, "failed to handle request");
+                status(StatusCode::INTERNAL_SERVER_ERROR, e.to_string())
+            }
+        })
+    }
+
+    fn route(&self, path: &str, query: &str) -> Result<Response<Body>, ApiError> {
+        let parts = path.split('/').collect_vec();
+
+        match parts.as_slice() {
+            ["bundles"] => self.bundles(BundleQuery::parse(query)?),
+            ["mev-block", block] => self.mev_block(parse("block", block)?),
+            ["searcher", address] => self.searcher(parse("address", address)?),
+            ["builder", address] => self.builder(parse("address", address)?),
+            ["token", address] => self.token(parse("address", address)?),
+            ["protocol", address] => self.protocol(parse("address", address)?),
+            _ => Err(ApiError::NotFound(format!("no endpoint at /{path}"))),
+        }
+    }
+
+    fn bundles(&self, query: BundleQuery) -> Result<Response<Body>, ApiError> {
+        let Some((first, last)) = self.db.mev_block_range()? else {
+            return json(&Vec::<BundleResponse>::new())
+        };
+
+        let end_block = query.end_block.unwrap_or(last);
+        let start_block = query.start_block.unwrap_or_else(|| {
+            end_block
+                .saturating_add(1)
+                .saturating_sub(self.max_range)
+                .max(first)
+        });
+        if start_block > end_block {
+            return Err(ApiError::BadRequest("start_block is after end_block".to_string()))
+        }
+        if end_block - start_block >= self.max_range {
+            return Err(ApiError::BadRequest(format!(
+                "range spans more than {} blocks",
+                self.max_range
+            )))
+        }
+
+        let bundles = self
+            .db
+            .try_fetch_mev_blocks(Some(start_block), end_block)?
+            .into_iter()
+            .filter(|mev_block| (start_block..=end_block).contains(&mev_block.block.block_number))
+            .flat_map(|mev_block| mev_block.mev)
+            .filter(|bundle| query.matches(bundle))
+            .map(BundleResponse::from)
+            .collect_vec();
+
+        json(&bundles)
+    }
+
+    fn mev_block(&self, block: u64) -> Result<Response<Body>, ApiError> {
+        let mev_block = self
+            .db
+            .get_if_exists::<MevBlocks>(block)?
+            .ok_or_else(|| ApiError::NotFound(format!("no mev block for block {block}")))?;
+
+        json(&MevBlockResponse::from(mev_block.block))
+    }
+
+    fn searcher(&self, address: Address) -> Result<Response<Body>, ApiError> {
+        let eoa = self.db.get_if_exists::<SearcherEOAs>(address)?;
+        let contract = self.db.get_if_exists::<SearcherContracts>(address)?;
+        if eoa.is_none() && contract.is_none() {
+            return Err(ApiError::NotFound(format!("no searcher info for {address:?}")))
+        }
+
+        json(&SearcherResponse { eoa, contract })
+    }
+
+    fn builder(&self, address: Address) -> Result<Response<Body>, ApiError> {
+        let info = self
+            .db
+            .get_if_exists::<Builder>(address)?
+            .ok_or_else(|| ApiError::NotFound(format!("no builder info for {address:?}")))?;
+
+        json(&info)
+    }
+
+    fn token(&self, address: Address) -> Result<Response<Body>, ApiError> {
+        // eth is stored as weth, same as `try_fetch_token_info`
+        let key = if address == ETH_ADDRESS { WETH_ADDRESS } else { address };
+        let mut info = self
+            .db
+            .get_if_exists::<TokenDecimals>(key)?
+            .map(|inner| TokenInfoWithAddress { inner, address })
+            .ok_or_else(|| ApiError::NotFound(format!("no token info for {address:?}")))?;
+        if address == ETH_ADDRESS {
+            info.symbol = "ETH".to_string();
+        }
+
+        json(&TokenResponse::from(info))
+    }
+
+    fn protocol(&self, address: Address) -> Result<Response<Body>, ApiError> {
+        let info = self
+            .db
+            .get_if_exists::<AddressToProtocolInfo>(address)?
+            .ok_or_else(|| ApiError::NotFound(format!("no protocol info for {address:?}")))?;
+
+        json(&info)
+    }
+}
+
+#[derive(Debug, Default)]
+struct BundleQuery {
+    start_block: Option<u64>,
+    end_block:   Option<u64>,
+    tx_hash:     Option<B256>,
+    searcher:    Option<Address>,
+    mev_type:    Option<MevType>,
+}
+
+impl BundleQuery {
+    fn parse(query: &str) -> Result<Self, ApiError> {
+        let mut this = Self::default();
+        for pair in query.split('&').filter(|pair| !pair.is_empty()) {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            match key {
+                "start_block" => this.start_block = Some(parse(key, value)?),
+                "end_block" => this.end_block = Some(parse(key, value)?),
+                "tx_hash" => this.tx_hash = Some(parse(key, value)?),
+                "searcher" => this.searcher = Some(parse(key, value)?),
+                "mev_type" => {
+                    this.mev_type = Some(
+                        <MevType as ValueEnum>::from_str(value, true)
+                            .map_err(|e| ApiError::BadRequest(format!("invalid {key}: {e}")))?,
+                    )
+                }
+                _ => return Err(ApiError::BadRequest(format!("unknown query parameter {key}"))),
+            }
+        }
+
+        // without a range only the latest blocks would be searched, which would
+        // silently miss older bundles of the tx or searcher
+        if (this.tx_hash.is_some() || this.searcher.is_some())
+            && this.start_block.is_none()
+            && this.end_block.is_none()
+        {
+            return Err(ApiError::BadRequest(
+                "tx_hash and searcher need a start_block or end_block".to_string(),
+            ))
+        }
+
+        Ok(this)
+    }
+
+    fn matches(&self, bundle: &Bundle) -> bool {
+        self.tx_hash.map_or(true, |hash| {
+            bundle.header.tx_hash == hash || bundle.data.mev_transaction_hashes().contains(&hash)
+        }) && self.searcher.map_or(true, |searcher| {
+            bundle.header.eoa == searcher || bundle.header.mev_contract == Some(searcher)
+        }) && self
+            .mev_type
+            .map_or(true, |mev_type| bundle.header.mev_type == mev_type)
+    }
+}
+
+#[derive(Debug)]
+enum ApiError {
+    BadRequest(String),
+    NotFound(String),
+    Internal(eyre::Report),
+}
+
+impl From<eyre::Report> for ApiError {
+    fn from(value: eyre::Report) -> Self {
+        Self::Internal(value)
+    }
+}
+
+fn parse<T: FromStr>(name: &str, value: &str) -> Result<T, ApiError> {
+    value
+        .parse()
+        .map_err(|_| ApiError::BadRequest(format!("invalid {name}: {value}")))
+}
+
+fn json<T: Serialize>(value: &T) -> Result<Response<Body>, ApiError> {
+    let body = serde_json::to_vec(value).map_err(|e| ApiError::Internal(e.into()))?;
+
+    Ok(Response::builder()
+        .header(hyper::header::CONTENT_TYPE, "application/json")
+        .body(Body::from(body))
+        .unwrap())
+}
+
+fn status(code: StatusCode, msg: String) -> Response<Body> {
+    Response::builder()
+        .status(code)
+        .body(Body::from(msg))
+        .unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use brontes_types::mev::{BundleData, BundleHeader, Sandwich};
+
+    use super::*;
+
+    fn sandwich() -> Bundle {
+        let frontrun = vec![B256::with_last_byte(1), B256::with_last_byte(2)];
+        Bundle {
+            header: BundleHeader {
+                tx_hash: frontrun[0],
+                eoa: Address::with_last_byte(10),
+                mev_contract: Some(Address::with_last_byte(11)),
+                mev_type: MevType::Sandwich,
+                ..Default::default()
+            },
+            data:   BundleData::Sandwich(Sandwich {
+                frontrun_tx_hash: frontrun,
+                victim_swaps_tx_hashes: vec![vec![B256::with_last_byte(3)]],
+                backrun_tx_hash: B256::with_last_byte(4),
+                ..Default::default()
+            }),
+        }
+    }
+
+    #[test]
+    fn test_parse_bundle_query() {
+        let query = BundleQuery::parse(&format!(
+            "start_block=10&end_block=20&tx_hash={:?}&searcher={:?}&mev_type=sandwich",
+            B256::with_last_byte(1),
+            Address::with_last_byte(10)
+        ))
+        .unwrap();
+        assert_eq!(query.start_block, Some(10));
+        assert_eq!(query.end_block, Some(20));
+        assert_eq!(query.tx_hash, Some(B256::with_last_byte(1)));
+        assert_eq!(query.searcher, Some(Address::with_last_byte(10)));
+        assert_eq!(query.mev_type, Some(MevType::Sandwich));
+
+        let empty = BundleQuery::parse("").unwrap();
+        assert!(empty.start_block.is_none() && empty.tx_hash.is_none());
+
+        let tx_hash = format!("tx_hash={:?}", B256::with_last_byte(1));
+        let searcher = format!("searcher={:?}", Address::with_last_byte(10));
+        assert!(BundleQuery::parse(&format!("end_block=20&{tx_hash}")).is_ok());
+        assert!(BundleQuery::parse(&format!("start_block=10&{searcher}")).is_ok());
+        assert!(BundleQuery::parse("mev_type=sandwich").is_ok());
+
+        for invalid in [
+            "start_block=ten",
+            "tx_hash=0x01",
+            "mev_type=frontrun",
+            "block=1",
+            tx_hash.as_str(),
+            searcher.as_str(),
+        ] {
+            assert!(
+                matches!(BundleQuery::parse(invalid), Err(ApiError::BadRequest(_))),
+                "{invalid} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn test_bundle_query_matches() {
+        let bundle = sandwich();
+        assert!(BundleQuery::default().matches(&bundle));
+
+        // every tx of the sandwich matches, not only the header hash
+        for hash in [1, 2, 3, 4] {
+            let query =
+                BundleQuery { tx_hash: Some(B256::with_last_byte(hash)), ..Default::default() };
+            assert!(query.matches(&bundle), "tx {hash} should match");
+        }
+        let query = BundleQuery { tx_hash: Some(B256::with_last_byte(5)), ..Default::default() };
+        assert!(!query.matches(&bundle));
+
+        for (searcher, matches) in [(10, true), (11, true), (12, false)] {
+            let query = BundleQuery {
+                searcher: Some(Address::with_last_byte(searcher)),
+                ..Default::default()
+            };
+            assert_eq!(query.matches(&bundle), matches);
+        }
+
+        let query = BundleQuery {
+            mev_type: Some(MevType::AtomicArb),
+            searcher: Some(Address::with_last_byte(10)),
+            ..Default::default()
+        };
+        assert!(!query.matches(&bundle));
+    }
+
+    #[test]
+    fn test_bundle_response_keeps_every_frontrun() {
+        let json = serde_json::to_value(BundleResponse::from(sandwich())).unwrap();
+
+        assert_eq!(json["data"]["kind"], "sandwich");
+        assert_eq!(
+            json["data"]["frontrun_tx_hash"],
+            serde_json::json!([B256::with_last_byte(1), B256::with_last_byte(2)])
+        );
+        assert_eq!(json["header"]["mev_contract"], serde_json::json!(Address::with_last_byte(11)));
+    }
+}
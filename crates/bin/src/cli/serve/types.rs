@@ -0,0 +1,730 @@
+//! The json bodies returned by the api. The types stored in libmdbx serialize
+//! into the flattened column layout clickhouse inserts expect, so everything
+//! is mapped into these before it's returned.
+
+use alloy_primitives::{Address, B256, U256};
+use brontes_types::{
+    db::{
+        cex::CexExchange,
+        searcher::{Fund, SearcherInfo},
+        token_info::TokenInfoWithAddress,
+    },
+    mev::{
+        ArbDetails, AtomicArb, AtomicArbType, Bundle, BundleData, BundleHeader, CexDex,
+        CexDexQuote, CexMethodology, JitLiquidity, JitLiquiditySandwich, Liquidation, MevBlock,
+        MevCount, MevType, NftArb, OfaRefund, OptimisticTrade, PossibleMev, PossibleMevTriggers,
+        QuoteAssetPnl, Sandwich, SearcherTx, TransactionAccounting, VictimLossAmount,
+    },
+    normalized_actions::{
+        NftStandard, NormalizedBurn, NormalizedLiquidation, NormalizedMint, NormalizedNftTrade,
+        NormalizedSwap, NormalizedTransfer,
+    },
+    pair::Pair,
+    GasDetails, Protocol, ToFloatNearest,
+};
+use serde::Serialize;
+
+#[derive(Debug, Serialize)]
+pub struct TokenResponse {
+    pub address:  Address,
+    pub symbol:   String,
+    pub decimals: u8,
+}
+
+impl From<TokenInfoWithAddress> for TokenResponse {
+    fn from(token: TokenInfoWithAddress) -> Self {
+        Self {
+            address:  token.address,
+            symbol:   token.inner.symbol,
+            decimals: token.inner.decimals,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct SearcherResponse {
+    pub eoa:      Option<SearcherInfo>,
+    pub contract: Option<SearcherInfo>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MevBlockResponse {
+    pub block_hash:                  B256,
+    pub block_number:                u64,
+    pub mev_count:                   MevCount,
+    pub eth_price:                   f64,
+    pub total_gas_used:              u128,
+    pub total_priority_fee:          u128,
+    pub total_bribe:                 u128,
+    pub total_mev_bribe:             u128,
+    pub total_mev_priority_fee_paid: u128,
+    pub builder_address:             Address,
+    pub builder_name:                Option<String>,
+    pub builder_eth_profit:          f64,
+    pub builder_profit_usd:          f64,
+    pub builder_mev_profit_usd:      f64,
+    pub builder_searcher_bribes:     u128,
+    pub builder_searcher_bribes_usd: f64,
+    pub builder_sponsorship_amount:  u128,
+    pub ultrasound_bid_adjusted:     bool,
+    pub proposer_fee_recipient:      Option<Address>,
+    pub proposer_mev_reward:         Option<u128>,
+    pub proposer_profit_usd:         Option<f64>,
+    pub total_mev_profit_usd:        f64,
+    pub quote_pnl:                   Vec<QuoteAssetPnl>,
+    pub possible_mev:                Vec<PossibleMevResponse>,
+}
+
+impl From<MevBlock> for MevBlockResponse {
+    fn from(block: MevBlock) -> Self {
+        Self {
+            block_hash:                  block.block_hash,
+            block_number:                block.block_number,
+            mev_count:                   block.mev_count,
+            eth_price:                   block.eth_price,
+            total_gas_used:              block.total_gas_used,
+            total_priority_fee:          block.total_priority_fee,
+            total_bribe:                 block.total_bribe,
+            total_mev_bribe:             block.total_mev_bribe,
+            total_mev_priority_fee_paid: block.total_mev_priority_fee_paid,
+            builder_address:             block.builder_address,
+            builder_name:                block.builder_name,
+            builder_eth_profit:          block.builder_eth_profit,
+            builder_profit_usd:          block.builder_profit_usd,
+            builder_mev_profit_usd:      block.builder_mev_profit_usd,
+            builder_searcher_bribes:     block.builder_searcher_bribes,
+            builder_searcher_bribes_usd: block.builder_searcher_bribes_usd,
+            builder_sponsorship_amount:  block.builder_sponsorship_amount,
+            ultrasound_bid_adjusted:     block.ultrasound_bid_adjusted,
+            proposer_fee_recipient:      block.proposer_fee_recipient,
+            proposer_mev_reward:         block.proposer_mev_reward,
+            proposer_profit_usd:         block.proposer_profit_usd,
+            total_mev_profit_usd:        block.total_mev_profit_usd,
+            quote_pnl:                   block.quote_pnl,
+            possible_mev:                block.possible_mev.0.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct PossibleMevResponse {
+    pub tx_hash:     B256,
+    pub tx_idx:      u64,
+    pub gas_details: GasDetails,
+    pub triggers:    PossibleMevTriggers,
+}
+
+impl From<PossibleMev> for PossibleMevResponse {
+    fn from(mev: PossibleMev) -> Self {
+        Self {
+            tx_hash:     mev.tx_hash,
+            tx_idx:      mev.tx_idx,
+            gas_details: mev.gas_details,
+            triggers:    mev.triggers,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct BundleResponse {
+    pub header: BundleHeaderResponse,
+    pub data:   BundleDataResponse,
+}
+
+impl From<Bundle> for BundleResponse {
+    fn from(bundle: Bundle) -> Self {
+        Self { header: bundle.header.into(), data: bundle.data.into() }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct BundleHeaderResponse {
+    pub block_number:          u64,
+    pub tx_index:              u64,
+    pub tx_hash:               B256,
+    pub eoa:                   Address,
+    pub mev_contract:          Option<Address>,
+    pub fund:                  Fund,
+    pub profit_usd:            f64,
+    pub bribe_usd:             f64,
+    pub mev_type:              MevType,
+    pub no_pricing_calculated: bool,
+    pub balance_deltas:        Vec<TransactionAccounting>,
+    pub quote_pnl:             Vec<QuoteAssetPnl>,
+    pub refund_usd:            f64,
+}
+
+impl From<BundleHeader> for BundleHeaderResponse {
+    fn from(header: BundleHeader) -> Self {
+        Self {
+            block_number:          header.block_number,
+            tx_index:              header.tx_index,
+            tx_hash:               header.tx_hash,
+            eoa:                   header.eoa,
+            mev_contract:          header.mev_contract,
+            fund:                  header.fund,
+            profit_usd:            header.profit_usd,
+            bribe_usd:             header.bribe_usd,
+            mev_type:              header.mev_type,
+            no_pricing_calculated: header.no_pricing_calculated,
+            balance_deltas:        header.balance_deltas,
+            quote_pnl:             header.quote_pnl,
+            refund_usd:            header.refund_usd,
+        }
+    }
+}
+
+/// Tagged with the kind of data so clients can tell the variants apart
+#[derive(Debug, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum BundleDataResponse {
+    Sandwich(SandwichResponse),
+    AtomicArb(AtomicArbResponse),
+    JitSandwich(JitSandwichResponse),
+    Jit(JitResponse),
+    CexDexQuote(CexDexQuoteResponse),
+    CexDex(CexDexResponse),
+    Liquidation(LiquidationResponse),
+    Unknown(SearcherTxResponse),
+    NftArb(NftArbResponse),
+}
+
+impl From<BundleData> for BundleDataResponse {
+    fn from(data: BundleData) -> Self {
+        match data {
+            BundleData::Sandwich(m) => Self::Sandwich(m.into()),
+            BundleData::AtomicArb(m) => Self::AtomicArb(m.into()),
+            BundleData::JitSandwich(m) => Self::JitSandwich(m.into()),
+            BundleData::Jit(m) => Self::Jit(m.into()),
+            BundleData::CexDexQuote(m) => Self::CexDexQuote(m.into()),
+            BundleData::CexDex(m) => Self::CexDex(m.into()),
+            BundleData::Liquidation(m) => Self::Liquidation(m.into()),
+            BundleData::Unknown(m) => Self::Unknown(m.into()),
+            BundleData::NftArb(m) => Self::NftArb(m.into()),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct SandwichResponse {
+    pub block_number:             u64,
+    pub frontrun_tx_hash:         Vec<B256>,
+    pub frontrun_swaps:           Vec<Vec<SwapResponse>>,
+    pub frontrun_gas_details:     Vec<GasDetails>,
+    pub victim_swaps_tx_hashes:   Vec<Vec<B256>>,
+    pub victim_swaps:             Vec<Vec<SwapResponse>>,
+    pub victim_swaps_gas_details: Vec<GasDetails>,
+    pub backrun_tx_hash:          B256,
+    pub backrun_swaps:            Vec<SwapResponse>,
+    pub backrun_gas_details:      GasDetails,
+    pub refunds:                  Vec<OfaRefund>,
+    pub victim_losses:            Vec<VictimLossAmount>,
+}
+
+impl From<Sandwich> for SandwichResponse {
+    fn from(sandwich: Sandwich) -> Self {
+        Self {
+            block_number:             sandwich.block_number,
+            frontrun_tx_hash:         sandwich.frontrun_tx_hash,
+            frontrun_swaps:           nested(sandwich.frontrun_swaps),
+            frontrun_gas_details:     sandwich.frontrun_gas_details,
+            victim_swaps_tx_hashes:   sandwich.victim_swaps_tx_hashes,
+            victim_swaps:             nested(sandwich.victim_swaps),
+            victim_swaps_gas_details: sandwich.victim_swaps_gas_details,
+            backrun_tx_hash:          sandwich.backrun_tx_hash,
+            backrun_swaps:            flat(sandwich.backrun_swaps),
+            backrun_gas_details:      sandwich.backrun_gas_details,
+            refunds:                  sandwich.refunds,
+            victim_losses:            sandwich.victim_losses,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct AtomicArbResponse {
+    pub tx_hash:      B256,
+    pub trigger_tx:   B256,
+    pub block_number: u64,
+    pub swaps:        Vec<SwapResponse>,
+    pub gas_details:  GasDetails,
+    pub arb_type:     AtomicArbType,
+    pub refunds:      Vec<OfaRefund>,
+}
+
+impl From<AtomicArb> for AtomicArbResponse {
+    fn from(arb: AtomicArb) -> Self {
+        Self {
+            tx_hash:      arb.tx_hash,
+            trigger_tx:   arb.trigger_tx,
+            block_number: arb.block_number,
+            swaps:        flat(arb.swaps),
+            gas_details:  arb.gas_details,
+            arb_type:     arb.arb_type,
+            refunds:      arb.refunds,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct JitSandwichResponse {
+    pub block_number:             u64,
+    pub frontrun_tx_hash:         Vec<B256>,
+    pub frontrun_swaps:           Vec<Vec<SwapResponse>>,
+    pub frontrun_mints:           Vec<Option<Vec<LiquidityResponse>>>,
+    pub frontrun_gas_details:     Vec<GasDetails>,
+    pub victim_swaps_tx_hashes:   Vec<Vec<B256>>,
+    pub victim_swaps:             Vec<Vec<SwapResponse>>,
+    pub victim_swaps_gas_details: Vec<GasDetails>,
+    pub backrun_tx_hash:          B256,
+    pub backrun_swaps:            Vec<SwapResponse>,
+    pub backrun_burns:            Vec<LiquidityResponse>,
+    pub backrun_gas_details:      GasDetails,
+}
+
+impl From<JitLiquiditySandwich> for JitSandwichResponse {
+    fn from(jit: JitLiquiditySandwich) -> Self {
+        Self {
+            block_number:             jit.block_number,
+            frontrun_tx_hash:         jit.frontrun_tx_hash,
+            frontrun_swaps:           nested(jit.frontrun_swaps),
+            frontrun_mints:           jit
+                .frontrun_mints
+                .into_iter()
+                .map(|mints| mints.map(flat))
+                .collect(),
+            frontrun_gas_details:     jit.frontrun_gas_details,
+            victim_swaps_tx_hashes:   jit.victim_swaps_tx_hashes,
+            victim_swaps:             nested(jit.victim_swaps),
+            victim_swaps_gas_details: jit.victim_swaps_gas_details,
+            backrun_tx_hash:          jit.backrun_tx_hash,
+            backrun_swaps:            flat(jit.backrun_swaps),
+            backrun_burns:            flat(jit.backrun_burns),
+            backrun_gas_details:      jit.backrun_gas_details,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct JitResponse {
+    pub block_number: u64,
+    pub frontrun_mint_tx_hash: B256,
+    pub frontrun_mints: Vec<LiquidityResponse>,
+    pub frontrun_mint_gas_details: GasDetails,
+    pub victim_swaps_tx_hashes: Vec<B256>,
+    pub victim_swaps: Vec<Vec<SwapResponse>>,
+    pub victim_swaps_gas_details_tx_hashes: Vec<B256>,
+    pub victim_swaps_gas_details: Vec<GasDetails>,
+    pub backrun_burn_tx_hash: B256,
+    pub backrun_burns: Vec<LiquidityResponse>,
+    pub backrun_burn_gas_details: GasDetails,
+}
+
+impl From<JitLiquidity> for JitResponse {
+    fn from(jit: JitLiquidity) -> Self {
+        Self {
+            block_number: jit.block_number,
+            frontrun_mint_tx_hash: jit.frontrun_mint_tx_hash,
+            frontrun_mints: flat(jit.frontrun_mints),
+            frontrun_mint_gas_details: jit.frontrun_mint_gas_details,
+            victim_swaps_tx_hashes: jit.victim_swaps_tx_hashes,
+            victim_swaps: nested(jit.victim_swaps),
+            victim_swaps_gas_details_tx_hashes: jit.victim_swaps_gas_details_tx_hashes,
+            victim_swaps_gas_details: jit.victim_swaps_gas_details,
+            backrun_burn_tx_hash: jit.backrun_burn_tx_hash,
+            backrun_burns: flat(jit.backrun_burns),
+            backrun_burn_gas_details: jit.backrun_burn_gas_details,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct CexDexQuoteResponse {
+    pub tx_hash:           B256,
+    pub block_timestamp:   u64,
+    pub block_number:      u64,
+    pub swaps:             Vec<SwapResponse>,
+    pub instant_mid_price: Vec<f64>,
+    pub t2_mid_price:      Vec<f64>,
+    pub t12_mid_price:     Vec<f64>,
+    pub t30_mid_price:     Vec<f64>,
+    pub t60_mid_price:     Vec<f64>,
+    pub t300_mid_price:    Vec<f64>,
+    pub exchange:          CexExchange,
+    pub pnl:               f64,
+    pub gas_details:       GasDetails,
+}
+
+impl From<CexDexQuote> for CexDexQuoteResponse {
+    fn from(quote: CexDexQuote) -> Self {
+        Self {
+            tx_hash:           quote.tx_hash,
+            block_timestamp:   quote.block_timestamp,
+            block_number:      quote.block_number,
+            swaps:             flat(quote.swaps),
+            instant_mid_price: quote.instant_mid_price,
+            t2_mid_price:      quote.t2_mid_price,
+            t12_mid_price:     quote.t12_mid_price,
+            t30_mid_price:     quote.t30_mid_price,
+            t60_mid_price:     quote.t60_mid_price,
+            t300_mid_price:    quote.t300_mid_price,
+            exchange:          quote.exchange,
+            pnl:               quote.pnl,
+            gas_details:       quote.gas_details,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct CexDexResponse {
+    pub tx_hash: B256,
+    pub block_timestamp: u64,
+    pub block_number: u64,
+    pub header_pnl_methodology: CexMethodology,
+    pub swaps: Vec<SwapResponse>,
+    pub global_vmap_details: Vec<ArbDetailsResponse>,
+    pub global_vmap_pnl_maker: f64,
+    pub global_vmap_pnl_taker: f64,
+    pub optimal_route_details: Vec<ArbDetailsResponse>,
+    pub optimal_route_pnl_maker: f64,
+    pub optimal_route_pnl_taker: f64,
+    pub optimistic_route_details: Vec<ArbDetailsResponse>,
+    pub optimistic_trade_details: Vec<Vec<OptimisticTradeResponse>>,
+    pub optimistic_route_pnl_maker: f64,
+    pub optimistic_route_pnl_taker: f64,
+    pub per_exchange_details: Vec<Vec<ArbDetailsResponse>>,
+    pub per_exchange_pnl: Vec<ExchangePnlResponse>,
+    pub gas_details: GasDetails,
+}
+
+impl From<CexDex> for CexDexResponse {
+    fn from(cex_dex: CexDex) -> Self {
+        Self {
+            tx_hash: cex_dex.tx_hash,
+            block_timestamp: cex_dex.block_timestamp,
+            block_number: cex_dex.block_number,
+            header_pnl_methodology: cex_dex.header_pnl_methodology,
+            swaps: flat(cex_dex.swaps),
+            global_vmap_details: flat(cex_dex.global_vmap_details),
+            global_vmap_pnl_maker: cex_dex.global_vmap_pnl_maker.to_float(),
+            global_vmap_pnl_taker: cex_dex.global_vmap_pnl_taker.to_float(),
+            optimal_route_details: flat(cex_dex.optimal_route_details),
+            optimal_route_pnl_maker: cex_dex.optimal_route_pnl_maker.to_float(),
+            optimal_route_pnl_taker: cex_dex.optimal_route_pnl_taker.to_float(),
+            optimistic_route_details: flat(cex_dex.optimistic_route_details),
+            optimistic_trade_details: nested(cex_dex.optimistic_trade_details),
+            optimistic_route_pnl_maker: cex_dex.optimistic_route_pnl_maker.to_float(),
+            optimistic_route_pnl_taker: cex_dex.optimistic_route_pnl_taker.to_float(),
+            per_exchange_details: nested(cex_dex.per_exchange_details),
+            per_exchange_pnl: cex_dex
+                .per_exchange_pnl
+                .into_iter()
+                .map(|(exchange, (pnl_maker, pnl_taker))| ExchangePnlResponse {
+                    exchange,
+                    pnl_maker: pnl_maker.to_float(),
+                    pnl_taker: pnl_taker.to_float(),
+                })
+                .collect(),
+            gas_details: cex_dex.gas_details,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct ArbDetailsResponse {
+    pub pairs:            Vec<Pair>,
+    pub trade_start_time: u64,
+    pub trade_end_time:   u64,
+    pub cex_exchange:     CexExchange,
+    pub price_maker:      f64,
+    pub price_taker:      f64,
+    pub dex_exchange:     Protocol,
+    pub dex_price:        f64,
+    pub dex_amount:       f64,
+    pub pnl_maker:        f64,
+    pub pnl_taker:        f64,
+}
+
+impl From<ArbDetails> for ArbDetailsResponse {
+    fn from(details: ArbDetails) -> Self {
+        Self {
+            pairs:            details.pairs,
+            trade_start_time: details.trade_start_time,
+            trade_end_time:   details.trade_end_time,
+            cex_exchange:     details.cex_exchange,
+            price_maker:      details.price_maker.to_float(),
+            price_taker:      details.price_taker.to_float(),
+            dex_exchange:     details.dex_exchange,
+            dex_price:        details.dex_price.to_float(),
+            dex_amount:       details.dex_amount.to_float(),
+            pnl_maker:        details.pnl_maker.to_float(),
+            pnl_taker:        details.pnl_taker.to_float(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct OptimisticTradeResponse {
+    pub exchange:  CexExchange,
+    pub pair:      Pair,
+    pub timestamp: u64,
+    pub price:     f64,
+    pub volume:    f64,
+}
+
+impl From<OptimisticTrade> for OptimisticTradeResponse {
+    fn from(trade: OptimisticTrade) -> Self {
+        Self {
+            exchange:  trade.exchange,
+            pair:      trade.pair,
+            timestamp: trade.timestamp,
+            price:     trade.price.to_float(),
+            volume:    trade.volume.to_float(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct ExchangePnlResponse {
+    pub exchange:  CexExchange,
+    pub pnl_maker: f64,
+    pub pnl_taker: f64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct LiquidationResponse {
+    pub liquidation_tx_hash: B256,
+    pub block_number:        u64,
+    pub trigger:             B256,
+    pub liquidation_swaps:   Vec<SwapResponse>,
+    pub liquidations:        Vec<LiquidationActionResponse>,
+    pub gas_details:         GasDetails,
+}
+
+impl From<Liquidation> for LiquidationResponse {
+    fn from(liquidation: Liquidation) -> Self {
+        Self {
+            liquidation_tx_hash: liquidation.liquidation_tx_hash,
+            block_number:        liquidation.block_number,
+            trigger:             liquidation.trigger,
+            liquidation_swaps:   flat(liquidation.liquidation_swaps),
+            liquidations:        flat(liquidation.liquidations),
+            gas_details:         liquidation.gas_details,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct SearcherTxResponse {
+    pub tx_hash:      B256,
+    pub block_number: u64,
+    pub transfers:    Vec<TransferResponse>,
+    pub gas_details:  GasDetails,
+}
+
+impl From<SearcherTx> for SearcherTxResponse {
+    fn from(tx: SearcherTx) -> Self {
+        Self {
+            tx_hash:      tx.tx_hash,
+            block_number: tx.block_number,
+            transfers:    flat(tx.transfers),
+            gas_details:  tx.gas_details,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct NftArbResponse {
+    pub tx_hash:      B256,
+    pub block_number: u64,
+    pub trades:       Vec<NftTradeResponse>,
+    pub gas_details:  GasDetails,
+}
+
+impl From<NftArb> for NftArbResponse {
+    fn from(arb: NftArb) -> Self {
+        Self {
+            tx_hash:      arb.tx_hash,
+            block_number: arb.block_number,
+            trades:       flat(arb.trades),
+            gas_details:  arb.gas_details,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct SwapResponse {
+    pub protocol:    Protocol,
+    pub trace_index: u64,
+    pub from:        Address,
+    pub recipient:   Address,
+    pub pool:        Address,
+    pub token_in:    TokenResponse,
+    pub token_out:   TokenResponse,
+    pub amount_in:   f64,
+    pub amount_out:  f64,
+    pub msg_value:   U256,
+}
+
+impl From<NormalizedSwap> for SwapResponse {
+    fn from(swap: NormalizedSwap) -> Self {
+        Self {
+            protocol:    swap.protocol,
+            trace_index: swap.trace_index,
+            from:        swap.from,
+            recipient:   swap.recipient,
+            pool:        swap.pool,
+            token_in:    swap.token_in.into(),
+            token_out:   swap.token_out.into(),
+            amount_in:   swap.amount_in.to_float(),
+            amount_out:  swap.amount_out.to_float(),
+            msg_value:   swap.msg_value,
+        }
+    }
+}
+
+/// A mint or a burn
+#[derive(Debug, Serialize)]
+pub struct LiquidityResponse {
+    pub protocol:    Protocol,
+    pub trace_index: u64,
+    pub from:        Address,
+    pub recipient:   Address,
+    pub pool:        Address,
+    pub tokens:      Vec<TokenResponse>,
+    pub amounts:     Vec<f64>,
+}
+
+impl From<NormalizedMint> for LiquidityResponse {
+    fn from(mint: NormalizedMint) -> Self {
+        Self {
+            protocol:    mint.protocol,
+            trace_index: mint.trace_index,
+            from:        mint.from,
+            recipient:   mint.recipient,
+            pool:        mint.pool,
+            tokens:      flat(mint.token),
+            amounts:     mint
+                .amount
+                .into_iter()
+                .map(ToFloatNearest::to_float)
+                .collect(),
+        }
+    }
+}
+
+impl From<NormalizedBurn> for LiquidityResponse {
+    fn from(burn: NormalizedBurn) -> Self {
+        Self {
+            protocol:    burn.protocol,
+            trace_index: burn.trace_index,
+            from:        burn.from,
+            recipient:   burn.recipient,
+            pool:        burn.pool,
+            tokens:      flat(burn.token),
+            amounts:     burn
+                .amount
+                .into_iter()
+                .map(ToFloatNearest::to_float)
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct LiquidationActionResponse {
+    pub protocol:              Protocol,
+    pub trace_index:           u64,
+    pub pool:                  Address,
+    pub liquidator:            Address,
+    pub debtor:                Address,
+    pub collateral_asset:      TokenResponse,
+    pub debt_asset:            TokenResponse,
+    pub covered_debt:          f64,
+    pub liquidated_collateral: f64,
+    pub msg_value:             U256,
+}
+
+impl From<NormalizedLiquidation> for LiquidationActionResponse {
+    fn from(liquidation: NormalizedLiquidation) -> Self {
+        Self {
+            protocol:              liquidation.protocol,
+            trace_index:           liquidation.trace_index,
+            pool:                  liquidation.pool,
+            liquidator:            liquidation.liquidator,
+            debtor:                liquidation.debtor,
+            collateral_asset:      liquidation.collateral_asset.into(),
+            debt_asset:            liquidation.debt_asset.into(),
+            covered_debt:          liquidation.covered_debt.to_float(),
+            liquidated_collateral: liquidation.liquidated_collateral.to_float(),
+            msg_value:             liquidation.msg_value,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct TransferResponse {
+    pub trace_index: u64,
+    pub from:        Address,
+    pub to:          Address,
+    pub token:       TokenResponse,
+    pub amount:      f64,
+    pub fee:         f64,
+    pub msg_value:   U256,
+}
+
+impl From<NormalizedTransfer> for TransferResponse {
+    fn from(transfer: NormalizedTransfer) -> Self {
+        Self {
+            trace_index: transfer.trace_index,
+            from:        transfer.from,
+            to:          transfer.to,
+            token:       transfer.token.into(),
+            amount:      transfer.amount.to_float(),
+            fee:         transfer.fee.to_float(),
+            msg_value:   transfer.msg_value,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct NftTradeResponse {
+    pub protocol:      Protocol,
+    pub trace_index:   u64,
+    pub marketplace:   Address,
+    pub buyer:         Address,
+    pub seller:        Address,
+    pub standard:      NftStandard,
+    pub collection:    Address,
+    pub token_ids:     Vec<U256>,
+    pub amounts:       Vec<U256>,
+    pub payment_token: TokenResponse,
+    pub price:         f64,
+    pub msg_value:     U256,
+}
+
+impl From<NormalizedNftTrade> for NftTradeResponse {
+    fn from(trade: NormalizedNftTrade) -> Self {
+        Self {
+            protocol:      trade.protocol,
+            trace_index:   trade.trace_index,
+            marketplace:   trade.marketplace,
+            buyer:         trade.buyer,
+            seller:        trade.seller,
+            standard:      trade.standard,
+            collection:    trade.collection,
+            token_ids:     trade.token_ids,
+            amounts:       trade.amounts,
+            payment_token: trade.payment_token.into(),
+            price:         trade.price.to_float(),
+            msg_value:     trade.msg_value,
+        }
+    }
+}
+
+fn flat<T, R: From<T>>(items: Vec<T>) -> Vec<R> {
+    items.into_iter().map(Into::into).collect()
+}
+
+fn nested<T, R: From<T>>(items: Vec<Vec<T>>) -> Vec<Vec<R>> {
+    items.into_iter().map(flat).collect()
+}
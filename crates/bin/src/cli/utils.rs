@@ -1,4 +1,8 @@
-use std::{env, path::Path};
+use std::{
+    env,
+    io::{BufRead, Write},
+    path::Path,
+};
 
 use alloy_primitives::Address;
 #[cfg(not(feature = "local-reth"))]
@@ -27,9 +31,12 @@ use brontes_types::{
     },
     db_write_trigger::HeartRateMonitor,
     mev::Bundle,
+    structured_trace::TxTrace,
+    traits::TracingProvider,
     BrontesTaskExecutor,
 };
 use itertools::Itertools;
+use reth_primitives::Header;
 #[cfg(feature = "local-reth")]
 use reth_tracing_ext::TracingClient;
 use strum::IntoEnumIterator;
@@ -41,8 +48,10 @@ pub async fn load_database(
     db_endpoint: String,
     _: Option<HeartRateMonitor>,
     _: Option<u64>,
+    persist_trees: bool,
 ) -> eyre::Result<LibmdbxReadWriter> {
-    LibmdbxReadWriter::init_db(db_endpoint, None, executor, true)
+    Ok(LibmdbxReadWriter::init_db(db_endpoint, None, executor, true)?
+        .with_persisted_trees(persist_trees))
 }
 
 #[cfg(not(feature = "local-clickhouse"))]
@@ -57,8 +66,10 @@ pub async fn load_database(
     db_endpoint: String,
     hr: Option<HeartRateMonitor>,
     run_id: Option<u64>,
+    persist_trees: bool,
 ) -> eyre::Result<ClickhouseMiddleware<LibmdbxReadWriter>> {
-    let inner = LibmdbxReadWriter::init_db(db_endpoint, None, executor, true)?;
+    let inner = LibmdbxReadWriter::init_db(db_endpoint, None, executor, true)?
+        .with_persisted_trees(persist_trees);
 
     let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
     spawn_db_writer_thread(executor, rx, hr);
@@ -97,6 +108,26 @@ pub fn load_libmdbx(
     LibmdbxReadWriter::init_db(db_endpoint, None, executor, true)
 }
 
+/// Loads the traces stored in libmdbx for a block along with its header. Unlike
+/// the parser, a block without stored traces is an error instead of being
+/// re-traced
+pub async fn load_stored_block<DB: LibmdbxReader, T: TracingProvider>(
+    libmdbx: &DB,
+    tracer: &T,
+    block: u64,
+) -> eyre::Result<(Vec<TxTrace>, Header)> {
+    let mut traces = libmdbx.load_trace(block)?;
+    traces.sort_by_key(|trace| trace.tx_index);
+    traces.dedup_by_key(|trace| trace.tx_index);
+
+    let header = tracer
+        .header_by_number(block)
+        .await?
+        .ok_or_else(|| eyre::eyre!("missing header for block {block}"))?;
+
+    Ok((traces, header))
+}
+
 #[allow(clippy::field_reassign_with_default)]
 #[cfg(feature = "local-clickhouse")]
 pub async fn load_clickhouse(
@@ -146,12 +177,24 @@ pub fn determine_max_tasks(max_tasks: Option<u64>) -> u64 {
     }
 }
 
+/// Asks for confirmation on stdin, anything other than `y` or `yes` declines
+pub fn confirm(prompt: &str) -> std::io::Result<bool> {
+    print!("{prompt} [y/N] ");
+    std::io::stdout().flush()?;
+
+    let mut answer = String::new();
+    std::io::stdin().lock().read_line(&mut answer)?;
+
+    Ok(matches!(answer.trim().to_lowercase().as_str(), "y" | "yes"))
+}
+
 pub fn static_object<T>(obj: T) -> &'static T {
     &*Box::leak(Box::new(obj))
 }
 
 pub fn init_inspectors<DB: LibmdbxReader>(
     quote_token: Address,
+    extra_quote_tokens: &[Address],
     db: &'static DB,
     inspectors: Option<Vec<Inspectors>>,
     cex_exchanges: Vec<CexExchange>,
@@ -166,6 +209,7 @@ pub fn init_inspectors<DB: LibmdbxReader>(
     {
         res.push(inspector.init_mev_inspector(
             quote_token,
+            extra_quote_tokens,
             db,
             &cex_exchanges,
             trade_config,
@@ -183,6 +227,18 @@ pub fn get_env_vars() -> eyre::Result<String> {
     Ok(db_path)
 }
 
+/// The reth db path, only required when reading from a local reth. Otherwise
+/// the tracing provider talks to the node's rpc and ignores it
+#[cfg(feature = "local-reth")]
+pub fn reth_db_path() -> eyre::Result<String> {
+    get_env_vars()
+}
+
+#[cfg(not(feature = "local-reth"))]
+pub fn reth_db_path() -> eyre::Result<String> {
+    Ok(String::new())
+}
+
 #[cfg(feature = "local-clickhouse")]
 fn spawn_db_writer_thread(
     executor: &BrontesTaskExecutor,
@@ -27,15 +27,16 @@ use alloy_primitives::Address;
 use brontes_classifier::Classifier;
 use brontes_core::decoding::{Parser, TracingProvider};
 use brontes_database::libmdbx::LibmdbxInit;
-use brontes_inspect::Inspector;
+use brontes_inspect::{lvr::LvrCalculator, Inspector};
 use brontes_pricing::{BrontesBatchPricer, GraphManager, LoadState};
 use brontes_types::{
-    db::traits::LibmdbxReader, BrontesTaskExecutor, FastHashMap, UnboundedYapperReceiver,
+    db::{run_checkpoint::RunCheckpoint, traits::LibmdbxReader},
+    BrontesTaskExecutor, FastHashMap, UnboundedYapperReceiver,
 };
 use futures::{stream::FuturesUnordered, Future, StreamExt};
 use indicatif::MultiProgress;
 use itertools::Itertools;
-pub use range::RangeExecutorWithPricing;
+pub use range::{RangeCheckpoint, RangeExecutorWithPricing};
 use reth_tasks::shutdown::GracefulShutdown;
 pub use tip::TipInspector;
 use tokio::{sync::mpsc::unbounded_channel, task::JoinHandle};
@@ -65,6 +66,10 @@ pub struct BrontesRunConfig<T: TracingProvider, DB: LibmdbxInit, CH: ClickhouseH
     pub metrics: bool,
     pub is_snapshot: bool,
     pub cex_window: usize,
+    /// progress of the run being resumed
+    pub resume: Option<RunCheckpoint>,
+    /// measures the lvr of every processed block when set
+    pub lvr: Option<&'static LvrCalculator<'static, DB>>,
     _p: PhantomData<P>,
 }
 
@@ -105,10 +110,26 @@ impl<T: TracingProvider, DB: LibmdbxInit, CH: ClickhouseHandle, P: Processor>
             tip_db,
             is_snapshot,
             cex_window,
+            resume: None,
+            lvr: None,
             _p: PhantomData,
         }
     }
 
+    /// Continues the range executors of a previous run from their checkpoints
+    /// instead of splitting the range into new chunks
+    pub fn with_resume(mut self, checkpoint: RunCheckpoint) -> Self {
+        self.resume = Some(checkpoint);
+        self
+    }
+
+    /// Stores the loss versus rebalancing of the pools swapped against in
+    /// each processed block
+    pub fn with_lvr(mut self, lvr: &'static LvrCalculator<'static, DB>) -> Self {
+        self.lvr = Some(lvr);
+        self
+    }
+
     pub async fn build(
         self,
         executor: BrontesTaskExecutor,
@@ -160,7 +181,32 @@ impl<T: TracingProvider, DB: LibmdbxInit, CH: ClickhouseHandle, P: Processor>
         }
 
         if !should_run_tip_inspector {
-            self.build_range_executors(executor.clone(), end_block, pricing_metrics.clone())
+            let checkpoint = self.run_checkpoint(end_block).await?;
+            self.build_range_executors(
+                executor.clone(),
+                end_block,
+                checkpoint,
+                pricing_metrics.clone(),
+            )
+            .for_each(|block_range| {
+                futures.push(executor.spawn_critical_with_graceful_shutdown_signal(
+                    "Range Executor",
+                    |shutdown| async move {
+                        block_range.run_until_graceful_shutdown(shutdown).await;
+                    },
+                ));
+                std::future::ready(())
+            })
+            .await;
+        } else {
+            if self.range_type.get_start_block(self.libmdbx).is_some() {
+                let checkpoint = self.run_checkpoint(end_block).await?;
+                self.build_range_executors(
+                    executor.clone(),
+                    end_block,
+                    checkpoint,
+                    pricing_metrics.clone(),
+                )
                 .for_each(|block_range| {
                     futures.push(executor.spawn_critical_with_graceful_shutdown_signal(
                         "Range Executor",
@@ -171,19 +217,6 @@ impl<T: TracingProvider, DB: LibmdbxInit, CH: ClickhouseHandle, P: Processor>
                     std::future::ready(())
                 })
                 .await;
-        } else {
-            if self.range_type.get_start_block(self.libmdbx).is_some() {
-                self.build_range_executors(executor.clone(), end_block, pricing_metrics.clone())
-                    .for_each(|block_range| {
-                        futures.push(executor.spawn_critical_with_graceful_shutdown_signal(
-                            "Range Executor",
-                            |shutdown| async move {
-                                block_range.run_until_graceful_shutdown(shutdown).await;
-                            },
-                        ));
-                        std::future::ready(())
-                    })
-                    .await;
             }
             tracing::info!("starting tip inspector");
             let tip_inspector = self.build_tip_inspector(
@@ -229,6 +262,7 @@ impl<T: TracingProvider, DB: LibmdbxInit, CH: ClickhouseHandle, P: Processor>
     ///
     /// * `executor` - The task executor for spawning asynchronous tasks.
     /// * `end_block` - The final block to process.
+    /// * `checkpoint` - The chunks of the run and how far each of them got.
     /// * `pricing_metrics` - Optional metrics for DEX pricing.
     ///
     /// # Returns
@@ -245,22 +279,14 @@ impl<T: TracingProvider, DB: LibmdbxInit, CH: ClickhouseHandle, P: Processor>
         &'_ self,
         executor: BrontesTaskExecutor,
         end_block: u64,
+        checkpoint: RunCheckpoint,
         pricing_metrics: Option<DexPricingMetrics>,
     ) -> impl Stream<Item = RangeExecutorWithPricing<T, DB, CH, P>> + '_ {
-        let chunks = match &self.range_type {
-            RangeType::SingleRange { start_block, from_db_tip, .. } => {
-                let start_block = if *from_db_tip {
-                    self.libmdbx
-                        .get_most_recent_block()
-                        .unwrap_or_else(|_| start_block.unwrap())
-                } else {
-                    start_block.unwrap()
-                };
-
-                self.calculate_chunks(start_block, end_block)
-            }
-            RangeType::MultipleRanges(ranges) => ranges.clone(),
-        };
+        let run_id = checkpoint.run_id;
+        let chunks = checkpoint
+            .remaining()
+            .map(|(chunk_id, chunk)| (chunk_id, chunk.clone()))
+            .collect_vec();
 
         let progress_bar = self.initialize_global_progress_bar();
 
@@ -289,11 +315,17 @@ impl<T: TracingProvider, DB: LibmdbxInit, CH: ClickhouseHandle, P: Processor>
         }
 
         let range_metrics = self.metrics.then(|| {
-            GlobalRangeMetrics::new(chunks.iter().map(|(start, end)| end - start).collect_vec())
+            GlobalRangeMetrics::new(
+                chunks
+                    .iter()
+                    .map(|(_, chunk)| chunk.end_block - chunk.next_block)
+                    .collect_vec(),
+            )
         });
 
         futures::stream::iter(chunks.into_iter().enumerate().map(
-            move |(batch_id, (start_block, end_block))| {
+            move |(batch_id, (chunk_id, chunk))| {
+                let (start_block, end_block) = (chunk.next_block, chunk.end_block);
                 let ranges =
                     state_to_init.get_state_for_ranges(start_block as usize, end_block as usize);
 
@@ -330,8 +362,14 @@ impl<T: TracingProvider, DB: LibmdbxInit, CH: ClickhouseHandle, P: Processor>
                         ),
                         self.libmdbx,
                         self.inspectors,
+                        self.lvr,
                         prgrs_bar,
                         metrics,
+                        RangeCheckpoint {
+                            run_id,
+                            chunk_id,
+                            committed: chunk.committed_ahead.into_iter().collect(),
+                        },
                     )
                 }
             },
@@ -339,6 +377,46 @@ impl<T: TracingProvider, DB: LibmdbxInit, CH: ClickhouseHandle, P: Processor>
         .buffer_unordered(buffer_size)
     }
 
+    /// Returns the checkpoint of the run being resumed, otherwise splits the
+    /// range into chunks and stores a new checkpoint for them
+    async fn run_checkpoint(&self, end_block: u64) -> eyre::Result<RunCheckpoint> {
+        if let Some(checkpoint) = &self.resume {
+            tracing::info!(run_id = checkpoint.run_id, "resuming run from its checkpoint");
+            return Ok(checkpoint.clone())
+        }
+
+        let chunks = match &self.range_type {
+            RangeType::SingleRange { start_block, from_db_tip, .. } => {
+                let start_block = if *from_db_tip {
+                    self.libmdbx
+                        .get_most_recent_block()
+                        .unwrap_or_else(|_| start_block.unwrap())
+                } else {
+                    start_block.unwrap()
+                };
+
+                self.calculate_chunks(start_block, end_block)
+            }
+            RangeType::MultipleRanges(ranges) => ranges.clone(),
+        };
+
+        let run_id = self
+            .libmdbx
+            .latest_run_checkpoint_id()?
+            .map(|id| id + 1)
+            .unwrap_or_default();
+        let checkpoint = RunCheckpoint::new(run_id, &chunks);
+        self.libmdbx
+            .write_run_checkpoint(checkpoint.clone())
+            .await?;
+        tracing::info!(
+            run_id,
+            "checkpointing run, continue it after a crash with `--resume {run_id}`"
+        );
+
+        Ok(checkpoint)
+    }
+
     fn build_tip_inspector(
         &self,
         range_id: usize,
@@ -362,6 +440,7 @@ impl<T: TracingProvider, DB: LibmdbxInit, CH: ClickhouseHandle, P: Processor>
             self.parser,
             self.tip_db,
             self.inspectors,
+            self.lvr,
         )
     }
 
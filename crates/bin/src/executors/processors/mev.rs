@@ -1,24 +1,23 @@
-#[cfg(feature = "local-clickhouse")]
 use std::sync::Arc;
 
+use alloy_primitives::Address;
 use brontes_database::libmdbx::{DBWriter, LibmdbxReader};
 use brontes_inspect::{
     composer::{run_block_inspection, ComposerResults},
+    lvr::LvrCalculator,
     Inspector,
 };
-#[cfg(feature = "local-clickhouse")]
-use brontes_types::frontend_prunes::{
-    remove_burn_transfers, remove_collect_transfers, remove_mint_transfers, remove_swap_transfers,
-};
-#[cfg(feature = "local-clickhouse")]
-use brontes_types::normalized_actions::Action;
-#[cfg(feature = "local-clickhouse")]
-use brontes_types::tree::BlockTree;
 use brontes_types::{
-    db::block_analysis::BlockAnalysis,
+    db::{block_analysis::BlockAnalysis, metadata::Metadata, searcher::SearcherInfo},
     execute_on,
-    mev::{Bundle, MevBlock, MevType},
-    BlockData, MultiBlockData,
+    frontend_prunes::{
+        remove_burn_transfers, remove_collect_transfers, remove_mint_transfers,
+        remove_swap_transfers,
+    },
+    mev::{BlockFailedAttempts, Bundle, MevBlock, MevType},
+    normalized_actions::Action,
+    tree::BlockTree,
+    BlockData, FastHashMap, MultiBlockData,
 };
 use tracing::debug;
 
@@ -34,6 +33,7 @@ impl Processor for MevProcessor {
     async fn process_results<DB: DBWriter + LibmdbxReader>(
         db: &'static DB,
         inspectors: &'static [&dyn Inspector<Result = Self::InspectType>],
+        lvr: Option<&'static LvrCalculator<'static, DB>>,
         data: MultiBlockData,
     ) {
         let last = data.get_most_recent_block().clone();
@@ -45,20 +45,22 @@ impl Processor for MevProcessor {
             tracing::error!(err=%e, block_num=metadata.block_num, "failed to insert dex pricing and state into db");
         }
 
-        #[cfg(feature = "local-clickhouse")]
-        {
-            let inner_tree = Arc::unwrap_or_clone(tree.clone());
-            insert_tree(db, inner_tree, metadata.block_num).await;
+        if let Some(lvr) = lvr {
+            insert_block_lvr(db, lvr, &tree, metadata.clone()).await;
         }
 
-        let ComposerResults { block_details, mev_details, block_analysis, .. } =
+        let inner_tree = Arc::unwrap_or_clone(tree.clone());
+        insert_tree(db, inner_tree, metadata.block_num).await;
+
+        let ComposerResults { block_details, mev_details, block_analysis, failed_attempts, .. } =
             execute_on!(async_inspect, { run_block_inspection(inspectors, data, db) }).await;
 
+        update_searcher_info(db, &mev_details, &failed_attempts).await;
         insert_mev_results(db, block_details, mev_details, block_analysis).await;
+        insert_failed_attempts(db, failed_attempts).await;
     }
 }
 
-#[cfg(feature = "local-clickhouse")]
 async fn insert_tree<DB: DBWriter + LibmdbxReader>(
     db: &DB,
     mut tree_owned: BlockTree<Action>,
@@ -74,6 +76,21 @@ async fn insert_tree<DB: DBWriter + LibmdbxReader>(
     }
 }
 
+async fn insert_block_lvr<DB: DBWriter + LibmdbxReader>(
+    db: &DB,
+    lvr: &LvrCalculator<'static, DB>,
+    tree: &BlockTree<Action>,
+    metadata: Arc<Metadata>,
+) {
+    let block_num = metadata.block_num;
+    if let Err(e) = db
+        .write_block_lvr(lvr.calculate_block_lvr(tree, metadata))
+        .await
+    {
+        tracing::error!(err=%e, %block_num, "failed to insert block lvr into db");
+    }
+}
+
 async fn insert_mev_results<DB: DBWriter + LibmdbxReader>(
     database: &'static DB,
     block_details: MevBlock,
@@ -86,8 +103,15 @@ async fn insert_mev_results<DB: DBWriter + LibmdbxReader>(
         block_details.to_string()
     );
 
+    for mev in &mev_details {
+        debug!(
+            target: "brontes::results",
+            "mev details\n {}",
+            mev.to_string()
+        );
+    }
+
     let block_number = block_details.block_number;
-    output_mev_and_update_searcher_info(database, &mev_details).await;
 
     // Attempt to save the MEV block details
     if let Err(e) = database
@@ -108,41 +132,178 @@ async fn insert_mev_results<DB: DBWriter + LibmdbxReader>(
         );
     }
 }
-async fn output_mev_and_update_searcher_info<DB: DBWriter + LibmdbxReader>(
+
+/// Folds the bundles and failed attempts of the block into the searcher info,
+/// reading and writing each searcher once
+async fn update_searcher_info<DB: DBWriter + LibmdbxReader>(
     database: &DB,
-    mev_details: &Vec<Bundle>,
+    mev_details: &[Bundle],
+    failed_attempts: &BlockFailedAttempts,
 ) {
-    for mev in mev_details {
-        debug!(
-            target: "brontes::results",
-            "mev details\n {}",
-            mev.to_string()
-        );
+    let (eoas, contracts) = fold_searcher_info(
+        mev_details,
+        failed_attempts,
+        |eoa| database.try_fetch_searcher_eoa_info(eoa),
+        |contract| database.try_fetch_searcher_contract_info(contract),
+    )
+    .expect("Failed to fetch searcher info from the database");
+
+    for (eoa, info) in eoas {
+        if let Err(e) = database.write_searcher_eoa_info(eoa, info).await {
+            tracing::error!("Failed to update searcher info in the database: {:?}", e);
+        }
+    }
+    for (contract, info) in contracts {
+        if let Err(e) = database.write_searcher_contract_info(contract, info).await {
+            tracing::error!("Failed to update searcher info in the database: {:?}", e);
+        }
+    }
+}
+
+type SearcherInfos = FastHashMap<Address, SearcherInfo>;
+
+/// Bundles create the info of the searchers that don't have one yet, failed
+/// attempts only count towards the contracts we already know
+fn fold_searcher_info(
+    mev_details: &[Bundle],
+    failed_attempts: &BlockFailedAttempts,
+    fetch_eoa: impl Fn(Address) -> eyre::Result<Option<SearcherInfo>>,
+    fetch_contract: impl Fn(Address) -> eyre::Result<Option<SearcherInfo>>,
+) -> eyre::Result<(SearcherInfos, SearcherInfos)> {
+    let mut eoas: FastHashMap<Address, Option<SearcherInfo>> = FastHashMap::default();
+    let mut contracts: FastHashMap<Address, Option<SearcherInfo>> = FastHashMap::default();
 
+    fn entry<'a>(
+        infos: &'a mut FastHashMap<Address, Option<SearcherInfo>>,
+        address: Address,
+        fetch: &impl Fn(Address) -> eyre::Result<Option<SearcherInfo>>,
+    ) -> eyre::Result<&'a mut Option<SearcherInfo>> {
+        if !infos.contains_key(&address) {
+            infos.insert(address, fetch(address)?);
+        }
+        Ok(infos.get_mut(&address).unwrap())
+    }
+
+    for mev in mev_details {
         if mev.header.mev_type == MevType::Unknown || mev.header.mev_type == MevType::SearcherTx {
             continue
         }
 
-        let (eoa_info, contract_info) = database
-            .try_fetch_searcher_info(mev.header.eoa, mev.header.mev_contract)
-            .expect("Failed to fetch searcher info from the database");
+        entry(&mut eoas, mev.header.eoa, &fetch_eoa)?
+            .get_or_insert_with(Default::default)
+            .update_with_bundle(&mev.header);
+        if let Some(contract) = mev.header.mev_contract {
+            entry(&mut contracts, contract, &fetch_contract)?
+                .get_or_insert_with(Default::default)
+                .update_with_bundle(&mev.header);
+        }
+    }
 
-        let mut eoa_info = eoa_info.unwrap_or_default();
-        let mut contract_info = contract_info.unwrap_or_default();
+    for attempt in &failed_attempts.attempts {
+        entry(&mut eoas, attempt.eoa, &fetch_eoa)?
+            .get_or_insert_with(Default::default)
+            .update_with_failed_attempt(attempt);
+        if let Some(contract) = attempt.mev_contract {
+            if let Some(info) = entry(&mut contracts, contract, &fetch_contract)? {
+                info.update_with_failed_attempt(attempt);
+            }
+        }
+    }
 
-        eoa_info.update_with_bundle(&mev.header);
-        contract_info.update_with_bundle(&mev.header);
+    let updated = |infos: FastHashMap<Address, Option<SearcherInfo>>| {
+        infos
+            .into_iter()
+            .filter_map(|(address, info)| Some((address, info?)))
+            .collect()
+    };
 
-        if let Err(e) = database
-            .write_searcher_info(
-                mev.header.eoa,
-                mev.header.mev_contract,
-                eoa_info,
-                Some(contract_info),
-            )
-            .await
-        {
-            tracing::error!("Failed to update searcher info in the database: {:?}", e);
-        }
+    Ok((updated(eoas), updated(contracts)))
+}
+
+async fn insert_failed_attempts<DB: DBWriter + LibmdbxReader>(
+    database: &DB,
+    failed_attempts: BlockFailedAttempts,
+) {
+    if failed_attempts.is_empty() {
+        return
+    }
+
+    let block_number = failed_attempts.block_number;
+    if let Err(e) = database.write_failed_attempts(failed_attempts).await {
+        tracing::error!(
+            "Failed to insert failed mev attempts into db: {:?} at block: {}",
+            e,
+            block_number
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+
+    use brontes_types::mev::{BundleData, BundleHeader, FailedMevAttempt};
+
+    use super::*;
+
+    #[test]
+    fn test_fold_searcher_info() {
+        let searcher = Address::with_last_byte(1);
+        let contract = Address::with_last_byte(2);
+        let unknown_contract = Address::with_last_byte(3);
+
+        let bundle = |mev_type| Bundle {
+            header: BundleHeader {
+                eoa: searcher,
+                mev_contract: Some(contract),
+                mev_type,
+                ..Default::default()
+            },
+            data:   BundleData::default(),
+        };
+        let attempt = |mev_contract| FailedMevAttempt {
+            eoa: searcher,
+            mev_contract,
+            mev_type: MevType::AtomicArb,
+            wasted_gas_usd: 10.0,
+            ..Default::default()
+        };
+
+        let bundles = vec![
+            bundle(MevType::AtomicArb),
+            bundle(MevType::AtomicArb),
+            bundle(MevType::SearcherTx),
+        ];
+        let failed_attempts = BlockFailedAttempts {
+            block_number: 1,
+            attempts:     vec![attempt(Some(contract)), attempt(Some(unknown_contract))],
+        };
+
+        let fetches = RefCell::new(Vec::new());
+        let (eoas, contracts) = fold_searcher_info(
+            &bundles,
+            &failed_attempts,
+            |eoa| {
+                fetches.borrow_mut().push(eoa);
+                Ok(None)
+            },
+            |contract| {
+                fetches.borrow_mut().push(contract);
+                Ok(None)
+            },
+        )
+        .unwrap();
+
+        // every searcher is read once, however many bundles and attempts it has
+        assert_eq!(fetches.into_inner(), vec![searcher, contract, unknown_contract]);
+
+        let eoa_info = &eoas[&searcher];
+        assert_eq!(eoa_info.mev_count.atomic_backrun_count, Some(2));
+        assert_eq!(eoa_info.failed_attempt_count, 2);
+
+        let contract_info = &contracts[&contract];
+        assert_eq!(contract_info.mev_count.atomic_backrun_count, Some(2));
+        assert_eq!(contract_info.failed_attempt_count, 1);
+        assert!(!contracts.contains_key(&unknown_contract));
     }
 }
@@ -1,7 +1,7 @@
 pub mod mev;
 
 use brontes_database::libmdbx::{DBWriter, LibmdbxReader};
-use brontes_inspect::Inspector;
+use brontes_inspect::{lvr::LvrCalculator, Inspector};
 use brontes_types::MultiBlockData;
 use futures::Future;
 pub use mev::*;
@@ -12,6 +12,7 @@ pub trait Processor: Send + Sync + 'static + Unpin + Copy + Clone {
     fn process_results<DB: DBWriter + LibmdbxReader>(
         db: &'static DB,
         inspectors: &'static [&dyn Inspector<Result = Self::InspectType>],
+        lvr: Option<&'static LvrCalculator<'static, DB>>,
         data: MultiBlockData,
     ) -> impl Future<Output = ()> + Send;
 }
@@ -9,9 +9,9 @@ use brontes_database::{
     clickhouse::ClickhouseHandle,
     libmdbx::{DBWriter, LibmdbxReader},
 };
-use brontes_inspect::Inspector;
+use brontes_inspect::{lvr::LvrCalculator, Inspector};
 use brontes_metrics::range::GlobalRangeMetrics;
-use brontes_types::MultiBlockData;
+use brontes_types::{FastHashSet, MultiBlockData};
 use futures::{pin_mut, stream::FuturesUnordered, Future, StreamExt};
 use reth_tasks::shutdown::GracefulShutdown;
 use tracing::debug;
@@ -21,6 +21,15 @@ use crate::{executors::ProgressBar, Processor};
 
 type InsertFutures = Pin<Box<dyn Future<Output = ()> + Send + 'static>>;
 
+/// Where a range executor records the blocks it has committed
+pub struct RangeCheckpoint {
+    pub run_id:    u64,
+    pub chunk_id:  usize,
+    /// blocks committed before the run was resumed. They are still fetched so
+    /// the pricing state and block window stay intact, but not processed again
+    pub committed: FastHashSet<u64>,
+}
+
 pub struct RangeExecutorWithPricing<
     T: TracingProvider,
     DB: DBWriter + LibmdbxReader,
@@ -34,8 +43,10 @@ pub struct RangeExecutorWithPricing<
     end_block:      u64,
     libmdbx:        &'static DB,
     inspectors:     &'static [&'static dyn Inspector<Result = P::InspectType>],
+    lvr:            Option<&'static LvrCalculator<'static, DB>>,
     progress_bar:   Option<ProgressBar>,
     global_metrics: Option<GlobalRangeMetrics>,
+    checkpoint:     RangeCheckpoint,
     _p:             PhantomData<P>,
 }
 
@@ -49,8 +60,10 @@ impl<T: TracingProvider, DB: LibmdbxReader + DBWriter, CH: ClickhouseHandle, P:
         state_collector: StateCollector<T, DB, CH>,
         libmdbx: &'static DB,
         inspectors: &'static [&'static dyn Inspector<Result = P::InspectType>],
+        lvr: Option<&'static LvrCalculator<'static, DB>>,
         progress_bar: Option<ProgressBar>,
         global_metrics: Option<GlobalRangeMetrics>,
+        checkpoint: RangeCheckpoint,
     ) -> Self {
         Self {
             id,
@@ -60,8 +73,10 @@ impl<T: TracingProvider, DB: LibmdbxReader + DBWriter, CH: ClickhouseHandle, P:
             end_block,
             libmdbx,
             inspectors,
+            lvr,
             progress_bar,
             global_metrics,
+            checkpoint,
             _p: PhantomData,
         }
     }
@@ -91,20 +106,39 @@ impl<T: TracingProvider, DB: LibmdbxReader + DBWriter, CH: ClickhouseHandle, P:
 
     fn on_price_finish(&mut self, data: MultiBlockData) {
         debug!(target:"brontes","Completed DEX pricing");
+        let block = data.get_most_recent_block().metadata.block_num;
+        if self.checkpoint.committed.remove(&block) {
+            debug!(target:"brontes", %block, "block was committed before resuming, skipping");
+            self.global_metrics
+                .as_ref()
+                .inspect(|m| m.finished_block(self.id));
+            return
+        }
+
         self.global_metrics
             .as_ref()
             .inspect(|m| m.inc_inspector(self.id));
 
         let metrics = self.global_metrics.clone();
         let inspectors = self.inspectors;
+        let lvr = self.lvr;
         let libmdbx = self.libmdbx;
+        let (run_id, chunk_id) = (self.checkpoint.run_id, self.checkpoint.chunk_id);
         self.insert_futures.push(Box::pin(async move {
             if let Some(metrics) = metrics {
                 metrics
-                    .meter_processing(|| Box::pin(P::process_results(libmdbx, inspectors, data)))
+                    .meter_processing(|| {
+                        Box::pin(P::process_results(libmdbx, inspectors, lvr, data))
+                    })
                     .await
             } else {
-                P::process_results(libmdbx, inspectors, data).await
+                P::process_results(libmdbx, inspectors, lvr, data).await
+            }
+
+            // every write of the block has been issued, so the block can be marked
+            // as committed
+            if let Err(e) = libmdbx.commit_run_checkpoint(run_id, chunk_id, block).await {
+                tracing::error!(err=%e, %block, "failed to commit run checkpoint");
             }
         }));
     }
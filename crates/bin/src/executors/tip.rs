@@ -10,7 +10,7 @@ use brontes_database::{
     clickhouse::ClickhouseHandle,
     libmdbx::{DBWriter, LibmdbxReader},
 };
-use brontes_inspect::Inspector;
+use brontes_inspect::{lvr::LvrCalculator, Inspector};
 use brontes_types::MultiBlockData;
 use futures::{pin_mut, stream::FuturesUnordered, Future, StreamExt};
 use reth_tasks::shutdown::GracefulShutdown;
@@ -32,6 +32,7 @@ pub struct TipInspector<
     state_collector:    StateCollector<T, DB, CH>,
     database:           &'static DB,
     inspectors:         &'static [&'static dyn Inspector<Result = P::InspectType>],
+    lvr:                Option<&'static LvrCalculator<'static, DB>>,
     processing_futures: FuturesUnordered<Pin<Box<dyn Future<Output = ()> + Send + 'static>>>,
     poll_interval:      Interval,
     _p:                 PhantomData<P>,
@@ -47,11 +48,13 @@ impl<T: TracingProvider, DB: DBWriter + LibmdbxReader, CH: ClickhouseHandle, P:
         parser: &'static Parser<T, DB>,
         database: &'static DB,
         inspectors: &'static [&'static dyn Inspector<Result = P::InspectType>],
+        lvr: Option<&'static LvrCalculator<'static, DB>>,
     ) -> Self {
         Self {
             back_from_tip,
             state_collector,
             inspectors,
+            lvr,
             current_block,
             parser,
             processing_futures: FuturesUnordered::new(),
@@ -119,6 +122,7 @@ impl<T: TracingProvider, DB: DBWriter + LibmdbxReader, CH: ClickhouseHandle, P:
         self.processing_futures.push(Box::pin(P::process_results(
             self.database,
             self.inspectors,
+            self.lvr,
             data,
         )));
     }
@@ -69,6 +69,21 @@ fn run() -> eyre::Result<()> {
                 command.execute(brontes_db_path, ctx)
             })
         }
+        Commands::Explain(command) => {
+            runner::run_command_until_exit(None, Duration::from_secs(5), |ctx| {
+                command.execute(brontes_db_path, ctx)
+            })
+        }
+        Commands::Distributed(command) => {
+            runner::run_command_until_exit(None, Duration::from_secs(5), |ctx| {
+                command.execute(brontes_db_path, ctx)
+            })
+        }
+        Commands::Serve(command) => {
+            runner::run_command_until_exit(None, Duration::from_secs(5), |ctx| {
+                command.execute(brontes_db_path, ctx)
+            })
+        }
     }
 }
 
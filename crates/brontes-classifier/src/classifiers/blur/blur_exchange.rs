@@ -0,0 +1,122 @@
+use alloy_primitives::{Address, U256};
+use brontes_macros::action_impl;
+use brontes_pricing::Protocol;
+use brontes_types::{
+    constants::ETH_ADDRESS,
+    db::traits::LibmdbxReader,
+    normalized_actions::{NftStandard, NormalizedNftTrade},
+    structured_trace::CallInfo,
+    ToScaledRational,
+};
+
+use crate::BlurExchange::OrdersMatched;
+
+// The exchange is a proxy, the match is emitted by the delegated implementation
+action_impl!(
+    Protocol::BlurExchange,
+    crate::BlurExchange::executeCall,
+    NftTrade,
+    [..OrdersMatched],
+    logs: true,
+    include_delegated_logs: true,
+    |
+    info: CallInfo,
+    log_data: BlurExchangeExecuteCallLogs,
+    db_tx: &DB| {
+        parse_orders_matched(info, vec![log_data.orders_matched_field?], db_tx)
+    }
+);
+
+// Executions that fail are skipped, only the filled ones emit a match
+action_impl!(
+    Protocol::BlurExchange,
+    crate::BlurExchange::bulkExecuteCall,
+    NftTrade,
+    [..OrdersMatched*],
+    logs: true,
+    include_delegated_logs: true,
+    |
+    info: CallInfo,
+    log_data: BlurExchangeBulkExecuteCallLogs,
+    db_tx: &DB| {
+        parse_orders_matched(info, log_data.orders_matched_field?, db_tx)
+    }
+);
+
+/// Summarizes the matches into one trade of the collection and payment token
+/// of the first match
+fn parse_orders_matched<DB: LibmdbxReader>(
+    info: CallInfo,
+    matches: Vec<OrdersMatched>,
+    db_tx: &DB,
+) -> eyre::Result<NormalizedNftTrade> {
+    let first = matches
+        .first()
+        .ok_or_else(|| eyre::eyre!("blur execution without a match"))?;
+    let (collection, payment_token) = (first.sell.collection, first.sell.paymentToken);
+
+    let matches = matches
+        .iter()
+        .filter(|m| m.sell.collection == collection && m.sell.paymentToken == payment_token)
+        .collect::<Vec<_>>();
+
+    // the matching policy settles at the price of the maker order
+    let price = matches.iter().fold(U256::ZERO, |acc, m| {
+        acc + if m.maker == m.sell.trader { m.sell.price } else { m.buy.price }
+    });
+    let payment_token = if payment_token == Address::ZERO { ETH_ADDRESS } else { payment_token };
+    let payment_token = db_tx.try_fetch_token_info(payment_token)?;
+    let standard = if matches.iter().any(|m| m.sell.amount > U256::from(1)) {
+        NftStandard::Erc1155
+    } else {
+        NftStandard::Erc721
+    };
+
+    Ok(NormalizedNftTrade {
+        protocol: Protocol::BlurExchange,
+        trace_index: info.trace_idx,
+        marketplace: info.target_address,
+        buyer: first.buy.trader,
+        seller: first.sell.trader,
+        standard,
+        collection,
+        token_ids: matches.iter().map(|m| m.sell.tokenId).collect(),
+        amounts: matches.iter().map(|m| m.sell.amount).collect(),
+        price: price.to_scaled_rational(payment_token.decimals),
+        payment_token,
+        msg_value: info.msg_value,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use brontes_classifier::test_utils::ClassifierTestUtils;
+    use brontes_types::{normalized_actions::Action, TreeSearchBuilder};
+
+    use super::*;
+
+    #[brontes_macros::test]
+    async fn test_blur_trades() {
+        let classifier_utils = ClassifierTestUtils::new().await;
+
+        let mut trades = 0;
+        for block in 17_500_000..17_500_010 {
+            let tree = std::sync::Arc::new(classifier_utils.build_block_tree(block).await.unwrap());
+            let blur_trades = tree
+                .collect_all(TreeSearchBuilder::default().with_action(Action::is_nft_trade))
+                .flat_map(|(_, actions)| actions)
+                .filter_map(|action| action.try_nft_trade())
+                .filter(|trade| trade.protocol == Protocol::BlurExchange)
+                .collect::<Vec<_>>();
+
+            for trade in blur_trades {
+                trades += 1;
+                assert_ne!(trade.collection, Address::ZERO);
+                assert_ne!(trade.buyer, trade.seller);
+                assert_eq!(trade.token_ids.len(), trade.amounts.len());
+            }
+        }
+
+        assert!(trades > 0, "no blur trades found in the block range");
+    }
+}
@@ -0,0 +1,3 @@
+mod blur_exchange;
+
+pub use blur_exchange::*;
@@ -41,6 +41,18 @@ pub use clipper::*;
 pub mod dodo;
 pub use dodo::*;
 
+pub mod uniswap_forks;
+pub use uniswap_forks::*;
+
+pub mod seaport;
+pub use seaport::*;
+
+pub mod blur;
+pub use blur::*;
+
+pub mod sudoswap;
+pub use sudoswap::*;
+
 discovery_dispatch!(
     DiscoveryClassifier,
     SushiSwapV2Discovery,
@@ -63,7 +75,9 @@ discovery_dispatch!(
     CurveCryptoSwapDiscovery,
     CurveTriCryptoDiscovery,
     BalancerV1CoreDiscovery,
-    BalancerV1SmartPoolDiscovery
+    BalancerV1SmartPoolDiscovery,
+    SudoswapDiscovery,
+    SudoswapERC20Discovery
 );
 
 action_dispatch!(
@@ -89,6 +103,13 @@ action_dispatch!(
     PancakeSwapV3MintCall,
     PancakeSwapV3BurnCall,
     PancakeSwapV3CollectCall,
+    UniswapV2ForkSwapCall,
+    UniswapV2ForkMintCall,
+    UniswapV2ForkBurnCall,
+    UniswapV3ForkSwapCall,
+    UniswapV3ForkMintCall,
+    UniswapV3ForkBurnCall,
+    UniswapV3ForkCollectCall,
     UniswapXExecuteCall,
     UniswapXExecuteBatchCall,
     UniswapXExecuteBatchWithCallbackCall,
@@ -208,5 +229,17 @@ action_dispatch!(
     DodoSellSharesCall,
     DodoSellBaseCall,
     DodoSellQuoteCall,
-    DodoFlashLoanCall
+    DodoFlashLoanCall,
+    SeaportFulfillBasicOrderCall,
+    SeaportFulfillBasicOrder_efficient_6GL6ycCall,
+    SeaportFulfillOrderCall,
+    SeaportFulfillAdvancedOrderCall,
+    SeaportFulfillAvailableOrdersCall,
+    SeaportFulfillAvailableAdvancedOrdersCall,
+    SeaportMatchOrdersCall,
+    SeaportMatchAdvancedOrdersCall,
+    BlurExchangeExecuteCall,
+    BlurExchangeBulkExecuteCall,
+    SudoswapSwapTokenForSpecificNFTsCall,
+    SudoswapSwapNFTsForTokenCall
 );
@@ -0,0 +1,3 @@
+mod seaport;
+
+pub use seaport::*;
@@ -0,0 +1,314 @@
+use alloy_primitives::{Address, U256};
+use brontes_macros::action_impl;
+use brontes_pricing::Protocol;
+use brontes_types::{
+    constants::ETH_ADDRESS,
+    db::traits::LibmdbxReader,
+    normalized_actions::{NftStandard, NormalizedNftTrade},
+    structured_trace::CallInfo,
+    ToScaledRational,
+};
+
+use crate::Seaport::OrderFulfilled;
+
+action_impl!(
+    Protocol::Seaport,
+    crate::Seaport::fulfillBasicOrderCall,
+    NftTrade,
+    [..OrderFulfilled],
+    logs: true,
+    |
+    info: CallInfo,
+    log_data: SeaportFulfillBasicOrderCallLogs,
+    db_tx: &DB| {
+        parse_orders_fulfilled(info, vec![log_data.order_fulfilled_field?], db_tx)
+    }
+);
+
+action_impl!(
+    Protocol::Seaport,
+    crate::Seaport::fulfillBasicOrder_efficient_6GL6ycCall,
+    NftTrade,
+    [..OrderFulfilled],
+    logs: true,
+    |
+    info: CallInfo,
+    log_data: SeaportFulfillBasicOrder_efficient_6GL6ycCallLogs,
+    db_tx: &DB| {
+        parse_orders_fulfilled(info, vec![log_data.order_fulfilled_field?], db_tx)
+    }
+);
+
+action_impl!(
+    Protocol::Seaport,
+    crate::Seaport::fulfillOrderCall,
+    NftTrade,
+    [..OrderFulfilled],
+    logs: true,
+    |
+    info: CallInfo,
+    log_data: SeaportFulfillOrderCallLogs,
+    db_tx: &DB| {
+        parse_orders_fulfilled(info, vec![log_data.order_fulfilled_field?], db_tx)
+    }
+);
+
+action_impl!(
+    Protocol::Seaport,
+    crate::Seaport::fulfillAdvancedOrderCall,
+    NftTrade,
+    [..OrderFulfilled],
+    logs: true,
+    |
+    info: CallInfo,
+    log_data: SeaportFulfillAdvancedOrderCallLogs,
+    db_tx: &DB| {
+        parse_orders_fulfilled(info, vec![log_data.order_fulfilled_field?], db_tx)
+    }
+);
+
+// The multi order entry points emit an `OrderFulfilled` event per order that
+// was filled
+
+action_impl!(
+    Protocol::Seaport,
+    crate::Seaport::fulfillAvailableOrdersCall,
+    NftTrade,
+    [..OrderFulfilled*],
+    logs: true,
+    |
+    info: CallInfo,
+    log_data: SeaportFulfillAvailableOrdersCallLogs,
+    db_tx: &DB| {
+        parse_orders_fulfilled(info, log_data.order_fulfilled_field?, db_tx)
+    }
+);
+
+action_impl!(
+    Protocol::Seaport,
+    crate::Seaport::fulfillAvailableAdvancedOrdersCall,
+    NftTrade,
+    [..OrderFulfilled*],
+    logs: true,
+    |
+    info: CallInfo,
+    log_data: SeaportFulfillAvailableAdvancedOrdersCallLogs,
+    db_tx: &DB| {
+        parse_orders_fulfilled(info, log_data.order_fulfilled_field?, db_tx)
+    }
+);
+
+action_impl!(
+    Protocol::Seaport,
+    crate::Seaport::matchOrdersCall,
+    NftTrade,
+    [..OrderFulfilled*],
+    logs: true,
+    |
+    info: CallInfo,
+    log_data: SeaportMatchOrdersCallLogs,
+    db_tx: &DB| {
+        parse_orders_fulfilled(info, log_data.order_fulfilled_field?, db_tx)
+    }
+);
+
+action_impl!(
+    Protocol::Seaport,
+    crate::Seaport::matchAdvancedOrdersCall,
+    NftTrade,
+    [..OrderFulfilled*],
+    logs: true,
+    |
+    info: CallInfo,
+    log_data: SeaportMatchAdvancedOrdersCallLogs,
+    db_tx: &DB| {
+        parse_orders_fulfilled(info, log_data.order_fulfilled_field?, db_tx)
+    }
+);
+
+const ITEM_NATIVE: u8 = 0;
+const ITEM_ERC20: u8 = 1;
+const ITEM_ERC1155: u8 = 3;
+const ITEM_ERC1155_WITH_CRITERIA: u8 = 5;
+
+/// (item type, token, identifier, amount) of an offer or consideration item
+type Item = (u8, Address, U256, U256);
+
+/// One side of a filled order
+struct OrderLeg {
+    is_listing: bool,
+    buyer:      Address,
+    seller:     Address,
+    nft_type:   u8,
+    collection: Address,
+    nfts:       Vec<(U256, U256)>,
+    /// payment token as (item type, token)
+    payment:    (u8, Address),
+    price:      U256,
+}
+
+/// Builds the order leg from the `OrderFulfilled` event. If the offerer put up
+/// the nft, the order was a listing and the fulfiller is the buyer. Otherwise
+/// the offerer put up a bid that the fulfiller accepted by selling into it.
+///
+/// Only the first collection of an order is recorded, bundles spanning
+/// multiple collections are rare.
+fn parse_order_leg(fulfilled: &OrderFulfilled) -> Option<OrderLeg> {
+    let offer = fulfilled
+        .offer
+        .iter()
+        .map(|item| (item.itemType, item.token, item.identifier, item.amount))
+        .collect::<Vec<Item>>();
+    let consideration = fulfilled
+        .consideration
+        .iter()
+        .map(|item| (item.itemType, item.token, item.identifier, item.amount))
+        .collect::<Vec<Item>>();
+
+    let is_listing = offer.iter().any(|(ty, ..)| !is_payment(*ty));
+    let (nft_items, payment_items, buyer, seller) = if is_listing {
+        (offer, consideration, fulfilled.recipient, fulfilled.offerer)
+    } else {
+        (consideration, offer, fulfilled.offerer, fulfilled.recipient)
+    };
+
+    let (nft_type, collection, ..) = *nft_items.iter().find(|(ty, ..)| !is_payment(*ty))?;
+
+    let nfts = nft_items
+        .iter()
+        .filter(|(ty, token, ..)| !is_payment(*ty) && *token == collection)
+        .map(|(_, _, id, amount)| (*id, *amount))
+        .collect();
+
+    let payment = payment_items
+        .iter()
+        .find(|(ty, ..)| is_payment(*ty))
+        .map(|(ty, token, ..)| (*ty, *token))
+        .unwrap_or((ITEM_NATIVE, Address::ZERO));
+
+    let price = payment_items
+        .iter()
+        .filter(|(ty, token, ..)| is_payment(*ty) && (*ty, *token) == payment)
+        .fold(U256::ZERO, |acc, (.., amount)| acc + amount);
+
+    Some(OrderLeg { is_listing, buyer, seller, nft_type, collection, nfts, payment, price })
+}
+
+/// Summarizes the orders filled by a single call into one trade of the
+/// collection of the first order.
+///
+/// The nfts leave the seller of the listings and end up with the buyer of the
+/// bids. When a call matches listings against bids, the bids set the price
+/// and the matching ids are only counted once. Sweeping listings or accepting
+/// bids on their own is priced by the orders that were filled.
+fn parse_orders_fulfilled<DB: LibmdbxReader>(
+    info: CallInfo,
+    orders: Vec<OrderFulfilled>,
+    db_tx: &DB,
+) -> eyre::Result<NormalizedNftTrade> {
+    let legs = orders
+        .iter()
+        .filter_map(parse_order_leg)
+        .collect::<Vec<_>>();
+    let first = legs
+        .first()
+        .ok_or_else(|| eyre::eyre!("seaport order without a nft item"))?;
+
+    let (collection, payment) = (first.collection, first.payment);
+    let (listings, bids): (Vec<_>, Vec<_>) = legs
+        .iter()
+        .filter(|leg| leg.collection == collection && leg.payment == payment)
+        .partition(|leg| leg.is_listing);
+
+    let seller = listings.first().unwrap_or(first).seller;
+    let buyer = bids.first().unwrap_or(first).buyer;
+    let (priced, nft_legs) = match (listings.is_empty(), bids.is_empty()) {
+        (_, true) => (&listings, &listings),
+        (true, false) => (&bids, &bids),
+        (false, false) => (&bids, &listings),
+    };
+
+    let (token_ids, amounts) = nft_legs
+        .iter()
+        .flat_map(|leg| leg.nfts.iter().copied())
+        .unzip();
+
+    let payment_token = if payment.0 == ITEM_NATIVE { ETH_ADDRESS } else { payment.1 };
+    let payment_token = db_tx.try_fetch_token_info(payment_token)?;
+    let price = priced
+        .iter()
+        .fold(U256::ZERO, |acc, leg| acc + leg.price)
+        .to_scaled_rational(payment_token.decimals);
+
+    let standard = if first.nft_type == ITEM_ERC1155 || first.nft_type == ITEM_ERC1155_WITH_CRITERIA
+    {
+        NftStandard::Erc1155
+    } else {
+        NftStandard::Erc721
+    };
+
+    Ok(NormalizedNftTrade {
+        protocol: Protocol::Seaport,
+        trace_index: info.trace_idx,
+        marketplace: info.target_address,
+        buyer,
+        seller,
+        standard,
+        collection,
+        token_ids,
+        amounts,
+        payment_token,
+        price,
+        msg_value: info.msg_value,
+    })
+}
+
+fn is_payment(item_type: u8) -> bool {
+    item_type == ITEM_NATIVE || item_type == ITEM_ERC20
+}
+
+#[cfg(test)]
+mod tests {
+    use brontes_classifier::test_utils::ClassifierTestUtils;
+    use brontes_types::{normalized_actions::Action, TreeSearchBuilder};
+
+    use super::*;
+
+    #[brontes_macros::test]
+    async fn test_seaport_trades() {
+        let classifier_utils = ClassifierTestUtils::new().await;
+
+        let mut trades = 0;
+        for block in 17_500_000..17_500_010 {
+            let tree = std::sync::Arc::new(classifier_utils.build_block_tree(block).await.unwrap());
+            let nft_actions = tree
+                .clone()
+                .collect_all(
+                    TreeSearchBuilder::default()
+                        .with_actions([Action::is_nft_trade, Action::is_nft_transfer]),
+                )
+                .flat_map(|(_, actions)| actions)
+                .collect::<Vec<_>>();
+
+            for action in nft_actions {
+                match action {
+                    Action::NftTrade(trade) if trade.protocol == Protocol::Seaport => {
+                        trades += 1;
+                        assert_ne!(trade.collection, Address::ZERO);
+                        assert_ne!(trade.buyer, trade.seller);
+                        assert!(!trade.token_ids.is_empty());
+                        assert_eq!(trade.token_ids.len(), trade.amounts.len());
+                    }
+                    // nft transfers are never decoded from erc20 tokens
+                    Action::NftTransfer(transfer) => assert!(classifier_utils
+                        .libmdbx
+                        .try_fetch_token_info(transfer.collection)
+                        .is_err()),
+                    _ => {}
+                }
+            }
+        }
+
+        assert!(trades > 0, "no seaport trades found in the block range");
+    }
+}
@@ -0,0 +1,34 @@
+use alloy_primitives::Address;
+use brontes_macros::discovery_impl;
+use brontes_pricing::Protocol;
+use brontes_types::constants::ETH_ADDRESS;
+
+// Sudoswap (LSSVM) pair factory. The payment token of a pair is stored as its
+// second token, native ETH for ETH pairs.
+discovery_impl!(
+    SudoswapDiscovery,
+    crate::SudoswapPairFactory::createPairETHCall,
+    0xb16c1342E617A5B6E4b631EB114483FDB289c0A4,
+    |deployed_address: Address, trace_index: u64, call_data: createPairETHCall, _| async move {
+        vec![NormalizedNewPool {
+            trace_index,
+            protocol: Protocol::Sudoswap,
+            pool_address: deployed_address,
+            tokens: vec![call_data._nft, ETH_ADDRESS],
+        }]
+    }
+);
+
+discovery_impl!(
+    SudoswapERC20Discovery,
+    crate::SudoswapPairFactory::createPairERC20Call,
+    0xb16c1342E617A5B6E4b631EB114483FDB289c0A4,
+    |deployed_address: Address, trace_index: u64, call_data: createPairERC20Call, _| async move {
+        vec![NormalizedNewPool {
+            trace_index,
+            protocol: Protocol::Sudoswap,
+            pool_address: deployed_address,
+            tokens: vec![call_data.params.nft, call_data.params.token],
+        }]
+    }
+);
@@ -0,0 +1,7 @@
+mod sudoswap_pair;
+
+pub use sudoswap_pair::*;
+
+mod discovery;
+
+pub use discovery::*;
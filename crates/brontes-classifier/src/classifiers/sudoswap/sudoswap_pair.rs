@@ -0,0 +1,77 @@
+use alloy_primitives::U256;
+use brontes_macros::action_impl;
+use brontes_pricing::Protocol;
+use brontes_types::{
+    normalized_actions::{NftStandard, NormalizedNftTrade},
+    structured_trace::CallInfo,
+    ToScaledRational,
+};
+
+// Sudoswap pairs are the counterparty of every trade, the collection and the
+// payment token are stored as token0 and token1 of the pair on discovery.
+action_impl!(
+    Protocol::Sudoswap,
+    crate::SudoswapPair::swapTokenForSpecificNFTsCall,
+    NftTrade,
+    [],
+    call_data: true,
+    return_data: true,
+    |
+    info: CallInfo,
+    call_data: swapTokenForSpecificNFTsCall,
+    return_data: swapTokenForSpecificNFTsReturn,
+    db_tx: &DB| {
+        let details = db_tx.get_protocol_details(info.target_address)?;
+        let payment_token = db_tx.try_fetch_token_info(details.token1)?;
+        let amounts = vec![U256::from(1); call_data.nftIds.len()];
+
+        Ok(NormalizedNftTrade {
+            protocol: Protocol::Sudoswap,
+            trace_index: info.trace_idx,
+            marketplace: info.target_address,
+            buyer: call_data.nftRecipient,
+            seller: info.target_address,
+            standard: NftStandard::Erc721,
+            collection: details.token0,
+            token_ids: call_data.nftIds,
+            amounts,
+            price: return_data.inputAmount.to_scaled_rational(payment_token.decimals),
+            payment_token,
+            msg_value: info.msg_value,
+        })
+    }
+);
+
+action_impl!(
+    Protocol::Sudoswap,
+    crate::SudoswapPair::swapNFTsForTokenCall,
+    NftTrade,
+    [],
+    call_data: true,
+    return_data: true,
+    |
+    info: CallInfo,
+    call_data: swapNFTsForTokenCall,
+    return_data: swapNFTsForTokenReturn,
+    db_tx: &DB| {
+        let details = db_tx.get_protocol_details(info.target_address)?;
+        let payment_token = db_tx.try_fetch_token_info(details.token1)?;
+        let amounts = vec![U256::from(1); call_data.nftIds.len()];
+        let seller = if call_data.isRouter { call_data.routerCaller } else { info.from_address };
+
+        Ok(NormalizedNftTrade {
+            protocol: Protocol::Sudoswap,
+            trace_index: info.trace_idx,
+            marketplace: info.target_address,
+            buyer: info.target_address,
+            seller,
+            standard: NftStandard::Erc721,
+            collection: details.token0,
+            token_ids: call_data.nftIds,
+            amounts,
+            price: return_data.outputAmount.to_scaled_rational(payment_token.decimals),
+            payment_token,
+            msg_value: info.msg_value,
+        })
+    }
+);
@@ -0,0 +1,149 @@
+//! Discovery of Uniswap V2 / V3 forks deployed by factories we don't have a
+//! dedicated classifier for.
+//!
+//! A created contract is registered as a fork pool when one of its parent
+//! frames emitted a `PairCreated` / `PoolCreated` event for it and its runtime
+//! bytecode dispatches on the function selectors of the matching template.
+
+use std::sync::Arc;
+
+use alloy_primitives::{Address, Log};
+use alloy_sol_types::{SolCall, SolEvent};
+use brontes_types::{
+    normalized_actions::pool::NormalizedNewPool, traits::TracingProvider, Protocol,
+};
+use tracing::trace;
+
+use crate::{UniswapV2, UniswapV2Factory, UniswapV3, UniswapV3Factory};
+
+/// `PUSH4` opcode, used by solidity to load the selectors it dispatches on
+const PUSH4: u8 = 0x63;
+
+/// Selectors every Uniswap V2 pair template exposes
+const V2_PAIR_SELECTORS: [[u8; 4]; 6] = [
+    UniswapV2::getReservesCall::SELECTOR,
+    UniswapV2::swapCall::SELECTOR,
+    UniswapV2::skimCall::SELECTOR,
+    UniswapV2::syncCall::SELECTOR,
+    UniswapV2::token0Call::SELECTOR,
+    UniswapV2::token1Call::SELECTOR,
+];
+
+/// Selectors every Uniswap V3 pool template exposes
+const V3_POOL_SELECTORS: [[u8; 4]; 6] = [
+    UniswapV3::slot0Call::SELECTOR,
+    UniswapV3::tickSpacingCall::SELECTOR,
+    UniswapV3::swapCall::SELECTOR,
+    UniswapV3::mintCall::SELECTOR,
+    UniswapV3::token0Call::SELECTOR,
+    UniswapV3::token1Call::SELECTOR,
+];
+
+/// Tries to recognize `created_addr` as a Uniswap V2 / V3 fork pool using the
+/// factory events found in the logs of its parent frames.
+pub async fn discover_fork_pool<T: TracingProvider>(
+    provider: &Arc<T>,
+    block: u64,
+    created_addr: Address,
+    trace_index: u64,
+    parent_logs: &[Log],
+) -> Option<NormalizedNewPool> {
+    let (protocol, tokens) = decode_factory_event(parent_logs, created_addr)?;
+
+    let bytecode = provider
+        .get_bytecode(Some(block), created_addr)
+        .await
+        .ok()
+        .flatten()?;
+
+    if fingerprint_bytecode(bytecode.bytecode.as_ref()) != Some(protocol) {
+        trace!(
+            target: "brontes_classifier::discovery",
+            ?created_addr,
+            %protocol,
+            "factory event found but bytecode doesn't match the pool template"
+        );
+        return None
+    }
+
+    Some(NormalizedNewPool { pool_address: created_addr, trace_index, protocol, tokens })
+}
+
+/// Finds the `PairCreated` / `PoolCreated` event for the created pool,
+/// returning the fork protocol and the sorted pool tokens.
+fn decode_factory_event(logs: &[Log], created_addr: Address) -> Option<(Protocol, Vec<Address>)> {
+    logs.iter().find_map(|log| {
+        if let Ok(pair) = UniswapV2Factory::PairCreated::decode_log_data(&log.data, false) {
+            if pair.pair == created_addr {
+                return Some((Protocol::UniswapV2Fork, sorted_tokens(pair.token0, pair.token1)))
+            }
+        }
+        if let Ok(pool) = UniswapV3Factory::PoolCreated::decode_log_data(&log.data, false) {
+            if pool.pool == created_addr {
+                return Some((Protocol::UniswapV3Fork, sorted_tokens(pool.token0, pool.token1)))
+            }
+        }
+
+        None
+    })
+}
+
+fn sorted_tokens(token_a: Address, token_b: Address) -> Vec<Address> {
+    if token_a > token_b {
+        vec![token_b, token_a]
+    } else {
+        vec![token_a, token_b]
+    }
+}
+
+/// Matches the runtime bytecode against the known pool templates
+pub fn fingerprint_bytecode(code: &[u8]) -> Option<Protocol> {
+    if contains_selectors(code, &V2_PAIR_SELECTORS) {
+        Some(Protocol::UniswapV2Fork)
+    } else if contains_selectors(code, &V3_POOL_SELECTORS) {
+        Some(Protocol::UniswapV3Fork)
+    } else {
+        None
+    }
+}
+
+fn contains_selectors(code: &[u8], selectors: &[[u8; 4]]) -> bool {
+    selectors.iter().all(|selector| {
+        code.windows(5)
+            .any(|window| window[0] == PUSH4 && window[1..] == selector[..])
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dispatcher(selectors: &[[u8; 4]]) -> Vec<u8> {
+        selectors
+            .iter()
+            .flat_map(|selector| {
+                // PUSH4 <selector> DUP2 EQ PUSH2 <dest> JUMPI
+                let mut op = vec![PUSH4];
+                op.extend_from_slice(selector);
+                op.extend_from_slice(&[0x81, 0x14, 0x61, 0x01, 0x00, 0x57]);
+                op
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_fingerprint_bytecode() {
+        assert_eq!(
+            fingerprint_bytecode(&dispatcher(&V2_PAIR_SELECTORS)),
+            Some(Protocol::UniswapV2Fork)
+        );
+        assert_eq!(
+            fingerprint_bytecode(&dispatcher(&V3_POOL_SELECTORS)),
+            Some(Protocol::UniswapV3Fork)
+        );
+        // missing getReserves
+        assert_eq!(fingerprint_bytecode(&dispatcher(&V2_PAIR_SELECTORS[1..])), None);
+        // selector bytes that aren't pushed aren't dispatched on
+        assert_eq!(fingerprint_bytecode(&V2_PAIR_SELECTORS.concat()), None);
+    }
+}
@@ -0,0 +1,9 @@
+mod discovery;
+#[allow(non_snake_case)]
+mod uniswap_v2_fork;
+#[allow(non_snake_case)]
+mod uniswap_v3_fork;
+
+pub use discovery::*;
+pub use uniswap_v2_fork::*;
+pub use uniswap_v3_fork::*;
@@ -0,0 +1,205 @@
+use alloy_primitives::U256;
+use brontes_macros::action_impl;
+use brontes_pricing::Protocol;
+use brontes_types::{
+    normalized_actions::{NormalizedBurn, NormalizedMint, NormalizedSwap},
+    structured_trace::CallInfo,
+    ToScaledRational,
+};
+
+action_impl!(
+    Protocol::UniswapV2Fork,
+    crate::UniswapV2::swapCall,
+    Swap,
+    [..Swap],
+    call_data: true,
+    logs: true,
+    |
+    info: CallInfo,
+    call_data: swapCall,
+    logs: UniswapV2ForkSwapCallLogs,
+    db_tx: &DB| {
+        let logs = logs.swap_field?;
+
+        let recipient = call_data.to;
+        let details = db_tx.get_protocol_details_sorted(info.target_address)?;
+        let [token_0, token_1] = [details.token0, details.token1];
+
+        let t0_info = db_tx.try_fetch_token_info(token_0)?;
+        let t1_info = db_tx.try_fetch_token_info(token_1)?;
+
+        if logs.amount0In == U256::ZERO {
+            let amount_in = logs.amount1In.to_scaled_rational(t1_info.decimals);
+            let amount_out = logs.amount0Out.to_scaled_rational(t0_info.decimals);
+
+            return Ok(NormalizedSwap {
+                protocol: Protocol::UniswapV2Fork,
+                pool: info.target_address,
+                trace_index: info.trace_idx,
+                from: info.from_address,
+                recipient,
+                token_in: t1_info,
+                token_out: t0_info,
+                amount_in,
+                amount_out,
+                msg_value: info.msg_value
+            })
+        } else {
+            let amount_in = logs.amount0In.to_scaled_rational(t0_info.decimals);
+            let amount_out = logs.amount1Out.to_scaled_rational(t1_info.decimals);
+            return Ok(NormalizedSwap {
+                protocol: Protocol::UniswapV2Fork,
+                pool: info.target_address,
+                trace_index: info.trace_idx,
+                from: info.from_address,
+                recipient,
+                token_in: t0_info,
+                token_out: t1_info,
+                amount_in,
+                amount_out,
+                msg_value: info.msg_value
+            })
+        }
+    }
+);
+
+action_impl!(
+    Protocol::UniswapV2Fork,
+    crate::UniswapV2::mintCall,
+    Mint,
+    // can be a double transfer if the pool has no liquidity
+    [..Mint],
+    logs: true,
+    call_data: true,
+    |
+        info: CallInfo,
+     call_data: mintCall,
+     log_data: UniswapV2ForkMintCallLogs,
+     db_tx: &DB| {
+        let log_data = log_data.mint_field?;
+
+        let details = db_tx.get_protocol_details_sorted(info.target_address)?;
+        let [token_0, token_1] = [details.token0, details.token1];
+
+        let t0_info = db_tx.try_fetch_token_info(token_0)?;
+        let t1_info = db_tx.try_fetch_token_info(token_1)?;
+
+        let am0 = log_data.amount0.to_scaled_rational(t0_info.decimals);
+        let am1 = log_data.amount1.to_scaled_rational(t1_info.decimals);
+
+        Ok(NormalizedMint {
+            protocol: Protocol::UniswapV2Fork,
+            recipient: call_data.to,
+            from: info.from_address,
+            trace_index: info.trace_idx,
+            pool: info.target_address,
+            token: vec![t0_info, t1_info],
+            amount: vec![am0, am1],
+        })
+    }
+);
+
+action_impl!(
+    Protocol::UniswapV2Fork,
+    crate::UniswapV2::burnCall,
+    Burn,
+    [..Burn],
+    call_data: true,
+    logs: true,
+    |
+     info: CallInfo,
+     call_data: burnCall,
+     log_data: UniswapV2ForkBurnCallLogs,
+     db_tx: &DB| {
+        let log_data = log_data.burn_field?;
+        let details = db_tx.get_protocol_details_sorted(info.target_address)?;
+        let [token_0, token_1] = [details.token0, details.token1];
+
+        let t0_info = db_tx.try_fetch_token_info(token_0)?;
+        let t1_info = db_tx.try_fetch_token_info(token_1)?;
+
+        let am0 = log_data.amount0.to_scaled_rational(t0_info.decimals);
+        let am1 = log_data.amount1.to_scaled_rational(t1_info.decimals);
+
+        Ok(NormalizedBurn {
+            protocol: Protocol::UniswapV2Fork,
+            trace_index: info.trace_idx,
+            from: info.from_address,
+            recipient: call_data.to,
+            pool: info.target_address,
+            token: vec![t0_info, t1_info],
+            amount: vec![am0, am1],
+        })
+    }
+);
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use alloy_primitives::{hex, Address};
+    use brontes_classifier::test_utils::ClassifierTestUtils;
+    use brontes_types::{
+        constants::WETH_ADDRESS, normalized_actions::Action, queries::make_call_request,
+        TreeSearchBuilder,
+    };
+    use malachite::{num::basic::traits::Zero, Rational};
+
+    use super::*;
+    use crate::UniswapV2Factory;
+
+    const SHIBASWAP_FACTORY: Address =
+        Address::new(hex!("115934131916C8b277Dd010Ee02de363c09d037c"));
+    const SHIB: Address = Address::new(hex!("95aD61b0a150d79219dCF64E1E6Cc01f0B64C4cE"));
+
+    #[brontes_macros::test]
+    async fn test_shibaswap_fork_swaps() {
+        let classifier_utils = ClassifierTestUtils::new().await;
+        let start_block = 17_500_000;
+
+        let pair = make_call_request(
+            UniswapV2Factory::getPairCall { _0: SHIB, _1: WETH_ADDRESS },
+            &classifier_utils.get_tracing_provider(),
+            SHIBASWAP_FACTORY,
+            Some(start_block),
+        )
+        .await
+        .unwrap()
+        ._0;
+        assert_ne!(pair, Address::ZERO, "shibaswap SHIB/WETH pair doesn't exist");
+
+        classifier_utils.ensure_protocol(
+            Protocol::UniswapV2Fork,
+            pair,
+            SHIB,
+            Some(WETH_ADDRESS),
+            None,
+            None,
+            None,
+            None,
+        );
+
+        let mut swaps = 0;
+        for block in start_block..start_block + 100 {
+            let tree = Arc::new(classifier_utils.build_block_tree(block).await.unwrap());
+            let fork_swaps = tree
+                .collect_all(TreeSearchBuilder::default().with_action(Action::is_swap))
+                .flat_map(|(_, actions)| actions)
+                .filter_map(|action| action.try_swaps_merged())
+                .filter(|swap| swap.pool == pair)
+                .collect::<Vec<_>>();
+
+            for swap in fork_swaps {
+                swaps += 1;
+                assert_eq!(swap.protocol, Protocol::UniswapV2Fork);
+                assert_ne!(swap.token_in.address, swap.token_out.address);
+                assert!([SHIB, WETH_ADDRESS].contains(&swap.token_in.address));
+                assert!([SHIB, WETH_ADDRESS].contains(&swap.token_out.address));
+                assert!(swap.amount_in > Rational::ZERO);
+                assert!(swap.amount_out > Rational::ZERO);
+            }
+        }
+
+        assert!(swaps > 0, "no shibaswap swaps found in the block range");
+    }
+}
@@ -0,0 +1,583 @@
+//! Classifiers declared in `config/dyn_classifier_config.toml`.
+//!
+//! These allow simple forks and custom venues to be classified without writing
+//! an `action_impl!`. Each entry points at a contract (or the factory that
+//! deployed it), the abi to decode with, the function or event that carries
+//! the action and a mapping of the decoded fields onto the normalized action.
+//! Decoding happens at runtime through the `dyn-decode` machinery, so these
+//! are only tried once none of the compiled classifiers matched.
+
+use std::{
+    path::{Path, PathBuf},
+    sync::{Arc, OnceLock},
+};
+
+use alloy_json_abi::JsonAbi;
+use alloy_primitives::{Address, B256, U256};
+use alloy_sol_types::sol;
+use brontes_core::decoding::dyn_decode::{decode_event_with_abi, decode_input_with_abi};
+use brontes_database::libmdbx::{DBWriter, LibmdbxReader};
+use brontes_pricing::types::{DexPriceMsg, PoolUpdate};
+use brontes_types::{
+    db::token_info::TokenInfoWithAddress,
+    make_call_request,
+    normalized_actions::{Action, NormalizedBurn, NormalizedMint, NormalizedSwap},
+    structured_trace::{CallFrameInfo, DecodedParams, TransactionTraceWithLogs},
+    traits::TracingProvider,
+    FastHashMap, Protocol, ToScaledRational,
+};
+use malachite::Rational;
+use parking_lot::RwLock;
+use serde::Deserialize;
+use tracing::error;
+
+const DYN_CLASSIFIER_CONFIG_FILE: &str = "config/dyn_classifier_config.toml";
+
+/// Env var pointing at the dyn classifier config file, overrides the config
+/// file of the current directory
+pub const DYN_CLASSIFIER_CONFIG_ENV: &str = "BRONTES_DYN_CLASSIFIER_CONFIG";
+
+static CONFIG_CLASSIFIERS: OnceLock<ConfigClassifiers> = OnceLock::new();
+
+sol! {
+    function factory() external view returns (address);
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ConfigAction {
+    Swap,
+    Mint,
+    Burn,
+}
+
+/// Where a field of the normalized action is sourced from. Values are:
+/// - `call.<name>`: decoded function input
+/// - `return.<name>`: decoded function output
+/// - `event.<name>`: decoded event param
+/// - `pool.token<n>`: the n-th token of the pool as stored in libmdbx. This
+///   only resolves for pools that are already in the db, e.g. found by the
+///   discovery of a compiled factory classifier. Pools that are only known
+///   through this config have to read their tokens from the call or event
+/// - `msg.sender` / `msg.value` / `target`
+/// - a literal address
+///
+/// Any other value fails to load. Amounts are read as absolute values and
+/// scaled by the token decimals.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct FieldMapping {
+    pub from:       Option<String>,
+    pub recipient:  Option<String>,
+    pub token_in:   Option<String>,
+    pub token_out:  Option<String>,
+    pub amount_in:  Option<String>,
+    pub amount_out: Option<String>,
+    #[serde(default)]
+    pub tokens:     Vec<String>,
+    #[serde(default)]
+    pub amounts:    Vec<String>,
+}
+
+impl FieldMapping {
+    /// Checks that every field is read from a known source
+    fn validate(&self) -> eyre::Result<()> {
+        for source in [&self.from, &self.recipient, &self.token_in, &self.token_out]
+            .into_iter()
+            .flatten()
+            .chain(&self.tokens)
+        {
+            FieldSource::parse(source)?;
+        }
+
+        for source in [&self.amount_in, &self.amount_out]
+            .into_iter()
+            .flatten()
+            .chain(&self.amounts)
+        {
+            if !FieldSource::parse(source)?.is_amount() {
+                eyre::bail!("{source} can't be read as an amount")
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// A parsed [`FieldMapping`] value
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FieldSource<'a> {
+    Call(&'a str),
+    Return(&'a str),
+    Event(&'a str),
+    PoolToken(usize),
+    MsgSender,
+    MsgValue,
+    Target,
+    Literal(Address),
+}
+
+impl<'a> FieldSource<'a> {
+    fn parse(source: &'a str) -> eyre::Result<Self> {
+        Ok(match source.split_once('.') {
+            Some(("call", name)) => Self::Call(name),
+            Some(("return", name)) => Self::Return(name),
+            Some(("event", name)) => Self::Event(name),
+            Some(("pool", token)) => Self::PoolToken(
+                token
+                    .strip_prefix("token")
+                    .and_then(|idx| idx.parse().ok())
+                    .ok_or_else(|| {
+                        eyre::eyre!("unknown pool field {source}, expected pool.token<n>")
+                    })?,
+            ),
+            Some(("msg", "sender")) => Self::MsgSender,
+            Some(("msg", "value")) => Self::MsgValue,
+            _ if source == "target" => Self::Target,
+            _ => Self::Literal(
+                source
+                    .parse()
+                    .map_err(|_| eyre::eyre!("unknown field source {source}"))?,
+            ),
+        })
+    }
+
+    fn is_amount(&self) -> bool {
+        matches!(self, Self::Call(_) | Self::Return(_) | Self::Event(_) | Self::MsgValue)
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ConfigClassifierToml {
+    pub protocol: Protocol,
+    pub action:   ConfigAction,
+    pub address:  Option<Address>,
+    pub factory:  Option<Address>,
+    /// path to the json abi, relative to the current directory
+    pub abi:      String,
+    pub function: Option<String>,
+    pub event:    Option<String>,
+    #[serde(default)]
+    pub fields:   FieldMapping,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct DynClassifierConfig {
+    #[serde(default)]
+    classifier: Vec<ConfigClassifierToml>,
+}
+
+#[derive(Debug)]
+pub struct ConfigClassifier {
+    pub config: ConfigClassifierToml,
+    abi:        JsonAbi,
+    selector:   Option<[u8; 4]>,
+    topic:      Option<B256>,
+}
+
+#[derive(Debug, Default)]
+pub struct ConfigClassifiers {
+    classifiers:   Vec<ConfigClassifier>,
+    /// pool -> factory, filled through `factory()` calls
+    factory_cache: RwLock<FastHashMap<Address, Option<Address>>>,
+}
+
+impl ConfigClassifiers {
+    /// Loads the classifiers from the given file, resolving the abi paths from
+    /// the current directory. Has to be called before the first
+    /// [`ConfigClassifiers::get`] to take effect.
+    pub fn init(config_path: &Path) -> eyre::Result<()> {
+        CONFIG_CLASSIFIERS
+            .set(Self::load(config_path, Path::new("."))?)
+            .map_err(|_| eyre::eyre!("dyn classifier config is already loaded"))
+    }
+
+    /// The classifiers set through [`ConfigClassifiers::init`], otherwise
+    /// loaded from `$BRONTES_DYN_CLASSIFIER_CONFIG` or
+    /// `config/dyn_classifier_config.toml` of the current directory. Failing to
+    /// load the file logs an error and disables config classifiers.
+    pub fn get() -> &'static Self {
+        CONFIG_CLASSIFIERS.get_or_init(|| {
+            let config_path = std::env::var_os(DYN_CLASSIFIER_CONFIG_ENV)
+                .map(PathBuf::from)
+                .unwrap_or_else(|| PathBuf::from(DYN_CLASSIFIER_CONFIG_FILE));
+
+            Self::load(&config_path, Path::new(".")).unwrap_or_else(|e| {
+                error!(
+                    target: "brontes_classifier::config", error=%e, path=?config_path,
+                    "failed to load config"
+                );
+                Self::default()
+            })
+        })
+    }
+
+    pub fn load(config_path: &Path, abi_root: &Path) -> eyre::Result<Self> {
+        let config: DynClassifierConfig = toml::from_str(&std::fs::read_to_string(config_path)?)?;
+
+        let classifiers = config
+            .classifier
+            .into_iter()
+            .map(|config| {
+                if config.address.is_none() && config.factory.is_none() {
+                    eyre::bail!("{} classifier needs either an address or factory", config.abi)
+                }
+                config
+                    .fields
+                    .validate()
+                    .map_err(|e| eyre::eyre!("{} classifier: {e}", config.abi))?;
+
+                let abi: JsonAbi =
+                    serde_json::from_str(&std::fs::read_to_string(abi_root.join(&config.abi))?)?;
+
+                if config.function.is_none() && config.event.is_none() {
+                    eyre::bail!("{} classifier needs a function or event", config.abi)
+                }
+
+                let selector = config
+                    .function
+                    .as_ref()
+                    .map(|function| {
+                        abi.function(function)
+                            .and_then(|f| f.first())
+                            .map(|f| f.selector().0)
+                            .ok_or_else(|| eyre::eyre!("{function} not found in {}", config.abi))
+                    })
+                    .transpose()?;
+
+                let topic = config
+                    .event
+                    .as_ref()
+                    .map(|event| {
+                        abi.event(event)
+                            .and_then(|e| e.first())
+                            .map(|e| e.selector())
+                            .ok_or_else(|| eyre::eyre!("{event} not found in {}", config.abi))
+                    })
+                    .transpose()?;
+
+                Ok(ConfigClassifier { config, abi, selector, topic })
+            })
+            .collect::<eyre::Result<Vec<_>>>()?;
+
+        Ok(Self { classifiers, factory_cache: RwLock::default() })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.classifiers.is_empty()
+    }
+
+    pub async fn dispatch<DB: LibmdbxReader + DBWriter, T: TracingProvider>(
+        &self,
+        call_info: &CallFrameInfo<'_>,
+        trace: &TransactionTraceWithLogs,
+        db: &DB,
+        provider: &Arc<T>,
+        block: u64,
+        tx_idx: u64,
+    ) -> Option<(DexPriceMsg, Action)> {
+        for classifier in &self.classifiers {
+            if !classifier.frame_matches(call_info) {
+                continue
+            }
+
+            if !self
+                .target_matches(&classifier.config, call_info.target_address, provider, block)
+                .await
+            {
+                continue
+            }
+
+            match classifier.classify(call_info, trace, db) {
+                Some(action) => {
+                    return Some((
+                        DexPriceMsg::Update(PoolUpdate {
+                            block,
+                            tx_idx,
+                            logs: call_info.logs.to_vec(),
+                            action: action.clone(),
+                        }),
+                        action,
+                    ))
+                }
+                None => continue,
+            }
+        }
+
+        None
+    }
+
+    async fn target_matches<T: TracingProvider>(
+        &self,
+        config: &ConfigClassifierToml,
+        target: Address,
+        provider: &Arc<T>,
+        block: u64,
+    ) -> bool {
+        if config.address == Some(target) {
+            return true
+        }
+        let Some(factory) = config.factory else { return false };
+
+        if let Some(cached) = self.factory_cache.read().get(&target) {
+            return *cached == Some(factory)
+        }
+
+        let pool_factory = make_call_request(factoryCall {}, provider, target, Some(block))
+            .await
+            .ok()
+            .map(|res| res._0);
+        self.factory_cache.write().insert(target, pool_factory);
+
+        pool_factory == Some(factory)
+    }
+}
+
+impl ConfigClassifier {
+    /// Cheap checks on the selector and emitted logs, done before we look up
+    /// the factory of the target.
+    fn frame_matches(&self, call_info: &CallFrameInfo<'_>) -> bool {
+        let selector_matches = self
+            .selector
+            .map_or(true, |selector| call_info.call_data.get(0..4) == Some(&selector[..]));
+
+        let topic_matches = self.topic.map_or(true, |topic| {
+            call_info
+                .logs
+                .iter()
+                .chain(call_info.delegate_logs.iter().copied())
+                .any(|log| log.topics().first() == Some(&topic))
+        });
+
+        selector_matches && topic_matches
+    }
+
+    fn classify<DB: LibmdbxReader + DBWriter>(
+        &self,
+        call_info: &CallFrameInfo<'_>,
+        trace: &TransactionTraceWithLogs,
+        db: &DB,
+    ) -> Option<Action> {
+        let decoded = self.decode(call_info, trace)?;
+        let fields = &self.config.fields;
+
+        let from = decoded
+            .address(fields.from.as_deref(), db)
+            .unwrap_or(call_info.msg_sender);
+        let recipient = decoded
+            .address(fields.recipient.as_deref(), db)
+            .unwrap_or(call_info.msg_sender);
+
+        match self.config.action {
+            ConfigAction::Swap => {
+                let token_in = decoded.token(fields.token_in.as_deref()?, db)?;
+                let token_out = decoded.token(fields.token_out.as_deref()?, db)?;
+                let amount_in = decoded.amount(fields.amount_in.as_deref()?, &token_in)?;
+                let amount_out = decoded.amount(fields.amount_out.as_deref()?, &token_out)?;
+
+                Some(Action::Swap(NormalizedSwap {
+                    protocol: self.config.protocol,
+                    trace_index: call_info.trace_idx,
+                    from,
+                    recipient,
+                    pool: call_info.target_address,
+                    token_in,
+                    token_out,
+                    amount_in,
+                    amount_out,
+                    msg_value: call_info.msg_value,
+                }))
+            }
+            ConfigAction::Mint | ConfigAction::Burn => {
+                let token = fields
+                    .tokens
+                    .iter()
+                    .map(|source| decoded.token(source, db))
+                    .collect::<Option<Vec<_>>>()?;
+                let amount = fields
+                    .amounts
+                    .iter()
+                    .zip(&token)
+                    .map(|(source, token)| decoded.amount(source, token))
+                    .collect::<Option<Vec<_>>>()?;
+
+                if self.config.action == ConfigAction::Mint {
+                    Some(Action::Mint(NormalizedMint {
+                        protocol: self.config.protocol,
+                        trace_index: call_info.trace_idx,
+                        from,
+                        recipient,
+                        pool: call_info.target_address,
+                        token,
+                        amount,
+                    }))
+                } else {
+                    Some(Action::Burn(NormalizedBurn {
+                        protocol: self.config.protocol,
+                        trace_index: call_info.trace_idx,
+                        from,
+                        recipient,
+                        pool: call_info.target_address,
+                        token,
+                        amount,
+                    }))
+                }
+            }
+        }
+    }
+
+    fn decode<'a>(
+        &self,
+        call_info: &'a CallFrameInfo<'a>,
+        trace: &TransactionTraceWithLogs,
+    ) -> Option<DecodedFields<'a>> {
+        let mut fields = DecodedFields { call_info, call: vec![], ret: vec![], event: vec![] };
+
+        if self.config.function.is_some() {
+            let decoded = decode_input_with_abi(&self.abi, &trace.trace).ok()??;
+            fields.call = decoded.call_data;
+            fields.ret = decoded.return_data;
+        }
+
+        if let Some(event) = &self.config.event {
+            fields.event = call_info
+                .logs
+                .iter()
+                .chain(call_info.delegate_logs.iter().copied())
+                .filter(|log| log.address == call_info.target_address)
+                .find_map(|log| decode_event_with_abi(&self.abi, event, log).ok().flatten())?;
+        }
+
+        Some(fields)
+    }
+}
+
+struct DecodedFields<'a> {
+    call_info: &'a CallFrameInfo<'a>,
+    call:      Vec<DecodedParams>,
+    ret:       Vec<DecodedParams>,
+    event:     Vec<DecodedParams>,
+}
+
+impl DecodedFields<'_> {
+    /// Sources are validated when the config is loaded, so the parse only
+    /// fails for values that can't be read here
+    fn raw<DB: LibmdbxReader>(&self, source: &str, db: &DB) -> Option<String> {
+        match FieldSource::parse(source).ok()? {
+            FieldSource::Call(name) => self.find(&self.call, name),
+            FieldSource::Return(name) => self.find(&self.ret, name),
+            FieldSource::Event(name) => self.find(&self.event, name),
+            FieldSource::PoolToken(idx) => {
+                let details = db
+                    .get_protocol_details(self.call_info.target_address)
+                    .ok()?;
+                details
+                    .get_tokens()
+                    .get(idx)
+                    .map(|token| format!("{:?}", token))
+            }
+            FieldSource::MsgSender => Some(format!("{:?}", self.call_info.msg_sender)),
+            FieldSource::MsgValue => Some(self.call_info.msg_value.to_string()),
+            FieldSource::Target => Some(format!("{:?}", self.call_info.target_address)),
+            FieldSource::Literal(address) => Some(format!("{:?}", address)),
+        }
+    }
+
+    fn address<DB: LibmdbxReader>(&self, source: Option<&str>, db: &DB) -> Option<Address> {
+        self.raw(source?, db)?.parse().ok()
+    }
+
+    fn token<DB: LibmdbxReader>(&self, source: &str, db: &DB) -> Option<TokenInfoWithAddress> {
+        let address = self.address(Some(source), db)?;
+        db.try_fetch_token_info(address).ok()
+    }
+
+    fn amount(&self, source: &str, token: &TokenInfoWithAddress) -> Option<Rational> {
+        let raw = match FieldSource::parse(source).ok()? {
+            FieldSource::Call(name) => self.find(&self.call, name)?,
+            FieldSource::Return(name) => self.find(&self.ret, name)?,
+            FieldSource::Event(name) => self.find(&self.event, name)?,
+            FieldSource::MsgValue => self.call_info.msg_value.to_string(),
+            _ => return None,
+        };
+
+        let amount: U256 = raw.trim_start_matches('-').parse().ok()?;
+        Some(amount.to_scaled_rational(token.decimals))
+    }
+
+    fn find(&self, params: &[DecodedParams], name: &str) -> Option<String> {
+        params
+            .iter()
+            .find(|param| param.field_name == name)
+            .map(|param| param.value.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use super::*;
+
+    #[test]
+    fn test_load_config_classifiers() {
+        let config = r#"
+            [[classifier]]
+            protocol = "UniswapV2"
+            action = "burn"
+            factory = "0x5C69bEe701ef814a2B6a3EDD4B1652CB9cc5aA6f"
+            abi = "crates/brontes-classifier/classifier-abis/UniswapV2.json"
+            function = "burn"
+            event = "Burn"
+
+            [classifier.fields]
+            recipient = "event.to"
+            tokens = ["pool.token0", "pool.token1"]
+            amounts = ["event.amount0", "event.amount1"]
+        "#;
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(config.as_bytes()).unwrap();
+
+        let workspace_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("../..");
+        let classifiers = ConfigClassifiers::load(file.path(), &workspace_dir).unwrap();
+        assert_eq!(classifiers.classifiers.len(), 1);
+
+        let classifier = &classifiers.classifiers[0];
+        assert_eq!(classifier.config.protocol, Protocol::UniswapV2);
+        assert_eq!(classifier.config.action, ConfigAction::Burn);
+        // burn(address)
+        assert_eq!(classifier.selector, Some([0x89, 0xaf, 0xcb, 0x44]));
+        assert!(classifier.topic.is_some());
+    }
+
+    #[test]
+    fn test_reject_unknown_field_source() {
+        let config = r#"
+            [[classifier]]
+            protocol = "UniswapV2"
+            action = "burn"
+            factory = "0x5C69bEe701ef814a2B6a3EDD4B1652CB9cc5aA6f"
+            abi = "crates/brontes-classifier/classifier-abis/UniswapV2.json"
+            event = "Burn"
+
+            [classifier.fields]
+            recipient = "evnt.to"
+            tokens = ["pool.token0", "pool.token1"]
+            amounts = ["event.amount0", "event.amount1"]
+        "#;
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(config.as_bytes()).unwrap();
+
+        let workspace_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("../..");
+        assert!(ConfigClassifiers::load(file.path(), &workspace_dir).is_err());
+
+        assert_eq!(FieldSource::parse("pool.token1").unwrap(), FieldSource::PoolToken(1));
+        assert_eq!(
+            FieldSource::parse("0x5C69bEe701ef814a2B6a3EDD4B1652CB9cc5aA6f").unwrap(),
+            FieldSource::Literal(Address::new(alloy_primitives::hex!(
+                "5C69bEe701ef814a2B6a3EDD4B1652CB9cc5aA6f"
+            )))
+        );
+        assert!(FieldSource::parse("pool.reserve0").is_err());
+        assert!(!FieldSource::parse("pool.token0").unwrap().is_amount());
+    }
+}
@@ -18,6 +18,8 @@ use futures::Future;
 
 pub mod tree_builder;
 pub use tree_builder::Classifier;
+#[cfg(feature = "dyn-decode")]
+pub mod config_classifier;
 pub mod discovery_only;
 pub mod multi_frame_classification;
 
@@ -122,6 +124,272 @@ sol! {
     }
 }
 
+// Nft Marketplace Interfaces. Enum fields are declared as `uint8` which is how
+// they are abi encoded.
+sol! {
+    interface Seaport {
+        struct AdditionalRecipient {
+            uint256 amount;
+            address recipient;
+        }
+
+        struct BasicOrderParameters {
+            address considerationToken;
+            uint256 considerationIdentifier;
+            uint256 considerationAmount;
+            address offerer;
+            address zone;
+            address offerToken;
+            uint256 offerIdentifier;
+            uint256 offerAmount;
+            uint8 basicOrderType;
+            uint256 startTime;
+            uint256 endTime;
+            bytes32 zoneHash;
+            uint256 salt;
+            bytes32 offererConduitKey;
+            bytes32 fulfillerConduitKey;
+            uint256 totalOriginalAdditionalRecipients;
+            AdditionalRecipient[] additionalRecipients;
+            bytes signature;
+        }
+
+        struct OfferItem {
+            uint8 itemType;
+            address token;
+            uint256 identifierOrCriteria;
+            uint256 startAmount;
+            uint256 endAmount;
+        }
+
+        struct ConsiderationItem {
+            uint8 itemType;
+            address token;
+            uint256 identifierOrCriteria;
+            uint256 startAmount;
+            uint256 endAmount;
+            address recipient;
+        }
+
+        struct OrderParameters {
+            address offerer;
+            address zone;
+            OfferItem[] offer;
+            ConsiderationItem[] consideration;
+            uint8 orderType;
+            uint256 startTime;
+            uint256 endTime;
+            bytes32 zoneHash;
+            uint256 salt;
+            bytes32 conduitKey;
+            uint256 totalOriginalConsiderationItems;
+        }
+
+        struct Order {
+            OrderParameters parameters;
+            bytes signature;
+        }
+
+        struct AdvancedOrder {
+            OrderParameters parameters;
+            uint120 numerator;
+            uint120 denominator;
+            bytes signature;
+            bytes extraData;
+        }
+
+        struct CriteriaResolver {
+            uint256 orderIndex;
+            uint8 side;
+            uint256 index;
+            uint256 identifier;
+            bytes32[] criteriaProof;
+        }
+
+        struct SpentItem {
+            uint8 itemType;
+            address token;
+            uint256 identifier;
+            uint256 amount;
+        }
+
+        struct ReceivedItem {
+            uint8 itemType;
+            address token;
+            uint256 identifier;
+            uint256 amount;
+            address recipient;
+        }
+
+        struct FulfillmentComponent {
+            uint256 orderIndex;
+            uint256 itemIndex;
+        }
+
+        struct Fulfillment {
+            FulfillmentComponent[] offerComponents;
+            FulfillmentComponent[] considerationComponents;
+        }
+
+        struct Execution {
+            ReceivedItem item;
+            address offerer;
+            bytes32 conduitKey;
+        }
+
+        event OrderFulfilled(
+            bytes32 orderHash,
+            address indexed offerer,
+            address indexed zone,
+            address recipient,
+            SpentItem[] offer,
+            ReceivedItem[] consideration
+        );
+
+        function fulfillBasicOrder(BasicOrderParameters calldata parameters)
+            external payable returns (bool fulfilled);
+
+        function fulfillBasicOrder_efficient_6GL6yc(BasicOrderParameters calldata parameters)
+            external payable returns (bool fulfilled);
+
+        function fulfillOrder(Order calldata order, bytes32 fulfillerConduitKey)
+            external payable returns (bool fulfilled);
+
+        function fulfillAdvancedOrder(
+            AdvancedOrder calldata advancedOrder,
+            CriteriaResolver[] calldata criteriaResolvers,
+            bytes32 fulfillerConduitKey,
+            address recipient
+        ) external payable returns (bool fulfilled);
+
+        function fulfillAvailableOrders(
+            Order[] calldata orders,
+            FulfillmentComponent[][] calldata offerFulfillments,
+            FulfillmentComponent[][] calldata considerationFulfillments,
+            bytes32 fulfillerConduitKey,
+            uint256 maximumFulfilled
+        ) external payable returns (bool[] memory availableOrders, Execution[] memory executions);
+
+        function fulfillAvailableAdvancedOrders(
+            AdvancedOrder[] calldata advancedOrders,
+            CriteriaResolver[] calldata criteriaResolvers,
+            FulfillmentComponent[][] calldata offerFulfillments,
+            FulfillmentComponent[][] calldata considerationFulfillments,
+            bytes32 fulfillerConduitKey,
+            address recipient,
+            uint256 maximumFulfilled
+        ) external payable returns (bool[] memory availableOrders, Execution[] memory executions);
+
+        function matchOrders(Order[] calldata orders, Fulfillment[] calldata fulfillments)
+            external payable returns (Execution[] memory executions);
+
+        function matchAdvancedOrders(
+            AdvancedOrder[] calldata orders,
+            CriteriaResolver[] calldata criteriaResolvers,
+            Fulfillment[] calldata fulfillments,
+            address recipient
+        ) external payable returns (Execution[] memory executions);
+    }
+
+    interface BlurExchange {
+        struct Fee {
+            uint16 rate;
+            address recipient;
+        }
+
+        struct Order {
+            address trader;
+            uint8 side;
+            address matchingPolicy;
+            address collection;
+            uint256 tokenId;
+            uint256 amount;
+            address paymentToken;
+            uint256 price;
+            uint256 listingTime;
+            uint256 expirationTime;
+            Fee[] fees;
+            uint256 salt;
+            bytes extraParams;
+        }
+
+        struct Input {
+            Order order;
+            uint8 v;
+            bytes32 r;
+            bytes32 s;
+            bytes extraSignature;
+            uint8 signatureVersion;
+            uint256 blockNumber;
+        }
+
+        event OrdersMatched(
+            address indexed maker,
+            address indexed taker,
+            Order sell,
+            bytes32 sellHash,
+            Order buy,
+            bytes32 buyHash
+        );
+
+        struct Execution {
+            Input sell;
+            Input buy;
+        }
+
+        function execute(Input calldata sell, Input calldata buy) external payable;
+
+        function bulkExecute(Execution[] calldata executions) external payable;
+    }
+
+    interface SudoswapPair {
+        function swapTokenForSpecificNFTs(
+            uint256[] calldata nftIds,
+            uint256 maxExpectedTokenInput,
+            address nftRecipient,
+            bool isRouter,
+            address routerCaller
+        ) external payable returns (uint256 inputAmount);
+
+        function swapNFTsForToken(
+            uint256[] calldata nftIds,
+            uint256 minExpectedTokenOutput,
+            address payable tokenRecipient,
+            bool isRouter,
+            address routerCaller
+        ) external returns (uint256 outputAmount);
+    }
+
+    interface SudoswapPairFactory {
+        struct CreateERC20PairParams {
+            address token;
+            address nft;
+            address bondingCurve;
+            address payable assetRecipient;
+            uint8 poolType;
+            uint128 delta;
+            uint96 fee;
+            uint128 spotPrice;
+            uint256[] initialNFTIDs;
+            uint256 initialTokenBalance;
+        }
+
+        function createPairETH(
+            address _nft,
+            address _bondingCurve,
+            address payable _assetRecipient,
+            uint8 _poolType,
+            uint128 _delta,
+            uint96 _fee,
+            uint128 _spotPrice,
+            uint256[] calldata _initialNFTIDs
+        ) external payable returns (address pair);
+
+        function createPairERC20(CreateERC20PairParams calldata params)
+            external returns (address pair);
+    }
+}
+
 sol! {
     event Transfer(address indexed from, address indexed to, uint256 value);
     function name() public view returns (string);
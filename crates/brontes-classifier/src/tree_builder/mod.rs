@@ -14,14 +14,17 @@ use brontes_types::{
 use malachite::{num::basic::traits::Zero, Rational};
 
 mod tree_pruning;
+pub mod user_operations;
 pub(crate) mod utils;
 use brontes_database::libmdbx::{DBWriter, LibmdbxReader};
 use brontes_pricing::types::DexPriceMsg;
+#[cfg(feature = "dyn-decode")]
+use brontes_types::structured_trace::CallFrameInfo;
 use brontes_types::{
     normalized_actions::{Action, SelfdestructWithIndex},
     structured_trace::{TraceActions, TransactionTraceWithLogs, TxTrace},
     traits::TracingProvider,
-    tree::{BlockTree, GasDetails, Node, Root},
+    tree::{BlockTree, GasDetails, Node, RevertedTx, Root, UserOperationDetails},
 };
 use futures::future::join_all;
 use itertools::Itertools;
@@ -31,9 +34,12 @@ use reth_rpc_types::trace::parity::{Action as TraceAction, CallType};
 use tokio::sync::mpsc::UnboundedSender;
 use tracing::{error, trace};
 use tree_pruning::{account_for_tax_tokens, remove_possible_transfer_double_counts};
-use utils::{decode_transfer, get_coinbase_transfer};
+use user_operations::split_user_operations;
+use utils::{decode_nft_transfers, decode_transfer, get_coinbase_transfer};
 
 use self::erc20::try_decode_transfer;
+#[cfg(feature = "dyn-decode")]
+use crate::config_classifier::ConfigClassifiers;
 use crate::{
     classifiers::*, multi_frame_classification::parse_multi_frame_requests, ActionCollection,
     FactoryDiscoveryDispatch,
@@ -45,6 +51,9 @@ pub struct Classifier<'db, T: TracingProvider, DB: LibmdbxReader + DBWriter> {
     libmdbx:               &'db DB,
     provider:              Arc<T>,
     pricing_update_sender: UnboundedSender<DexPriceMsg>,
+    /// when false, discovered pools and pool config updates are classified
+    /// but never written to libmdbx.
+    persist_pools:         bool,
 }
 
 impl<'db, T: TracingProvider, DB: LibmdbxReader + DBWriter> Classifier<'db, T, DB> {
@@ -53,7 +62,14 @@ impl<'db, T: TracingProvider, DB: LibmdbxReader + DBWriter> Classifier<'db, T, D
         pricing_update_sender: UnboundedSender<DexPriceMsg>,
         provider: Arc<T>,
     ) -> Self {
-        Self { libmdbx, pricing_update_sender, provider }
+        Self { libmdbx, pricing_update_sender, provider, persist_pools: true }
+    }
+
+    /// Builds trees without writing any discovered pools or pool config
+    /// updates to libmdbx. Used by the read only analysis commands.
+    pub fn without_persisting_pools(mut self) -> Self {
+        self.persist_pools = false;
+        self
     }
 
     pub fn block_load_failure(&self, number: u64) {
@@ -68,6 +84,19 @@ impl<'db, T: TracingProvider, DB: LibmdbxReader + DBWriter> Classifier<'db, T, D
         header: Header,
         generate_pricing: bool,
     ) -> BlockTree<Action> {
+        self.build_block_tree_with_merges(traces, header, generate_pricing)
+            .await
+            .0
+    }
+
+    /// Builds the block tree, also returning the multi call frame
+    /// classification requests that were merged for each transaction.
+    pub async fn build_block_tree_with_merges(
+        &self,
+        traces: Vec<TxTrace>,
+        header: Header,
+        generate_pricing: bool,
+    ) -> (BlockTree<Action>, Vec<(usize, Vec<MultiFrameRequest>)>) {
         let block_number = header.number;
         if !generate_pricing {
             self.pricing_update_sender
@@ -75,8 +104,20 @@ impl<'db, T: TracingProvider, DB: LibmdbxReader + DBWriter> Classifier<'db, T, D
                 .unwrap();
         }
 
+        let base_fee = header.base_fee_per_gas.unwrap_or_default() as u128;
+        let reverted_txs = traces
+            .iter()
+            .filter(|trace| !trace.is_success)
+            .filter_map(|trace| RevertedTx::from_trace(trace, base_fee))
+            .collect_vec();
+
         let tx_roots = self.build_tx_trees(traces, &header).await;
         let mut tree = BlockTree::new(header, tx_roots.len());
+        tree.reverted_txs = reverted_txs;
+        let merges = tx_roots
+            .iter()
+            .filter_map(|root| root.further_classification_requests.clone())
+            .collect_vec();
 
         // send out all updates
         let further_classification_requests =
@@ -88,7 +129,7 @@ impl<'db, T: TracingProvider, DB: LibmdbxReader + DBWriter> Classifier<'db, T, D
         self.finish_classification(&mut tree, further_classification_requests);
         tree.finalize_tree();
 
-        tree
+        (tree, merges)
     }
 
     fn process_tx_roots(
@@ -97,7 +138,11 @@ impl<'db, T: TracingProvider, DB: LibmdbxReader + DBWriter> Classifier<'db, T, D
         tree: &mut BlockTree<Action>,
         block: u64,
     ) -> Vec<Option<(usize, Vec<MultiCallFrameClassification<Action>>)>> {
-        let root_count = tx_roots.len();
+        // user operations share the index of their bundle tx
+        let root_count = tx_roots
+            .iter()
+            .filter(|root_data| !root_data.root.is_user_op())
+            .count();
         let results = tx_roots
             .into_iter()
             .map(|root_data| {
@@ -131,138 +176,26 @@ impl<'db, T: TracingProvider, DB: LibmdbxReader + DBWriter> Classifier<'db, T, D
         traces: Vec<TxTrace>,
         header: &Header,
     ) -> Vec<TxTreeResult> {
+        // handleOps bundles are split into a virtual tx per user operation, these
+        // are placed directly after the bundle tx and keep its on chain index, so
+        // they are priced at the same index as the bundle
+        let traces = traces
+            .into_iter()
+            .enumerate()
+            .flat_map(|(tx_idx, trace)| {
+                let (trace, user_ops) = split_user_operations(trace);
+                std::iter::once((tx_idx, trace, None)).chain(
+                    user_ops
+                        .into_iter()
+                        .map(move |op| (tx_idx, op.trace, Some(op.details))),
+                )
+            })
+            .collect_vec();
+
         join_all(
             traces
                 .into_iter()
-                .enumerate()
-                .map(|(tx_idx, mut trace)| async move {
-                    // here only traces where the root tx failed are filtered out
-                    if trace.trace.is_empty() || !trace.is_success {
-                        tracing::trace!(
-                            empty = trace.trace.is_empty(),
-                            is_success = trace.is_success
-                        );
-                        return None
-                    }
-                    // post classification processing collectors
-                    let mut further_classification_requests = Vec::new();
-                    let mut pool_updates: Vec<DexPriceMsg> = Vec::new();
-
-                    let root_trace = trace.trace.remove(0);
-
-                    let address = root_trace.get_from_addr();
-                    let trace_idx = root_trace.trace_idx;
-
-                    let classification = self
-                        .process_classification(
-                            header.number,
-                            None,
-                            &NodeData(vec![]),
-                            tx_idx as u64,
-                            trace_idx,
-                            root_trace,
-                            &trace.trace,
-                            &mut further_classification_requests,
-                            &mut pool_updates,
-                        )
-                        .await;
-
-                    let node = Node::new(trace_idx, address, vec![]);
-
-                    let total_msg_value_transfers = classification
-                        .iter()
-                        .filter_map(|s| s.get_msg_value_not_eth_transfer())
-                        .collect::<Vec<NormalizedEthTransfer>>();
-
-                    let mut tx_root = Root {
-                        position: tx_idx,
-                        head: node,
-                        tx_hash: trace.tx_hash,
-                        private: false,
-                        total_msg_value_transfers,
-                        gas_details: GasDetails {
-                            coinbase_transfer:   None,
-                            gas_used:            trace.gas_used,
-                            effective_gas_price: trace.effective_price,
-                            priority_fee:        trace.effective_price
-                                - (header.base_fee_per_gas.unwrap_or_default() as u128),
-                        },
-                        data_store: NodeData(vec![Some(classification)]),
-                    };
-
-                    let tx_trace = &trace.trace;
-                    for trace in &trace.trace {
-                        let from_addr = trace.get_from_addr();
-
-                        let node = Node::new(
-                            trace.trace_idx,
-                            from_addr,
-                            trace.trace.trace_address.clone(),
-                        );
-
-                        if trace.trace.error.is_none() {
-                            if let Some(coinbase_transfer) =
-                                get_coinbase_transfer(header.beneficiary, &trace.trace.action)
-                            {
-                                if let Some(coinbase) = &mut tx_root.gas_details.coinbase_transfer {
-                                    *coinbase += coinbase_transfer;
-                                } else {
-                                    tx_root.gas_details.coinbase_transfer = Some(coinbase_transfer);
-                                }
-
-                                let classification = Action::EthTransfer(NormalizedEthTransfer {
-                                    from:              from_addr,
-                                    to:                trace.get_to_address(),
-                                    value:             trace.get_msg_value(),
-                                    trace_index:       trace.trace_idx,
-                                    coinbase_transfer: true,
-                                });
-
-                                tx_root.insert(node, vec![classification]);
-                                continue
-                            }
-                        }
-
-                        let classification = self
-                            .process_classification(
-                                header.number,
-                                Some(&tx_root.head),
-                                &tx_root.data_store,
-                                tx_idx as u64,
-                                trace.trace_idx,
-                                trace.clone(),
-                                tx_trace,
-                                &mut further_classification_requests,
-                                &mut pool_updates,
-                            )
-                            .await;
-
-                        tx_root.total_msg_value_transfers.extend(
-                            classification
-                                .iter()
-                                .filter_map(|s| s.get_msg_value_not_eth_transfer()),
-                        );
-
-                        tx_root.insert(node, classification);
-                    }
-
-                    // Here we reverse the requests to ensure that we always classify the most
-                    // nested action & its children first. This is to prevent the
-                    // case where we classify a parent action where its children also require
-                    // further classification.
-                    let tx_classification_requests = if !further_classification_requests.is_empty()
-                    {
-                        further_classification_requests.reverse();
-                        Some((tx_idx, further_classification_requests))
-                    } else {
-                        None
-                    };
-                    Some(TxTreeResult {
-                        root: tx_root,
-                        further_classification_requests: tx_classification_requests,
-                        pool_updates,
-                    })
-                }),
+                .map(|(tx_idx, trace, user_op)| self.build_tx_tree(tx_idx, trace, header, user_op)),
         )
         .await
         .into_iter()
@@ -270,6 +203,139 @@ impl<'db, T: TracingProvider, DB: LibmdbxReader + DBWriter> Classifier<'db, T, D
         .collect_vec()
     }
 
+    async fn build_tx_tree(
+        &self,
+        tx_idx: usize,
+        mut trace: TxTrace,
+        header: &Header,
+        user_op: Option<UserOperationDetails>,
+    ) -> Option<TxTreeResult> {
+        // here only traces where the root tx failed are filtered out
+        if trace.trace.is_empty() || !trace.is_success {
+            tracing::trace!(empty = trace.trace.is_empty(), is_success = trace.is_success);
+            return None
+        }
+        // post classification processing collectors
+        let mut further_classification_requests = Vec::new();
+        let mut pool_updates: Vec<DexPriceMsg> = Vec::new();
+
+        let root_trace = trace.trace.remove(0);
+
+        // user operations are originated by the sender smart account, not the entry
+        // point
+        let address = user_op
+            .map(|op| op.sender)
+            .unwrap_or_else(|| root_trace.get_from_addr());
+        let trace_idx = root_trace.trace_idx;
+
+        let classification = self
+            .process_classification(
+                header.number,
+                None,
+                &NodeData(vec![]),
+                tx_idx as u64,
+                trace_idx,
+                root_trace,
+                &trace.trace,
+                &mut further_classification_requests,
+                &mut pool_updates,
+            )
+            .await;
+
+        let node = Node::new(trace_idx, address, vec![]);
+
+        let total_msg_value_transfers = classification
+            .iter()
+            .filter_map(|s| s.get_msg_value_not_eth_transfer())
+            .collect::<Vec<NormalizedEthTransfer>>();
+
+        let mut tx_root = Root {
+            position: tx_idx,
+            head: node,
+            tx_hash: trace.tx_hash,
+            private: false,
+            total_msg_value_transfers,
+            gas_details: GasDetails {
+                coinbase_transfer:   None,
+                gas_used:            trace.gas_used,
+                effective_gas_price: trace.effective_price,
+                priority_fee:        trace
+                    .effective_price
+                    .saturating_sub(header.base_fee_per_gas.unwrap_or_default() as u128),
+            },
+            data_store: NodeData(vec![Some(classification)]),
+            user_op,
+        };
+
+        let tx_trace = &trace.trace;
+        for trace in &trace.trace {
+            let from_addr = trace.get_from_addr();
+
+            let node = Node::new(trace.trace_idx, from_addr, trace.trace.trace_address.clone());
+
+            if trace.trace.error.is_none() {
+                if let Some(coinbase_transfer) =
+                    get_coinbase_transfer(header.beneficiary, &trace.trace.action)
+                {
+                    if let Some(coinbase) = &mut tx_root.gas_details.coinbase_transfer {
+                        *coinbase += coinbase_transfer;
+                    } else {
+                        tx_root.gas_details.coinbase_transfer = Some(coinbase_transfer);
+                    }
+
+                    let classification = Action::EthTransfer(NormalizedEthTransfer {
+                        from:              from_addr,
+                        to:                trace.get_to_address(),
+                        value:             trace.get_msg_value(),
+                        trace_index:       trace.trace_idx,
+                        coinbase_transfer: true,
+                    });
+
+                    tx_root.insert(node, vec![classification]);
+                    continue
+                }
+            }
+
+            let classification = self
+                .process_classification(
+                    header.number,
+                    Some(&tx_root.head),
+                    &tx_root.data_store,
+                    tx_idx as u64,
+                    trace.trace_idx,
+                    trace.clone(),
+                    tx_trace,
+                    &mut further_classification_requests,
+                    &mut pool_updates,
+                )
+                .await;
+
+            tx_root.total_msg_value_transfers.extend(
+                classification
+                    .iter()
+                    .filter_map(|s| s.get_msg_value_not_eth_transfer()),
+            );
+
+            tx_root.insert(node, classification);
+        }
+
+        // Here we reverse the requests to ensure that we always classify the most
+        // nested action & its children first. This is to prevent the
+        // case where we classify a parent action where its children also require
+        // further classification.
+        let tx_classification_requests = if !further_classification_requests.is_empty() {
+            further_classification_requests.reverse();
+            Some((tx_idx, further_classification_requests))
+        } else {
+            None
+        };
+        Some(TxTreeResult {
+            root: tx_root,
+            further_classification_requests: tx_classification_requests,
+            pool_updates,
+        })
+    }
+
     async fn process_classification(
         &self,
         block_number: u64,
@@ -386,13 +452,26 @@ impl<'db, T: TracingProvider, DB: LibmdbxReader + DBWriter> Classifier<'db, T, D
             }
         }
 
-        if let Some(results) =
-            ProtocolClassifier::default().dispatch(call_info, self.libmdbx, block, tx_idx)
-        {
+        #[cfg(feature = "dyn-decode")]
+        let config_call_info = call_info.clone();
+
+        let classified =
+            ProtocolClassifier::default().dispatch(call_info, self.libmdbx, block, tx_idx);
+
+        #[cfg(feature = "dyn-decode")]
+        let classified = match classified {
+            Some(results) => Some(results),
+            None => {
+                self.classify_with_config(&config_call_info, &trace, block, tx_idx)
+                    .await
+            }
+        };
+
+        if let Some(results) = classified {
             if results.1.is_new_pool() {
                 let Action::NewPool(p) = &results.1 else { unreachable!() };
                 self.insert_new_pool(block, p).await;
-            } else if results.1.is_pool_config_update() {
+            } else if results.1.is_pool_config_update() && self.persist_pools {
                 let Action::PoolConfigUpdate(p) = &results.1 else { unreachable!() };
                 if self
                     .libmdbx
@@ -420,6 +499,25 @@ impl<'db, T: TracingProvider, DB: LibmdbxReader + DBWriter> Classifier<'db, T, D
         }
     }
 
+    /// Falls back onto the classifiers declared in the dyn classifier config
+    #[cfg(feature = "dyn-decode")]
+    async fn classify_with_config(
+        &self,
+        call_info: &CallFrameInfo<'_>,
+        trace: &TransactionTraceWithLogs,
+        block: u64,
+        tx_idx: u64,
+    ) -> Option<(DexPriceMsg, Action)> {
+        let classifiers = ConfigClassifiers::get();
+        if classifiers.is_empty() {
+            return None
+        }
+
+        classifiers
+            .dispatch(call_info, trace, self.libmdbx, &self.provider, block, tx_idx)
+            .await
+    }
+
     async fn classify_transfer(
         &self,
         tx_idx: u64,
@@ -431,6 +529,33 @@ impl<'db, T: TracingProvider, DB: LibmdbxReader + DBWriter> Classifier<'db, T, D
             return None
         };
 
+        // ERC721 `transferFrom` shares its selector with ERC20, so nft transfers
+        // are detected through the logs of the called collection first. Known
+        // ERC20 tokens are skipped as they can't emit nft transfers.
+        let collection = trace.get_to_address();
+        if self.libmdbx.try_fetch_token_info(collection).is_err() {
+            let mut nft_transfers = trace
+                .logs
+                .iter()
+                .filter(|log| log.address == collection)
+                .flat_map(|log| decode_nft_transfers(log, trace_idx))
+                .map(Action::NftTransfer)
+                .collect_vec();
+
+            if !nft_transfers.is_empty() {
+                if trace.get_msg_value() != U256::ZERO {
+                    nft_transfers.push(Action::EthTransfer(NormalizedEthTransfer {
+                        coinbase_transfer: false,
+                        trace_index:       trace_idx,
+                        to:                collection,
+                        from:              trace.get_from_addr(),
+                        value:             trace.get_msg_value(),
+                    }));
+                }
+                return Some((vec![], nft_transfers))
+            }
+        }
+
         // Attempt to decode the transfer
         match try_decode_transfer(
             trace_idx,
@@ -608,10 +733,28 @@ impl<'db, T: TracingProvider, DB: LibmdbxReader + DBWriter> Classifier<'db, T, D
             return (vec![], vec![Action::Unclassified(trace)])
         }
 
+        let mut discovered = DiscoveryClassifier::default()
+            .dispatch(self.provider.clone(), search_data, created_addr, trace_index)
+            .await;
+
+        // the deploying factory isn't one we know, check if the created contract is a
+        // uniswap fork announced through its factory's creation event
+        if discovered.is_empty() {
+            let parent_logs = all_nodes
+                .iter()
+                .filter_map(|node| node_data_store.get_ref(node.data))
+                .flatten()
+                .flat_map(|node_data| node_data.get_logs())
+                .collect::<Vec<_>>();
+
+            discovered.extend(
+                discover_fork_pool(&self.provider, block, created_addr, trace_index, &parent_logs)
+                    .await,
+            );
+        }
+
         join_all(
-            DiscoveryClassifier::default()
-                .dispatch(self.provider.clone(), search_data, created_addr, trace_index)
-                .await
+            discovered
                 .into_iter()
                 // insert the pool returning if it has token values.
                 .map(|pool| async {
@@ -635,6 +778,10 @@ impl<'db, T: TracingProvider, DB: LibmdbxReader + DBWriter> Classifier<'db, T, D
     }
 
     async fn insert_new_pool(&self, block: u64, pool: &NormalizedNewPool) {
+        if !self.persist_pools {
+            return
+        }
+
         if self
             .libmdbx
             .insert_pool(block, pool.pool_address, &pool.tokens, None, pool.protocol)
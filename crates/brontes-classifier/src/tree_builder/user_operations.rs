@@ -0,0 +1,263 @@
+//! Splits ERC-4337 `EntryPoint.handleOps` transactions into a virtual
+//! sub-transaction per executed user operation.
+//!
+//! A bundler submits many user operations in a single transaction, which
+//! would otherwise attribute every action to the bundler eoa. For each
+//! `innerHandleOp` frame we take the call from the entry point into the
+//! sender smart account, move it (and all of its children) into its own
+//! [`TxTrace`] and charge it the gas reported in the `UserOperationEvent`.
+//! The split traces keep the on chain tx index but are keyed by their user op
+//! hash, so that every operation of a bundle can be looked up on its own. The
+//! on chain tx hash and the position of the operation in the bundle are
+//! carried in [`UserOperationDetails`]. Everything else (validation, paymaster
+//! post ops, bundler compensation) stays in the bundler transaction.
+
+use alloy_primitives::{Address, Log, U256};
+use alloy_sol_types::{sol, SolEvent};
+use brontes_types::{
+    structured_trace::{TransactionTraceWithLogs, TxTrace},
+    tree::UserOperationDetails,
+};
+use hex_literal::hex;
+use reth_rpc_types::trace::parity::{Action as TraceAction, CallType};
+
+/// EntryPoint v0.6
+pub const ENTRY_POINT_V06: Address = Address::new(hex!("5FF137D4b0FDCD49DcA30c7CF57E578a026d2789"));
+/// EntryPoint v0.7
+pub const ENTRY_POINT_V07: Address = Address::new(hex!("0000000071727De22E5E9d8BAf0edAc6f37da032"));
+
+sol! {
+    event UserOperationEvent(
+        bytes32 indexed userOpHash,
+        address indexed sender,
+        address indexed paymaster,
+        uint256 nonce,
+        bool success,
+        uint256 actualGasCost,
+        uint256 actualGasUsed
+    );
+}
+
+pub fn is_entry_point(address: Address) -> bool {
+    address == ENTRY_POINT_V06 || address == ENTRY_POINT_V07
+}
+
+/// A user operation that was split out of its bundle transaction
+pub struct UserOperationTrace {
+    pub trace:   TxTrace,
+    pub details: UserOperationDetails,
+}
+
+/// Splits the bundle transaction into the bundler's own trace and a trace
+/// per executed user operation. Returns the trace unchanged with no user
+/// operations if it isn't a direct call to a known entry point.
+pub fn split_user_operations(mut tx: TxTrace) -> (TxTrace, Vec<UserOperationTrace>) {
+    let Some(root) = tx.trace.first() else { return (tx, vec![]) };
+
+    let TraceAction::Call(root_call) = &root.trace.action else { return (tx, vec![]) };
+    if !is_entry_point(root_call.to) || !tx.is_success {
+        return (tx, vec![])
+    }
+    let entry_point = root_call.to;
+    let bundler = root_call.from;
+
+    // (trace address of the sender execution frame, op details, gas used, gas cost)
+    let user_ops = tx
+        .trace
+        .iter()
+        .filter(|trace| trace.trace.trace_address.len() == 1 && trace.trace.error.is_none())
+        .filter(|trace| is_inner_handle_op(trace, entry_point))
+        .filter_map(|inner| {
+            let event = decode_user_op_event(&inner.logs, entry_point)?;
+            if !event.success {
+                return None
+            }
+
+            let execution = tx.trace.iter().find(|trace| {
+                trace.trace.trace_address.len() == 2
+                    && trace.trace.trace_address[0] == inner.trace.trace_address[0]
+                    && matches!(
+                        &trace.trace.action,
+                        TraceAction::Call(call) if call.from == entry_point
+                            && call.to == event.sender
+                    )
+            })?;
+
+            let details = UserOperationDetails {
+                user_op_hash: event.userOpHash,
+                sender: event.sender,
+                paymaster: event.paymaster,
+                nonce: event.nonce,
+                entry_point,
+                bundler,
+                bundle_tx_hash: tx.tx_hash,
+                // set once the executed operations are known
+                op_index: 0,
+            };
+
+            Some((
+                execution.trace.trace_address.clone(),
+                details,
+                event.actualGasUsed,
+                event.actualGasCost,
+            ))
+        })
+        .collect::<Vec<_>>();
+
+    if user_ops.is_empty() {
+        return (tx, vec![])
+    }
+
+    let removed = user_ops
+        .iter()
+        .map(|(address, ..)| address.clone())
+        .collect::<Vec<_>>();
+
+    let mut op_traces = user_ops
+        .into_iter()
+        .enumerate()
+        .map(|(op_index, (_, mut details, gas_used, gas_cost))| {
+            details.op_index = op_index;
+            let gas_used = gas_used.saturating_to::<u128>();
+            let effective_price = if gas_used == 0 {
+                0
+            } else {
+                (gas_cost / U256::from(gas_used)).saturating_to::<u128>()
+            };
+
+            UserOperationTrace {
+                trace: TxTrace::new(
+                    tx.block_number,
+                    vec![],
+                    details.user_op_hash,
+                    tx.tx_index,
+                    gas_used,
+                    effective_price,
+                    true,
+                ),
+                details,
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let mut bundler_traces = Vec::with_capacity(tx.trace.len());
+    for mut trace in tx.trace.drain(..) {
+        let address = &trace.trace.trace_address;
+        if let Some(op) = removed
+            .iter()
+            .position(|prefix| address.starts_with(prefix))
+        {
+            let prefix_len = removed[op].len();
+            trace.trace.trace_address = trace.trace.trace_address[prefix_len..].to_vec();
+            op_traces[op].trace.trace.push(trace);
+            continue
+        }
+
+        trace.trace.trace_address = reindex_trace_address(address, &removed);
+        bundler_traces.push(trace);
+    }
+    tx.trace = bundler_traces;
+
+    (tx, op_traces)
+}
+
+/// The entry point executes every user operation through a self call to
+/// `innerHandleOp`, this is the only self call made during `handleOps`
+fn is_inner_handle_op(trace: &TransactionTraceWithLogs, entry_point: Address) -> bool {
+    matches!(
+        &trace.trace.action,
+        TraceAction::Call(call) if call.from == entry_point
+            && call.to == entry_point
+            && call.call_type == CallType::Call
+    )
+}
+
+fn decode_user_op_event(logs: &[Log], entry_point: Address) -> Option<UserOperationEvent> {
+    logs.iter()
+        .filter(|log| log.address == entry_point)
+        .find_map(|log| UserOperationEvent::decode_log_data(&log.data, false).ok())
+}
+
+/// Shifts the trace address of a remaining frame so that the siblings of the
+/// removed frames stay contiguous, which is required when inserting into the
+/// tree.
+fn reindex_trace_address(address: &[usize], removed: &[Vec<usize>]) -> Vec<usize> {
+    let mut new_address = address.to_vec();
+    for prefix in removed {
+        let depth = prefix.len() - 1;
+        if address.len() > depth
+            && address[..depth] == prefix[..depth]
+            && address[depth] > prefix[depth]
+        {
+            new_address[depth] -= 1;
+        }
+    }
+
+    new_address
+}
+
+#[cfg(test)]
+mod tests {
+    use brontes_classifier::test_utils::ClassifierTestUtils;
+
+    use super::*;
+
+    #[brontes_macros::test]
+    async fn test_split_handle_ops_keeps_tx_index() {
+        let classifier_utils = ClassifierTestUtils::new().await;
+
+        // EntryPoint v0.6 bundles were landing every few blocks at this height
+        let mut user_ops = 0;
+        for block in 19_000_000..19_000_010 {
+            let tree = classifier_utils.build_block_tree(block).await.unwrap();
+
+            for (idx, root) in tree.tx_roots.iter().enumerate() {
+                let Some(op) = root.user_op else { continue };
+                user_ops += 1;
+
+                // the bundle tx sits directly before its user operations, which keep its
+                // on chain index but are keyed by their user op hash
+                let prev_ops = tree.tx_roots[..idx]
+                    .iter()
+                    .rev()
+                    .take_while(|r| r.is_user_op())
+                    .count();
+                let bundle = &tree.tx_roots[idx - prev_ops - 1];
+                assert_eq!(root.position, bundle.position);
+                assert_eq!(op.bundle_tx_hash, bundle.tx_hash);
+                assert_eq!(root.tx_hash, op.user_op_hash);
+                assert_eq!(root.on_chain_tx_hash(), bundle.tx_hash);
+                assert!(is_entry_point(op.entry_point));
+                assert_eq!(root.head.address, op.sender);
+                assert_eq!(bundle.head.address, op.bundler);
+
+                // operations are indexed in bundle order and resolve to their own root
+                assert_eq!(op.op_index, prev_ops);
+                let found = tree.get_root(op.user_op_hash).unwrap();
+                assert_eq!(found.user_op.unwrap().op_index, op.op_index);
+            }
+
+            // positions follow the on chain index
+            assert!(tree
+                .tx_roots
+                .windows(2)
+                .all(|roots| roots[0].position <= roots[1].position));
+        }
+
+        assert!(user_ops > 0, "no handleOps bundles found in the block range");
+    }
+
+    #[test]
+    fn test_reindex_trace_address() {
+        // removed the sender execution frames of the 1st and 3rd user operation
+        let removed = vec![vec![1, 0], vec![3, 0]];
+
+        assert_eq!(reindex_trace_address(&[], &removed), Vec::<usize>::new());
+        assert_eq!(reindex_trace_address(&[0], &removed), vec![0]);
+        assert_eq!(reindex_trace_address(&[1], &removed), vec![1]);
+        assert_eq!(reindex_trace_address(&[1, 1], &removed), vec![1, 0]);
+        assert_eq!(reindex_trace_address(&[1, 1, 2], &removed), vec![1, 0, 2]);
+        assert_eq!(reindex_trace_address(&[2, 0], &removed), vec![2, 0]);
+        assert_eq!(reindex_trace_address(&[3, 2], &removed), vec![3, 1]);
+    }
+}
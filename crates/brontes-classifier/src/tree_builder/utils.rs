@@ -1,4 +1,6 @@
 use alloy_primitives::{Address, FixedBytes, Log, B256, U256};
+use alloy_sol_types::{sol_data, SolType};
+use brontes_types::normalized_actions::{NftStandard, NormalizedNftTransfer};
 use hex_literal::hex;
 use reth_rpc_types::trace::parity::Action;
 
@@ -31,3 +33,67 @@ pub(crate) fn decode_transfer(log: &Log) -> Option<(Address, Address, Address, U
 
     None
 }
+
+const TRANSFER_SINGLE_TOPIC: B256 =
+    FixedBytes(hex!("c3d58168c5ae7397731d063d5bbf3d657854427343f4c083240f7aacaa2d0f62"));
+
+const TRANSFER_BATCH_TOPIC: B256 =
+    FixedBytes(hex!("4a39dc06d4c0dbc64b70af90fd698a233a518aa5d07e595d983b8c0526c8f7fb"));
+
+type TransferBatchData =
+    (sol_data::Array<sol_data::Uint<256>>, sol_data::Array<sol_data::Uint<256>>);
+
+/// Decodes ERC721 `Transfer` and ERC1155 `TransferSingle` / `TransferBatch`
+/// logs. ERC721 transfers share the ERC20 topic but index the token id, so
+/// they have four topics instead of three.
+pub(crate) fn decode_nft_transfers(log: &Log, trace_index: u64) -> Vec<NormalizedNftTransfer> {
+    let topics = log.topics();
+    let topic_address = |i: usize| Address::from_slice(&topics[i][12..]);
+
+    match topics.first() {
+        Some(topic) if *topic == TRANSFER_TOPIC && topics.len() == 4 => {
+            vec![NormalizedNftTransfer {
+                trace_index,
+                standard: NftStandard::Erc721,
+                collection: log.address,
+                from: topic_address(1),
+                to: topic_address(2),
+                token_id: U256::from_be_bytes(topics[3].0),
+                amount: U256::from(1),
+            }]
+        }
+        Some(topic) if *topic == TRANSFER_SINGLE_TOPIC && topics.len() == 4 => {
+            if log.data.data.len() != 64 {
+                return vec![]
+            }
+            vec![NormalizedNftTransfer {
+                trace_index,
+                standard: NftStandard::Erc1155,
+                collection: log.address,
+                from: topic_address(2),
+                to: topic_address(3),
+                token_id: U256::from_be_slice(&log.data.data[..32]),
+                amount: U256::from_be_slice(&log.data.data[32..]),
+            }]
+        }
+        Some(topic) if *topic == TRANSFER_BATCH_TOPIC && topics.len() == 4 => {
+            let Ok((ids, amounts)) = TransferBatchData::abi_decode_params(&log.data.data, false)
+            else {
+                return vec![]
+            };
+            ids.into_iter()
+                .zip(amounts)
+                .map(|(token_id, amount)| NormalizedNftTransfer {
+                    trace_index,
+                    standard: NftStandard::Erc1155,
+                    collection: log.address,
+                    from: topic_address(2),
+                    to: topic_address(3),
+                    token_id,
+                    amount,
+                })
+                .collect()
+        }
+        _ => vec![],
+    }
+}
@@ -1,5 +1,6 @@
 use alloy_dyn_abi::*;
 use alloy_json_abi::JsonAbi;
+use alloy_primitives::Log;
 use brontes_types::structured_trace::{DecodedCallData, DecodedParams};
 use reth_rpc_types::trace::parity::{Action, TraceOutput, TransactionTrace};
 
@@ -78,6 +79,35 @@ pub fn decode_input_with_abi(
     Ok(None)
 }
 
+/// Decodes the log with the event of the given name in the abi. Returns
+/// `None` if the log wasn't emitted by that event.
+pub fn decode_event_with_abi(
+    abi: &JsonAbi,
+    event_name: &str,
+    log: &Log,
+) -> Result<Option<Vec<DecodedParams>>, TraceParseError> {
+    let Some(event) = abi.events.get(event_name).and_then(|events| {
+        events
+            .iter()
+            .find(|event| log.topics().first() == Some(&event.selector()))
+    }) else {
+        return Ok(None);
+    };
+
+    let decoded = event.decode_log_parts(log.topics().iter().copied(), &log.data.data, false)?;
+    let mut indexed = decoded.indexed.into_iter();
+    let mut body = decoded.body.into_iter();
+
+    let mut results = Vec::new();
+    for input in &event.inputs {
+        let value = if input.indexed { indexed.next() } else { body.next() };
+        let Some(value) = value else { break };
+        decode_params(value, &mut vec![input.name.clone()], &mut results);
+    }
+
+    Ok(Some(results))
+}
+
 fn decode_params(
     sol_value: DynSolValue,
     field_name: &mut Vec<String>,
@@ -10,7 +10,7 @@ use tokio::sync::mpsc::UnboundedSender;
 use self::parser::TraceParser;
 
 #[cfg(feature = "dyn-decode")]
-mod dyn_decode;
+pub mod dyn_decode;
 
 pub mod parser;
 mod utils;
@@ -3,7 +3,10 @@ use std::sync::Arc;
 use alloy_provider::{Provider, RootProvider};
 use alloy_rpc_types::AnyReceiptEnvelope;
 use alloy_transport_http::Http;
-use brontes_types::{structured_trace::TxTrace, traits::TracingProvider};
+use brontes_types::{
+    structured_trace::TxTrace,
+    traits::{SimulatedTx, TracingProvider},
+};
 use itertools::Itertools;
 use reth_primitives::{
     Address, BlockId, BlockNumber, BlockNumberOrTag, Bytecode, Bytes, Header, StorageValue, TxHash,
@@ -100,6 +103,15 @@ impl TracingProvider for LocalProvider {
         Ok((tx.block_number.ok_or_else(err)?, tx.transaction_index.ok_or_else(err)? as usize))
     }
 
+    async fn replay_txs_without(
+        &self,
+        _: u64,
+        _: Vec<TxHash>,
+        _: Vec<TxHash>,
+    ) -> eyre::Result<Vec<SimulatedTx>> {
+        Err(eyre::eyre!("counterfactual replays require direct access to the reth db"))
+    }
+
     async fn header_by_number(&self, number: BlockNumber) -> eyre::Result<Option<Header>> {
         let err = || eyre::eyre!("failed to unwrap option");
         let block = self
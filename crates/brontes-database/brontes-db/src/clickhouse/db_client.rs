@@ -183,6 +183,7 @@ impl Clickhouse {
                         tx.send(vec![(s, self.tip, self.run_id).into()])?
                     }
                     BundleData::Unknown(s) => tx.send(vec![(s, self.tip, self.run_id).into()])?,
+                    BundleData::NftArb(s) => tx.send(vec![(s, self.tip, self.run_id).into()])?,
                 };
 
                 Ok(()) as eyre::Result<()>
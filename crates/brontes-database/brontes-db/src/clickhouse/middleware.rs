@@ -7,14 +7,19 @@ use brontes_types::{
         address_to_protocol_info::ProtocolInfo,
         block_analysis::BlockAnalysis,
         builder::BuilderInfo,
-        dex::DexQuotes,
+        cex::{quotes::CexPriceMap, trades::CexTradeMap},
+        cex_dex_inventory::DailyCexDexInventory,
+        dex::{DexQuoteWithIndex, DexQuotes},
+        lvr::BlockLvr,
         metadata::Metadata,
         mev_block::MevBlockWithClassified,
+        normalized_actions::ClassifiedBlock,
+        run_checkpoint::RunCheckpoint,
         searcher::SearcherInfo,
         token_info::TokenInfoWithAddress,
         traits::{DBWriter, LibmdbxReader, ProtocolCreatedRange},
     },
-    mev::{Bundle, MevBlock},
+    mev::{BlockFailedAttempts, Bundle, MevBlock},
     normalized_actions::Action,
     pair::Pair,
     structured_trace::TxTrace,
@@ -51,7 +56,9 @@ impl<I: DBWriter + Send + Sync> DBWriter for ClickhouseMiddleware<I> {
     }
 
     async fn write_block_analysis(&self, block_analysis: BlockAnalysis) -> eyre::Result<()> {
-        self.client.block_analysis(block_analysis).await
+        self.client.block_analysis(block_analysis.clone()).await?;
+
+        self.inner().write_block_analysis(block_analysis).await
     }
 
     async fn write_dex_quotes(
@@ -333,6 +340,63 @@ impl<I: LibmdbxInit> LibmdbxReader for ClickhouseMiddleware<I> {
         todo!("Joe");
     }
 
+    fn try_fetch_block_analysis(&self, block_num: u64) -> eyre::Result<Option<BlockAnalysis>> {
+        self.inner.try_fetch_block_analysis(block_num)
+    }
+
+    fn try_fetch_block_analyses(
+        &self,
+        start_block: Option<u64>,
+        end_block: u64,
+    ) -> eyre::Result<Vec<BlockAnalysis>> {
+        self.inner.try_fetch_block_analyses(start_block, end_block)
+    }
+
+    fn try_fetch_classified_block(&self, block_num: u64) -> eyre::Result<Option<ClassifiedBlock>> {
+        self.inner.try_fetch_classified_block(block_num)
+    }
+
+    fn try_fetch_classified_blocks(
+        &self,
+        start_block: Option<u64>,
+        end_block: u64,
+    ) -> eyre::Result<Vec<ClassifiedBlock>> {
+        self.inner
+            .try_fetch_classified_blocks(start_block, end_block)
+    }
+
+    fn try_fetch_block_lvrs(
+        &self,
+        start_block: Option<u64>,
+        end_block: u64,
+    ) -> eyre::Result<Vec<BlockLvr>> {
+        self.inner.try_fetch_block_lvrs(start_block, end_block)
+    }
+
+    fn try_fetch_failed_attempts(
+        &self,
+        start_block: Option<u64>,
+        end_block: u64,
+    ) -> eyre::Result<Vec<BlockFailedAttempts>> {
+        self.inner.try_fetch_failed_attempts(start_block, end_block)
+    }
+
+    fn try_fetch_cex_dex_inventories(
+        &self,
+        start_day: Option<u64>,
+        end_day: u64,
+    ) -> eyre::Result<Vec<DailyCexDexInventory>> {
+        self.inner.try_fetch_cex_dex_inventories(start_day, end_day)
+    }
+
+    fn try_fetch_run_checkpoint(&self, run_id: u64) -> eyre::Result<Option<RunCheckpoint>> {
+        self.inner.try_fetch_run_checkpoint(run_id)
+    }
+
+    fn latest_run_checkpoint_id(&self) -> eyre::Result<Option<u64>> {
+        self.inner.latest_run_checkpoint_id()
+    }
+
     fn get_metadata(&self, block_num: u64, quote_asset: Address) -> eyre::Result<Metadata> {
         self.inner.get_metadata(block_num, quote_asset)
     }
@@ -359,6 +423,41 @@ impl<I: LibmdbxInit> LibmdbxReader for ClickhouseMiddleware<I> {
         self.inner.get_dex_quotes(block)
     }
 
+    fn try_fetch_dex_quotes_range(
+        &self,
+        start_block: u64,
+        end_block: u64,
+    ) -> eyre::Result<Vec<(u64, DexQuoteWithIndex)>> {
+        self.inner
+            .try_fetch_dex_quotes_range(start_block, end_block)
+    }
+
+    fn try_fetch_cex_trades_range(
+        &self,
+        start_block: u64,
+        end_block: u64,
+    ) -> eyre::Result<Vec<(u64, CexTradeMap)>> {
+        self.inner
+            .try_fetch_cex_trades_range(start_block, end_block)
+    }
+
+    fn try_fetch_cex_quotes_range(
+        &self,
+        start_block: u64,
+        end_block: u64,
+    ) -> eyre::Result<Vec<(u64, CexPriceMap)>> {
+        self.inner
+            .try_fetch_cex_quotes_range(start_block, end_block)
+    }
+
+    fn fetch_all_token_info(&self) -> eyre::Result<Vec<TokenInfoWithAddress>> {
+        self.inner.fetch_all_token_info()
+    }
+
+    fn fetch_all_protocol_info(&self) -> eyre::Result<Vec<(Address, ProtocolInfo)>> {
+        self.inner.fetch_all_protocol_info()
+    }
+
     fn try_fetch_token_info(&self, address: Address) -> eyre::Result<TokenInfoWithAddress> {
         self.inner.try_fetch_token_info(address)
     }
@@ -494,6 +593,15 @@ impl<I: DBWriter + Send + Sync> DBWriter for ReadOnlyMiddleware<I> {
     async fn save_traces(&self, block: u64, traces: Vec<TxTrace>) -> eyre::Result<()> {
         self.client.save_traces(block, traces.clone()).await
     }
+
+    // nothing is written locally, so there is no progress to checkpoint
+    async fn write_run_checkpoint(&self, _: RunCheckpoint) -> eyre::Result<()> {
+        Ok(())
+    }
+
+    async fn commit_run_checkpoint(&self, _: u64, _: usize, _: u64) -> eyre::Result<()> {
+        Ok(())
+    }
 }
 
 impl<I: LibmdbxInit> LibmdbxInit for ReadOnlyMiddleware<I> {
@@ -655,6 +763,63 @@ impl<I: LibmdbxInit> LibmdbxReader for ReadOnlyMiddleware<I> {
         todo!("Joe");
     }
 
+    fn try_fetch_block_analysis(&self, block_num: u64) -> eyre::Result<Option<BlockAnalysis>> {
+        self.inner.try_fetch_block_analysis(block_num)
+    }
+
+    fn try_fetch_block_analyses(
+        &self,
+        start_block: Option<u64>,
+        end_block: u64,
+    ) -> eyre::Result<Vec<BlockAnalysis>> {
+        self.inner.try_fetch_block_analyses(start_block, end_block)
+    }
+
+    fn try_fetch_classified_block(&self, block_num: u64) -> eyre::Result<Option<ClassifiedBlock>> {
+        self.inner.try_fetch_classified_block(block_num)
+    }
+
+    fn try_fetch_classified_blocks(
+        &self,
+        start_block: Option<u64>,
+        end_block: u64,
+    ) -> eyre::Result<Vec<ClassifiedBlock>> {
+        self.inner
+            .try_fetch_classified_blocks(start_block, end_block)
+    }
+
+    fn try_fetch_block_lvrs(
+        &self,
+        start_block: Option<u64>,
+        end_block: u64,
+    ) -> eyre::Result<Vec<BlockLvr>> {
+        self.inner.try_fetch_block_lvrs(start_block, end_block)
+    }
+
+    fn try_fetch_failed_attempts(
+        &self,
+        start_block: Option<u64>,
+        end_block: u64,
+    ) -> eyre::Result<Vec<BlockFailedAttempts>> {
+        self.inner.try_fetch_failed_attempts(start_block, end_block)
+    }
+
+    fn try_fetch_cex_dex_inventories(
+        &self,
+        start_day: Option<u64>,
+        end_day: u64,
+    ) -> eyre::Result<Vec<DailyCexDexInventory>> {
+        self.inner.try_fetch_cex_dex_inventories(start_day, end_day)
+    }
+
+    fn try_fetch_run_checkpoint(&self, run_id: u64) -> eyre::Result<Option<RunCheckpoint>> {
+        self.inner.try_fetch_run_checkpoint(run_id)
+    }
+
+    fn latest_run_checkpoint_id(&self) -> eyre::Result<Option<u64>> {
+        self.inner.latest_run_checkpoint_id()
+    }
+
     fn get_metadata(&self, block_num: u64, quote_asset: Address) -> eyre::Result<Metadata> {
         self.inner.get_metadata(block_num, quote_asset)
     }
@@ -681,6 +846,41 @@ impl<I: LibmdbxInit> LibmdbxReader for ReadOnlyMiddleware<I> {
         self.inner.get_dex_quotes(block)
     }
 
+    fn try_fetch_dex_quotes_range(
+        &self,
+        start_block: u64,
+        end_block: u64,
+    ) -> eyre::Result<Vec<(u64, DexQuoteWithIndex)>> {
+        self.inner
+            .try_fetch_dex_quotes_range(start_block, end_block)
+    }
+
+    fn try_fetch_cex_trades_range(
+        &self,
+        start_block: u64,
+        end_block: u64,
+    ) -> eyre::Result<Vec<(u64, CexTradeMap)>> {
+        self.inner
+            .try_fetch_cex_trades_range(start_block, end_block)
+    }
+
+    fn try_fetch_cex_quotes_range(
+        &self,
+        start_block: u64,
+        end_block: u64,
+    ) -> eyre::Result<Vec<(u64, CexPriceMap)>> {
+        self.inner
+            .try_fetch_cex_quotes_range(start_block, end_block)
+    }
+
+    fn fetch_all_token_info(&self) -> eyre::Result<Vec<TokenInfoWithAddress>> {
+        self.inner.fetch_all_token_info()
+    }
+
+    fn fetch_all_protocol_info(&self) -> eyre::Result<Vec<(Address, ProtocolInfo)>> {
+        self.inner.fetch_all_protocol_info()
+    }
+
     fn try_fetch_token_info(&self, address: Address) -> eyre::Result<TokenInfoWithAddress> {
         self.inner.try_fetch_token_info(address)
     }
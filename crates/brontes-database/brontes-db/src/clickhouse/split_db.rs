@@ -126,6 +126,7 @@ impl ClickhouseBuffered {
             (MevJit_Sandwich, JitLiquiditySandwich),
             (MevSandwiches, Sandwich),
             (MevAtomic_Arbs, AtomicArb),
+            (MevNft_Arbs, NftArb),
             (MevLiquidations, Liquidation),
             (BrontesDex_Price_Mapping, DexQuotesWithBlockNumber),
             (BrontesToken_Info, TokenInfoWithAddress),
@@ -20,6 +20,13 @@ CREATE TABLE mev.atomic_arbs ON CLUSTER eth_cluster0
         `effective_gas_price` UInt128
     ),
     `arb_type` String,
+    `refunds` Nested(
+        `tx_hash` String,
+        `payer` String,
+        `recipient` String,
+        `amount` Float64,
+        `usd_value` Float64
+    ),
     `run_id` UInt64
 ) 
 ENGINE = ReplicatedMergeTree('/clickhouse/eth_cluster0/tables/all/mev/atomic_arbs', '{replica}')
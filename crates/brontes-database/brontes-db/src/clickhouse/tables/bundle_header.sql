@@ -16,6 +16,12 @@ CREATE TABLE mev.bundle_header ON CLUSTER eth_cluster0
         `name` Nullable(String),
         `token_deltas` Array(Tuple(Tuple(String, UInt8, String), Float64, Float64))
     ),
+    `quote_pnl` Nested (
+        `quote_asset` String,
+        `profit` Float64,
+        `revenue` Float64
+    ),
+    `refund_usd` Float64 DEFAULT 0,
     `run_id` UInt64
 ) 
 ENGINE = ReplicatedMergeTree('/clickhouse/eth_cluster0/tables/all/mev/bundle_header', '{replica}')
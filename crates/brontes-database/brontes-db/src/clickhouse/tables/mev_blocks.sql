@@ -11,7 +11,8 @@ CREATE TABLE mev.mev_blocks ON CLUSTER eth_cluster0
         `jit_count` UInt64,
         `jit_sandwich_count` UInt64,
         `atomic_backrun_count` UInt64,
-        `liquidation_count` UInt64
+        `liquidation_count` UInt64,
+        `nft_arb_count` UInt64
     ),
     `eth_price` Float64,
     `total_gas_used` UInt128,
@@ -32,6 +33,11 @@ CREATE TABLE mev.mev_blocks ON CLUSTER eth_cluster0
     `proposer_mev_reward` Nullable(UInt128),
     `proposer_profit_usd` Nullable(Float64),
     `total_mev_profit_usd` Float64,
+    `quote_pnl` Nested (
+        `quote_asset` String,
+        `profit` Float64,
+        `revenue` Float64
+    ),
     `possible_mev` Nested (
         `tx_hash` String,
         `tx_idx` UInt64,
@@ -0,0 +1,25 @@
+CREATE TABLE mev.nft_arbs ON CLUSTER eth_cluster0
+(
+    `tx_hash` String,
+    `block_number` UInt64,
+    `trades` Nested(
+        `trace_idx` UInt64,
+        `marketplace` String,
+        `buyer` String,
+        `seller` String,
+        `collection` String,
+        `token_ids` Array(UInt256),
+        `payment_token` Tuple(String, String),
+        `price` Tuple(UInt256, UInt256)
+    ),
+    `gas_details` Tuple(
+        `coinbase_transfer` Nullable(UInt128), 
+        `priority_fee` UInt128,
+        `gas_used` UInt128,
+        `effective_gas_price` UInt128
+    ),
+    `run_id` UInt64
+) 
+ENGINE = ReplicatedMergeTree('/clickhouse/eth_cluster0/tables/all/mev/nft_arbs', '{replica}')
+PRIMARY KEY (`block_number`, `tx_hash`)
+ORDER BY (`block_number`, `tx_hash`)
@@ -57,6 +57,21 @@ CREATE TABLE mev.sandwiches ON CLUSTER eth_cluster0
         `gas_used` UInt128,
         `effective_gas_price` UInt128
     ),
+    `refunds` Nested(
+        `tx_hash` String,
+        `payer` String,
+        `recipient` String,
+        `amount` Float64,
+        `usd_value` Float64
+    ),
+    `victim_losses` Nested(
+        `tx_hash` String,
+        `token_in` Tuple(String, String),
+        `extra_amount_in` Float64,
+        `token_out` Tuple(String, String),
+        `lost_amount_out` Float64,
+        `amount_lost_usd` Float64
+    ),
     `run_id` UInt64
 ) 
 ENGINE = ReplicatedMergeTree('/clickhouse/eth_cluster0/tables/all/mev/sandwiches', '{replica}')
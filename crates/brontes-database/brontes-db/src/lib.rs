@@ -6,6 +6,7 @@
 pub mod clickhouse;
 pub mod libmdbx;
 pub mod parquet;
+pub mod sqlite;
 pub use libmdbx::{
     tables::*,
     types::{CompressedTable, IntoTableKey},
@@ -1,12 +1,18 @@
 use std::path::PathBuf;
 
-use brontes_types::BrontesTaskExecutor;
+use brontes_types::{db::traits::LibmdbxReader, BrontesTaskExecutor};
+use eyre::ErrReport;
 use fs_extra::dir::get_dir_content;
 use indicatif::{MultiProgress, ProgressBar, ProgressDrawTarget, ProgressState, ProgressStyle};
 use rayon::iter::*;
 
 use crate::{libmdbx::LibmdbxReadWriter, move_tables_to_partition, *};
 
+/// Merges every partition db in the folder into the final db. Searcher infos
+/// are summed with the ones already in the final db, as partitions that cover
+/// different block ranges each hold the activity of their own range. The
+/// cex-dex inventory of a day is a running ledger, so the day accounted
+/// furthest is kept
 pub fn merge_libmdbx_dbs(
     final_db: LibmdbxReadWriter,
     partition_db_folder: &PathBuf,
@@ -15,24 +21,22 @@ pub fn merge_libmdbx_dbs(
 ) -> eyre::Result<()> {
     let files = get_dir_content(partition_db_folder)?;
     let multi = MultiProgress::default();
-    // we can par this due to the single reader and not have any read locks.
-    let directory_count = files
+    let partitions = files
         .directories
         .iter()
         .filter(|dir_name| *dir_name != partition_db_folder.to_str().unwrap())
-        .count() as u64;
+        .collect::<Vec<_>>();
 
-    let total_progress_bar = total_merge_bar(&multi, directory_count);
+    let total_progress_bar = total_merge_bar(&multi, partitions.len() as u64);
 
     let pool = rayon::ThreadPoolBuilder::default()
         .num_threads(max_merge_tasks)
         .build()?;
 
+    // we can par this due to the single reader and not have any read locks.
     pool.install(|| {
-        files
-            .directories
+        partitions
             .par_iter()
-            .filter(|dir_name| *dir_name != partition_db_folder.to_str().unwrap())
             .filter_map(|path| LibmdbxReadWriter::init_db(path, None, &executor, false).ok())
             .try_for_each(|db| {
                 move_tables_to_partition!(FULL_RANGE db, final_db, Some(multi.clone()),
@@ -44,18 +48,89 @@ pub fn merge_libmdbx_dbs(
                 PoolCreationBlocks,
                 TxTraces,
                 AddressMeta,
-                SearcherEOAs,
-                SearcherContracts,
                 Builder,
                 AddressToProtocolInfo,
                 TokenDecimals,
-                DexPrice
+                DexPrice,
+                BlockAnalyses,
+                ClassifiedBlocks,
+                BlockLvrs,
+                FailedMevAttempts
                 );
                 total_progress_bar.inc(1);
 
                 eyre::Ok(())
             })
-    })
+    })?;
+
+    // read, add and write back one db at a time so that partitions holding the
+    // same searcher or day don't overwrite each other
+    for path in partitions {
+        let Ok(db) = LibmdbxReadWriter::init_db(path, None, &executor, false) else { continue };
+        sum_searcher_infos(&final_db, &db)?;
+        keep_latest_inventories(&final_db, &db)?;
+    }
+
+    Ok(())
+}
+
+/// Reads the final db directly, as the searcher cache isn't updated by the
+/// partition writes
+fn sum_searcher_infos(final_db: &LibmdbxReadWriter, db: &LibmdbxReadWriter) -> eyre::Result<()> {
+    let eoas = db
+        .fetch_all_searcher_eoa_info()?
+        .into_iter()
+        .map(|(eoa, info)| {
+            let mut total = final_db
+                .db
+                .view_db(|tx| tx.get::<SearcherEOAs>(eoa).map_err(ErrReport::from))?
+                .unwrap_or_default();
+            total.accumulate(info);
+            eyre::Ok((eoa, total))
+        })
+        .collect::<eyre::Result<Vec<_>>>()?;
+    final_db.write_partitioned_range_data::<SearcherEOAs, SearcherEOAsData>(eoas)?;
+
+    let contracts = db
+        .fetch_all_searcher_contract_info()?
+        .into_iter()
+        .map(|(contract, info)| {
+            let mut total = final_db
+                .db
+                .view_db(|tx| {
+                    tx.get::<SearcherContracts>(contract)
+                        .map_err(ErrReport::from)
+                })?
+                .unwrap_or_default();
+            total.accumulate(info);
+            eyre::Ok((contract, total))
+        })
+        .collect::<eyre::Result<Vec<_>>>()?;
+    final_db.write_partitioned_range_data::<SearcherContracts, SearcherContractsData>(contracts)?;
+
+    Ok(())
+}
+
+/// Partitions whose ranges end in the same day both hold that day, the one
+/// with the greater `last_block` includes the trades of the other
+fn keep_latest_inventories(
+    final_db: &LibmdbxReadWriter,
+    db: &LibmdbxReadWriter,
+) -> eyre::Result<()> {
+    let mut inventories = vec![];
+    for inventory in db.try_fetch_cex_dex_inventories(None, u64::MAX)? {
+        let current = final_db.db.view_db(|tx| {
+            tx.get::<CexDexInventory>(inventory.day)
+                .map_err(ErrReport::from)
+        })?;
+        if current.is_some_and(|current| current.last_block >= inventory.last_block) {
+            continue
+        }
+        inventories.push((inventory.day, inventory));
+    }
+    final_db.write_partitioned_range_data::<CexDexInventory, CexDexInventoryData>(inventories)?;
+
+    Ok(())
 }
 
 pub fn total_merge_bar(mutli_bar: &MultiProgress, count: u64) -> ProgressBar {
@@ -5,7 +5,12 @@ use std::{
     time::Duration,
 };
 
-use brontes_types::{db::dex::make_filter_key_range, BrontesTaskExecutor};
+use alloy_primitives::Address;
+use brontes_types::{
+    db::{dex::make_filter_key_range, searcher::SearcherInfo, traits::LibmdbxReader},
+    mev::MevType,
+    BrontesTaskExecutor, FastHashMap,
+};
 use futures::FutureExt;
 use indicatif::{MultiProgress, ProgressBar, ProgressDrawTarget, ProgressState, ProgressStyle};
 use libmdbx::libmdbx_writer::InitTables;
@@ -104,7 +109,8 @@ impl LibmdbxPartitioner {
             SearcherContracts,
             Builder,
             AddressToProtocolInfo,
-            TokenDecimals
+            TokenDecimals,
+            CexDexInventory
         );
 
         // because we are just doing read operations. we can do all this in parallel
@@ -119,25 +125,12 @@ impl LibmdbxPartitioner {
                     let db = LibmdbxReadWriter::init_db(path, None, &self.executor, false)?;
                     tracing::info!("database opened");
 
-                    move_tables_to_partition!(
-                        BLOCK_RANGE
-                        self.parent_db,
-                        db,
+                    let r = self.parent_db.write_block_range_partition(
                         *start_block,
                         *end_block,
-                        None,
-                        CexPrice,
-                        CexTrades,
-                        BlockInfo,
-                        MevBlocks,
-                        InitializedState,
-                        PoolCreationBlocks,
-                        TxTraces
+                        &db,
+                        true,
                     );
-                    // manually dex pricing
-                    let r =
-                        self.parent_db
-                            .write_dex_price_range(*start_block, *end_block, &db, None);
                     drop(db);
                     r
                 })
@@ -148,6 +141,165 @@ impl LibmdbxPartitioner {
 }
 
 impl LibmdbxReadWriter {
+    /// Copies all block keyed data for `start_block..end_block` into the
+    /// partition db along with the protocol and token info of the pools that
+    /// were created over the range
+    pub fn write_block_range_partition(
+        &self,
+        start_block: u64,
+        end_block: u64,
+        write_db: &LibmdbxReadWriter,
+        include_traces: bool,
+    ) -> eyre::Result<()> {
+        move_tables_to_partition!(
+            BLOCK_RANGE
+            self,
+            write_db,
+            start_block,
+            end_block,
+            None,
+            CexPrice,
+            CexTrades,
+            BlockInfo,
+            MevBlocks,
+            InitializedState,
+            PoolCreationBlocks,
+            BlockAnalyses,
+            ClassifiedBlocks,
+            BlockLvrs,
+            FailedMevAttempts
+        );
+        if include_traces {
+            move_tables_to_partition!(
+                BLOCK_RANGE
+                self,
+                write_db,
+                start_block,
+                end_block,
+                None,
+                TxTraces
+            );
+        }
+        // manually dex pricing
+        self.write_dex_price_range(start_block, end_block, write_db, None)?;
+
+        let mut protocols = vec![];
+        let mut tokens = FastHashMap::default();
+        for (address, ..) in self
+            .protocols_created_range(start_block, end_block)?
+            .into_values()
+            .flatten()
+        {
+            let Ok(info) = self.get_protocol_details(address) else { continue };
+            for token in info.get_tokens() {
+                if let Ok(token_info) = self.try_fetch_token_info(token) {
+                    tokens.insert(token, token_info.inner);
+                }
+            }
+            protocols.push((address, info));
+        }
+
+        write_db.write_partitioned_range_data::<AddressToProtocolInfo, AddressToProtocolInfoData>(
+            protocols,
+        )?;
+        write_db.write_partitioned_range_data::<TokenDecimals, TokenDecimalsData>(
+            tokens.into_iter().collect(),
+        )?;
+
+        Ok(())
+    }
+
+    /// Writes the searcher activity of `start_block..end_block` into the
+    /// partition db. The searcher infos are rebuilt from the bundles and failed
+    /// attempts of the range, so that the partitions of one db can be summed on
+    /// merge without counting the blocks before the range again. The cex-dex
+    /// inventory of the days closed inside the range is copied over as is
+    pub fn write_range_searcher_activity(
+        &self,
+        start_block: u64,
+        end_block: u64,
+        write_db: &LibmdbxReadWriter,
+    ) -> eyre::Result<()> {
+        let last_block = end_block.saturating_sub(1);
+        let mut eoas: FastHashMap<Address, SearcherInfo> = FastHashMap::default();
+        let mut contracts: FastHashMap<Address, Option<SearcherInfo>> = FastHashMap::default();
+
+        let labels = |info: Option<SearcherInfo>| info.map(|info| info.labels_only());
+
+        for block in self.try_fetch_mev_blocks(Some(start_block), last_block)? {
+            for bundle in block.mev {
+                let header = bundle.header;
+                if header.mev_type == MevType::Unknown || header.mev_type == MevType::SearcherTx {
+                    continue
+                }
+
+                if !eoas.contains_key(&header.eoa) {
+                    let info = labels(self.try_fetch_searcher_eoa_info(header.eoa)?);
+                    eoas.insert(header.eoa, info.unwrap_or_default());
+                }
+                eoas.get_mut(&header.eoa)
+                    .unwrap()
+                    .update_with_bundle(&header);
+
+                if let Some(contract) = header.mev_contract {
+                    if !contracts.contains_key(&contract) {
+                        let info = labels(self.try_fetch_searcher_contract_info(contract)?);
+                        contracts.insert(contract, info);
+                    }
+                    contracts
+                        .get_mut(&contract)
+                        .unwrap()
+                        .get_or_insert_with(Default::default)
+                        .update_with_bundle(&header);
+                }
+            }
+        }
+
+        // same as the run, failed attempts only count towards known contracts
+        for block in self.try_fetch_failed_attempts(Some(start_block), last_block)? {
+            for attempt in block.attempts {
+                if !eoas.contains_key(&attempt.eoa) {
+                    let info = labels(self.try_fetch_searcher_eoa_info(attempt.eoa)?);
+                    eoas.insert(attempt.eoa, info.unwrap_or_default());
+                }
+                eoas.get_mut(&attempt.eoa)
+                    .unwrap()
+                    .update_with_failed_attempt(&attempt);
+
+                if let Some(contract) = attempt.mev_contract {
+                    if !contracts.contains_key(&contract) {
+                        let info = labels(self.try_fetch_searcher_contract_info(contract)?);
+                        contracts.insert(contract, info);
+                    }
+                    if let Some(info) = contracts.get_mut(&contract).unwrap() {
+                        info.update_with_failed_attempt(&attempt);
+                    }
+                }
+            }
+        }
+
+        write_db.write_partitioned_range_data::<SearcherEOAs, SearcherEOAsData>(
+            eoas.into_iter().collect(),
+        )?;
+        write_db.write_partitioned_range_data::<SearcherContracts, SearcherContractsData>(
+            contracts
+                .into_iter()
+                .filter_map(|(address, info)| Some((address, info?)))
+                .collect(),
+        )?;
+
+        let inventories = self
+            .try_fetch_cex_dex_inventories(None, u64::MAX)?
+            .into_iter()
+            .filter(|day| (start_block..end_block).contains(&day.last_block))
+            .map(|day| (day.day, day))
+            .collect();
+        write_db
+            .write_partitioned_range_data::<CexDexInventory, CexDexInventoryData>(inventories)?;
+
+        Ok(())
+    }
+
     pub fn write_partition_range_data<T, D>(
         &self,
         start_block: u64,
@@ -0,0 +1,244 @@
+use std::path::Path;
+
+use alloy_primitives::B256;
+use brontes_libmdbx::RW;
+use brontes_types::{
+    db::{
+        initialized_state::{DATA_NOT_PRESENT_UNKNOWN, TRACE_FLAG},
+        traces::TxTracesInner,
+    },
+    mev::Mev,
+    FastHashSet,
+};
+use indicatif::ProgressBar;
+
+use crate::{
+    libmdbx::{implementation::compressed_wrappers::tx::CompressedLibmdbxTx, Libmdbx},
+    *,
+};
+
+/// Blocks pruned per write transaction
+const PRUNE_BATCH_SIZE: u64 = 1_000;
+
+/// What happens to the traces of the blocks that are pruned
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TracePruneMode {
+    /// remove the traces of the block
+    Drop,
+    /// keep the traces of the transactions that are part of a bundle or
+    /// flagged as possible mev. Blocks that brontes hasn't run over yet are
+    /// left untouched
+    KeepMev,
+}
+
+#[derive(Debug, Default)]
+pub struct TracePruneStats {
+    pub blocks_pruned:  usize,
+    pub traces_removed: usize,
+    /// blocks kept whole as they have no `MevBlocks` entry to filter on
+    pub blocks_skipped: usize,
+}
+
+impl Libmdbx {
+    /// The first and last block with stored traces
+    pub fn trace_range(&self) -> eyre::Result<Option<(u64, u64)>> {
+        self.view_db(|tx| {
+            let mut cur = tx.cursor_read::<TxTraces>()?;
+            let first = cur.first()?.map(|(block, _)| block);
+            let last = cur.last()?.map(|(block, _)| block);
+
+            Ok(first.zip(last))
+        })
+    }
+
+    /// Prunes the traces of every block before `before`, or of every block
+    /// when no bound is set. `MevBlocks` and `DexPrice` aren't touched
+    pub fn prune_traces(
+        &self,
+        before: Option<u64>,
+        mode: TracePruneMode,
+        pb: Option<&ProgressBar>,
+    ) -> eyre::Result<TracePruneStats> {
+        let mut stats = TracePruneStats::default();
+        let Some((first, last)) = self.trace_range()? else { return Ok(stats) };
+        let end = before.unwrap_or(last + 1).min(last + 1);
+
+        let mut start = first;
+        while start < end {
+            let batch_end = (start + PRUNE_BATCH_SIZE).min(end);
+            self.update_db(|tx| {
+                for block in start..batch_end {
+                    let Some(TxTracesInner { traces }) = tx.get::<TxTraces>(block)? else {
+                        continue
+                    };
+                    let traces = traces.unwrap_or_default();
+
+                    match mode {
+                        TracePruneMode::Drop => {
+                            tx.delete::<TxTraces>(block, None)?;
+                            clear_trace_flag(tx, block)?;
+
+                            stats.blocks_pruned += 1;
+                            stats.traces_removed += traces.len();
+                        }
+                        TracePruneMode::KeepMev => {
+                            let Some(mev_block) = tx.get::<MevBlocks>(block)? else {
+                                stats.blocks_skipped += 1;
+                                continue
+                            };
+
+                            let mev_txs = mev_block
+                                .mev
+                                .iter()
+                                .flat_map(|bundle| bundle.data.mev_transaction_hashes())
+                                .chain(
+                                    mev_block
+                                        .block
+                                        .possible_mev
+                                        .0
+                                        .iter()
+                                        .map(|possible| possible.tx_hash),
+                                )
+                                .collect::<FastHashSet<B256>>();
+
+                            let total = traces.len();
+                            let kept = traces
+                                .into_iter()
+                                .filter(|trace| mev_txs.contains(&trace.tx_hash))
+                                .collect::<Vec<_>>();
+                            if kept.len() == total {
+                                continue
+                            }
+
+                            stats.blocks_pruned += 1;
+                            stats.traces_removed += total - kept.len();
+                            tx.put::<TxTraces>(block, TxTracesInner::new(Some(kept)))?;
+                            clear_trace_flag(tx, block)?;
+                        }
+                    }
+                }
+
+                eyre::Ok(())
+            })??;
+
+            pb.inspect(|pb| pb.inc(batch_end - start));
+            start = batch_end;
+        }
+
+        Ok(stats)
+    }
+
+    /// Writes a compacted copy of the db to the `dest` file so the space freed
+    /// by pruning is given back to the file system
+    pub fn write_compacted_copy(&self, dest: &Path) -> eyre::Result<()> {
+        self.copy_compacted(dest)
+    }
+}
+
+/// Marks the traces of the block as not present so that a later run over the
+/// block traces it again instead of running on the pruned traces
+fn clear_trace_flag(tx: &CompressedLibmdbxTx<RW>, block: u64) -> eyre::Result<()> {
+    let mut state = tx.get::<InitializedState>(block)?.unwrap_or_default();
+    state.set(TRACE_FLAG, DATA_NOT_PRESENT_UNKNOWN);
+    tx.put::<InitializedState>(block, state)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::TxHash;
+    use brontes_types::{
+        db::{
+            dex::{make_key, DexQuoteWithIndex},
+            initialized_state::{InitializedStateMeta, DATA_PRESENT, DEX_PRICE_FLAG, META_FLAG},
+            mev_block::MevBlockWithClassified,
+        },
+        mev::{PossibleMev, PossibleMevCollection},
+        structured_trace::TxTrace,
+    };
+    use reth_db::DatabaseError;
+
+    use super::*;
+
+    const BLOCK: u64 = 18_000_000;
+
+    fn trace(tx_index: u64) -> TxTrace {
+        TxTrace {
+            block_number: BLOCK,
+            tx_hash: TxHash::with_last_byte(tx_index as u8),
+            tx_index,
+            is_success: true,
+            ..Default::default()
+        }
+    }
+
+    /// A run over `BLOCK` that flagged its second transaction as possible mev
+    fn mev_block() -> MevBlockWithClassified {
+        let mut mev_block = MevBlockWithClassified::default();
+        mev_block.block.possible_mev = PossibleMevCollection(vec![PossibleMev {
+            tx_hash: TxHash::with_last_byte(1),
+            tx_idx: 1,
+            ..Default::default()
+        }]);
+
+        mev_block
+    }
+
+    fn dex_quote() -> DexQuoteWithIndex {
+        DexQuoteWithIndex { tx_idx: 0, quote: vec![] }
+    }
+
+    fn write_block(tx: &CompressedLibmdbxTx<RW>) -> Result<(), DatabaseError> {
+        tx.put::<TxTraces>(BLOCK, TxTracesInner::new(Some((0..3).map(trace).collect())))?;
+        tx.put::<MevBlocks>(BLOCK, mev_block())?;
+        tx.put::<DexPrice>(make_key(BLOCK, 0), dex_quote())?;
+        tx.put::<InitializedState>(
+            BLOCK,
+            InitializedStateMeta::new(DATA_PRESENT, DATA_PRESENT, 0, 0, DATA_PRESENT),
+        )
+    }
+
+    /// Prunes a db holding `BLOCK` and returns its traces and state afterwards
+    fn prune(
+        mode: TracePruneMode,
+    ) -> (TracePruneStats, Option<TxTracesInner>, InitializedStateMeta) {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Libmdbx::init_db(dir.path(), None).unwrap();
+        db.update_db(write_block).unwrap().unwrap();
+
+        let stats = db.prune_traces(None, mode, None).unwrap();
+
+        db.view_db(|tx| {
+            // pruning only ever touches the traces
+            assert_eq!(tx.get::<MevBlocks>(BLOCK)?, Some(mev_block()));
+            assert_eq!(tx.get::<DexPrice>(make_key(BLOCK, 0))?, Some(dex_quote()));
+
+            let state = tx.get::<InitializedState>(BLOCK)?.unwrap();
+            assert!(state.is_initialized(DEX_PRICE_FLAG));
+            assert!(state.is_initialized(META_FLAG));
+
+            eyre::Ok((stats, tx.get::<TxTraces>(BLOCK)?, state))
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_prune_drop() {
+        let (stats, traces, state) = prune(TracePruneMode::Drop);
+
+        assert_eq!((stats.blocks_pruned, stats.traces_removed), (1, 3));
+        assert!(traces.is_none());
+        assert!(!state.is_initialized(TRACE_FLAG));
+    }
+
+    #[test]
+    fn test_prune_keep_mev() {
+        let (stats, traces, state) = prune(TracePruneMode::KeepMev);
+
+        assert_eq!((stats.blocks_pruned, stats.traces_removed), (1, 2));
+        assert_eq!(traces.and_then(|traces| traces.traces), Some(vec![trace(1)]));
+        // a rerun over the block has to trace it again
+        assert!(!state.is_initialized(TRACE_FLAG));
+    }
+}
@@ -0,0 +1,425 @@
+use std::fmt::{self, Display};
+
+use alloy_primitives::{Address, TxHash};
+use brontes_libmdbx::RO;
+use brontes_types::{
+    db::{
+        address_to_protocol_info::ProtocolInfo, dex::make_filter_key_range,
+        initialized_state::DEX_PRICE_FLAG, metadata::BlockMetadataInner, traces::TxTracesInner,
+    },
+    structured_trace::TxTrace,
+    FastHashMap, FastHashSet,
+};
+use indicatif::ProgressBar;
+use reth_db::table::{Decode, Decompress, Encode, Table};
+
+use crate::{
+    libmdbx::{
+        implementation::compressed_wrappers::tx::CompressedLibmdbxTx, types::CompressedTable,
+        LibmdbxReadWriter,
+    },
+    *,
+};
+
+/// Integrity problems found over a block range of the db
+#[derive(Debug, Default)]
+pub struct VerifyReport {
+    /// blocks without an entry per block keyed table
+    pub missing:                FastHashMap<Tables, Vec<u64>>,
+    /// blocks whose entry exists but has a broken zstd frame or is too short
+    /// for the stored value
+    pub undecodable:            FastHashMap<Tables, Vec<u64>>,
+    pub inconsistent_traces:    Vec<(u64, TraceInconsistency)>,
+    pub undecodable_protocols:  usize,
+    /// (pool, token, pool init block) for every pool token without decimals
+    pub missing_token_decimals: Vec<(Address, Address, u64)>,
+}
+
+impl VerifyReport {
+    pub fn is_clean(&self) -> bool {
+        self.missing.is_empty()
+            && self.undecodable.is_empty()
+            && self.inconsistent_traces.is_empty()
+            && self.undecodable_protocols == 0
+            && self.missing_token_decimals.is_empty()
+    }
+
+    /// All blocks of the table that are either missing or fail to decode
+    pub fn broken_blocks(&self, table: Tables) -> Vec<u64> {
+        let mut blocks = self
+            .missing
+            .get(&table)
+            .into_iter()
+            .chain(self.undecodable.get(&table))
+            .flatten()
+            .copied()
+            .collect::<Vec<_>>();
+        if table == Tables::TxTraces {
+            blocks.extend(self.inconsistent_traces.iter().map(|(block, _)| *block));
+        }
+        blocks.sort_unstable();
+        blocks.dedup();
+
+        blocks
+    }
+
+    fn missing(&mut self, table: Tables, block: u64) {
+        self.missing.entry(table).or_default().push(block);
+    }
+
+    fn undecodable(&mut self, table: Tables, block: u64) {
+        self.undecodable.entry(table).or_default().push(block);
+    }
+
+    fn add(&mut self, table: Tables, block: u64, row: Row) {
+        match row {
+            Row::Missing => self.missing(table, block),
+            Row::Corrupt => self.undecodable(table, block),
+            Row::Intact => {}
+        }
+    }
+}
+
+/// Why the stored traces of a block don't line up with the block
+#[derive(Debug, Clone, PartialEq)]
+pub enum TraceInconsistency {
+    /// the block has a different number of transactions than were traced
+    TxCountMismatch { traced: usize, expected: usize },
+    /// a trace is stored under a different block than it was traced for
+    WrongBlock(u64),
+    /// a private tx listed in the `BlockInfo` of the block isn't traced
+    MissingPrivateTx(TxHash),
+}
+
+impl Display for TraceInconsistency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TxCountMismatch { traced, expected } => {
+                write!(f, "{traced} traces but the block has {expected} transactions")
+            }
+            Self::WrongBlock(block) => write!(f, "contains a trace of block {block}"),
+            Self::MissingPrivateTx(hash) => write!(f, "private tx {hash:?} is not traced"),
+        }
+    }
+}
+
+impl LibmdbxReadWriter {
+    /// Checks that every block of `start_block..=end_block` has its block
+    /// keyed data, that the stored rows look intact and that the traces are
+    /// consistent with the block. `tx_counts` holds the number of transactions
+    /// of the blocks according to the node, blocks without a count skip that
+    /// check. Also checks that the tokens of every stored protocol have their
+    /// decimals.
+    ///
+    /// The stored types don't derive `CheckBytes`, so the archived value of a
+    /// row can't be validated and decoding a corrupt row is undefined
+    /// behaviour. Rows are therefore only checked on their raw bytes: a row
+    /// counts as undecodable when its zstd frame is broken or it's too short
+    /// for the archived value, which is what truncated or overwritten rows look
+    /// like. Corruption that keeps a valid zstd frame of the right length isn't
+    /// detected. Only the traces, block info and protocol rows that pass these
+    /// checks are decoded, for the consistency checks
+    pub fn verify_block_range(
+        &self,
+        start_block: u64,
+        end_block: u64,
+        tx_counts: &FastHashMap<u64, usize>,
+        pb: Option<&ProgressBar>,
+    ) -> eyre::Result<VerifyReport> {
+        let tx = self.db.no_timeout_ro_tx()?;
+        let mut report = VerifyReport::default();
+
+        for block in start_block..=end_block {
+            let block_info = match read_row::<BlockInfo>(&tx, block)? {
+                Row::Intact => tx.get::<BlockInfo>(block)?,
+                row => {
+                    report.add(Tables::BlockInfo, block, row);
+                    None
+                }
+            };
+
+            match read_row::<TxTraces>(&tx, block)? {
+                Row::Intact => match tx.get::<TxTraces>(block)? {
+                    Some(TxTracesInner { traces: Some(traces) }) => {
+                        let expected_txs = tx_counts.get(&block).copied();
+                        for inconsistency in
+                            check_traces(block, &traces, expected_txs, block_info.as_ref())
+                        {
+                            report.inconsistent_traces.push((block, inconsistency));
+                        }
+                    }
+                    _ => report.missing(Tables::TxTraces, block),
+                },
+                row => report.add(Tables::TxTraces, block, row),
+            }
+
+            let row = read_row::<CexTrades>(&tx, block)?;
+            report.add(Tables::CexTrades, block, row);
+            let row = read_row::<MevBlocks>(&tx, block)?;
+            report.add(Tables::MevBlocks, block, row);
+
+            self.verify_dex_prices(&tx, block, &mut report)?;
+            pb.inspect(|pb| pb.inc(1));
+        }
+
+        self.verify_protocol_tokens(&tx, &mut report)?;
+
+        Ok(report)
+    }
+
+    /// Blocks without swaps have no dex prices, so a block only counts as
+    /// missing when there are no prices and it was never marked as priced
+    fn verify_dex_prices(
+        &self,
+        tx: &CompressedLibmdbxTx<RO>,
+        block: u64,
+        report: &mut VerifyReport,
+    ) -> eyre::Result<()> {
+        let (start_key, end_key) = make_filter_key_range(block);
+        let rows = tx
+            .cursor_read::<DexPrice>()?
+            .raw_rows_in(start_key.encode().as_ref(), Some(end_key.encode().as_ref()))?;
+
+        if !rows.iter().all(|(_, value)| is_intact::<DexPrice>(value)) {
+            report.undecodable(Tables::DexPrice, block);
+        } else if rows.is_empty() {
+            let priced = matches!(read_row::<InitializedState>(tx, block)?, Row::Intact)
+                && tx
+                    .get::<InitializedState>(block)?
+                    .is_some_and(|state| state.is_initialized(DEX_PRICE_FLAG));
+            if !priced {
+                report.missing(Tables::DexPrice, block);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn verify_protocol_tokens(
+        &self,
+        tx: &CompressedLibmdbxTx<RO>,
+        report: &mut VerifyReport,
+    ) -> eyre::Result<()> {
+        let mut checked = FastHashMap::<Address, bool>::default();
+        for (key, value) in tx
+            .cursor_read::<AddressToProtocolInfo>()?
+            .raw_rows_in(&[], None)?
+        {
+            let decoded = is_intact::<AddressToProtocolInfo>(&value)
+                .then(|| {
+                    let pool = Address::decode(key).ok()?;
+                    let info: ProtocolInfo =
+                        <AddressToProtocolInfo as Table>::Value::decompress(value)
+                            .ok()?
+                            .into();
+                    Some((pool, info))
+                })
+                .flatten();
+            let Some((pool, info)) = decoded else {
+                report.undecodable_protocols += 1;
+                continue
+            };
+
+            for token in info.get_tokens() {
+                let has_decimals = *checked.entry(token).or_insert_with(|| {
+                    matches!(read_row::<TokenDecimals>(tx, token), Ok(Row::Intact))
+                });
+                if !has_decimals {
+                    report
+                        .missing_token_decimals
+                        .push((pool, token, info.init_block));
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// The state of a stored row, judged on its raw bytes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Row {
+    Missing,
+    Corrupt,
+    Intact,
+}
+
+fn read_row<T>(tx: &CompressedLibmdbxTx<RO>, key: T::Key) -> eyre::Result<Row>
+where
+    T: CompressedTable,
+    T::Value: From<T::DecompressedValue> + Into<T::DecompressedValue> + rkyv::Archive,
+{
+    Ok(match tx.get_bytes::<T>(key)? {
+        None => Row::Missing,
+        Some(bytes) if is_intact::<T>(&bytes) => Row::Intact,
+        Some(_) => Row::Corrupt,
+    })
+}
+
+/// Whether the zstd frame of the row decodes and is long enough for the
+/// archived value, the most that can be checked without `CheckBytes`
+fn is_intact<T>(bytes: &[u8]) -> bool
+where
+    T: CompressedTable,
+    T::Value: From<T::DecompressedValue> + Into<T::DecompressedValue> + rkyv::Archive,
+{
+    zstd::decode_all(bytes)
+        .is_ok_and(|value| value.len() >= std::mem::size_of::<rkyv::Archived<T::Value>>())
+}
+
+fn check_traces(
+    block: u64,
+    traces: &[TxTrace],
+    expected_txs: Option<usize>,
+    block_info: Option<&BlockMetadataInner>,
+) -> Vec<TraceInconsistency> {
+    let mut res = vec![];
+
+    if let Some(expected) = expected_txs.filter(|expected| *expected != traces.len()) {
+        res.push(TraceInconsistency::TxCountMismatch { traced: traces.len(), expected });
+    }
+
+    if let Some(trace) = traces.iter().find(|trace| trace.block_number != block) {
+        res.push(TraceInconsistency::WrongBlock(trace.block_number));
+    }
+
+    if let Some(info) = block_info {
+        let hashes = traces
+            .iter()
+            .map(|trace| trace.tx_hash)
+            .collect::<FastHashSet<_>>();
+        res.extend(
+            info.private_flow
+                .iter()
+                .filter(|hash| !hashes.contains(*hash))
+                .map(|hash| TraceInconsistency::MissingPrivateTx(*hash)),
+        );
+    }
+
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use brontes_libmdbx::RW;
+    use brontes_types::db::{
+        cex::trades::CexTradeMap,
+        initialized_state::{InitializedStateMeta, DATA_PRESENT},
+        mev_block::MevBlockWithClassified,
+        traces::TxTracesInnerRedefined,
+    };
+    use reth_db::{
+        table::{Compress, Encode},
+        DatabaseError,
+    };
+
+    use super::*;
+    use crate::libmdbx::Libmdbx;
+
+    const BLOCK: u64 = 18_000_000;
+
+    fn trace(block: u64, tx_index: u64) -> TxTrace {
+        TxTrace {
+            block_number: block,
+            tx_hash: TxHash::with_last_byte(tx_index as u8),
+            tx_index,
+            is_success: true,
+            ..Default::default()
+        }
+    }
+
+    /// Writes the block keyed data of a block without swaps that has a trace
+    /// for each of its `txs` transactions
+    fn write_block(
+        tx: &CompressedLibmdbxTx<RW>,
+        block: u64,
+        txs: u64,
+    ) -> Result<(), DatabaseError> {
+        let traces = (0..txs).map(|tx_index| trace(block, tx_index)).collect();
+
+        tx.put::<BlockInfo>(block, BlockMetadataInner::default())?;
+        tx.put::<TxTraces>(block, TxTracesInner { traces: Some(traces) })?;
+        tx.put::<CexTrades>(block, CexTradeMap::default())?;
+        tx.put::<MevBlocks>(block, MevBlockWithClassified::default())?;
+        tx.put::<InitializedState>(block, InitializedStateMeta::new(DATA_PRESENT, 0, 0, 0, 0))
+    }
+
+    fn verify(
+        write: impl FnOnce(&CompressedLibmdbxTx<RW>) -> Result<(), DatabaseError>,
+        tx_counts: &[(u64, usize)],
+    ) -> VerifyReport {
+        let dir = tempfile::tempdir().unwrap();
+        Libmdbx::init_db(dir.path(), None)
+            .unwrap()
+            .update_db(write)
+            .unwrap()
+            .unwrap();
+
+        let db = LibmdbxReadWriter::init_db_read_only(dir.path(), None).unwrap();
+        db.verify_block_range(BLOCK, BLOCK, &tx_counts.iter().copied().collect(), None)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_complete_block() {
+        let report = verify(|tx| write_block(tx, BLOCK, 3), &[(BLOCK, 3)]);
+        assert!(report.is_clean(), "{report:?}");
+    }
+
+    #[test]
+    fn test_missing_block() {
+        let report = verify(|_| Ok(()), &[(BLOCK, 3)]);
+
+        for table in [Tables::BlockInfo, Tables::TxTraces, Tables::CexTrades, Tables::MevBlocks] {
+            assert_eq!(report.broken_blocks(table), vec![BLOCK], "{table}");
+        }
+        assert_eq!(report.broken_blocks(Tables::DexPrice), vec![BLOCK]);
+    }
+
+    #[test]
+    fn test_truncated_block() {
+        // the last transaction of the block was never traced
+        let report = verify(|tx| write_block(tx, BLOCK, 2), &[(BLOCK, 3)]);
+
+        assert_eq!(
+            report.inconsistent_traces,
+            vec![(BLOCK, TraceInconsistency::TxCountMismatch { traced: 2, expected: 3 })]
+        );
+        assert_eq!(report.broken_blocks(Tables::TxTraces), vec![BLOCK]);
+        assert!(report.broken_blocks(Tables::BlockInfo).is_empty());
+
+        // without the tx count from the node the traces can't be checked
+        let report = verify(|tx| write_block(tx, BLOCK, 2), &[]);
+        assert!(report.is_clean(), "{report:?}");
+    }
+
+    #[test]
+    fn test_corrupt_rows() {
+        let report = verify(
+            |tx| {
+                write_block(tx, BLOCK, 3)?;
+
+                // a row that was cut off while being written
+                let traces = TxTracesInner { traces: Some(vec![trace(BLOCK, 0)]) };
+                let mut compressed = TxTracesInnerRedefined::from(traces).compress();
+                compressed.truncate(compressed.len() / 2);
+                tx.put_bytes::<TxTraces>(&BLOCK.encode(), compressed)?;
+
+                // a row that was overwritten with garbage
+                tx.put_bytes::<MevBlocks>(&BLOCK.encode(), vec![0xde, 0xad, 0xbe, 0xef])?;
+
+                // a valid zstd frame that is too short for the stored value
+                let too_short = zstd::encode_all(&[0u8; 2][..], 0).unwrap();
+                tx.put_bytes::<BlockInfo>(&BLOCK.encode(), too_short)
+            },
+            &[(BLOCK, 3)],
+        );
+
+        for table in [Tables::TxTraces, Tables::MevBlocks, Tables::BlockInfo] {
+            assert_eq!(report.undecodable.get(&table), Some(&vec![BLOCK]), "{table}");
+        }
+        assert!(report.missing.is_empty(), "{report:?}");
+        assert!(report.inconsistent_traces.is_empty());
+        assert!(report.broken_blocks(Tables::CexTrades).is_empty());
+    }
+}
@@ -4,4 +4,10 @@ pub use libmdbx_merger::*;
 pub mod libmdbx_partitioning;
 pub use libmdbx_partitioning::*;
 
+pub mod libmdbx_pruning;
+pub use libmdbx_pruning::*;
+
 pub mod rclone_wrapper;
+
+pub mod libmdbx_verifier;
+pub use libmdbx_verifier::*;
@@ -33,6 +33,15 @@ where
     pub fn new(inner: LibmdbxCursor<T, K>) -> Self {
         Self(inner)
     }
+
+    /// See [`LibmdbxCursor::raw_rows_in`]
+    pub fn raw_rows_in(
+        &mut self,
+        start: &[u8],
+        end: Option<&[u8]>,
+    ) -> Result<Vec<(Vec<u8>, Vec<u8>)>, DatabaseError> {
+        self.0.raw_rows_in(start, end)
+    }
 }
 
 impl<T, K> CompressedCursor<T, K>
@@ -1,3 +1,5 @@
+use std::str::FromStr;
+
 use brontes_libmdbx::{ffi::DBI, TransactionKind, RO, RW};
 use reth_db::{
     table::DupSort,
@@ -6,7 +8,9 @@ use reth_db::{
 };
 
 use super::cursor::CompressedCursor;
-use crate::libmdbx::{implementation::native::tx::LibmdbxTx, types::CompressedTable, DatabaseEnv};
+use crate::libmdbx::{
+    implementation::native::tx::LibmdbxTx, tables::Tables, types::CompressedTable, DatabaseEnv,
+};
 pub struct CompressedLibmdbxTx<K: TransactionKind>(pub(crate) LibmdbxTx<K>);
 
 impl<K: TransactionKind> CompressedLibmdbxTx<K> {
@@ -36,6 +40,15 @@ impl<K: TransactionKind> CompressedLibmdbxTx<K> {
         self.0.get::<T>(key).map(|opt| opt.map(Into::into))
     }
 
+    /// The stored bytes of the key, without decompressing or decoding them
+    pub fn get_bytes<T>(&self, key: T::Key) -> Result<Option<Vec<u8>>, DatabaseError>
+    where
+        T: CompressedTable,
+        T::Value: From<T::DecompressedValue> + Into<T::DecompressedValue>,
+    {
+        self.0.get_bytes::<T>(key)
+    }
+
     pub fn commit(self) -> Result<bool, DatabaseError> {
         self.0.commit()
     }
@@ -60,6 +73,17 @@ impl<K: TransactionKind> CompressedLibmdbxTx<K> {
         Ok(CompressedCursor::new(self.0.new_cursor()?))
     }
 
+    /// Whether the table has been created, see [`LibmdbxTx::table_exists`]
+    pub fn table_exists<T>(&self) -> Result<bool, DatabaseError>
+    where
+        T: CompressedTable,
+        T::Value: From<T::DecompressedValue> + Into<T::DecompressedValue>,
+    {
+        self.0.table_exists(
+            &Tables::from_str(T::NAME).expect("Requested table should be part of `Tables`."),
+        )
+    }
+
     pub fn entries<T>(&self) -> Result<usize, DatabaseError>
     where
         T: CompressedTable,
@@ -44,6 +44,26 @@ impl<T: Table, K: TransactionKind> LibmdbxCursor<T, K> {
     pub fn seek_raw(&mut self, key: &[u8]) -> PairResult<T> {
         decode!(self.inner.set_key(key))
     }
+
+    /// The stored `(key, value)` bytes of the rows from `start` up to and
+    /// including `end`, without decoding them
+    pub fn raw_rows_in(
+        &mut self,
+        start: &[u8],
+        end: Option<&[u8]>,
+    ) -> Result<Vec<(Vec<u8>, Vec<u8>)>, DatabaseError> {
+        let mut rows = vec![];
+        let mut next = self.inner.set_range::<Vec<u8>, Vec<u8>>(start);
+        while let Some((key, value)) = next.map_err(|e| DatabaseError::Read(e.into()))? {
+            if end.is_some_and(|end| key.as_slice() > end) {
+                break
+            }
+            rows.push((key, value));
+            next = self.inner.next::<Vec<u8>, Vec<u8>>();
+        }
+
+        Ok(rows)
+    }
 }
 
 /// Takes `(key, value)` from the database and decodes it appropriately.
@@ -84,20 +84,64 @@ impl LibmdbxTx<RW> {
                 .into()
             })
     }
+
+    /// Replaces every value of the table with the output of `f`, which is
+    /// passed the raw stored bytes. Returns the amount of rows rewritten
+    pub(crate) fn rewrite_values(
+        &self,
+        table: &Tables,
+        mut f: impl FnMut(&[u8]) -> eyre::Result<Vec<u8>>,
+    ) -> eyre::Result<usize> {
+        let mut cursor = self
+            .inner
+            .cursor_with_dbi(self.get_table_dbi(table)?)
+            .map_err(|e| DatabaseError::InitCursor(e.into()))?;
+
+        let mut rows = 0;
+        let mut entry = cursor
+            .first::<Vec<u8>, Vec<u8>>()
+            .map_err(|e| DatabaseError::Read(e.into()))?;
+        while let Some((key, value)) = entry {
+            let value =
+                f(&value).map_err(|e| eyre::eyre!("failed to migrate {table} row {key:?}: {e}"))?;
+            cursor
+                .put(&key, &value, WriteFlags::CURRENT)
+                .map_err(|e| eyre::eyre!("failed to write migrated {table} row {key:?}: {e}"))?;
+
+            rows += 1;
+            entry = cursor
+                .next::<Vec<u8>, Vec<u8>>()
+                .map_err(|e| DatabaseError::Read(e.into()))?;
+        }
+
+        Ok(rows)
+    }
 }
 
 impl<K: TransactionKind> LibmdbxTx<K> {
+    /// The stored bytes of the key, without decoding them
+    pub fn get_bytes<T: Table>(&self, key: T::Key) -> Result<Option<Vec<u8>>, DatabaseError> {
+        self.inner
+            .get(self.get_dbi::<T>()?, key.encode().as_ref())
+            .map_err(|e| DatabaseError::Read(e.into()))
+    }
+
     /// Gets a table database handle if it exists, otherwise creates it.
     pub(crate) fn get_dbi<T: Table>(&self) -> Result<DBI, DatabaseError> {
-        let mut handles = self.db_handles.write();
-
         let table = Tables::from_str(T::NAME).expect("Requested table should be part of `Tables`.");
 
-        let dbi_handle = handles.get_mut(table as usize).expect("should exist");
+        self.get_table_dbi(&table)
+    }
+
+    /// Gets the database handle of a table that is only known at runtime
+    pub(crate) fn get_table_dbi(&self, table: &Tables) -> Result<DBI, DatabaseError> {
+        let mut handles = self.db_handles.write();
+
+        let dbi_handle = handles.get_mut(*table as usize).expect("should exist");
         if dbi_handle.is_none() {
             *dbi_handle = Some(
                 self.inner
-                    .open_db(Some(T::NAME))
+                    .open_db(Some(table.name()))
                     .map_err(|e| DatabaseError::InitCursor(e.into()))?
                     .dbi(),
             );
@@ -106,6 +150,26 @@ impl<K: TransactionKind> LibmdbxTx<K> {
         Ok(dbi_handle.expect("is some; qed"))
     }
 
+    /// Whether the table has been created. Dbs written by older versions of
+    /// brontes are missing the tables added since, which can't be created
+    /// when the db is opened read only
+    pub(crate) fn table_exists(&self, table: &Tables) -> Result<bool, DatabaseError> {
+        match self.inner.open_db(Some(table.name())) {
+            Ok(_) => Ok(true),
+            Err(brontes_libmdbx::Error::NotFound) => Ok(false),
+            Err(e) => Err(DatabaseError::InitCursor(e.into())),
+        }
+    }
+
+    /// Returns number of entries in a table that is only known at runtime
+    pub(crate) fn table_entries(&self, table: &Tables) -> Result<usize, DatabaseError> {
+        Ok(self
+            .inner
+            .db_stat_with_dbi(self.get_table_dbi(table)?)
+            .map_err(|e| DatabaseError::Stats(e.into()))?
+            .entries())
+    }
+
     /// Create db Cursor
     pub(crate) fn new_cursor<T: Table>(&self) -> Result<LibmdbxCursor<T, K>, DatabaseError> {
         let inner = self
@@ -8,20 +8,25 @@ use brontes_types::{
     db::{
         address_metadata::AddressMetadata,
         address_to_protocol_info::ProtocolInfo,
+        block_analysis::BlockAnalysis,
         builder::BuilderInfo,
         cex::{quotes::CexPriceMap, trades::CexTradeMap},
-        dex::{make_filter_key_range, DexPrices, DexQuotes},
+        cex_dex_inventory::DailyCexDexInventory,
+        dex::{decompose_key, make_filter_key_range, DexPrices, DexQuoteWithIndex, DexQuotes},
         initialized_state::{
             InitializedStateMeta, CEX_QUOTES_FLAG, CEX_TRADES_FLAG, DATA_NOT_PRESENT_NOT_AVAILABLE,
             DATA_PRESENT, DEX_PRICE_FLAG, META_FLAG,
         },
+        lvr::BlockLvr,
         metadata::{BlockMetadata, BlockMetadataInner, Metadata},
         mev_block::MevBlockWithClassified,
+        normalized_actions::ClassifiedBlock,
+        run_checkpoint::RunCheckpoint,
         searcher::SearcherInfo,
         token_info::{TokenInfo, TokenInfoWithAddress},
         traits::{DBWriter, LibmdbxReader},
     },
-    mev::{Bundle, MevBlock},
+    mev::{BlockFailedAttempts, Bundle, MevBlock},
     normalized_actions::Action,
     pair::Pair,
     structured_trace::TxTrace,
@@ -94,11 +99,13 @@ pub trait LibmdbxInit: LibmdbxReader + DBWriter {
 
 #[derive(Clone)]
 pub struct LibmdbxReadWriter {
-    pub db:  Arc<Libmdbx>,
-    pub tx:  UnboundedSender<StampedWriterMessage>,
-    metrics: Option<LibmdbxMetrics>,
+    pub db:        Arc<Libmdbx>,
+    pub tx:        UnboundedSender<StampedWriterMessage>,
+    metrics:       Option<LibmdbxMetrics>,
     // 100 shards for now, might change in future
-    cache:   ReadWriteCache,
+    cache:         ReadWriteCache,
+    /// whether the classified trees passed to `insert_tree` are stored
+    persist_trees: bool,
 }
 
 impl LibmdbxReadWriter {
@@ -124,6 +131,34 @@ impl LibmdbxReadWriter {
             tx,
             metrics: metrics.then(LibmdbxMetrics::default),
             cache: ReadWriteCache::new(memory_per_table_mb, metrics),
+            persist_trees: false,
+        })
+    }
+
+    /// Stores the pruned classified tree of every processed block in the
+    /// `ClassifiedBlocks` table. Off by default as the trees take up a lot of
+    /// space
+    pub fn with_persisted_trees(mut self, persist_trees: bool) -> Self {
+        self.persist_trees = persist_trees;
+        self
+    }
+
+    /// Opens the db read only without a writer task, any write errors. Reads
+    /// aren't cached as another process may be writing to the db
+    pub fn init_db_read_only<P: AsRef<Path>>(
+        path: P,
+        log_level: Option<LogLevel>,
+    ) -> eyre::Result<Self> {
+        // the receiver is dropped so writes fail instead of being queued
+        let (tx, _) = unbounded_channel();
+        let db = Arc::new(Libmdbx::init_db_read_only(path, log_level)?);
+
+        Ok(Self {
+            db,
+            tx,
+            metrics: None,
+            cache: ReadWriteCache::new(0, false),
+            persist_trees: false,
         })
     }
 
@@ -138,7 +173,53 @@ impl LibmdbxReadWriter {
         let writer = LibmdbxWriter::new(db.clone(), yapper, false);
         writer.run_no_shutdown();
 
-        Ok(Self { db, tx, metrics: None, cache: ReadWriteCache::new(memory_per_table_mb, false) })
+        Ok(Self {
+            db,
+            tx,
+            metrics: None,
+            cache: ReadWriteCache::new(memory_per_table_mb, false),
+            persist_trees: false,
+        })
+    }
+
+    /// The first and last block brontes has written a `MevBlocks` entry for
+    pub fn mev_block_range(&self) -> eyre::Result<Option<(u64, u64)>> {
+        self.db.view_db(|tx| {
+            if !tx.table_exists::<MevBlocks>()? {
+                return Ok(None)
+            }
+            let mut cur = tx.cursor_read::<MevBlocks>()?;
+            let first = cur.first()?.map(|(block, _)| block);
+            let last = cur.last()?.map(|(block, _)| block);
+
+            Ok(first.zip(last))
+        })
+    }
+
+    /// Reads a single row without going through the cache. Returns `None` if
+    /// the row is missing, or the whole table is because the db was written by
+    /// an older version of brontes and opened read only
+    pub fn get_if_exists<T>(&self, key: T::Key) -> eyre::Result<Option<T::DecompressedValue>>
+    where
+        T: CompressedTable,
+        T::Value: From<T::DecompressedValue> + Into<T::DecompressedValue>,
+    {
+        self.db.view_db(|tx| {
+            if !tx.table_exists::<T>()? {
+                return Ok(None)
+            }
+
+            Ok(tx.get::<T>(key)?)
+        })
+    }
+
+    /// Whether `T` exists in the db. Tables added after the db was written
+    /// are only created when it's opened read write
+    fn has_table<T: CompressedTable>(&self) -> eyre::Result<bool>
+    where
+        T::Value: From<T::DecompressedValue> + Into<T::DecompressedValue>,
+    {
+        self.db.view_db(|tx| Ok(tx.table_exists::<T>()?))
     }
 }
 
@@ -879,6 +960,264 @@ impl LibmdbxReader for LibmdbxReadWriter {
         )
     }
 
+    fn try_fetch_block_analysis(&self, block_num: u64) -> eyre::Result<Option<BlockAnalysis>> {
+        self.get_if_exists::<BlockAnalyses>(block_num)
+    }
+
+    fn try_fetch_block_analyses(
+        &self,
+        start_block: Option<u64>,
+        end_block: u64,
+    ) -> eyre::Result<Vec<BlockAnalysis>> {
+        if !self.has_table::<BlockAnalyses>()? {
+            return Ok(vec![])
+        }
+
+        self.db.export_db(
+            start_block,
+            |start_key, tx| {
+                let mut cur = tx.cursor_read::<BlockAnalyses>()?;
+                if let Some(key) = start_key {
+                    let _ = cur.seek(key);
+                } else {
+                    // move to first entry and make sure .next() is first
+                    let _ = cur.first();
+                    let _ = cur.prev();
+                }
+                Ok(cur)
+            },
+            |cursor| {
+                Ok(cursor
+                    .next()
+                    .map(|inner| inner.filter(|f| f.0 <= end_block).map(|i| i.1))?)
+            },
+        )
+    }
+
+    fn try_fetch_classified_block(&self, block_num: u64) -> eyre::Result<Option<ClassifiedBlock>> {
+        self.get_if_exists::<ClassifiedBlocks>(block_num)
+    }
+
+    fn try_fetch_classified_blocks(
+        &self,
+        start_block: Option<u64>,
+        end_block: u64,
+    ) -> eyre::Result<Vec<ClassifiedBlock>> {
+        if !self.has_table::<ClassifiedBlocks>()? {
+            return Ok(vec![])
+        }
+
+        self.db.export_db(
+            start_block,
+            |start_key, tx| {
+                let mut cur = tx.cursor_read::<ClassifiedBlocks>()?;
+                if let Some(key) = start_key {
+                    let _ = cur.seek(key);
+                } else {
+                    // move to first entry and make sure .next() is first
+                    let _ = cur.first();
+                    let _ = cur.prev();
+                }
+                Ok(cur)
+            },
+            |cursor| {
+                Ok(cursor
+                    .next()
+                    .map(|inner| inner.filter(|f| f.0 <= end_block).map(|i| i.1))?)
+            },
+        )
+    }
+
+    fn try_fetch_dex_quotes_range(
+        &self,
+        start_block: u64,
+        end_block: u64,
+    ) -> eyre::Result<Vec<(u64, DexQuoteWithIndex)>> {
+        let (start_key, _) = make_filter_key_range(start_block);
+        let (_, end_key) = make_filter_key_range(end_block);
+        self.db.view_db(|tx| {
+            tx.cursor_read::<DexPrice>()?
+                .walk_range(start_key..=end_key)?
+                .map(|row| {
+                    let row = row?;
+                    Ok((decompose_key(row.0).0, row.1))
+                })
+                .collect()
+        })
+    }
+
+    fn try_fetch_cex_trades_range(
+        &self,
+        start_block: u64,
+        end_block: u64,
+    ) -> eyre::Result<Vec<(u64, CexTradeMap)>> {
+        self.db.view_db(|tx| {
+            tx.cursor_read::<CexTrades>()?
+                .walk_range(start_block..=end_block)?
+                .map(|row| row.map(|row| (row.0, row.1)).map_err(ErrReport::from))
+                .collect()
+        })
+    }
+
+    fn try_fetch_cex_quotes_range(
+        &self,
+        start_block: u64,
+        end_block: u64,
+    ) -> eyre::Result<Vec<(u64, CexPriceMap)>> {
+        self.db.view_db(|tx| {
+            tx.cursor_read::<CexPrice>()?
+                .walk_range(start_block..=end_block)?
+                .map(|row| row.map(|row| (row.0, row.1)).map_err(ErrReport::from))
+                .collect()
+        })
+    }
+
+    #[instrument(level = "error", skip_all)]
+    fn fetch_all_token_info(&self) -> eyre::Result<Vec<TokenInfoWithAddress>> {
+        self.db.export_db(
+            None,
+            |start_key, tx| {
+                let mut cur = tx.cursor_read::<TokenDecimals>()?;
+                if let Some(key) = start_key {
+                    let _ = cur.seek(key);
+                } else {
+                    // move to first entry and make sure .next() is first
+                    let _ = cur.first();
+                    let _ = cur.prev();
+                }
+                Ok(cur)
+            },
+            |cursor| {
+                Ok(cursor.next().map(|inner| {
+                    inner.map(|(address, inner)| TokenInfoWithAddress { address, inner })
+                })?)
+            },
+        )
+    }
+
+    #[instrument(level = "error", skip_all)]
+    fn fetch_all_protocol_info(&self) -> eyre::Result<Vec<(Address, ProtocolInfo)>> {
+        self.db.export_db(
+            None,
+            |start_key, tx| {
+                let mut cur = tx.cursor_read::<AddressToProtocolInfo>()?;
+                if let Some(key) = start_key {
+                    let _ = cur.seek(key);
+                } else {
+                    // move to first entry and make sure .next() is first
+                    let _ = cur.first();
+                    let _ = cur.prev();
+                }
+                Ok(cur)
+            },
+            |cursor| Ok(cursor.next().map(|inner| inner.map(|i| (i.0, i.1)))?),
+        )
+    }
+
+    fn try_fetch_block_lvrs(
+        &self,
+        start_block: Option<u64>,
+        end_block: u64,
+    ) -> eyre::Result<Vec<BlockLvr>> {
+        if !self.has_table::<BlockLvrs>()? {
+            return Ok(vec![])
+        }
+
+        self.db.export_db(
+            start_block,
+            |start_key, tx| {
+                let mut cur = tx.cursor_read::<BlockLvrs>()?;
+                if let Some(key) = start_key {
+                    let _ = cur.seek(key);
+                } else {
+                    // move to first entry and make sure .next() is first
+                    let _ = cur.first();
+                    let _ = cur.prev();
+                }
+                Ok(cur)
+            },
+            |cursor| {
+                Ok(cursor
+                    .next()
+                    .map(|inner| inner.filter(|f| f.0 <= end_block).map(|i| i.1))?)
+            },
+        )
+    }
+
+    fn try_fetch_failed_attempts(
+        &self,
+        start_block: Option<u64>,
+        end_block: u64,
+    ) -> eyre::Result<Vec<BlockFailedAttempts>> {
+        if !self.has_table::<FailedMevAttempts>()? {
+            return Ok(vec![])
+        }
+
+        self.db.export_db(
+            start_block,
+            |start_key, tx| {
+                let mut cur = tx.cursor_read::<FailedMevAttempts>()?;
+                if let Some(key) = start_key {
+                    let _ = cur.seek(key);
+                } else {
+                    // move to first entry and make sure .next() is first
+                    let _ = cur.first();
+                    let _ = cur.prev();
+                }
+                Ok(cur)
+            },
+            |cursor| {
+                Ok(cursor
+                    .next()
+                    .map(|inner| inner.filter(|f| f.0 <= end_block).map(|i| i.1))?)
+            },
+        )
+    }
+
+    fn try_fetch_cex_dex_inventories(
+        &self,
+        start_day: Option<u64>,
+        end_day: u64,
+    ) -> eyre::Result<Vec<DailyCexDexInventory>> {
+        if !self.has_table::<CexDexInventory>()? {
+            return Ok(vec![])
+        }
+
+        self.db.export_db(
+            start_day,
+            |start_key, tx| {
+                let mut cur = tx.cursor_read::<CexDexInventory>()?;
+                if let Some(key) = start_key {
+                    let _ = cur.seek(key);
+                } else {
+                    // move to first entry and make sure .next() is first
+                    let _ = cur.first();
+                    let _ = cur.prev();
+                }
+                Ok(cur)
+            },
+            |cursor| {
+                Ok(cursor
+                    .next()
+                    .map(|inner| inner.filter(|f| f.0 <= end_day).map(|i| i.1))?)
+            },
+        )
+    }
+
+    fn try_fetch_run_checkpoint(&self, run_id: u64) -> eyre::Result<Option<RunCheckpoint>> {
+        self.get_if_exists::<RunCheckpoints>(run_id)
+    }
+
+    fn latest_run_checkpoint_id(&self) -> eyre::Result<Option<u64>> {
+        self.db.view_db(|tx| {
+            if !tx.table_exists::<RunCheckpoints>()? {
+                return Ok(None)
+            }
+            let mut cur = tx.cursor_read::<RunCheckpoints>()?;
+            Ok(cur.last()?.map(|(run_id, _)| run_id))
+        })
+    }
+
     #[instrument(level = "error", skip_all)]
     fn fetch_all_address_metadata(&self) -> eyre::Result<Vec<(Address, AddressMetadata)>> {
         self.db.export_db(
@@ -1071,17 +1410,56 @@ impl DBWriter for LibmdbxReadWriter {
         )?)
     }
 
-    /// only for internal functionality (i.e. clickhouse)
-    async fn insert_tree(&self, _tree: BlockTree<Action>) -> eyre::Result<()> {
-        Ok(())
+    async fn insert_tree(&self, tree: BlockTree<Action>) -> eyre::Result<()> {
+        if !self.persist_trees {
+            return Ok(())
+        }
+
+        let block = ClassifiedBlock::from(&tree);
+        Ok(self
+            .tx
+            .send(WriterMessage::ClassifiedBlock(Box::new(block)).stamp())?)
+    }
+
+    async fn write_block_analysis(&self, block_analysis: BlockAnalysis) -> eyre::Result<()> {
+        Ok(self
+            .tx
+            .send(WriterMessage::BlockAnalysis(Box::new(block_analysis)).stamp())?)
+    }
+
+    async fn write_block_lvr(&self, block_lvr: BlockLvr) -> eyre::Result<()> {
+        Ok(self
+            .tx
+            .send(WriterMessage::BlockLvr(Box::new(block_lvr)).stamp())?)
     }
 
-    /// only for internal functionality (i.e. clickhouse)
-    async fn write_block_analysis(
+    async fn write_failed_attempts(&self, attempts: BlockFailedAttempts) -> eyre::Result<()> {
+        Ok(self
+            .tx
+            .send(WriterMessage::FailedAttempts(Box::new(attempts)).stamp())?)
+    }
+
+    async fn write_cex_dex_inventory(&self, inventory: DailyCexDexInventory) -> eyre::Result<()> {
+        Ok(self
+            .tx
+            .send(WriterMessage::CexDexInventory(Box::new(inventory)).stamp())?)
+    }
+
+    async fn write_run_checkpoint(&self, checkpoint: RunCheckpoint) -> eyre::Result<()> {
+        Ok(self
+            .tx
+            .send(WriterMessage::RunCheckpoint(Box::new(checkpoint)).stamp())?)
+    }
+
+    async fn commit_run_checkpoint(
         &self,
-        _: brontes_types::db::block_analysis::BlockAnalysis,
+        run_id: u64,
+        chunk_id: usize,
+        block: u64,
     ) -> eyre::Result<()> {
-        Ok(())
+        Ok(self
+            .tx
+            .send(WriterMessage::CommitRunCheckpoint { run_id, chunk_id, block }.stamp())?)
     }
 }
 
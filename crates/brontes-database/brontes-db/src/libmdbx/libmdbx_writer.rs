@@ -1,4 +1,5 @@
 use std::{
+    collections::hash_map::Entry,
     ops::Deref,
     sync::Arc,
     task::Poll,
@@ -6,21 +7,27 @@ use std::{
 };
 
 use alloy_primitives::Address;
+use brontes_libmdbx::RW;
 use brontes_metrics::db_writer::WriterMetrics;
 use brontes_types::{
     db::{
         address_metadata::AddressMetadata,
         address_to_protocol_info::ProtocolInfo,
+        block_analysis::BlockAnalysis,
         builder::BuilderInfo,
+        cex_dex_inventory::DailyCexDexInventory,
         dex::{make_key, DexQuoteWithIndex, DexQuotes},
         initialized_state::{DATA_PRESENT, DEX_PRICE_FLAG, TRACE_FLAG},
+        lvr::BlockLvr,
         mev_block::MevBlockWithClassified,
+        normalized_actions::ClassifiedBlock,
         pool_creation_block::PoolsToAddresses,
+        run_checkpoint::RunCheckpoint,
         searcher::SearcherInfo,
         token_info::TokenInfo,
         traces::TxTracesInner,
     },
-    mev::{Bundle, MevBlock},
+    mev::{BlockFailedAttempts, Bundle, MevBlock},
     structured_trace::TxTrace,
     FastHashMap, Protocol, UnboundedYapperReceiver,
 };
@@ -36,6 +43,7 @@ use tracing::instrument;
 
 use crate::{
     libmdbx::{
+        implementation::compressed_wrappers::tx::CompressedLibmdbxTx,
         tables::*,
         types::{LibmdbxData, ReturnKV},
         Libmdbx,
@@ -45,6 +53,9 @@ use crate::{
 
 // how often we will append data
 const CLEAR_AM: usize = 1000;
+// how many committed blocks are held before the run checkpoint is written
+// together with the queued data
+const CHECKPOINT_INTERVAL: usize = 50;
 
 //TODO: Mark instant here
 type InsetQueue = FastHashMap<Tables, Vec<(Vec<u8>, Vec<u8>)>>;
@@ -64,6 +75,17 @@ pub enum WriterMessage {
         block:        Box<MevBlock>,
         mev:          Vec<Bundle>,
     },
+    BlockAnalysis(Box<BlockAnalysis>),
+    ClassifiedBlock(Box<ClassifiedBlock>),
+    BlockLvr(Box<BlockLvr>),
+    FailedAttempts(Box<BlockFailedAttempts>),
+    CexDexInventory(Box<DailyCexDexInventory>),
+    RunCheckpoint(Box<RunCheckpoint>),
+    CommitRunCheckpoint {
+        run_id:   u64,
+        chunk_id: usize,
+        block:    u64,
+    },
     SearcherInfo {
         eoa_address:      Address,
         contract_address: Option<Address>,
@@ -214,16 +236,25 @@ init!(
     MevBlocks,
     SearcherEOAs,
     SearcherContracts,
-    InitializedState
+    InitializedState,
+    BlockAnalyses,
+    ClassifiedBlocks,
+    BlockLvrs,
+    FailedMevAttempts,
+    CexDexInventory,
+    RunCheckpoints
 );
 
 /// due to libmdbx's 1 write tx limit. it makes sense
 /// to split db and ensure we never breach this
 pub struct LibmdbxWriter {
-    db:           Arc<Libmdbx>,
-    insert_queue: InsetQueue,
-    rx:           UnboundedYapperReceiver<StampedWriterMessage>,
-    metrics:      WriterMetrics,
+    db:              Arc<Libmdbx>,
+    insert_queue:    InsetQueue,
+    /// (run id, chunk id, block) of the blocks committed since the last
+    /// checkpoint write
+    pending_commits: Vec<(u64, usize, u64)>,
+    rx:              UnboundedYapperReceiver<StampedWriterMessage>,
+    metrics:         WriterMetrics,
 }
 
 impl LibmdbxWriter {
@@ -232,7 +263,13 @@ impl LibmdbxWriter {
         rx: UnboundedYapperReceiver<StampedWriterMessage>,
         metrics: bool,
     ) -> Self {
-        Self { rx, db, insert_queue: FastHashMap::default(), metrics: WriterMetrics::new(metrics) }
+        Self {
+            rx,
+            db,
+            insert_queue: FastHashMap::default(),
+            pending_commits: vec![],
+            metrics: WriterMetrics::new(metrics),
+        }
     }
 
     fn handle_msg(&mut self, stamped_msg: StampedWriterMessage) -> eyre::Result<()> {
@@ -258,6 +295,34 @@ impl LibmdbxWriter {
                 self.save_mev_blocks(block_number, *block, mev)?;
                 "mevblocks"
             }
+            WriterMessage::BlockAnalysis(analysis) => {
+                self.save_block_analysis(*analysis)?;
+                "blockanalysis"
+            }
+            WriterMessage::ClassifiedBlock(block) => {
+                self.save_classified_block(*block)?;
+                "classifiedblock"
+            }
+            WriterMessage::BlockLvr(lvr) => {
+                self.save_block_lvr(*lvr)?;
+                "blocklvr"
+            }
+            WriterMessage::FailedAttempts(attempts) => {
+                self.save_failed_attempts(*attempts)?;
+                "failedattempts"
+            }
+            WriterMessage::CexDexInventory(inventory) => {
+                self.write_cex_dex_inventory(*inventory)?;
+                "cexdexinventory"
+            }
+            WriterMessage::RunCheckpoint(checkpoint) => {
+                self.write_run_checkpoint(*checkpoint)?;
+                "runcheckpoint"
+            }
+            WriterMessage::CommitRunCheckpoint { run_id, chunk_id, block } => {
+                self.commit_run_checkpoint(run_id, chunk_id, block)?;
+                "commitruncheckpoint"
+            }
             WriterMessage::BuilderInfo { builder_address, builder_info } => {
                 self.write_builder_info(builder_address, *builder_info)?;
                 "builderinfo"
@@ -348,44 +413,64 @@ impl LibmdbxWriter {
 
     #[instrument(target = "libmdbx_read_write::searcher_info", skip_all, level = "warn")]
     fn write_searcher_info(
-        &self,
+        &mut self,
         eoa_address: Address,
         contract_address: Option<Address>,
         eoa_info: SearcherInfo,
         contract_info: Option<SearcherInfo>,
     ) -> eyre::Result<()> {
-        self.write_searcher_eoa_info(eoa_address, eoa_info)
-            .expect("libmdbx write failure");
+        self.write_searcher_eoa_info(eoa_address, eoa_info)?;
 
         if let Some(contract_address) = contract_address {
-            self.write_searcher_contract_info(contract_address, contract_info.unwrap_or_default())
-                .expect("libmdbx write failure");
+            self.write_searcher_contract_info(contract_address, contract_info.unwrap_or_default())?;
         }
         Ok(())
     }
 
+    /// Searcher info is queued like the mev blocks it's derived from, so both
+    /// land in the same transaction as the run checkpoint. Reads are served
+    /// by the write through cache of the [`LibmdbxReadWriter`] in the meantime
+    ///
+    /// [`LibmdbxReadWriter`]: crate::libmdbx::LibmdbxReadWriter
     #[instrument(target = "libmdbx_read_write::searcher_eoa_info", skip_all, level = "warn")]
     fn write_searcher_eoa_info(
-        &self,
+        &mut self,
         searcher_eoa: Address,
         searcher_info: SearcherInfo,
     ) -> eyre::Result<()> {
-        let data = SearcherEOAsData::new(searcher_eoa, searcher_info);
-        self.instrumented_write::<SearcherEOAs, SearcherEOAsData>(&[data])
-            .expect("libmdbx write failure");
+        let data = SearcherEOAsData::new(searcher_eoa, searcher_info).into_key_val();
+        let (key, value) = Self::convert_into_save_bytes(data);
+
+        let entry = self.insert_queue.entry(Tables::SearcherEOAs).or_default();
+        entry.push((key.to_vec(), value));
+
+        if entry.len() > CLEAR_AM {
+            let data = std::mem::take(entry);
+            self.insert_batched_data::<SearcherEOAs>(data)?;
+        }
 
         Ok(())
     }
 
     #[instrument(target = "libmdbx_read_write::searcher_contract_info", skip_all, level = "warn")]
     fn write_searcher_contract_info(
-        &self,
+        &mut self,
         searcher_contract: Address,
         searcher_info: SearcherInfo,
     ) -> eyre::Result<()> {
-        let data = SearcherContractsData::new(searcher_contract, searcher_info);
-        self.instrumented_write::<SearcherContracts, SearcherContractsData>(&[data])
-            .expect("libmdbx write failure");
+        let data = SearcherContractsData::new(searcher_contract, searcher_info).into_key_val();
+        let (key, value) = Self::convert_into_save_bytes(data);
+
+        let entry = self
+            .insert_queue
+            .entry(Tables::SearcherContracts)
+            .or_default();
+        entry.push((key.to_vec(), value));
+
+        if entry.len() > CLEAR_AM {
+            let data = std::mem::take(entry);
+            self.insert_batched_data::<SearcherContracts>(data)?;
+        }
 
         Ok(())
     }
@@ -422,6 +507,156 @@ impl LibmdbxWriter {
         Ok(())
     }
 
+    #[instrument(target = "libmdbx_read_write::save_block_analysis", skip_all, level = "warn")]
+    fn save_block_analysis(&mut self, analysis: BlockAnalysis) -> eyre::Result<()> {
+        let data = BlockAnalysesData::new(analysis.block_number, analysis).into_key_val();
+        let (key, value) = Self::convert_into_save_bytes(data);
+
+        let entry = self.insert_queue.entry(Tables::BlockAnalyses).or_default();
+        entry.push((key.to_vec(), value));
+
+        if entry.len() > CLEAR_AM {
+            let data = std::mem::take(entry);
+            self.insert_batched_data::<BlockAnalyses>(data)?;
+        }
+
+        Ok(())
+    }
+
+    #[instrument(target = "libmdbx_read_write::save_classified_block", skip_all, level = "warn")]
+    fn save_classified_block(&mut self, block: ClassifiedBlock) -> eyre::Result<()> {
+        let data = ClassifiedBlocksData::new(block.block_number, block).into_key_val();
+        let (key, value) = Self::convert_into_save_bytes(data);
+
+        let entry = self
+            .insert_queue
+            .entry(Tables::ClassifiedBlocks)
+            .or_default();
+        entry.push((key.to_vec(), value));
+
+        // fat table
+        if entry.len() > 5 {
+            let data = std::mem::take(entry);
+            self.insert_batched_data::<ClassifiedBlocks>(data)?;
+        }
+
+        Ok(())
+    }
+
+    #[instrument(target = "libmdbx_read_write::save_block_lvr", skip_all, level = "warn")]
+    fn save_block_lvr(&mut self, lvr: BlockLvr) -> eyre::Result<()> {
+        let data = BlockLvrsData::new(lvr.block_number, lvr).into_key_val();
+        let (key, value) = Self::convert_into_save_bytes(data);
+
+        let entry = self.insert_queue.entry(Tables::BlockLvrs).or_default();
+        entry.push((key.to_vec(), value));
+
+        if entry.len() > CLEAR_AM {
+            let data = std::mem::take(entry);
+            self.insert_batched_data::<BlockLvrs>(data)?;
+        }
+
+        Ok(())
+    }
+
+    #[instrument(target = "libmdbx_read_write::save_failed_attempts", skip_all, level = "warn")]
+    fn save_failed_attempts(&mut self, attempts: BlockFailedAttempts) -> eyre::Result<()> {
+        let data = FailedMevAttemptsData::new(attempts.block_number, attempts).into_key_val();
+        let (key, value) = Self::convert_into_save_bytes(data);
+
+        let entry = self
+            .insert_queue
+            .entry(Tables::FailedMevAttempts)
+            .or_default();
+        entry.push((key.to_vec(), value));
+
+        if entry.len() > CLEAR_AM {
+            let data = std::mem::take(entry);
+            self.insert_batched_data::<FailedMevAttempts>(data)?;
+        }
+
+        Ok(())
+    }
+
+    #[instrument(target = "libmdbx_read_write::write_cex_dex_inventory", skip_all, level = "warn")]
+    fn write_cex_dex_inventory(&self, inventory: DailyCexDexInventory) -> eyre::Result<()> {
+        // written straight away as the next day's ledger carries on from it
+        let data = CexDexInventoryData::new(inventory.day, inventory);
+        self.instrumented_write::<CexDexInventory, CexDexInventoryData>(&[data])
+            .expect("libmdbx cex dex inventory write failure");
+
+        Ok(())
+    }
+
+    #[instrument(target = "libmdbx_read_write::write_run_checkpoint", skip_all, level = "warn")]
+    fn write_run_checkpoint(&self, checkpoint: RunCheckpoint) -> eyre::Result<()> {
+        let data = RunCheckpointsData::new(checkpoint.run_id, checkpoint);
+        self.instrumented_write::<RunCheckpoints, RunCheckpointsData>(&[data])
+            .expect("libmdbx run checkpoint write failure");
+
+        Ok(())
+    }
+
+    /// All of the block's writes were sent before the commit. The commit is
+    /// held until [`CHECKPOINT_INTERVAL`] blocks are committed, then written
+    /// together with the queued data
+    #[instrument(target = "libmdbx_read_write::commit_run_checkpoint", skip_all, level = "warn")]
+    fn commit_run_checkpoint(
+        &mut self,
+        run_id: u64,
+        chunk_id: usize,
+        block: u64,
+    ) -> eyre::Result<()> {
+        self.pending_commits.push((run_id, chunk_id, block));
+
+        if self.pending_commits.len() >= CHECKPOINT_INTERVAL {
+            self.flush()?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes the queued data and the pending checkpoint commits in a single
+    /// transaction, so a persisted checkpoint never points past data that was
+    /// lost
+    fn flush(&mut self) -> eyre::Result<()> {
+        if self.insert_queue.values().all(Vec::is_empty) && self.pending_commits.is_empty() {
+            return Ok(())
+        }
+
+        let start_time = Instant::now();
+        let tx = self.db.rw_tx()?;
+
+        for (table, values) in std::mem::take(&mut self.insert_queue) {
+            Self::put_batched_data(&tx, table, values)?;
+        }
+
+        let mut checkpoints = FastHashMap::default();
+        for (run_id, chunk_id, block) in std::mem::take(&mut self.pending_commits) {
+            let checkpoint = match checkpoints.entry(run_id) {
+                Entry::Occupied(entry) => entry.into_mut(),
+                Entry::Vacant(entry) => entry.insert(tx.get::<RunCheckpoints>(run_id)?),
+            };
+
+            if let Some(checkpoint) = checkpoint {
+                checkpoint.commit(chunk_id, block);
+            } else {
+                tracing::error!(run_id, "no checkpoint stored for run");
+            }
+        }
+        for (run_id, checkpoint) in checkpoints {
+            if let Some(checkpoint) = checkpoint {
+                tx.put::<RunCheckpoints>(run_id, checkpoint)?;
+            }
+        }
+        tx.commit()?;
+
+        self.metrics
+            .observe_write_latency_batch(Instant::now() - start_time);
+
+        Ok(())
+    }
+
     #[instrument(target = "libmdbx_read_write::write_dex_quotes", skip_all, level = "warn")]
     fn write_dex_quotes(&mut self, block_num: u64, quotes: Option<DexQuotes>) -> eyre::Result<()> {
         if let Some(quotes) = quotes {
@@ -614,7 +849,9 @@ impl LibmdbxWriter {
                     tracing::error!(error=%e, "libmdbx write error on shutdown");
                 }
             }
-            inserts.insert_remaining();
+            if let Err(e) = inserts.flush() {
+                tracing::error!(error=%e, "libmdbx write error on shutdown");
+            }
             // inserts take some time so we update last message here
             if message {
                 last_message = Instant::now();
@@ -625,37 +862,42 @@ impl LibmdbxWriter {
         drop(graceful_guard)
     }
 
-    fn insert_remaining(&mut self) {
-        std::mem::take(&mut self.insert_queue)
-            .into_iter()
-            .for_each(|(table, values)| {
-                if values.is_empty() {
-                    return
-                }
-                match table {
-                    Tables::DexPrice => {
-                        self.insert_batched_data::<DexPrice>(values).unwrap();
-                    }
-                    Tables::CexPrice => {
-                        self.insert_batched_data::<CexPrice>(values).unwrap();
-                    }
-                    Tables::CexTrades => {
-                        self.insert_batched_data::<CexTrades>(values).unwrap();
-                    }
-                    Tables::MevBlocks => {
-                        self.insert_batched_data::<MevBlocks>(values).unwrap();
-                    }
-                    Tables::TxTraces => {
-                        self.insert_batched_data::<TxTraces>(values).unwrap();
-                    }
-                    Tables::InitializedState => {
-                        self.insert_batched_data::<InitializedState>(values)
-                            .unwrap();
-                    }
+    /// Puts queued data into an open transaction, so it can be committed
+    /// together with other writes
+    fn put_batched_data(
+        tx: &CompressedLibmdbxTx<RW>,
+        table: Tables,
+        values: Vec<(Vec<u8>, Vec<u8>)>,
+    ) -> eyre::Result<()> {
+        match table {
+            Tables::DexPrice => Self::put_bytes::<DexPrice>(tx, values),
+            Tables::CexPrice => Self::put_bytes::<CexPrice>(tx, values),
+            Tables::CexTrades => Self::put_bytes::<CexTrades>(tx, values),
+            Tables::MevBlocks => Self::put_bytes::<MevBlocks>(tx, values),
+            Tables::TxTraces => Self::put_bytes::<TxTraces>(tx, values),
+            Tables::BlockAnalyses => Self::put_bytes::<BlockAnalyses>(tx, values),
+            Tables::ClassifiedBlocks => Self::put_bytes::<ClassifiedBlocks>(tx, values),
+            Tables::BlockLvrs => Self::put_bytes::<BlockLvrs>(tx, values),
+            Tables::FailedMevAttempts => Self::put_bytes::<FailedMevAttempts>(tx, values),
+            Tables::InitializedState => Self::put_bytes::<InitializedState>(tx, values),
+            Tables::SearcherEOAs => Self::put_bytes::<SearcherEOAs>(tx, values),
+            Tables::SearcherContracts => Self::put_bytes::<SearcherContracts>(tx, values),
+            table => eyre::bail!("{table} doesn't have batch inserts"),
+        }
+    }
 
-                    table => unreachable!("{table} doesn't have batch inserts"),
-                }
-            });
+    fn put_bytes<T: CompressedTable>(
+        tx: &CompressedLibmdbxTx<RW>,
+        values: Vec<(Vec<u8>, Vec<u8>)>,
+    ) -> eyre::Result<()>
+    where
+        T::Value: From<T::DecompressedValue> + Into<T::DecompressedValue>,
+    {
+        for (key, value) in values {
+            tx.put_bytes::<T>(&key, value)?;
+        }
+
+        Ok(())
     }
 }
 
@@ -685,3 +927,177 @@ impl Future for LibmdbxWriter {
         Poll::Pending
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::{B256, U256};
+    use brontes_types::{
+        db::normalized_actions::{ActionKind, ClassifiedTraceNode, ClassifiedTxRoot},
+        normalized_actions::{
+            Action, NormalizedAggregator, NormalizedFlashLoan, NormalizedSwap, NormalizedTransfer,
+        },
+    };
+    use malachite::Rational;
+    use tokio::sync::mpsc::unbounded_channel;
+
+    use super::*;
+
+    fn writer(db: Arc<Libmdbx>) -> LibmdbxWriter {
+        let (_, rx) = unbounded_channel();
+        LibmdbxWriter::new(db, UnboundedYapperReceiver::new(rx, 1500, "test".to_string()), false)
+    }
+
+    fn process_block(writer: &mut LibmdbxWriter, block_number: u64) {
+        let block = Box::new(MevBlock { block_number, ..Default::default() });
+        let searcher_info =
+            Box::new(SearcherInfo { failed_attempt_count: block_number, ..Default::default() });
+
+        for msg in [
+            WriterMessage::MevBlocks { block_number, block, mev: vec![] },
+            WriterMessage::SearcherEoaInfo { searcher_eoa: Address::ZERO, searcher_info },
+            WriterMessage::CommitRunCheckpoint { run_id: 1, chunk_id: 0, block: block_number },
+        ] {
+            writer.handle_msg(msg.stamp()).unwrap();
+        }
+    }
+
+    fn stored(db: &Libmdbx) -> (RunCheckpoint, Vec<u64>, Option<SearcherInfo>) {
+        db.view_db(|tx| {
+            let checkpoint = tx.get::<RunCheckpoints>(1)?.unwrap();
+            let mut cur = tx.cursor_read::<MevBlocks>()?;
+            let mut blocks = vec![];
+            let mut entry = cur.first()?;
+            while let Some((block, _)) = entry {
+                blocks.push(block);
+                entry = cur.next()?;
+            }
+            let searcher = tx.get::<SearcherEOAs>(Address::ZERO)?;
+
+            Ok((checkpoint, blocks, searcher))
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_checkpoint_resumes_after_crash() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Arc::new(Libmdbx::init_db(dir.path(), None).unwrap());
+        let start = 100;
+        let end = start + CHECKPOINT_INTERVAL as u64 + 10;
+
+        let mut crashed = writer(db.clone());
+        crashed
+            .handle_msg(
+                WriterMessage::RunCheckpoint(Box::new(RunCheckpoint::new(1, &[(start, end)])))
+                    .stamp(),
+            )
+            .unwrap();
+        for block in start..start + CHECKPOINT_INTERVAL as u64 + 5 {
+            process_block(&mut crashed, block);
+        }
+        // the writer dies without flushing the blocks committed after the last
+        // checkpoint
+        drop(crashed);
+
+        let (checkpoint, blocks, searcher) = stored(&db);
+        let resume_from = checkpoint.chunks[0].next_block;
+        assert_eq!(resume_from, start + CHECKPOINT_INTERVAL as u64);
+        // everything the checkpoint covers was written with it, nothing after
+        assert_eq!(blocks, (start..resume_from).collect::<Vec<_>>());
+        assert_eq!(searcher.unwrap().failed_attempt_count, resume_from - 1);
+
+        let mut resumed = writer(db.clone());
+        for block in resume_from..end {
+            process_block(&mut resumed, block);
+        }
+        resumed.flush().unwrap();
+
+        let (checkpoint, blocks, searcher) = stored(&db);
+        assert!(checkpoint.is_finished());
+        assert_eq!(blocks, (start..end).collect::<Vec<_>>());
+        assert_eq!(searcher.unwrap().failed_attempt_count, end - 1);
+    }
+
+    #[test]
+    fn test_unbatched_table_errors() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Libmdbx::init_db(dir.path(), None).unwrap();
+        let tx = db.rw_tx().unwrap();
+
+        assert!(LibmdbxWriter::put_batched_data(&tx, Tables::Builder, vec![]).is_err());
+    }
+
+    /// A flash loan wrapping a swap and an aggregator, so the stored action
+    /// nests two levels deep
+    fn classified_block(block_number: u64) -> ClassifiedBlock {
+        let aggregator = NormalizedAggregator {
+            protocol:      Protocol::ZeroX,
+            trace_index:   2,
+            from:          Address::with_last_byte(1),
+            to:            Address::with_last_byte(2),
+            recipient:     Address::with_last_byte(1),
+            child_actions: vec![Action::Transfer(NormalizedTransfer {
+                amount: Rational::from(5),
+                ..Default::default()
+            })],
+            msg_value:     U256::ZERO,
+        };
+        let flash_loan = NormalizedFlashLoan {
+            protocol:          Protocol::BalancerV2,
+            trace_index:       0,
+            from:              Address::with_last_byte(1),
+            pool:              Address::with_last_byte(3),
+            receiver_contract: Address::with_last_byte(1),
+            assets:            vec![],
+            amounts:           vec![Rational::from(10)],
+            aave_mode:         Some((vec![U256::from(1)], Address::with_last_byte(4))),
+            child_actions:     vec![
+                Action::Swap(NormalizedSwap { trace_index: 1, ..Default::default() }),
+                Action::Aggregator(aggregator),
+            ],
+            repayments:        vec![NormalizedTransfer::default()],
+            fees_paid:         vec![Rational::from(1)],
+            msg_value:         U256::ZERO,
+        };
+
+        ClassifiedBlock {
+            block_number,
+            tx_roots: vec![ClassifiedTxRoot {
+                tx_hash: B256::with_last_byte(1),
+                trace_nodes: vec![
+                    ClassifiedTraceNode {
+                        trace_idx:     0,
+                        trace_address: vec![],
+                        action_kind:   Some(ActionKind::FlashLoan),
+                        action:        Some(Action::FlashLoan(flash_loan)),
+                    },
+                    ClassifiedTraceNode {
+                        trace_idx:     3,
+                        trace_address: vec![0],
+                        action_kind:   Some(ActionKind::Revert),
+                        action:        Some(Action::Revert),
+                    },
+                ],
+                ..Default::default()
+            }],
+        }
+    }
+
+    #[test]
+    fn test_store_and_load_classified_block() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Arc::new(Libmdbx::init_db(dir.path(), None).unwrap());
+        let block = classified_block(100);
+
+        let mut writer = writer(db.clone());
+        writer
+            .handle_msg(WriterMessage::ClassifiedBlock(Box::new(block.clone())).stamp())
+            .unwrap();
+        writer.flush().unwrap();
+
+        let stored = db
+            .view_db(|tx| Ok(tx.get::<ClassifiedBlocks>(100)?))
+            .unwrap();
+        assert_eq!(stored, Some(block));
+    }
+}
@@ -0,0 +1,211 @@
+//! Conversions of stored values from the layout of an older schema version to
+//! the next one. Each function takes and returns the raw (compressed) bytes of
+//! a single value, see [`super::tables::MIGRATIONS`].
+mod v0;
+
+use brontes_types::db::{
+    mev_block::{MevBlockWithClassified, MevBlockWithClassifiedRedefined},
+    searcher::{SearcherInfo, SearcherInfoRedefined},
+};
+use redefined::RedefinedConvert;
+use rkyv::{ser::serializers::AllocSerializer, Archive, Deserialize, Infallible};
+pub use v0::*;
+
+/// Adds the quote asset pnl & refunds of the bundle headers, the refunds and
+/// victim losses of sandwiches, the refunds of atomic arbs, the nft arb count
+/// and the `NftArb` bundle variant
+pub(crate) fn mev_blocks_v0(value: &[u8]) -> eyre::Result<Vec<u8>> {
+    let old = decode::<MevBlockWithClassifiedV0Redefined>(value)?.to_source();
+
+    encode(&MevBlockWithClassifiedRedefined::from_source(MevBlockWithClassified::from(old)))
+}
+
+/// Adds the failed attempt count, the wasted gas and the nft arb count & pnl
+pub(crate) fn searcher_info_v0(value: &[u8]) -> eyre::Result<Vec<u8>> {
+    let old = decode::<SearcherInfoV0Redefined>(value)?.to_source();
+
+    encode(&SearcherInfoRedefined::from_source(SearcherInfo::from(old)))
+}
+
+/// Decompresses a stored value and reads it in the layout of `T`. The caller
+/// must pass the layout the value was written in, which the stored schema
+/// version of the table guarantees.
+fn decode<T>(value: &[u8]) -> eyre::Result<T>
+where
+    T: Archive,
+    T::Archived: Deserialize<T, Infallible>,
+{
+    let decompressed = zstd::decode_all(value)?;
+    if decompressed.len() < std::mem::size_of::<T::Archived>() {
+        eyre::bail!(
+            "value of {} bytes is too short for the layout it's migrated from",
+            decompressed.len()
+        );
+    }
+
+    // archived values have to be read from an aligned buffer
+    let mut aligned = rkyv::AlignedVec::with_capacity(decompressed.len());
+    aligned.extend_from_slice(&decompressed);
+
+    let archived = unsafe { rkyv::archived_root::<T>(&aligned) };
+
+    Ok(archived.deserialize(&mut Infallible).unwrap())
+}
+
+/// Writes a value the way the table codecs do
+fn encode<T>(value: &T) -> eyre::Result<Vec<u8>>
+where
+    T: rkyv::Serialize<AllocSerializer<256>>,
+{
+    let encoded = rkyv::to_bytes::<_, 256>(value)
+        .map_err(|e| eyre::eyre!("failed to encode migrated value: {e}"))?;
+
+    Ok(zstd::encode_all(&*encoded, 0)?)
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use alloy_primitives::{Address, B256};
+    use brontes_types::{
+        db::searcher::Fund,
+        mev::{AtomicArbType, MevType, PossibleMevCollection},
+        GasDetails,
+    };
+    use reth_db::table::Decompress;
+
+    use super::*;
+
+    pub(crate) fn mev_block_v0() -> MevBlockWithClassifiedV0 {
+        let header = BundleHeaderV0 {
+            block_number:          18_000_000,
+            tx_index:              3,
+            tx_hash:               B256::with_last_byte(1),
+            eoa:                   Address::with_last_byte(2),
+            mev_contract:          Some(Address::with_last_byte(3)),
+            fund:                  Fund::None,
+            profit_usd:            120.5,
+            bribe_usd:             20.0,
+            mev_type:              MevType::AtomicArb,
+            no_pricing_calculated: false,
+            balance_deltas:        vec![],
+        };
+        let arb = AtomicArbV0 {
+            tx_hash:      B256::with_last_byte(1),
+            trigger_tx:   B256::with_last_byte(4),
+            block_number: 18_000_000,
+            swaps:        vec![],
+            gas_details:  GasDetails {
+                coinbase_transfer:   Some(10),
+                priority_fee:        2,
+                gas_used:            150_000,
+                effective_gas_price: 30,
+            },
+            arb_type:     AtomicArbType::Triangle,
+        };
+
+        MevBlockWithClassifiedV0 {
+            block: MevBlockV0 {
+                block_hash:                  B256::with_last_byte(5),
+                block_number:                18_000_000,
+                mev_count:                   MevCountV0 {
+                    bundle_count: 1,
+                    atomic_backrun_count: Some(1),
+                    ..Default::default()
+                },
+                eth_price:                   2000.0,
+                total_gas_used:              15_000_000,
+                total_priority_fee:          1_000,
+                total_bribe:                 10,
+                total_mev_bribe:             10,
+                total_mev_priority_fee_paid: 300_000,
+                builder_address:             Address::with_last_byte(6),
+                builder_name:                Some("builder".to_string()),
+                builder_eth_profit:          0.1,
+                builder_profit_usd:          200.0,
+                builder_mev_profit_usd:      0.0,
+                builder_searcher_bribes:     0,
+                builder_searcher_bribes_usd: 0.0,
+                builder_sponsorship_amount:  0,
+                ultrasound_bid_adjusted:     false,
+                proposer_fee_recipient:      Some(Address::with_last_byte(7)),
+                proposer_mev_reward:         Some(100),
+                proposer_profit_usd:         Some(150.0),
+                total_mev_profit_usd:        120.5,
+                possible_mev:                PossibleMevCollection::default(),
+            },
+            mev:   vec![BundleV0 { header, data: BundleDataV0::AtomicArb(arb) }],
+        }
+    }
+
+    pub(crate) fn searcher_info_v0_row() -> SearcherInfoV0 {
+        SearcherInfoV0 {
+            name:              Some("searcher".to_string()),
+            fund:              Fund::None,
+            mev_count:         MevCountV0 {
+                bundle_count: 4,
+                sandwich_count: Some(4),
+                ..Default::default()
+            },
+            pnl:               TollByTypeV0 {
+                total: 50.0,
+                sandwich: Some(50.0),
+                ..Default::default()
+            },
+            gas_bids:          TollByTypeV0 {
+                total: 5.0,
+                sandwich: Some(5.0),
+                ..Default::default()
+            },
+            builder:           None,
+            config_labels:     vec![MevType::Sandwich],
+            sibling_searchers: vec![Address::with_last_byte(9)],
+        }
+    }
+
+    pub(crate) fn stored_v0<T, R>(value: T) -> Vec<u8>
+    where
+        R: RedefinedConvert<T> + rkyv::Serialize<AllocSerializer<256>>,
+    {
+        encode(&R::from_source(value)).unwrap()
+    }
+
+    #[test]
+    fn test_migrate_mev_block_v0() {
+        let old = mev_block_v0();
+        let stored = stored_v0::<_, MevBlockWithClassifiedV0Redefined>(old.clone());
+
+        let migrated = mev_blocks_v0(&stored).unwrap();
+        let block: MevBlockWithClassified = MevBlockWithClassifiedRedefined::decompress(migrated)
+            .unwrap()
+            .into();
+
+        assert_eq!(block, MevBlockWithClassified::from(old));
+        assert_eq!(block.block.mev_count.atomic_backrun_count, Some(1));
+        assert_eq!(block.block.mev_count.nft_arb_count, None);
+        assert!(block.block.quote_pnl.is_empty());
+        assert_eq!(block.mev[0].header.profit_usd, 120.5);
+        assert_eq!(block.mev[0].header.refund_usd, 0.0);
+    }
+
+    #[test]
+    fn test_migrate_searcher_info_v0() {
+        let old = searcher_info_v0_row();
+        let stored = stored_v0::<_, SearcherInfoV0Redefined>(old.clone());
+
+        let migrated = searcher_info_v0(&stored).unwrap();
+        let info: SearcherInfo = SearcherInfoRedefined::decompress(migrated).unwrap().into();
+
+        assert_eq!(info, SearcherInfo::from(old));
+        assert_eq!(info.pnl.sandwich, Some(50.0));
+        assert_eq!(info.failed_attempt_count, 0);
+        assert_eq!(info.wasted_gas, Default::default());
+    }
+
+    #[test]
+    fn test_migrate_rejects_garbage() {
+        assert!(mev_blocks_v0(b"not zstd").is_err());
+
+        let too_short = zstd::encode_all(&[0u8; 4][..], 0).unwrap();
+        assert!(searcher_info_v0(&too_short).is_err());
+    }
+}
@@ -0,0 +1,346 @@
+//! The layouts values were stored in before tables were versioned. Only the
+//! types that changed since are mirrored here, everything else is shared with
+//! the current layout.
+use alloy_primitives::{Address, B256};
+use brontes_types::{
+    db::{
+        mev_block::MevBlockWithClassified,
+        redefined_types::primitives::{AddressRedefined, B256Redefined},
+        searcher::{Fund, SearcherInfo, TollByType},
+    },
+    mev::{
+        AtomicArb, AtomicArbType, Bundle, BundleData, BundleHeader, CexDex, CexDexQuote,
+        CexDexQuoteRedefined, CexDexRedefined, JitLiquidity, JitLiquidityRedefined,
+        JitLiquiditySandwich, JitLiquiditySandwichRedefined, Liquidation, LiquidationRedefined,
+        MevBlock, MevCount, MevType, PossibleMevCollection, PossibleMevCollectionRedefined,
+        Sandwich, SearcherTx, SearcherTxRedefined, TransactionAccounting,
+        TransactionAccountingRedefined,
+    },
+    normalized_actions::{NormalizedSwap, NormalizedSwapRedefined},
+    GasDetails,
+};
+use redefined::Redefined;
+use rkyv::{Archive, Deserialize as rDeserialize, Serialize as rSerialize};
+
+#[derive(Debug, PartialEq, Clone, Redefined)]
+#[redefined_attr(derive(Debug, PartialEq, Clone, rSerialize, rDeserialize, Archive))]
+pub struct MevBlockWithClassifiedV0 {
+    pub block: MevBlockV0,
+    pub mev:   Vec<BundleV0>,
+}
+
+#[derive(Debug, PartialEq, Clone, Redefined)]
+#[redefined_attr(derive(Debug, PartialEq, Clone, rSerialize, rDeserialize, Archive))]
+pub struct MevBlockV0 {
+    pub block_hash:                  B256,
+    pub block_number:                u64,
+    #[redefined(same_fields)]
+    pub mev_count:                   MevCountV0,
+    pub eth_price:                   f64,
+    pub total_gas_used:              u128,
+    pub total_priority_fee:          u128,
+    pub total_bribe:                 u128,
+    pub total_mev_bribe:             u128,
+    pub total_mev_priority_fee_paid: u128,
+    pub builder_address:             Address,
+    pub builder_name:                Option<String>,
+    pub builder_eth_profit:          f64,
+    pub builder_profit_usd:          f64,
+    pub builder_mev_profit_usd:      f64,
+    pub builder_searcher_bribes:     u128,
+    pub builder_searcher_bribes_usd: f64,
+    pub builder_sponsorship_amount:  u128,
+    pub ultrasound_bid_adjusted:     bool,
+    pub proposer_fee_recipient:      Option<Address>,
+    pub proposer_mev_reward:         Option<u128>,
+    pub proposer_profit_usd:         Option<f64>,
+    pub total_mev_profit_usd:        f64,
+    pub possible_mev:                PossibleMevCollection,
+}
+
+/// Before `nft_arb_count`
+#[derive(Debug, PartialEq, Clone, Default, rSerialize, rDeserialize, Archive)]
+pub struct MevCountV0 {
+    pub bundle_count:         u64,
+    pub sandwich_count:       Option<u64>,
+    pub cex_dex_trade_count:  Option<u64>,
+    pub cex_dex_quote_count:  Option<u64>,
+    pub cex_dex_rfq_count:    Option<u64>,
+    pub jit_cex_dex_count:    Option<u64>,
+    pub jit_count:            Option<u64>,
+    pub jit_sandwich_count:   Option<u64>,
+    pub atomic_backrun_count: Option<u64>,
+    pub liquidation_count:    Option<u64>,
+    pub searcher_tx_count:    Option<u64>,
+}
+
+#[derive(Debug, PartialEq, Clone, Redefined)]
+#[redefined_attr(derive(Debug, PartialEq, Clone, rSerialize, rDeserialize, Archive))]
+pub struct BundleV0 {
+    pub header: BundleHeaderV0,
+    pub data:   BundleDataV0,
+}
+
+/// Before `quote_pnl` and `refund_usd`
+#[derive(Debug, PartialEq, Clone, Redefined)]
+#[redefined_attr(derive(Debug, PartialEq, Clone, rSerialize, rDeserialize, Archive))]
+pub struct BundleHeaderV0 {
+    pub block_number:          u64,
+    pub tx_index:              u64,
+    pub tx_hash:               B256,
+    pub eoa:                   Address,
+    pub mev_contract:          Option<Address>,
+    #[redefined(same_fields)]
+    pub fund:                  Fund,
+    pub profit_usd:            f64,
+    pub bribe_usd:             f64,
+    #[redefined(same_fields)]
+    pub mev_type:              MevType,
+    pub no_pricing_calculated: bool,
+    pub balance_deltas:        Vec<TransactionAccounting>,
+}
+
+/// Before the `NftArb` variant
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, PartialEq, Clone, Redefined)]
+#[redefined_attr(derive(Debug, PartialEq, Clone, rSerialize, rDeserialize, Archive))]
+pub enum BundleDataV0 {
+    Sandwich(SandwichV0),
+    AtomicArb(AtomicArbV0),
+    JitSandwich(JitLiquiditySandwich),
+    Jit(JitLiquidity),
+    CexDexQuote(CexDexQuote),
+    CexDex(CexDex),
+    Liquidation(Liquidation),
+    Unknown(SearcherTx),
+}
+
+/// Before `refunds` and `victim_losses`
+#[derive(Debug, PartialEq, Clone, Redefined)]
+#[redefined_attr(derive(Debug, PartialEq, Clone, rSerialize, rDeserialize, Archive))]
+pub struct SandwichV0 {
+    pub block_number:             u64,
+    pub frontrun_tx_hash:         Vec<B256>,
+    pub frontrun_swaps:           Vec<Vec<NormalizedSwap>>,
+    #[redefined(same_fields)]
+    pub frontrun_gas_details:     Vec<GasDetails>,
+    pub victim_swaps_tx_hashes:   Vec<Vec<B256>>,
+    pub victim_swaps:             Vec<Vec<NormalizedSwap>>,
+    #[redefined(same_fields)]
+    pub victim_swaps_gas_details: Vec<GasDetails>,
+    pub backrun_tx_hash:          B256,
+    pub backrun_swaps:            Vec<NormalizedSwap>,
+    #[redefined(same_fields)]
+    pub backrun_gas_details:      GasDetails,
+}
+
+/// Before `refunds`
+#[derive(Debug, PartialEq, Clone, Redefined)]
+#[redefined_attr(derive(Debug, PartialEq, Clone, rSerialize, rDeserialize, Archive))]
+pub struct AtomicArbV0 {
+    pub tx_hash:      B256,
+    pub trigger_tx:   B256,
+    pub block_number: u64,
+    pub swaps:        Vec<NormalizedSwap>,
+    #[redefined(same_fields)]
+    pub gas_details:  GasDetails,
+    #[redefined(same_fields)]
+    pub arb_type:     AtomicArbType,
+}
+
+/// Before `failed_attempt_count` and `wasted_gas`
+#[derive(Debug, PartialEq, Clone, Redefined)]
+#[redefined_attr(derive(Debug, PartialEq, Clone, rSerialize, rDeserialize, Archive))]
+pub struct SearcherInfoV0 {
+    pub name:              Option<String>,
+    #[redefined(same_fields)]
+    pub fund:              Fund,
+    #[redefined(same_fields)]
+    pub mev_count:         MevCountV0,
+    #[redefined(same_fields)]
+    pub pnl:               TollByTypeV0,
+    #[redefined(same_fields)]
+    pub gas_bids:          TollByTypeV0,
+    pub builder:           Option<Address>,
+    #[redefined(same_fields)]
+    pub config_labels:     Vec<MevType>,
+    pub sibling_searchers: Vec<Address>,
+}
+
+/// Before `nft_arb`
+#[derive(Debug, PartialEq, Clone, Default, rSerialize, rDeserialize, Archive)]
+pub struct TollByTypeV0 {
+    pub total:          f64,
+    pub sandwich:       Option<f64>,
+    pub cex_dex_quotes: Option<f64>,
+    pub cex_dex_trades: Option<f64>,
+    pub jit:            Option<f64>,
+    pub jit_sandwich:   Option<f64>,
+    pub atomic_backrun: Option<f64>,
+    pub liquidation:    Option<f64>,
+    pub searcher_tx:    Option<f64>,
+}
+
+impl From<MevBlockWithClassifiedV0> for MevBlockWithClassified {
+    fn from(value: MevBlockWithClassifiedV0) -> Self {
+        Self { block: value.block.into(), mev: value.mev.into_iter().map(Into::into).collect() }
+    }
+}
+
+impl From<MevBlockV0> for MevBlock {
+    fn from(value: MevBlockV0) -> Self {
+        Self {
+            block_hash:                  value.block_hash,
+            block_number:                value.block_number,
+            mev_count:                   value.mev_count.into(),
+            eth_price:                   value.eth_price,
+            total_gas_used:              value.total_gas_used,
+            total_priority_fee:          value.total_priority_fee,
+            total_bribe:                 value.total_bribe,
+            total_mev_bribe:             value.total_mev_bribe,
+            total_mev_priority_fee_paid: value.total_mev_priority_fee_paid,
+            builder_address:             value.builder_address,
+            builder_name:                value.builder_name,
+            builder_eth_profit:          value.builder_eth_profit,
+            builder_profit_usd:          value.builder_profit_usd,
+            builder_mev_profit_usd:      value.builder_mev_profit_usd,
+            builder_searcher_bribes:     value.builder_searcher_bribes,
+            builder_searcher_bribes_usd: value.builder_searcher_bribes_usd,
+            builder_sponsorship_amount:  value.builder_sponsorship_amount,
+            ultrasound_bid_adjusted:     value.ultrasound_bid_adjusted,
+            proposer_fee_recipient:      value.proposer_fee_recipient,
+            proposer_mev_reward:         value.proposer_mev_reward,
+            proposer_profit_usd:         value.proposer_profit_usd,
+            total_mev_profit_usd:        value.total_mev_profit_usd,
+            quote_pnl:                   vec![],
+            possible_mev:                value.possible_mev,
+        }
+    }
+}
+
+impl From<MevCountV0> for MevCount {
+    fn from(value: MevCountV0) -> Self {
+        Self {
+            bundle_count:         value.bundle_count,
+            sandwich_count:       value.sandwich_count,
+            cex_dex_trade_count:  value.cex_dex_trade_count,
+            cex_dex_quote_count:  value.cex_dex_quote_count,
+            cex_dex_rfq_count:    value.cex_dex_rfq_count,
+            jit_cex_dex_count:    value.jit_cex_dex_count,
+            jit_count:            value.jit_count,
+            jit_sandwich_count:   value.jit_sandwich_count,
+            atomic_backrun_count: value.atomic_backrun_count,
+            liquidation_count:    value.liquidation_count,
+            searcher_tx_count:    value.searcher_tx_count,
+            nft_arb_count:        None,
+        }
+    }
+}
+
+impl From<BundleV0> for Bundle {
+    fn from(value: BundleV0) -> Self {
+        Self { header: value.header.into(), data: value.data.into() }
+    }
+}
+
+impl From<BundleHeaderV0> for BundleHeader {
+    fn from(value: BundleHeaderV0) -> Self {
+        Self {
+            block_number:          value.block_number,
+            tx_index:              value.tx_index,
+            tx_hash:               value.tx_hash,
+            eoa:                   value.eoa,
+            mev_contract:          value.mev_contract,
+            fund:                  value.fund,
+            profit_usd:            value.profit_usd,
+            bribe_usd:             value.bribe_usd,
+            mev_type:              value.mev_type,
+            no_pricing_calculated: value.no_pricing_calculated,
+            balance_deltas:        value.balance_deltas,
+            quote_pnl:             vec![],
+            refund_usd:            0.0,
+        }
+    }
+}
+
+impl From<BundleDataV0> for BundleData {
+    fn from(value: BundleDataV0) -> Self {
+        match value {
+            BundleDataV0::Sandwich(s) => BundleData::Sandwich(s.into()),
+            BundleDataV0::AtomicArb(a) => BundleData::AtomicArb(a.into()),
+            BundleDataV0::JitSandwich(j) => BundleData::JitSandwich(j),
+            BundleDataV0::Jit(j) => BundleData::Jit(j),
+            BundleDataV0::CexDexQuote(c) => BundleData::CexDexQuote(c),
+            BundleDataV0::CexDex(c) => BundleData::CexDex(c),
+            BundleDataV0::Liquidation(l) => BundleData::Liquidation(l),
+            BundleDataV0::Unknown(s) => BundleData::Unknown(s),
+        }
+    }
+}
+
+impl From<SandwichV0> for Sandwich {
+    fn from(value: SandwichV0) -> Self {
+        Self {
+            block_number:             value.block_number,
+            frontrun_tx_hash:         value.frontrun_tx_hash,
+            frontrun_swaps:           value.frontrun_swaps,
+            frontrun_gas_details:     value.frontrun_gas_details,
+            victim_swaps_tx_hashes:   value.victim_swaps_tx_hashes,
+            victim_swaps:             value.victim_swaps,
+            victim_swaps_gas_details: value.victim_swaps_gas_details,
+            backrun_tx_hash:          value.backrun_tx_hash,
+            backrun_swaps:            value.backrun_swaps,
+            backrun_gas_details:      value.backrun_gas_details,
+            refunds:                  vec![],
+            victim_losses:            vec![],
+        }
+    }
+}
+
+impl From<AtomicArbV0> for AtomicArb {
+    fn from(value: AtomicArbV0) -> Self {
+        Self {
+            tx_hash:      value.tx_hash,
+            trigger_tx:   value.trigger_tx,
+            block_number: value.block_number,
+            swaps:        value.swaps,
+            gas_details:  value.gas_details,
+            arb_type:     value.arb_type,
+            refunds:      vec![],
+        }
+    }
+}
+
+impl From<SearcherInfoV0> for SearcherInfo {
+    fn from(value: SearcherInfoV0) -> Self {
+        Self {
+            name:                 value.name,
+            fund:                 value.fund,
+            mev_count:            value.mev_count.into(),
+            pnl:                  value.pnl.into(),
+            gas_bids:             value.gas_bids.into(),
+            builder:              value.builder,
+            config_labels:        value.config_labels,
+            sibling_searchers:    value.sibling_searchers,
+            failed_attempt_count: 0,
+            wasted_gas:           TollByType::default(),
+        }
+    }
+}
+
+impl From<TollByTypeV0> for TollByType {
+    fn from(value: TollByTypeV0) -> Self {
+        Self {
+            total:          value.total,
+            sandwich:       value.sandwich,
+            cex_dex_quotes: value.cex_dex_quotes,
+            cex_dex_trades: value.cex_dex_trades,
+            jit:            value.jit,
+            jit_sandwich:   value.jit_sandwich,
+            atomic_backrun: value.atomic_backrun,
+            liquidation:    value.liquidation,
+            searcher_tx:    value.searcher_tx,
+            nft_arb:        None,
+        }
+    }
+}
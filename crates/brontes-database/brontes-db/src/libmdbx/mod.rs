@@ -2,7 +2,7 @@
 #![allow(private_bounds)]
 
 use std::{
-    ffi::c_int,
+    ffi::{c_int, CString},
     path::Path,
     time::{Duration, Instant},
 };
@@ -18,6 +18,8 @@ pub mod libmdbx_writer;
 
 pub mod initialize;
 mod libmdbx_read_write;
+mod migrations;
+mod schema;
 use brontes_libmdbx::{RO, RW};
 use env::{DatabaseArguments, DatabaseEnv, DatabaseEnvKind};
 use eyre::Context;
@@ -34,6 +36,7 @@ use reth_db::{
     DatabaseError,
 };
 use reth_interfaces::db::LogLevel;
+pub use schema::TableSchema;
 use tables::*;
 use tracing::info;
 
@@ -67,8 +70,21 @@ pub(crate) fn mdbx_result(err_code: c_int) -> eyre::Result<bool> {
 
 impl Libmdbx {
     /// Opens up an existing database or creates a new one at the specified
-    /// path. Creates tables if necessary. Opens in read/write mode.
+    /// path. Creates tables if necessary. Opens in read/write mode. Errors if a
+    /// table is stored in a schema version this build can't read
     pub fn init_db<P: AsRef<Path>>(path: P, log_level: Option<LogLevel>) -> eyre::Result<Self> {
+        let this = Self::init_db_unchecked(path, log_level)?;
+        this.check_schema_versions()?;
+
+        Ok(this)
+    }
+
+    /// Opens the database like [`Libmdbx::init_db`] without checking the
+    /// schema versions of the tables, so that outdated tables can be migrated
+    pub fn init_db_unchecked<P: AsRef<Path>>(
+        path: P,
+        log_level: Option<LogLevel>,
+    ) -> eyre::Result<Self> {
         let rpath = path.as_ref();
         if is_database_empty(rpath) {
             std::fs::create_dir_all(rpath).wrap_err_with(|| {
@@ -104,13 +120,39 @@ impl Libmdbx {
         Ok(this)
     }
 
-    /// Creates all the defined tables, opens if already created
-    fn create_tables(&self) -> Result<(), DatabaseError> {
+    /// Opens an existing database in read only mode. Safe to use next to a
+    /// brontes process that has the database open for writing
+    pub fn init_db_read_only<P: AsRef<Path>>(
+        path: P,
+        log_level: Option<LogLevel>,
+    ) -> eyre::Result<Self> {
+        let rpath = path.as_ref();
+        if is_database_empty(rpath) {
+            eyre::bail!("no database found at {}", rpath.display());
+        }
+        check_db_version_file(rpath)?;
+
+        let db = DatabaseEnv::open(
+            rpath,
+            DatabaseEnvKind::RO,
+            DatabaseArguments::new(ClientVersion::default()).with_log_level(log_level),
+        )?;
+
+        let this = Self(db);
+        this.check_schema_versions()?;
+
+        Ok(this)
+    }
+
+    /// Creates all the defined tables, opens if already created. Tables that
+    /// are created get stamped with the current schema version
+    fn create_tables(&self) -> eyre::Result<()> {
         let tx = CompressedLibmdbxTx::new_rw_tx(&self.0)?;
 
         for table in Tables::ALL {
             tx.0.create_table(&table)?;
         }
+        Self::stamp_new_tables(&tx)?;
 
         tx.commit()?;
 
@@ -131,6 +173,22 @@ impl Libmdbx {
         Ok(())
     }
 
+    /// Writes a compacted copy of the database to the `dest` file. Pages freed
+    /// by deletes are left out, so the copy only takes the space of the live
+    /// data
+    pub fn copy_compacted<P: AsRef<Path>>(&self, dest: P) -> eyre::Result<()> {
+        let dest = CString::new(dest.as_ref().to_string_lossy().as_bytes())?;
+        self.0.with_raw_env_ptr(|ptr| unsafe {
+            mdbx_result(reth_mdbx_sys::mdbx_env_copy(
+                ptr,
+                dest.as_ptr(),
+                reth_mdbx_sys::MDBX_CP_COMPACT,
+            ))
+        })?;
+
+        Ok(())
+    }
+
     /// writes to a table
     pub fn write_table<T, D>(&self, entries: &[D]) -> Result<(), DatabaseError>
     where
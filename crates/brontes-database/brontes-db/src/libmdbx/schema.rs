@@ -0,0 +1,255 @@
+use brontes_libmdbx::RW;
+use brontes_types::db::schema_version::SchemaVersion;
+use indicatif::ProgressBar;
+
+use super::{
+    tables::{Migration, SchemaVersions, Tables},
+    CompressedLibmdbxTx, Libmdbx,
+};
+
+/// The schema version a table is stored in next to the one this build reads
+#[derive(Debug, Clone, Copy)]
+pub struct TableSchema {
+    pub table:           Tables,
+    pub stored_version:  u32,
+    pub current_version: u32,
+    pub entries:         usize,
+}
+
+impl TableSchema {
+    pub fn is_outdated(&self) -> bool {
+        self.stored_version < self.current_version
+    }
+
+    /// The table was written by a newer build of brontes
+    pub fn is_ahead(&self) -> bool {
+        self.stored_version > self.current_version
+    }
+
+    pub fn pending_migrations(&self) -> impl Iterator<Item = &'static Migration> + '_ {
+        self.table.pending_migrations(self.stored_version)
+    }
+}
+
+impl Libmdbx {
+    /// Errors if any table is stored in a layout this build can't read
+    pub(crate) fn check_schema_versions(&self) -> eyre::Result<()> {
+        check_schemas(self.table_schemas()?)
+    }
+
+    /// The schema of every table. Only reads, tables without a stored version
+    /// are reported in the version they would be stamped with. Tables that
+    /// don't exist yet, which happens when an older db is opened read only,
+    /// are treated as empty
+    pub fn table_schemas(&self) -> eyre::Result<Vec<TableSchema>> {
+        self.view_db(|tx| {
+            let versions_stored = tx.table_exists::<SchemaVersions>()?;
+            Tables::ALL
+                .into_iter()
+                .map(|table| {
+                    let current_version = table.schema_version();
+                    let entries =
+                        if tx.0.table_exists(&table)? { tx.0.table_entries(&table)? } else { 0 };
+                    let stored_version = if versions_stored {
+                        tx.get::<SchemaVersions>(table.name().to_string())?
+                    } else {
+                        None
+                    }
+                    .map(|SchemaVersion { version }| version)
+                    .unwrap_or_else(|| unversioned(entries, current_version));
+
+                    Ok(TableSchema { table, stored_version, current_version, entries })
+                })
+                .collect()
+        })
+    }
+
+    /// Stamps the tables that are still empty and have no stored version with
+    /// the current one, so rows written by this build are never taken for
+    /// rows of version 0. Run in the tx that creates the tables
+    pub(crate) fn stamp_new_tables(tx: &CompressedLibmdbxTx<RW>) -> eyre::Result<()> {
+        let mut new_tables = Vec::new();
+        for table in Tables::ALL {
+            if tx.0.table_entries(&table)? == 0
+                && tx
+                    .get::<SchemaVersions>(table.name().to_string())?
+                    .is_none()
+            {
+                new_tables.push(table);
+            }
+        }
+
+        for table in new_tables {
+            tx.put::<SchemaVersions>(
+                table.name().to_string(),
+                SchemaVersion::new(table.schema_version()),
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Runs the pending migrations of the table in order. Each migration is
+    /// committed together with the version bump, so an interrupted migrate
+    /// picks up at the migration it failed on. Returns the amount of rows
+    /// rewritten
+    pub fn migrate_table(
+        &self,
+        schema: &TableSchema,
+        pb: Option<&ProgressBar>,
+    ) -> eyre::Result<usize> {
+        let mut version = schema.stored_version;
+        let mut rows = 0;
+
+        for migration in schema.pending_migrations() {
+            if migration.from_version != version {
+                eyre::bail!(
+                    "no migration registered for {} from schema version {version}",
+                    schema.table
+                );
+            }
+            tracing::info!(table = %schema.table, version, migration = migration.description, "migrating");
+
+            let tx = self.rw_tx()?;
+            rows += tx.0.rewrite_values(&schema.table, |value| {
+                pb.inspect(|pb| pb.inc(1));
+                (migration.migrate)(value)
+            })?;
+            version += 1;
+            tx.put::<SchemaVersions>(schema.table.name().to_string(), SchemaVersion::new(version))?;
+            tx.commit()?;
+        }
+
+        Ok(rows)
+    }
+}
+
+/// The version of a table that has none stored. Rows written before versioning
+/// was added are in the layout of version 0, empty tables can start out at the
+/// current version
+fn unversioned(entries: usize, current_version: u32) -> u32 {
+    if entries == 0 {
+        current_version
+    } else {
+        0
+    }
+}
+
+fn check_schemas(schemas: Vec<TableSchema>) -> eyre::Result<()> {
+    for schema in schemas {
+        if schema.is_ahead() {
+            eyre::bail!(
+                "{} is stored in schema version {} but this build of brontes only reads up to \
+                 version {}, upgrade brontes to open this db",
+                schema.table,
+                schema.stored_version,
+                schema.current_version
+            );
+        }
+        if schema.is_outdated() {
+            eyre::bail!(
+                "{} is stored in schema version {} but this build of brontes reads version {}, \
+                 run `brontes db migrate` to convert it",
+                schema.table,
+                schema.stored_version,
+                schema.current_version
+            );
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use brontes_types::db::mev_block::MevBlockWithClassified;
+    use reth_db::table::Encode;
+
+    use super::*;
+    use crate::libmdbx::{
+        migrations::{tests::*, MevBlockWithClassifiedV0Redefined},
+        tables::{MevBlocks, MIGRATIONS},
+    };
+
+    fn stored_version(db: &Libmdbx, table: Tables) -> Option<u32> {
+        db.view_db(|tx| {
+            Ok(tx
+                .get::<SchemaVersions>(table.name().to_string())?
+                .map(|SchemaVersion { version }| version))
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_migrations_are_registered_in_order() {
+        for table in Tables::ALL {
+            let from_versions = MIGRATIONS
+                .iter()
+                .filter(|migration| migration.table == table)
+                .map(|migration| migration.from_version)
+                .collect::<Vec<_>>();
+
+            assert_eq!(from_versions, (0..table.schema_version()).collect::<Vec<_>>(), "{table}");
+        }
+    }
+
+    #[test]
+    fn test_pending_migrations() {
+        assert_eq!(Tables::MevBlocks.schema_version(), 1);
+        assert_eq!(Tables::MevBlocks.pending_migrations(0).count(), 1);
+        assert_eq!(Tables::MevBlocks.pending_migrations(1).count(), 0);
+
+        assert_eq!(Tables::DexPrice.schema_version(), 0);
+        assert_eq!(Tables::DexPrice.pending_migrations(0).count(), 0);
+    }
+
+    #[test]
+    fn test_new_tables_are_stamped_on_create() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Libmdbx::init_db(dir.path(), None).unwrap();
+
+        for table in Tables::ALL {
+            assert_eq!(stored_version(&db, table), Some(table.schema_version()), "{table}");
+        }
+    }
+
+    #[test]
+    fn test_migrate_table() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Libmdbx::init_db_unchecked(dir.path(), None).unwrap();
+
+        // a mev block written before the table was versioned
+        let old = mev_block_v0();
+        db.update_db(|tx| {
+            tx.delete::<SchemaVersions>(Tables::MevBlocks.name().to_string(), None)?;
+            tx.put_bytes::<MevBlocks>(
+                &old.block.block_number.encode(),
+                stored_v0::<_, MevBlockWithClassifiedV0Redefined>(old.clone()),
+            )
+        })
+        .unwrap()
+        .unwrap();
+
+        let schema = db
+            .table_schemas()
+            .unwrap()
+            .into_iter()
+            .find(|schema| schema.table == Tables::MevBlocks)
+            .unwrap();
+        assert_eq!(schema.stored_version, 0);
+        assert_eq!(schema.entries, 1);
+        assert!(schema.is_outdated());
+        assert!(db.check_schema_versions().is_err());
+        // reading the schemas doesn't stamp the table
+        assert_eq!(stored_version(&db, Tables::MevBlocks), None);
+
+        assert_eq!(db.migrate_table(&schema, None).unwrap(), 1);
+
+        assert_eq!(stored_version(&db, Tables::MevBlocks), Some(1));
+        db.check_schema_versions().unwrap();
+        let migrated = db
+            .view_db(|tx| Ok(tx.get::<MevBlocks>(old.block.block_number)?))
+            .unwrap();
+        assert_eq!(migrated, Some(MevBlockWithClassified::from(old)));
+    }
+}
@@ -10,25 +10,32 @@ use brontes_types::{
     db::{
         address_metadata::{AddressMetadata, AddressMetadataRedefined},
         address_to_protocol_info::{ProtocolInfo, ProtocolInfoRedefined},
+        block_analysis::{BlockAnalysis, BlockAnalysisRedefined},
         builder::{BuilderInfo, BuilderInfoRedefined},
         cex::{
             quotes::{CexPriceMap, CexPriceMapRedefined},
             trades::{CexTradeMap, CexTradeMapRedefined},
         },
+        cex_dex_inventory::{DailyCexDexInventory, DailyCexDexInventoryRedefined},
         clickhouse_serde::tx_trace::tx_traces_inner,
         dex::{DexKey, DexQuoteWithIndex, DexQuoteWithIndexRedefined},
         initialized_state::{
             InitializedStateMeta, CEX_QUOTES_FLAG, CEX_TRADES_FLAG, DEX_PRICE_FLAG, META_FLAG,
             TRACE_FLAG,
         },
+        lvr::{BlockLvr, BlockLvrRedefined},
         metadata::{BlockMetadataInner, BlockMetadataInnerRedefined},
         mev_block::{MevBlockWithClassified, MevBlockWithClassifiedRedefined},
+        normalized_actions::{ClassifiedBlock, ClassifiedBlockRedefined},
         pool_creation_block::{PoolsToAddresses, PoolsToAddressesRedefined},
+        run_checkpoint::{RunCheckpoint, RunCheckpointRedefined},
+        schema_version::{SchemaVersion, SchemaVersionRedefined},
         searcher::{SearcherInfo, SearcherInfoRedefined},
         token_info::TokenInfo,
         traces::{TxTracesInner, TxTracesInnerRedefined},
         traits::LibmdbxReader,
     },
+    mev::{BlockFailedAttempts, BlockFailedAttemptsRedefined},
     serde_utils::*,
     traits::TracingProvider,
 };
@@ -40,7 +47,7 @@ use serde_with::serde_as;
 
 use crate::{
     clickhouse::ClickhouseHandle,
-    libmdbx::{types::ReturnKV, utils::protocol_info, LibmdbxData, LibmdbxReadWriter},
+    libmdbx::{migrations, types::ReturnKV, utils::protocol_info, LibmdbxData, LibmdbxReadWriter},
     parquet::ParquetExporter,
 };
 mod const_sql;
@@ -58,7 +65,7 @@ use super::{
     CompressedTable,
 };
 
-pub const NUM_TABLES: usize = 14;
+pub const NUM_TABLES: usize = 21;
 
 macro_rules! tables {
     ($($table:ident),*) => {
@@ -252,7 +259,14 @@ impl Tables {
                     )
                     .await
             }
-            Tables::MevBlocks => Ok(()),
+            Tables::MevBlocks
+            | Tables::BlockAnalyses
+            | Tables::ClassifiedBlocks
+            | Tables::BlockLvrs
+            | Tables::FailedMevAttempts
+            | Tables::CexDexInventory
+            | Tables::RunCheckpoints
+            | Tables::SchemaVersions => Ok(()),
             Tables::TxTraces => {
                 initializer
                     .initialize_table_from_clickhouse::<TxTraces, TxTracesData>(
@@ -373,6 +387,16 @@ impl Tables {
             Self::MevBlocks => exporter.export_mev_blocks().await,
             Self::SearcherContracts | Self::SearcherEOAs => exporter.export_searcher_info().await,
             Self::Builder => exporter.export_builder_info().await,
+            Self::BlockAnalyses => exporter.export_block_analysis().await,
+            Self::ClassifiedBlocks => exporter.export_classified_blocks().await,
+            Self::BlockLvrs => exporter.export_pool_lvr().await,
+            Self::FailedMevAttempts => exporter.export_failed_attempts().await,
+            Self::CexDexInventory => exporter.export_cex_dex_inventory().await,
+            Self::DexPrice => exporter.export_dex_prices().await,
+            Self::CexTrades => exporter.export_cex_trades().await,
+            Self::CexPrice => exporter.export_cex_quotes().await,
+            Self::TokenDecimals => exporter.export_token_info().await,
+            Self::AddressToProtocolInfo => exporter.export_protocol_info().await,
             _ => unreachable!("Parquet export not yet supported for this table"),
         }
     }
@@ -537,9 +561,74 @@ tables!(
     SearcherEOAs,
     SearcherContracts,
     InitializedState,
-    CexTrades
+    CexTrades,
+    BlockAnalyses,
+    ClassifiedBlocks,
+    BlockLvrs,
+    FailedMevAttempts,
+    CexDexInventory,
+    RunCheckpoints,
+    SchemaVersions
 );
 
+/// Converts the values of a table from the archived layout of schema version
+/// `from_version` to the layout of `from_version + 1`. The stored bytes are
+/// passed as is, so the old layout is decoded with a frozen copy of the
+/// `Redefined` type it was written with
+#[derive(Debug, Clone, Copy)]
+pub struct Migration {
+    pub table:        Tables,
+    pub from_version: u32,
+    pub description:  &'static str,
+    pub migrate:      fn(&[u8]) -> eyre::Result<Vec<u8>>,
+}
+
+/// Every migration in the order they are applied. Changing the layout of a
+/// table value is done by appending a migration for the table here, the schema
+/// version of a table is the amount of migrations registered for it
+pub static MIGRATIONS: &[Migration] = &[
+    Migration {
+        table:        Tables::MevBlocks,
+        from_version: 0,
+        description:  "quote asset pnl, ofa refunds, victim losses & nft arbs",
+        migrate:      migrations::mev_blocks_v0,
+    },
+    Migration {
+        table:        Tables::SearcherEOAs,
+        from_version: 0,
+        description:  "failed attempts, wasted gas & nft arbs",
+        migrate:      migrations::searcher_info_v0,
+    },
+    Migration {
+        table:        Tables::SearcherContracts,
+        from_version: 0,
+        description:  "failed attempts, wasted gas & nft arbs",
+        migrate:      migrations::searcher_info_v0,
+    },
+];
+
+impl Tables {
+    /// The schema version the values of this table are written in by this
+    /// build
+    pub fn schema_version(&self) -> u32 {
+        MIGRATIONS
+            .iter()
+            .filter(|migration| migration.table == *self)
+            .count() as u32
+    }
+
+    /// The migrations that bring the table from `stored_version` to the
+    /// current schema version, in order
+    pub fn pending_migrations(
+        &self,
+        stored_version: u32,
+    ) -> impl Iterator<Item = &'static Migration> + '_ {
+        MIGRATIONS.iter().filter(move |migration| {
+            migration.table == *self && migration.from_version >= stored_version
+        })
+    }
+}
+
 /// Must be in this order when defining
 /// Table {
 ///     Data {
@@ -1031,3 +1120,129 @@ compressed_table!(
         }
     }
 );
+
+compressed_table!(
+    Table BlockAnalyses {
+        Data {
+            key: u64,
+            value: BlockAnalysis,
+            compressed_value: BlockAnalysisRedefined
+        },
+        Init {
+            init_size: None,
+            init_method: Other,
+            http_endpoint: None
+        },
+        CLI {
+            can_insert: False
+        }
+    }
+);
+
+compressed_table!(
+    Table ClassifiedBlocks {
+        Data {
+            key: u64,
+            value: ClassifiedBlock,
+            compressed_value: ClassifiedBlockRedefined
+        },
+        Init {
+            init_size: None,
+            init_method: Other,
+            http_endpoint: None
+        },
+        CLI {
+            can_insert: False
+        }
+    }
+);
+
+compressed_table!(
+    Table BlockLvrs {
+        Data {
+            key: u64,
+            value: BlockLvr,
+            compressed_value: BlockLvrRedefined
+        },
+        Init {
+            init_size: None,
+            init_method: Other,
+            http_endpoint: None
+        },
+        CLI {
+            can_insert: False
+        }
+    }
+);
+
+compressed_table!(
+    Table FailedMevAttempts {
+        Data {
+            key: u64,
+            value: BlockFailedAttempts,
+            compressed_value: BlockFailedAttemptsRedefined
+        },
+        Init {
+            init_size: None,
+            init_method: Other,
+            http_endpoint: None
+        },
+        CLI {
+            can_insert: False
+        }
+    }
+);
+
+compressed_table!(
+    Table CexDexInventory {
+        Data {
+            key: u64,
+            value: DailyCexDexInventory,
+            compressed_value: DailyCexDexInventoryRedefined
+        },
+        Init {
+            init_size: None,
+            init_method: Other,
+            http_endpoint: None
+        },
+        CLI {
+            can_insert: False
+        }
+    }
+);
+
+compressed_table!(
+    Table RunCheckpoints {
+        Data {
+            key: u64,
+            value: RunCheckpoint,
+            compressed_value: RunCheckpointRedefined
+        },
+        Init {
+            init_size: None,
+            init_method: Other,
+            http_endpoint: None
+        },
+        CLI {
+            can_insert: False
+        }
+    }
+);
+
+compressed_table!(
+    Table SchemaVersions {
+        Data {
+            key: String,
+            value: SchemaVersion,
+            compressed_value: SchemaVersionRedefined
+        },
+        Init {
+            init_size: None,
+            init_method: Other,
+            http_endpoint: None
+        },
+        CLI {
+            can_insert: False
+        }
+    }
+);
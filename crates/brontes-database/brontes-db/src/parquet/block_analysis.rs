@@ -0,0 +1,92 @@
+use std::sync::Arc;
+
+use arrow::{
+    array::{Array, Float64Builder, StringBuilder},
+    datatypes::{DataType, Field, Schema},
+    error::ArrowError,
+    record_batch::RecordBatch,
+};
+use brontes_types::db::block_analysis::BlockAnalysis;
+
+use super::utils::{build_float64_array, build_record_batch, build_uint64_array};
+
+/// Exports the headline per block figures of the analysis, the per searcher /
+/// pool / dex breakdowns are left to the ClickHouse tables.
+pub fn block_analysis_to_record_batch(
+    analyses: Vec<BlockAnalysis>,
+) -> Result<RecordBatch, ArrowError> {
+    let mut fields = Vec::new();
+    let mut columns: Vec<Arc<dyn Array>> = Vec::new();
+
+    let mut push_u64 = |name: &str, f: &dyn Fn(&BlockAnalysis) -> u64| {
+        fields.push(Field::new(name, DataType::UInt64, false));
+        columns.push(Arc::new(build_uint64_array(analyses.iter().map(f).collect())));
+    };
+    push_u64("block_number", &|a| a.block_number);
+    push_u64("all_bundle_count", &|a| a.all_bundle_count);
+    push_u64("all_searcher_count", &|a| a.all_searcher_count);
+    push_u64("all_fund_count", &|a| a.all_fund_count);
+    push_u64("atomic_bundle_count", &|a| a.atomic_bundle_count);
+    push_u64("sandwich_bundle_count", &|a| a.sandwich_bundle_count);
+    push_u64("jit_bundle_count", &|a| a.jit_bundle_count);
+    push_u64("jit_sandwich_bundle_count", &|a| a.jit_sandwich_bundle_count);
+    push_u64("cex_dex_bundle_count", &|a| a.cex_dex_bundle_count);
+    push_u64("liquidation_bundle_count", &|a| a.liquidation_bundle_count);
+
+    let mut push_f64 = |name: &str, f: &dyn Fn(&BlockAnalysis) -> f64| {
+        fields.push(Field::new(name, DataType::Float64, false));
+        columns.push(Arc::new(build_float64_array(analyses.iter().map(f).collect())));
+    };
+    push_f64("eth_price", &|a| a.eth_price);
+    push_f64("all_total_profit", &|a| a.all_total_profit);
+    push_f64("all_total_revenue", &|a| a.all_total_revenue);
+    push_f64("all_average_profit_margin", &|a| a.all_average_profit_margin);
+    push_f64("atomic_total_profit", &|a| a.atomic_total_profit);
+    push_f64("atomic_total_revenue", &|a| a.atomic_total_revenue);
+    push_f64("sandwich_total_profit", &|a| a.sandwich_total_profit);
+    push_f64("sandwich_total_revenue", &|a| a.sandwich_total_revenue);
+    push_f64("jit_total_profit", &|a| a.jit_total_profit);
+    push_f64("jit_total_revenue", &|a| a.jit_total_revenue);
+    push_f64("jit_sandwich_total_profit", &|a| a.jit_sandwich_total_profit);
+    push_f64("jit_sandwich_total_revenue", &|a| a.jit_sandwich_total_revenue);
+    push_f64("cex_dex_total_profit", &|a| a.cex_dex_total_profit);
+    push_f64("cex_dex_total_revenue", &|a| a.cex_dex_total_revenue);
+    push_f64("liquidation_total_profit", &|a| a.liquidation_total_profit);
+    push_f64("liquidation_total_revenue", &|a| a.liquidation_total_revenue);
+
+    let mut push_opt_f64 = |name: &str, f: &dyn Fn(&BlockAnalysis) -> Option<f64>| {
+        let mut builder = Float64Builder::new();
+        analyses.iter().for_each(|a| builder.append_option(f(a)));
+        fields.push(Field::new(name, DataType::Float64, true));
+        columns.push(Arc::new(builder.finish()));
+    };
+    push_opt_f64("all_top_searcher_profit_amt", &|a| a.all_top_searcher_profit_amt);
+    push_opt_f64("all_top_searcher_revenue_amt", &|a| a.all_top_searcher_revenue_amt);
+    push_opt_f64("all_biggest_arb_profit_amt", &|a| a.all_biggest_arb_profit_amt);
+    push_opt_f64("all_biggest_arb_revenue_amt", &|a| a.all_biggest_arb_revenue_amt);
+
+    let mut push_opt_string = |name: &str, f: &dyn Fn(&BlockAnalysis) -> Option<String>| {
+        let mut builder = StringBuilder::new();
+        analyses.iter().for_each(|a| builder.append_option(f(a)));
+        fields.push(Field::new(name, DataType::Utf8, true));
+        columns.push(Arc::new(builder.finish()));
+    };
+    push_opt_string("all_top_searcher_profit", &|a| {
+        a.all_top_searcher_profit.map(|s| s.to_string())
+    });
+    push_opt_string("all_top_searcher_revenue", &|a| {
+        a.all_top_searcher_revenue.map(|s| s.to_string())
+    });
+    push_opt_string("all_top_fund_profit", &|a| {
+        a.all_top_fund_profit.as_ref().map(|f| f.to_string())
+    });
+    push_opt_string("all_most_arbed_pool_profit", &|a| {
+        a.all_most_arbed_pool_profit.map(|p| p.to_string())
+    });
+    push_opt_string("all_most_arbed_dex_profit", &|a| {
+        a.all_most_arbed_dex_profit.map(|p| p.to_string())
+    });
+    push_opt_string("all_biggest_arb_profit", &|a| a.all_biggest_arb_profit.map(|h| h.to_string()));
+
+    build_record_batch(Schema::new(fields), columns)
+}
@@ -10,6 +10,7 @@ use brontes_types::mev::BundleHeader;
 
 use super::utils::{
     build_float64_array, build_record_batch, build_string_array, build_uint64_array,
+    get_list_float_array_from_owned, get_list_string_array_from_owned,
 };
 
 pub fn bundle_headers_to_record_batch(
@@ -33,12 +34,37 @@ pub fn bundle_headers_to_record_batch(
         build_float64_array(bundle_headers.iter().map(|bh| bh.profit_usd).collect());
     let bribe_usd_array =
         build_float64_array(bundle_headers.iter().map(|bh| bh.bribe_usd).collect());
+    let refund_usd_array =
+        build_float64_array(bundle_headers.iter().map(|bh| bh.refund_usd).collect());
     let mev_type_array = build_string_array(
         bundle_headers
             .iter()
             .map(|bh| bh.mev_type.to_string())
             .collect(),
     );
+    let quote_pnl_asset_array = get_list_string_array_from_owned(
+        bundle_headers
+            .iter()
+            .map(|bh| {
+                bh.quote_pnl
+                    .iter()
+                    .map(|pnl| pnl.quote_asset.to_string())
+                    .collect()
+            })
+            .collect(),
+    );
+    let quote_pnl_profit_array = get_list_float_array_from_owned(
+        bundle_headers
+            .iter()
+            .map(|bh| bh.quote_pnl.iter().map(|pnl| pnl.profit).collect())
+            .collect(),
+    );
+    let quote_pnl_revenue_array = get_list_float_array_from_owned(
+        bundle_headers
+            .iter()
+            .map(|bh| bh.quote_pnl.iter().map(|pnl| pnl.revenue).collect())
+            .collect(),
+    );
 
     let schema = Schema::new(vec![
         Field::new("block_number", DataType::UInt64, false),
@@ -48,7 +74,23 @@ pub fn bundle_headers_to_record_batch(
         Field::new("mev_contract", DataType::Utf8, true),
         Field::new("profit_usd", DataType::Float64, false),
         Field::new("bribe_usd", DataType::Float64, false),
+        Field::new("refund_usd", DataType::Float64, false),
         Field::new("mev_type", DataType::Utf8, false),
+        Field::new(
+            "quote_pnl_asset",
+            DataType::List(Arc::new(Field::new("item", DataType::Utf8, true))),
+            true,
+        ),
+        Field::new(
+            "quote_pnl_profit",
+            DataType::List(Arc::new(Field::new("item", DataType::Float64, true))),
+            true,
+        ),
+        Field::new(
+            "quote_pnl_revenue",
+            DataType::List(Arc::new(Field::new("item", DataType::Float64, true))),
+            true,
+        ),
     ]);
 
     build_record_batch(
@@ -61,7 +103,11 @@ pub fn bundle_headers_to_record_batch(
             Arc::new(mev_contract_array),
             Arc::new(profit_usd_array),
             Arc::new(bribe_usd_array),
+            Arc::new(refund_usd_array),
             Arc::new(mev_type_array),
+            Arc::new(quote_pnl_asset_array),
+            Arc::new(quote_pnl_profit_array),
+            Arc::new(quote_pnl_revenue_array),
         ],
     )
 }
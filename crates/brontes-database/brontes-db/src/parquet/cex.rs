@@ -0,0 +1,122 @@
+use std::sync::Arc;
+
+use arrow::{
+    datatypes::{DataType, Field, Schema},
+    error::ArrowError,
+    record_batch::RecordBatch,
+};
+use brontes_types::{
+    db::cex::{quotes::CexPriceMap, trades::CexTradeMap},
+    ToFloatNearest,
+};
+use itertools::Itertools;
+
+use super::utils::{
+    build_float64_array, build_record_batch, build_string_array, build_uint64_array,
+};
+
+/// One row per trade
+pub fn cex_trades_to_record_batch(
+    trades: Vec<(u64, CexTradeMap)>,
+) -> Result<RecordBatch, ArrowError> {
+    let rows = trades
+        .iter()
+        .flat_map(|(block, map)| {
+            map.0.iter().flat_map(move |(exchange, pairs)| {
+                pairs.iter().flat_map(move |(pair, trades)| {
+                    trades
+                        .iter()
+                        .map(move |trade| (*block, exchange, pair, trade))
+                })
+            })
+        })
+        .collect_vec();
+
+    let schema = Schema::new(vec![
+        Field::new("block_number", DataType::UInt64, false),
+        Field::new("exchange", DataType::Utf8, false),
+        Field::new("base_token", DataType::Utf8, false),
+        Field::new("quote_token", DataType::Utf8, false),
+        Field::new("timestamp", DataType::UInt64, false),
+        Field::new("price", DataType::Float64, false),
+        Field::new("amount", DataType::Float64, false),
+    ]);
+
+    build_record_batch(
+        schema,
+        vec![
+            Arc::new(build_uint64_array(rows.iter().map(|r| r.0).collect())),
+            Arc::new(build_string_array(rows.iter().map(|r| r.1.to_string()).collect())),
+            Arc::new(build_string_array(rows.iter().map(|r| r.2 .0.to_string()).collect())),
+            Arc::new(build_string_array(rows.iter().map(|r| r.2 .1.to_string()).collect())),
+            Arc::new(build_uint64_array(rows.iter().map(|r| r.3.timestamp).collect())),
+            Arc::new(build_float64_array(
+                rows.iter().map(|r| r.3.price.clone().to_float()).collect(),
+            )),
+            Arc::new(build_float64_array(
+                rows.iter().map(|r| r.3.amount.clone().to_float()).collect(),
+            )),
+        ],
+    )
+}
+
+/// One row per best bid / ask quote
+pub fn cex_quotes_to_record_batch(
+    quotes: Vec<(u64, CexPriceMap)>,
+) -> Result<RecordBatch, ArrowError> {
+    let rows = quotes
+        .iter()
+        .flat_map(|(block, map)| {
+            map.quotes.iter().flat_map(move |(exchange, pairs)| {
+                pairs.iter().flat_map(move |(pair, quotes)| {
+                    quotes
+                        .iter()
+                        .map(move |quote| (*block, exchange, pair, quote))
+                })
+            })
+        })
+        .collect_vec();
+
+    let schema = Schema::new(vec![
+        Field::new("block_number", DataType::UInt64, false),
+        Field::new("exchange", DataType::Utf8, false),
+        Field::new("base_token", DataType::Utf8, false),
+        Field::new("quote_token", DataType::Utf8, false),
+        Field::new("timestamp", DataType::UInt64, false),
+        Field::new("bid_price", DataType::Float64, false),
+        Field::new("ask_price", DataType::Float64, false),
+        Field::new("bid_amount", DataType::Float64, false),
+        Field::new("ask_amount", DataType::Float64, false),
+    ]);
+
+    build_record_batch(
+        schema,
+        vec![
+            Arc::new(build_uint64_array(rows.iter().map(|r| r.0).collect())),
+            Arc::new(build_string_array(rows.iter().map(|r| r.1.to_string()).collect())),
+            Arc::new(build_string_array(rows.iter().map(|r| r.2 .0.to_string()).collect())),
+            Arc::new(build_string_array(rows.iter().map(|r| r.2 .1.to_string()).collect())),
+            Arc::new(build_uint64_array(rows.iter().map(|r| r.3.timestamp).collect())),
+            Arc::new(build_float64_array(
+                rows.iter()
+                    .map(|r| r.3.price.0.clone().to_float())
+                    .collect(),
+            )),
+            Arc::new(build_float64_array(
+                rows.iter()
+                    .map(|r| r.3.price.1.clone().to_float())
+                    .collect(),
+            )),
+            Arc::new(build_float64_array(
+                rows.iter()
+                    .map(|r| r.3.amount.0.clone().to_float())
+                    .collect(),
+            )),
+            Arc::new(build_float64_array(
+                rows.iter()
+                    .map(|r| r.3.amount.1.clone().to_float())
+                    .collect(),
+            )),
+        ],
+    )
+}
@@ -0,0 +1,101 @@
+use std::sync::Arc;
+
+use arrow::{
+    datatypes::{DataType, Field, Schema},
+    error::ArrowError,
+    record_batch::RecordBatch,
+};
+use brontes_types::db::cex_dex_inventory::{DailyCexDexInventory, SECONDS_PER_DAY};
+use chrono::DateTime;
+use itertools::Itertools;
+
+use super::utils::{
+    build_float64_array, build_record_batch, build_string_array, build_uint64_array,
+};
+
+/// One row per searcher and day
+pub fn searcher_inventory_to_record_batch(
+    days: &[DailyCexDexInventory],
+) -> Result<RecordBatch, ArrowError> {
+    let rows = days
+        .iter()
+        .flat_map(|day| day.searchers.iter().map(move |searcher| (day, searcher)))
+        .collect_vec();
+
+    let schema = Schema::new(vec![
+        Field::new("date", DataType::Utf8, false),
+        Field::new("last_block", DataType::UInt64, false),
+        Field::new("searcher", DataType::Utf8, false),
+        Field::new("open_positions", DataType::UInt64, false),
+        Field::new("tx_count", DataType::UInt64, false),
+        Field::new("realized_pnl_usd", DataType::Float64, false),
+        Field::new("unrealized_pnl_usd", DataType::Float64, false),
+        Field::new("markout_pnl_usd", DataType::Float64, false),
+    ]);
+
+    build_record_batch(
+        schema,
+        vec![
+            Arc::new(build_string_array(rows.iter().map(|(day, _)| format_day(day.day)).collect())),
+            Arc::new(build_uint64_array(rows.iter().map(|(day, _)| day.last_block).collect())),
+            Arc::new(build_string_array(
+                rows.iter().map(|(_, s)| s.searcher.to_string()).collect(),
+            )),
+            Arc::new(build_uint64_array(
+                rows.iter().map(|(_, s)| s.positions.len() as u64).collect(),
+            )),
+            Arc::new(build_uint64_array(rows.iter().map(|(_, s)| s.tx_count).collect())),
+            Arc::new(build_float64_array(rows.iter().map(|(_, s)| s.realized_pnl_usd).collect())),
+            Arc::new(build_float64_array(rows.iter().map(|(_, s)| s.unrealized_pnl_usd).collect())),
+            Arc::new(build_float64_array(rows.iter().map(|(_, s)| s.markout_pnl_usd).collect())),
+        ],
+    )
+}
+
+/// One row per open position at the end of each day
+pub fn inventory_positions_to_record_batch(
+    days: &[DailyCexDexInventory],
+) -> Result<RecordBatch, ArrowError> {
+    let rows = days
+        .iter()
+        .flat_map(|day| {
+            day.searchers.iter().flat_map(move |searcher| {
+                searcher
+                    .positions
+                    .iter()
+                    .map(move |position| (day.day, searcher.searcher, position))
+            })
+        })
+        .collect_vec();
+
+    let schema = Schema::new(vec![
+        Field::new("date", DataType::Utf8, false),
+        Field::new("searcher", DataType::Utf8, false),
+        Field::new("token", DataType::Utf8, false),
+        Field::new("amount", DataType::Float64, false),
+        Field::new("avg_price_usd", DataType::Float64, false),
+        Field::new("mark_price_usd", DataType::Float64, false),
+        Field::new("unrealized_pnl_usd", DataType::Float64, false),
+    ]);
+
+    build_record_batch(
+        schema,
+        vec![
+            Arc::new(build_string_array(rows.iter().map(|(day, ..)| format_day(*day)).collect())),
+            Arc::new(build_string_array(rows.iter().map(|(_, s, _)| s.to_string()).collect())),
+            Arc::new(build_string_array(rows.iter().map(|(.., p)| p.token.to_string()).collect())),
+            Arc::new(build_float64_array(rows.iter().map(|(.., p)| p.amount).collect())),
+            Arc::new(build_float64_array(rows.iter().map(|(.., p)| p.avg_price_usd).collect())),
+            Arc::new(build_float64_array(rows.iter().map(|(.., p)| p.mark_price_usd).collect())),
+            Arc::new(build_float64_array(
+                rows.iter().map(|(.., p)| p.unrealized_pnl_usd()).collect(),
+            )),
+        ],
+    )
+}
+
+fn format_day(day: u64) -> String {
+    DateTime::from_timestamp((day * SECONDS_PER_DAY) as i64, 0)
+        .map(|date| date.format("%Y-%m-%d").to_string())
+        .unwrap_or_default()
+}
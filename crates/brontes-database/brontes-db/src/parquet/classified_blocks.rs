@@ -0,0 +1,77 @@
+use std::sync::Arc;
+
+use arrow::{
+    array::{ListBuilder, StringBuilder, UInt64Builder},
+    datatypes::{DataType, Field, Schema},
+    error::ArrowError,
+    record_batch::RecordBatch,
+};
+use brontes_types::db::normalized_actions::ClassifiedBlock;
+
+use super::utils::{build_record_batch, build_string_array, build_uint64_array};
+
+/// Flattens the classified trees into one row per trace node, with the action
+/// json encoded
+pub fn classified_blocks_to_record_batch(
+    blocks: Vec<ClassifiedBlock>,
+) -> Result<RecordBatch, ArrowError> {
+    let mut block_numbers = Vec::new();
+    let mut tx_hashes = Vec::new();
+    let mut tx_idxs = Vec::new();
+    let mut trace_idxs = Vec::new();
+    let mut trace_address_builder = ListBuilder::new(UInt64Builder::new());
+    let mut action_kind_builder = StringBuilder::new();
+    let mut action_builder = StringBuilder::new();
+
+    for block in &blocks {
+        for root in &block.tx_roots {
+            for node in &root.trace_nodes {
+                block_numbers.push(block.block_number);
+                tx_hashes.push(root.tx_hash.to_string());
+                tx_idxs.push(root.tx_idx);
+                trace_idxs.push(node.trace_idx);
+
+                trace_address_builder
+                    .values()
+                    .append_slice(&node.trace_address);
+                trace_address_builder.append(true);
+
+                action_kind_builder.append_option(node.action_kind.map(|k| format!("{k:?}")));
+                let action = node
+                    .action
+                    .as_ref()
+                    .map(serde_json::to_string)
+                    .transpose()
+                    .map_err(|e| ArrowError::ExternalError(Box::new(e)))?;
+                action_builder.append_option(action);
+            }
+        }
+    }
+
+    let schema = Schema::new(vec![
+        Field::new("block_number", DataType::UInt64, false),
+        Field::new("tx_hash", DataType::Utf8, false),
+        Field::new("tx_idx", DataType::UInt64, false),
+        Field::new("trace_idx", DataType::UInt64, false),
+        Field::new(
+            "trace_address",
+            DataType::List(Arc::new(Field::new("item", DataType::UInt64, true))),
+            true,
+        ),
+        Field::new("action_kind", DataType::Utf8, true),
+        Field::new("action", DataType::Utf8, true),
+    ]);
+
+    build_record_batch(
+        schema,
+        vec![
+            Arc::new(build_uint64_array(block_numbers)),
+            Arc::new(build_string_array(tx_hashes)),
+            Arc::new(build_uint64_array(tx_idxs)),
+            Arc::new(build_uint64_array(trace_idxs)),
+            Arc::new(trace_address_builder.finish()),
+            Arc::new(action_kind_builder.finish()),
+            Arc::new(action_builder.finish()),
+        ],
+    )
+}
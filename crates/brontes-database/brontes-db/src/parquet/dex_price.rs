@@ -0,0 +1,96 @@
+use std::sync::Arc;
+
+use arrow::{
+    array::BooleanArray,
+    datatypes::{DataType, Field, Schema},
+    error::ArrowError,
+    record_batch::RecordBatch,
+};
+use brontes_types::{db::dex::DexQuoteWithIndex, ToFloatNearest};
+use itertools::Itertools;
+
+use super::utils::{
+    build_float64_array, build_record_batch, build_string_array, build_uint64_array,
+};
+
+/// One row per block, tx and priced pair
+pub fn dex_quotes_to_record_batch(
+    quotes: Vec<(u64, DexQuoteWithIndex)>,
+) -> Result<RecordBatch, ArrowError> {
+    let rows = quotes
+        .iter()
+        .flat_map(|(block, quote)| {
+            quote
+                .quote
+                .iter()
+                .map(move |(pair, price)| (*block, quote.tx_idx, pair, price))
+        })
+        .collect_vec();
+
+    let block_number_array = build_uint64_array(rows.iter().map(|r| r.0).collect());
+    let tx_idx_array = build_uint64_array(rows.iter().map(|r| r.1 as u64).collect());
+    let token0_array = build_string_array(rows.iter().map(|r| r.2 .0.to_string()).collect());
+    let token1_array = build_string_array(rows.iter().map(|r| r.2 .1.to_string()).collect());
+    let pre_state_array = build_float64_array(
+        rows.iter()
+            .map(|r| r.3.pre_state.clone().to_float())
+            .collect(),
+    );
+    let post_state_array = build_float64_array(
+        rows.iter()
+            .map(|r| r.3.post_state.clone().to_float())
+            .collect(),
+    );
+    let pool_liquidity_array = build_float64_array(
+        rows.iter()
+            .map(|r| r.3.pool_liquidity.clone().to_float())
+            .collect(),
+    );
+    let goes_through0_array = build_string_array(
+        rows.iter()
+            .map(|r| r.3.goes_through.0.to_string())
+            .collect(),
+    );
+    let goes_through1_array = build_string_array(
+        rows.iter()
+            .map(|r| r.3.goes_through.1.to_string())
+            .collect(),
+    );
+    let is_transfer_array = BooleanArray::from(rows.iter().map(|r| r.3.is_transfer).collect_vec());
+    let first_hop_connections_array = build_uint64_array(
+        rows.iter()
+            .map(|r| r.3.first_hop_connections as u64)
+            .collect(),
+    );
+
+    let schema = Schema::new(vec![
+        Field::new("block_number", DataType::UInt64, false),
+        Field::new("tx_idx", DataType::UInt64, false),
+        Field::new("token0", DataType::Utf8, false),
+        Field::new("token1", DataType::Utf8, false),
+        Field::new("pre_state", DataType::Float64, false),
+        Field::new("post_state", DataType::Float64, false),
+        Field::new("pool_liquidity", DataType::Float64, false),
+        Field::new("goes_through_token0", DataType::Utf8, false),
+        Field::new("goes_through_token1", DataType::Utf8, false),
+        Field::new("is_transfer", DataType::Boolean, false),
+        Field::new("first_hop_connections", DataType::UInt64, false),
+    ]);
+
+    build_record_batch(
+        schema,
+        vec![
+            Arc::new(block_number_array),
+            Arc::new(tx_idx_array),
+            Arc::new(token0_array),
+            Arc::new(token1_array),
+            Arc::new(pre_state_array),
+            Arc::new(post_state_array),
+            Arc::new(pool_liquidity_array),
+            Arc::new(goes_through0_array),
+            Arc::new(goes_through1_array),
+            Arc::new(is_transfer_array),
+            Arc::new(first_hop_connections_array),
+        ],
+    )
+}
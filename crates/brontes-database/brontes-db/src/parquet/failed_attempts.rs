@@ -0,0 +1,116 @@
+use std::sync::Arc;
+
+use arrow::{
+    datatypes::{DataType, Field, Schema},
+    error::ArrowError,
+    record_batch::RecordBatch,
+};
+use brontes_types::mev::BlockFailedAttempts;
+use itertools::Itertools;
+
+use super::utils::{
+    build_float64_array, build_record_batch, build_string_array, build_uint64_array,
+    get_string_array_from_owned, u128_to_binary_array,
+};
+
+/// One row per failed attempt
+pub fn failed_attempts_to_record_batch(
+    blocks: &[BlockFailedAttempts],
+) -> Result<RecordBatch, ArrowError> {
+    let rows = blocks
+        .iter()
+        .flat_map(|block| {
+            block
+                .attempts
+                .iter()
+                .map(move |attempt| (block.block_number, attempt))
+        })
+        .collect_vec();
+
+    let schema = Schema::new(vec![
+        Field::new("block_number", DataType::UInt64, false),
+        Field::new("tx_hash", DataType::Utf8, false),
+        Field::new("tx_index", DataType::UInt64, false),
+        Field::new("eoa", DataType::Utf8, false),
+        Field::new("mev_contract", DataType::Utf8, true),
+        Field::new("opportunity_tx_hash", DataType::Utf8, false),
+        Field::new("mev_type", DataType::Utf8, false),
+        Field::new("gas_used", DataType::Binary, false),
+        Field::new("effective_gas_price", DataType::Binary, false),
+        Field::new("wasted_gas_usd", DataType::Float64, false),
+    ]);
+
+    build_record_batch(
+        schema,
+        vec![
+            Arc::new(build_uint64_array(rows.iter().map(|(block, _)| *block).collect())),
+            Arc::new(build_string_array(rows.iter().map(|(_, a)| a.tx_hash.to_string()).collect())),
+            Arc::new(build_uint64_array(rows.iter().map(|(_, a)| a.tx_index).collect())),
+            Arc::new(build_string_array(rows.iter().map(|(_, a)| a.eoa.to_string()).collect())),
+            Arc::new(get_string_array_from_owned(
+                rows.iter()
+                    .map(|(_, a)| a.mev_contract.map(|contract| contract.to_string()))
+                    .collect(),
+            )),
+            Arc::new(build_string_array(
+                rows.iter()
+                    .map(|(_, a)| a.opportunity_tx_hash.to_string())
+                    .collect(),
+            )),
+            Arc::new(build_string_array(
+                rows.iter().map(|(_, a)| a.mev_type.to_string()).collect(),
+            )),
+            Arc::new(u128_to_binary_array(
+                rows.iter().map(|(_, a)| a.gas_details.gas_used).collect(),
+            )),
+            Arc::new(u128_to_binary_array(
+                rows.iter()
+                    .map(|(_, a)| a.gas_details.effective_gas_price)
+                    .collect(),
+            )),
+            Arc::new(build_float64_array(rows.iter().map(|(_, a)| a.wasted_gas_usd).collect())),
+        ],
+    )
+}
+
+/// One row per opportunity that had failed attempts
+pub fn opportunity_wasted_gas_to_record_batch(
+    blocks: &[BlockFailedAttempts],
+) -> Result<RecordBatch, ArrowError> {
+    let rows = blocks
+        .iter()
+        .flat_map(|block| {
+            block
+                .wasted_gas_by_opportunity()
+                .into_iter()
+                .map(move |opportunity| (block.block_number, opportunity))
+        })
+        .collect_vec();
+
+    let schema = Schema::new(vec![
+        Field::new("block_number", DataType::UInt64, false),
+        Field::new("opportunity_tx_hash", DataType::Utf8, false),
+        Field::new("mev_type", DataType::Utf8, false),
+        Field::new("attempts", DataType::UInt64, false),
+        Field::new("searchers", DataType::UInt64, false),
+        Field::new("wasted_gas_usd", DataType::Float64, false),
+    ]);
+
+    build_record_batch(
+        schema,
+        vec![
+            Arc::new(build_uint64_array(rows.iter().map(|(block, _)| *block).collect())),
+            Arc::new(build_string_array(
+                rows.iter()
+                    .map(|(_, o)| o.opportunity_tx_hash.to_string())
+                    .collect(),
+            )),
+            Arc::new(build_string_array(
+                rows.iter().map(|(_, o)| o.mev_type.to_string()).collect(),
+            )),
+            Arc::new(build_uint64_array(rows.iter().map(|(_, o)| o.attempts).collect())),
+            Arc::new(build_uint64_array(rows.iter().map(|(_, o)| o.searchers).collect())),
+            Arc::new(build_float64_array(rows.iter().map(|(_, o)| o.wasted_gas_usd).collect())),
+        ],
+    )
+}
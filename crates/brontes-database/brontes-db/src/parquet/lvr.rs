@@ -0,0 +1,114 @@
+use std::sync::Arc;
+
+use arrow::{
+    array::Array,
+    datatypes::{DataType, Field, Schema},
+    error::ArrowError,
+    record_batch::RecordBatch,
+};
+use brontes_types::db::lvr::{BlockLvr, DailyPoolLvr, PoolLvr};
+use chrono::DateTime;
+use itertools::Itertools;
+
+use super::utils::{
+    build_float64_array, build_record_batch, build_string_array, build_uint64_array,
+};
+
+/// One row per pool and block
+pub fn pool_lvr_to_record_batch(blocks: Vec<BlockLvr>) -> Result<RecordBatch, ArrowError> {
+    let rows = blocks
+        .iter()
+        .flat_map(|block| block.pools.iter().map(move |pool| (block, pool)))
+        .collect_vec();
+
+    let block_number_array =
+        build_uint64_array(rows.iter().map(|(block, _)| block.block_number).collect());
+    let block_timestamp_array = build_uint64_array(
+        rows.iter()
+            .map(|(block, _)| block.block_timestamp)
+            .collect(),
+    );
+
+    let schema = Schema::new(
+        [
+            Field::new("block_number", DataType::UInt64, false),
+            Field::new("block_timestamp", DataType::UInt64, false),
+        ]
+        .into_iter()
+        .chain(pool_fields())
+        .collect_vec(),
+    );
+
+    let mut columns: Vec<Arc<dyn Array>> =
+        vec![Arc::new(block_number_array), Arc::new(block_timestamp_array)];
+    columns.extend(pool_columns(rows.into_iter().map(|(_, pool)| pool).collect_vec()));
+
+    build_record_batch(schema, columns)
+}
+
+/// One row per pool and day
+pub fn daily_pool_lvr_to_record_batch(daily: Vec<DailyPoolLvr>) -> Result<RecordBatch, ArrowError> {
+    let date_array = build_string_array(
+        daily
+            .iter()
+            .map(|entry| {
+                DateTime::from_timestamp((entry.day * 86_400) as i64, 0)
+                    .map(|date| date.format("%Y-%m-%d").to_string())
+                    .unwrap_or_default()
+            })
+            .collect(),
+    );
+    let blocks_array = build_uint64_array(daily.iter().map(|entry| entry.blocks).collect());
+
+    let pools = daily
+        .iter()
+        .map(|entry| PoolLvr {
+            pool:           entry.pool,
+            protocol:       entry.protocol,
+            token0:         entry.token0,
+            token1:         entry.token1,
+            swap_count:     entry.swap_count,
+            unpriced_swaps: entry.unpriced_swaps,
+            volume_usd:     entry.volume_usd,
+            lvr_usd:        entry.lvr_usd,
+        })
+        .collect_vec();
+
+    let schema = Schema::new(
+        [Field::new("date", DataType::Utf8, false), Field::new("blocks", DataType::UInt64, false)]
+            .into_iter()
+            .chain(pool_fields())
+            .collect_vec(),
+    );
+
+    let mut columns: Vec<Arc<dyn Array>> = vec![Arc::new(date_array), Arc::new(blocks_array)];
+    columns.extend(pool_columns(pools.iter().collect_vec()));
+
+    build_record_batch(schema, columns)
+}
+
+fn pool_fields() -> Vec<Field> {
+    vec![
+        Field::new("pool", DataType::Utf8, false),
+        Field::new("protocol", DataType::Utf8, false),
+        Field::new("token0", DataType::Utf8, false),
+        Field::new("token1", DataType::Utf8, false),
+        Field::new("swap_count", DataType::UInt64, false),
+        Field::new("unpriced_swaps", DataType::UInt64, false),
+        Field::new("volume_usd", DataType::Float64, false),
+        Field::new("lvr_usd", DataType::Float64, false),
+    ]
+}
+
+fn pool_columns(pools: Vec<&PoolLvr>) -> Vec<Arc<dyn Array>> {
+    vec![
+        Arc::new(build_string_array(pools.iter().map(|p| p.pool.to_string()).collect())),
+        Arc::new(build_string_array(pools.iter().map(|p| p.protocol.to_string()).collect())),
+        Arc::new(build_string_array(pools.iter().map(|p| p.token0.to_string()).collect())),
+        Arc::new(build_string_array(pools.iter().map(|p| p.token1.to_string()).collect())),
+        Arc::new(build_uint64_array(pools.iter().map(|p| p.swap_count).collect())),
+        Arc::new(build_uint64_array(pools.iter().map(|p| p.unpriced_swaps).collect())),
+        Arc::new(build_float64_array(pools.iter().map(|p| p.volume_usd).collect())),
+        Arc::new(build_float64_array(pools.iter().map(|p| p.lvr_usd).collect())),
+    ]
+}
@@ -13,7 +13,7 @@ use brontes_types::mev::MevBlock;
 
 use super::utils::{
     build_float64_array, build_record_batch, build_string_array, build_uint64_array,
-    u128_to_binary_array,
+    get_list_float_array_from_owned, get_list_string_array_from_owned, u128_to_binary_array,
 };
 
 pub fn mev_block_to_record_batch(mev_blocks: Vec<MevBlock>) -> Result<RecordBatch, ArrowError> {
@@ -63,6 +63,30 @@ pub fn mev_block_to_record_batch(mev_blocks: Vec<MevBlock>) -> Result<RecordBatc
             .collect(),
     );
 
+    let quote_pnl_asset_array = get_list_string_array_from_owned(
+        mev_blocks
+            .iter()
+            .map(|mb| {
+                mb.quote_pnl
+                    .iter()
+                    .map(|pnl| pnl.quote_asset.to_string())
+                    .collect()
+            })
+            .collect(),
+    );
+    let quote_pnl_profit_array = get_list_float_array_from_owned(
+        mev_blocks
+            .iter()
+            .map(|mb| mb.quote_pnl.iter().map(|pnl| pnl.profit).collect())
+            .collect(),
+    );
+    let quote_pnl_revenue_array = get_list_float_array_from_owned(
+        mev_blocks
+            .iter()
+            .map(|mb| mb.quote_pnl.iter().map(|pnl| pnl.revenue).collect())
+            .collect(),
+    );
+
     let proposer_mev_reward_array = u128_to_binary_array(
         mev_blocks
             .iter()
@@ -95,6 +119,9 @@ pub fn mev_block_to_record_batch(mev_blocks: Vec<MevBlock>) -> Result<RecordBatc
             Arc::new(proposer_mev_reward_array),
             Arc::new(proposer_profit_usd_array),
             Arc::new(total_mev_profit_usds_array),
+            Arc::new(quote_pnl_asset_array),
+            Arc::new(quote_pnl_profit_array),
+            Arc::new(quote_pnl_revenue_array),
         ],
     )
 }
@@ -117,6 +144,21 @@ fn build_schema(mev_count_array: &StructArray) -> Schema {
         Field::new("proposer_mev_reward", DataType::Binary, true),
         Field::new("proposer_profit_usd", DataType::Float64, true),
         Field::new("total_mev_profit_usd", DataType::Float64, false),
+        Field::new(
+            "quote_pnl_asset",
+            DataType::List(Arc::new(Field::new("item", DataType::Utf8, true))),
+            true,
+        ),
+        Field::new(
+            "quote_pnl_profit",
+            DataType::List(Arc::new(Field::new("item", DataType::Float64, true))),
+            true,
+        ),
+        Field::new(
+            "quote_pnl_revenue",
+            DataType::List(Arc::new(Field::new("item", DataType::Float64, true))),
+            true,
+        ),
     ])
 }
 
@@ -129,6 +171,7 @@ fn get_mev_count_array(mev_blocks: &Vec<MevBlock>) -> StructArray {
     let mut jit_count_builder = UInt64Builder::new();
     let mut jit_sandwich_count_builder = UInt64Builder::new();
     let mut searcher_tx_count_builder = UInt64Builder::new();
+    let mut nft_arb_count_builder = UInt64Builder::new();
 
     for block in mev_blocks {
         mev_count_builder.append_value(block.mev_count.bundle_count);
@@ -139,6 +182,7 @@ fn get_mev_count_array(mev_blocks: &Vec<MevBlock>) -> StructArray {
         jit_count_builder.append_option(block.mev_count.jit_count);
         jit_sandwich_count_builder.append_option(block.mev_count.jit_sandwich_count);
         searcher_tx_count_builder.append_option(block.mev_count.searcher_tx_count);
+        nft_arb_count_builder.append_option(block.mev_count.nft_arb_count);
     }
 
     let mev_count_array = mev_count_builder.finish();
@@ -149,6 +193,7 @@ fn get_mev_count_array(mev_blocks: &Vec<MevBlock>) -> StructArray {
     let jit_count_array = jit_count_builder.finish();
     let jit_sandwich_count_array = jit_sandwich_count_builder.finish();
     let searcher_tx_count_array = searcher_tx_count_builder.finish();
+    let nft_arb_count_array = nft_arb_count_builder.finish();
 
     let fields = vec![
         Field::new("mev_count", DataType::UInt64, false),
@@ -159,6 +204,7 @@ fn get_mev_count_array(mev_blocks: &Vec<MevBlock>) -> StructArray {
         Field::new("jit_count", DataType::UInt64, true),
         Field::new("jit_sandwich_count", DataType::UInt64, true),
         Field::new("searcher_tx_count", DataType::UInt64, true),
+        Field::new("nft_arb_count", DataType::UInt64, true),
     ];
 
     let arrays = vec![
@@ -170,6 +216,7 @@ fn get_mev_count_array(mev_blocks: &Vec<MevBlock>) -> StructArray {
         Arc::new(jit_count_array) as ArrayRef,
         Arc::new(jit_sandwich_count_array) as ArrayRef,
         Arc::new(searcher_tx_count_array) as ArrayRef,
+        Arc::new(nft_arb_count_array) as ArrayRef,
     ];
 
     StructArray::try_new(fields.into(), arrays, None).expect("Failed to init struct arrays")
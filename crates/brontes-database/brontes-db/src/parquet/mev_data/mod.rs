@@ -4,6 +4,7 @@ mod cex_dex;
 mod jit;
 mod jit_sandwich;
 mod liquidation;
+mod nft_arb;
 mod sandwich;
 mod searcher_tx;
 
@@ -12,5 +13,6 @@ pub use atomic_arb::*;
 pub use jit::*;
 pub use jit_sandwich::*;
 pub use liquidation::*;
+pub use nft_arb::*;
 pub use sandwich::*;
 pub use searcher_tx::*;
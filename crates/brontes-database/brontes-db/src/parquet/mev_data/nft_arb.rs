@@ -0,0 +1,47 @@
+use std::sync::Arc;
+
+use arrow::{
+    array::Array,
+    datatypes::{DataType, Field, Schema},
+    error::ArrowError,
+    record_batch::RecordBatch,
+};
+use brontes_types::mev::NftArb;
+use itertools::Itertools;
+
+use crate::parquet::{
+    normalized_actions::{
+        gas_details::get_gas_details_array, nft_trades::get_normalized_nft_trade_list_array,
+    },
+    utils::get_string_array_from_owned,
+};
+
+pub fn nft_arb_to_record_batch(nft_arbs: Vec<NftArb>) -> Result<RecordBatch, ArrowError> {
+    let tx_hash_array = get_string_array_from_owned(
+        nft_arbs
+            .iter()
+            .map(|arb| Some(arb.tx_hash.to_string()))
+            .collect_vec(),
+    );
+
+    let trades_array = get_normalized_nft_trade_list_array(
+        nft_arbs
+            .iter()
+            .map(|arb| arb.trades.iter().collect_vec())
+            .collect_vec(),
+    );
+
+    let gas_details_array =
+        get_gas_details_array(nft_arbs.iter().map(|arb| arb.gas_details).collect());
+
+    let schema = Schema::new(vec![
+        Field::new("tx_hash", DataType::Utf8, false),
+        Field::new("trades", trades_array.data_type().clone(), false),
+        Field::new("gas_details", gas_details_array.data_type().clone(), false),
+    ]);
+
+    RecordBatch::try_new(
+        Arc::new(schema),
+        vec![Arc::new(tx_hash_array), Arc::new(trades_array), Arc::new(gas_details_array)],
+    )
+}
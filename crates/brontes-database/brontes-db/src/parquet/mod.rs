@@ -1,12 +1,15 @@
 use std::{
     fs::File,
     path::{Path, PathBuf},
+    sync::Arc,
 };
 
-use arrow::record_batch::RecordBatch;
+use arrow::{error::ArrowError, record_batch::RecordBatch};
 use brontes_types::{
-    db::traits::LibmdbxReader,
+    db::{lvr::aggregate_daily_lvr, traits::LibmdbxReader},
     mev::{BundleData, MevType},
+    normalized_actions::Action,
+    BlockTree,
 };
 use chrono::Local;
 use eyre::{Error, Ok, Result, WrapErr};
@@ -22,26 +25,51 @@ use crate::Tables;
 
 #[allow(dead_code)]
 mod address_meta;
+mod block_analysis;
 mod builder;
 mod bundle_header;
+mod cex;
+mod cex_dex_inventory;
+mod classified_blocks;
+mod dex_price;
+mod failed_attempts;
+mod lvr;
 mod mev_block;
 mod mev_data;
 mod normalized_actions;
+mod protocol_info;
 mod searcher;
+mod token_info;
+mod tx_actions;
 pub mod utils;
 
 use address_meta::address_metadata_to_record_batch;
+use block_analysis::block_analysis_to_record_batch;
 use builder::builder_info_to_record_batch;
 use bundle_header::bundle_headers_to_record_batch;
+use cex::{cex_quotes_to_record_batch, cex_trades_to_record_batch};
+use cex_dex_inventory::{inventory_positions_to_record_batch, searcher_inventory_to_record_batch};
+use classified_blocks::classified_blocks_to_record_batch;
+use dex_price::dex_quotes_to_record_batch;
+use failed_attempts::{failed_attempts_to_record_batch, opportunity_wasted_gas_to_record_batch};
+use lvr::{daily_pool_lvr_to_record_batch, pool_lvr_to_record_batch};
 use mev_block::mev_block_to_record_batch;
 use mev_data::*;
+use protocol_info::protocol_info_to_record_batch;
 use searcher::searcher_info_to_record_batch;
+use token_info::token_info_to_record_batch;
+use tx_actions::normalized_actions_to_record_batch;
+
+/// Default amount of blocks written per file for the tables keyed by block
+pub const DEFAULT_PARTITION_SIZE: u64 = 10_000;
 
 pub struct ParquetExporter<DB: LibmdbxReader> {
-    pub start_block:   Option<u64>,
-    pub end_block:     Option<u64>,
-    pub base_dir_path: Option<String>,
-    pub db:            &'static DB,
+    pub start_block:    Option<u64>,
+    pub end_block:      Option<u64>,
+    pub base_dir_path:  Option<String>,
+    /// amount of blocks written per file for the tables keyed by block
+    pub partition_size: u64,
+    pub db:             &'static DB,
 }
 
 impl<DB> ParquetExporter<DB>
@@ -52,9 +80,70 @@ where
         start_block: Option<u64>,
         end_block: Option<u64>,
         base_dir_path: Option<String>,
+        partition_size: u64,
         db: &'static DB,
     ) -> Self {
-        Self { start_block, end_block, base_dir_path, db }
+        Self { start_block, end_block, base_dir_path, partition_size, db }
+    }
+
+    /// Splits the export range into inclusive block ranges of
+    /// `partition_size` blocks
+    pub fn block_partitions(&self) -> Result<Vec<(u64, u64)>> {
+        let start_block = self.start_block.unwrap_or_default();
+        let end_block = match self.end_block {
+            Some(end_block) => end_block,
+            None => self.db.get_most_recent_block()?,
+        };
+
+        Ok(self.partitions_in(start_block, end_block))
+    }
+
+    /// Splits the inclusive block range into ranges of `partition_size`
+    /// blocks
+    pub fn partitions_in(&self, start_block: u64, end_block: u64) -> Vec<(u64, u64)> {
+        let partition_size = self.partition_size.max(1);
+        (start_block..=end_block)
+            .step_by(partition_size as usize)
+            .map(|start| (start, (start + partition_size - 1).min(end_block)))
+            .collect()
+    }
+
+    /// Writes one file per block partition, skipping the partitions without
+    /// any data
+    async fn export_partitioned<R>(
+        &self,
+        table: Tables,
+        fetch: impl Fn(u64, u64) -> Result<Vec<R>>,
+        to_record_batch: impl Fn(Vec<R>) -> Result<RecordBatch, ArrowError>,
+    ) -> Result<(), Error> {
+        let mut written = 0usize;
+
+        for (start_block, end_block) in self.block_partitions()? {
+            let rows = fetch(start_block, end_block).wrap_err_with(|| {
+                format!("Failed to fetch {table} for blocks {start_block}-{end_block}")
+            })?;
+            if rows.is_empty() {
+                continue
+            }
+
+            let batch = to_record_batch(rows)
+                .wrap_err_with(|| format!("Failed to convert {table} to record batch"))?;
+            write_parquet(
+                batch,
+                get_partition_path(self.base_dir_path.clone(), table, start_block, end_block)?,
+            )
+            .await
+            .wrap_err_with(|| format!("Failed to write {table} to parquet file"))?;
+
+            written += 1;
+        }
+
+        if written == 0 {
+            error!("No {table} data fetched for the given range.");
+            return Err(Error::msg(format!("No {table} data fetched for the given range.")))
+        }
+
+        Ok(())
     }
 
     pub async fn export_mev_blocks(&self) -> Result<(), Error> {
@@ -84,6 +173,7 @@ where
             jit_sandwich,
             searcher_tx,
             liquidation,
+            nft_arbs,
         ) = {
             let mut blocks = Vec::new();
             let mut bundle_headers = Vec::new();
@@ -94,6 +184,7 @@ where
             let mut jit_sandwich = Vec::new();
             let mut searcher_tx = Vec::new();
             let mut liquidation = Vec::new();
+            let mut nft_arbs = Vec::new();
 
             for mb in mev_blocks_iter {
                 blocks.push(mb.block);
@@ -111,6 +202,7 @@ where
                         BundleData::Liquidation(liquidation_data) => {
                             liquidation.push(liquidation_data)
                         }
+                        BundleData::NftArb(nft_arb) => nft_arbs.push(nft_arb),
                         _ => continue,
                     }
                 }
@@ -126,6 +218,7 @@ where
                 jit_sandwich,
                 searcher_tx,
                 liquidation,
+                nft_arbs,
             )
         };
 
@@ -245,6 +338,20 @@ where
             }));
         }
 
+        if !nft_arbs.is_empty() {
+            bundle_futures.push(tokio::task::spawn_blocking({
+                let base_dir_path = base_dir_path.clone();
+                move || {
+                    let nft_arb_batch = nft_arb_to_record_batch(nft_arbs)
+                        .wrap_err("Failed to convert Nft Arb data to record batch")?;
+                    sync_write_parquet(
+                        nft_arb_batch,
+                        get_path(base_dir_path, Tables::MevBlocks, Some(MevType::NftArb))?,
+                    )
+                }
+            }));
+        }
+
         if !bundle_headers.is_empty() {
             bundle_futures.push(tokio::task::spawn_blocking({
                 let base_dir_path = base_dir_path.clone();
@@ -334,6 +441,267 @@ where
 
         Ok(())
     }
+
+    pub async fn export_dex_prices(&self) -> Result<(), Error> {
+        self.export_partitioned(
+            Tables::DexPrice,
+            |start, end| self.db.try_fetch_dex_quotes_range(start, end),
+            dex_quotes_to_record_batch,
+        )
+        .await
+    }
+
+    pub async fn export_cex_trades(&self) -> Result<(), Error> {
+        self.export_partitioned(
+            Tables::CexTrades,
+            |start, end| self.db.try_fetch_cex_trades_range(start, end),
+            cex_trades_to_record_batch,
+        )
+        .await
+    }
+
+    pub async fn export_cex_quotes(&self) -> Result<(), Error> {
+        self.export_partitioned(
+            Tables::CexPrice,
+            |start, end| self.db.try_fetch_cex_quotes_range(start, end),
+            cex_quotes_to_record_batch,
+        )
+        .await
+    }
+
+    pub async fn export_token_info(&self) -> Result<(), Error> {
+        let tokens = self
+            .db
+            .fetch_all_token_info()
+            .wrap_err("Failed to query token decimals table")?;
+
+        if tokens.is_empty() {
+            error!("Token decimals table is empty.");
+            return Err(Error::msg("No token info"))
+        }
+
+        let token_batch = token_info_to_record_batch(tokens)
+            .wrap_err("Failed to convert token info to record batch")?;
+
+        write_parquet(
+            token_batch,
+            get_path(self.base_dir_path.clone(), Tables::TokenDecimals, None)?,
+        )
+        .await
+        .wrap_err("Failed to write token info to parquet file")?;
+
+        Ok(())
+    }
+
+    pub async fn export_protocol_info(&self) -> Result<(), Error> {
+        let protocols = self
+            .db
+            .fetch_all_protocol_info()
+            .wrap_err("Failed to query address to protocol info table")?;
+
+        if protocols.is_empty() {
+            error!("Address to protocol info table is empty.");
+            return Err(Error::msg("No protocol info"))
+        }
+
+        let protocol_batch = protocol_info_to_record_batch(protocols)
+            .wrap_err("Failed to convert protocol info to record batch")?;
+
+        write_parquet(
+            protocol_batch,
+            get_path(self.base_dir_path.clone(), Tables::AddressToProtocolInfo, None)?,
+        )
+        .await
+        .wrap_err("Failed to write protocol info to parquet file")?;
+
+        Ok(())
+    }
+
+    /// Writes the normalized actions of the classified trees of a block
+    /// partition. The trees are built by the caller as classification lives
+    /// outside of the db crate.
+    pub async fn export_normalized_actions(
+        &self,
+        trees: Vec<Arc<BlockTree<Action>>>,
+        start_block: u64,
+        end_block: u64,
+    ) -> Result<(), Error> {
+        if trees.is_empty() {
+            return Ok(())
+        }
+
+        let base_dir_path = self.base_dir_path.clone();
+        tokio::task::spawn_blocking(move || {
+            let actions_batch = normalized_actions_to_record_batch(&trees)
+                .wrap_err("Failed to convert normalized actions to record batch")?;
+            sync_write_parquet(
+                actions_batch,
+                partition_path_in(
+                    base_dir_path,
+                    DEFAULT_NORMALIZED_ACTIONS_DIR,
+                    start_block,
+                    end_block,
+                )?,
+            )
+        })
+        .await
+        .wrap_err("Normalized actions export task panicked")??;
+
+        Ok(())
+    }
+
+    pub async fn export_block_analysis(&self) -> Result<(), Error> {
+        let analyses = self
+            .db
+            .try_fetch_block_analyses(self.start_block, self.end_block.unwrap_or(u64::MAX))
+            .wrap_err("Failed to fetch block analysis from the database")?;
+
+        if analyses.is_empty() {
+            error!("No block analysis fetched for the given range.");
+            return Err(Error::msg("No block analysis fetched for the given range."))
+        }
+
+        let analysis_batch = block_analysis_to_record_batch(analyses)
+            .wrap_err("Failed to convert block analysis to record batch")?;
+
+        write_parquet(
+            analysis_batch,
+            get_path(self.base_dir_path.clone(), Tables::BlockAnalyses, None)?,
+        )
+        .await
+        .wrap_err("Failed to write block analysis to parquet file")?;
+
+        Ok(())
+    }
+
+    pub async fn export_classified_blocks(&self) -> Result<(), Error> {
+        let blocks = self
+            .db
+            .try_fetch_classified_blocks(self.start_block, self.end_block.unwrap_or(u64::MAX))
+            .wrap_err("Failed to fetch classified blocks from the database")?;
+
+        if blocks.is_empty() {
+            error!("No classified blocks fetched for the given range.");
+            return Err(Error::msg("No classified blocks fetched for the given range."))
+        }
+
+        let base_dir_path = self.base_dir_path.clone();
+        tokio::task::spawn_blocking(move || {
+            let classified_batch = classified_blocks_to_record_batch(blocks)
+                .wrap_err("Failed to convert classified blocks to record batch")?;
+            sync_write_parquet(
+                classified_batch,
+                get_path(base_dir_path, Tables::ClassifiedBlocks, None)?,
+            )
+        })
+        .await
+        .wrap_err("Classified blocks export task panicked")??;
+
+        Ok(())
+    }
+
+    /// Exports the lvr of each pool per block and aggregated per day
+    pub async fn export_pool_lvr(&self) -> Result<(), Error> {
+        let blocks = self
+            .db
+            .try_fetch_block_lvrs(self.start_block, self.end_block.unwrap_or(u64::MAX))
+            .wrap_err("Failed to fetch pool lvr from the database")?;
+
+        if blocks.is_empty() {
+            error!("No pool lvr fetched for the given range.");
+            return Err(Error::msg("No pool lvr fetched for the given range."))
+        }
+
+        let daily_batch = daily_pool_lvr_to_record_batch(aggregate_daily_lvr(&blocks))
+            .wrap_err("Failed to convert daily pool lvr to record batch")?;
+        let block_batch = pool_lvr_to_record_batch(blocks)
+            .wrap_err("Failed to convert pool lvr to record batch")?;
+
+        write_parquet(
+            block_batch,
+            get_sub_path(self.base_dir_path.clone(), Tables::BlockLvrs, "blocks")?,
+        )
+        .await
+        .wrap_err("Failed to write pool lvr to parquet file")?;
+
+        write_parquet(
+            daily_batch,
+            get_sub_path(self.base_dir_path.clone(), Tables::BlockLvrs, "daily")?,
+        )
+        .await
+        .wrap_err("Failed to write daily pool lvr to parquet file")?;
+
+        Ok(())
+    }
+
+    /// Exports the failed mev attempts and the gas they wasted per opportunity
+    pub async fn export_failed_attempts(&self) -> Result<(), Error> {
+        let blocks = self
+            .db
+            .try_fetch_failed_attempts(self.start_block, self.end_block.unwrap_or(u64::MAX))
+            .wrap_err("Failed to fetch failed mev attempts from the database")?;
+
+        if blocks.is_empty() {
+            error!("No failed mev attempts fetched for the given range.");
+            return Err(Error::msg("No failed mev attempts fetched for the given range."))
+        }
+
+        let attempts_batch = failed_attempts_to_record_batch(&blocks)
+            .wrap_err("Failed to convert failed mev attempts to record batch")?;
+        let opportunities_batch = opportunity_wasted_gas_to_record_batch(&blocks)
+            .wrap_err("Failed to convert opportunity wasted gas to record batch")?;
+
+        write_parquet(
+            attempts_batch,
+            get_sub_path(self.base_dir_path.clone(), Tables::FailedMevAttempts, "attempts")?,
+        )
+        .await
+        .wrap_err("Failed to write failed mev attempts to parquet file")?;
+
+        write_parquet(
+            opportunities_batch,
+            get_sub_path(self.base_dir_path.clone(), Tables::FailedMevAttempts, "opportunities")?,
+        )
+        .await
+        .wrap_err("Failed to write opportunity wasted gas to parquet file")?;
+
+        Ok(())
+    }
+
+    /// Exports the daily pnl of each cex-dex searcher and its open positions.
+    /// The inventory is keyed by day, so the block range doesn't apply
+    pub async fn export_cex_dex_inventory(&self) -> Result<(), Error> {
+        let days = self
+            .db
+            .try_fetch_cex_dex_inventories(None, u64::MAX)
+            .wrap_err("Failed to fetch cex dex inventory from the database")?;
+
+        if days.is_empty() {
+            error!("No cex dex inventory found in the database.");
+            return Err(Error::msg("No cex dex inventory found in the database."))
+        }
+
+        let searchers_batch = searcher_inventory_to_record_batch(&days)
+            .wrap_err("Failed to convert searcher inventory to record batch")?;
+        let positions_batch = inventory_positions_to_record_batch(&days)
+            .wrap_err("Failed to convert inventory positions to record batch")?;
+
+        write_parquet(
+            searchers_batch,
+            get_sub_path(self.base_dir_path.clone(), Tables::CexDexInventory, "searchers")?,
+        )
+        .await
+        .wrap_err("Failed to write searcher inventory to parquet file")?;
+
+        write_parquet(
+            positions_batch,
+            get_sub_path(self.base_dir_path.clone(), Tables::CexDexInventory, "positions")?,
+        )
+        .await
+        .wrap_err("Failed to write inventory positions to parquet file")?;
+
+        Ok(())
+    }
 }
 
 async fn write_parquet(record_batch: RecordBatch, file_path: PathBuf) -> Result<()> {
@@ -402,6 +770,50 @@ pub fn get_path(
     create_file_path(path)
 }
 
+/// Path of a table exported as several datasets:
+/// `<base>/<table>/<sub_dir>/<date>/<time>.parquet`
+pub fn get_sub_path(
+    custom_path: Option<String>,
+    batch_type: Tables,
+    sub_dir: &str,
+) -> Result<PathBuf> {
+    let base_path = custom_path
+        .as_deref()
+        .unwrap_or("../brontes-notebook/data/brontes-exports");
+
+    let path = PathBuf::from(base_path)
+        .join(batch_type.get_default_path())
+        .join(sub_dir);
+    create_file_path(path)
+}
+
+/// Path of a block partition:
+/// `<base>/<table>/<start_block>-<end_block>.parquet`
+pub fn get_partition_path(
+    custom_path: Option<String>,
+    batch_type: Tables,
+    start_block: u64,
+    end_block: u64,
+) -> Result<PathBuf> {
+    partition_path_in(custom_path, batch_type.get_default_path(), start_block, end_block)
+}
+
+fn partition_path_in(
+    custom_path: Option<String>,
+    dir: &str,
+    start_block: u64,
+    end_block: u64,
+) -> Result<PathBuf> {
+    let base_path = custom_path
+        .as_deref()
+        .unwrap_or("../brontes-notebook/data/brontes-exports");
+
+    let dir_path = PathBuf::from(base_path).join(dir);
+    std::fs::create_dir_all(&dir_path)?;
+
+    Ok(dir_path.join(format!("{start_block}-{end_block}.parquet")))
+}
+
 pub fn create_file_path<P: AsRef<Path>>(base_dir: P) -> Result<PathBuf> {
     let now = Local::now();
     let date_str = now.format("%m-%d").to_string();
@@ -424,6 +836,16 @@ impl Tables {
             Tables::SearcherEOAs => DEFAULT_SEARCHER_INFO_DIR,
             Tables::SearcherContracts => DEFAULT_SEARCHER_INFO_DIR,
             Tables::Builder => DEFAULT_BUILDER_INFO_DIR,
+            Tables::BlockAnalyses => DEFAULT_BLOCK_ANALYSIS_DIR,
+            Tables::ClassifiedBlocks => DEFAULT_CLASSIFIED_BLOCKS_DIR,
+            Tables::BlockLvrs => DEFAULT_POOL_LVR_DIR,
+            Tables::FailedMevAttempts => DEFAULT_FAILED_ATTEMPTS_DIR,
+            Tables::CexDexInventory => DEFAULT_CEX_DEX_INVENTORY_DIR,
+            Tables::DexPrice => DEFAULT_DEX_PRICE_DIR,
+            Tables::CexTrades => DEFAULT_CEX_TRADES_DIR,
+            Tables::CexPrice => DEFAULT_CEX_QUOTES_DIR,
+            Tables::TokenDecimals => DEFAULT_TOKEN_INFO_DIR,
+            Tables::AddressToProtocolInfo => DEFAULT_PROTOCOL_INFO_DIR,
             _ => panic!("Unsupported table type"),
         }
     }
@@ -433,3 +855,14 @@ pub const DEFAULT_BLOCK_DIR: &str = "mev";
 pub const DEFAULT_METADATA_DIR: &str = "address_metadata";
 pub const DEFAULT_SEARCHER_INFO_DIR: &str = "searcher_info";
 pub const DEFAULT_BUILDER_INFO_DIR: &str = "builder-info";
+pub const DEFAULT_BLOCK_ANALYSIS_DIR: &str = "block_analysis";
+pub const DEFAULT_CLASSIFIED_BLOCKS_DIR: &str = "classified_blocks";
+pub const DEFAULT_POOL_LVR_DIR: &str = "pool_lvr";
+pub const DEFAULT_FAILED_ATTEMPTS_DIR: &str = "failed_attempts";
+pub const DEFAULT_CEX_DEX_INVENTORY_DIR: &str = "cex_dex_inventory";
+pub const DEFAULT_DEX_PRICE_DIR: &str = "dex_prices";
+pub const DEFAULT_CEX_TRADES_DIR: &str = "cex_trades";
+pub const DEFAULT_CEX_QUOTES_DIR: &str = "cex_quotes";
+pub const DEFAULT_TOKEN_INFO_DIR: &str = "token_info";
+pub const DEFAULT_PROTOCOL_INFO_DIR: &str = "protocol_info";
+pub const DEFAULT_NORMALIZED_ACTIONS_DIR: &str = "normalized_actions";
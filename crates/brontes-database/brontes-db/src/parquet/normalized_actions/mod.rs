@@ -2,5 +2,6 @@ pub mod burns;
 pub mod gas_details;
 pub mod liquidations;
 pub mod mints;
+pub mod nft_trades;
 pub mod swaps;
 pub mod transfers;
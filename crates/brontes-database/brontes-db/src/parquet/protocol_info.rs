@@ -0,0 +1,70 @@
+use std::sync::Arc;
+
+use alloy_primitives::Address;
+use arrow::{
+    datatypes::{DataType, Field, Schema},
+    error::ArrowError,
+    record_batch::RecordBatch,
+};
+use brontes_types::db::address_to_protocol_info::ProtocolInfo;
+use itertools::Itertools;
+
+use super::utils::{
+    build_record_batch, build_string_array, build_uint64_array, get_list_string_array_from_owned,
+    get_string_array_from_owned,
+};
+
+pub fn protocol_info_to_record_batch(
+    protocols: Vec<(Address, ProtocolInfo)>,
+) -> Result<RecordBatch, ArrowError> {
+    let address_array = build_string_array(protocols.iter().map(|(a, _)| a.to_string()).collect());
+    let protocol_array = build_string_array(
+        protocols
+            .iter()
+            .map(|(_, p)| p.protocol.to_string())
+            .collect(),
+    );
+    let tokens_array = get_list_string_array_from_owned(
+        protocols
+            .iter()
+            .map(|(_, p)| {
+                [Some(p.token0), Some(p.token1), p.token2, p.token3, p.token4]
+                    .into_iter()
+                    .flatten()
+                    .map(|t| t.to_string())
+                    .collect_vec()
+            })
+            .collect_vec(),
+    );
+    let curve_lp_token_array = get_string_array_from_owned(
+        protocols
+            .iter()
+            .map(|(_, p)| p.curve_lp_token.map(|t| t.to_string()))
+            .collect_vec(),
+    );
+    let init_block_array =
+        build_uint64_array(protocols.iter().map(|(_, p)| p.init_block).collect());
+
+    let schema = Schema::new(vec![
+        Field::new("address", DataType::Utf8, false),
+        Field::new("protocol", DataType::Utf8, false),
+        Field::new(
+            "tokens",
+            DataType::List(Arc::new(Field::new("item", DataType::Utf8, true))),
+            true,
+        ),
+        Field::new("curve_lp_token", DataType::Utf8, true),
+        Field::new("init_block", DataType::UInt64, false),
+    ]);
+
+    build_record_batch(
+        schema,
+        vec![
+            Arc::new(address_array),
+            Arc::new(protocol_array),
+            Arc::new(tokens_array),
+            Arc::new(curve_lp_token_array),
+            Arc::new(init_block_array),
+        ],
+    )
+}
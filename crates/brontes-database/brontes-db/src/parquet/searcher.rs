@@ -72,6 +72,8 @@ pub fn searcher_info_to_record_batch(
         UInt64Builder::with_capacity(eoa_info.len() + contract_info.len());
     let mut searcher_tx_count_builder =
         UInt64Builder::with_capacity(eoa_info.len() + contract_info.len());
+    let mut nft_arb_count_builder =
+        UInt64Builder::with_capacity(eoa_info.len() + contract_info.len());
 
     // Flatten TollByType fields for pnl and gas_bids
     let mut pnl_total_builder = Float64Builder::with_capacity(eoa_info.len() + contract_info.len());
@@ -88,6 +90,8 @@ pub fn searcher_info_to_record_batch(
         Float64Builder::with_capacity(eoa_info.len() + contract_info.len());
     let mut pnl_searcher_tx_builder =
         Float64Builder::with_capacity(eoa_info.len() + contract_info.len());
+    let mut pnl_nft_arb_builder =
+        Float64Builder::with_capacity(eoa_info.len() + contract_info.len());
 
     let mut gas_bids_total_builder =
         Float64Builder::with_capacity(eoa_info.len() + contract_info.len());
@@ -105,6 +109,13 @@ pub fn searcher_info_to_record_batch(
         Float64Builder::with_capacity(eoa_info.len() + contract_info.len());
     let mut gas_bids_searcher_tx_builder =
         Float64Builder::with_capacity(eoa_info.len() + contract_info.len());
+    let mut gas_bids_nft_arb_builder =
+        Float64Builder::with_capacity(eoa_info.len() + contract_info.len());
+
+    let mut failed_attempt_count_builder =
+        UInt64Builder::with_capacity(eoa_info.len() + contract_info.len());
+    let mut wasted_gas_total_builder =
+        Float64Builder::with_capacity(eoa_info.len() + contract_info.len());
 
     for info in eoa_info.iter().chain(&contract_info) {
         let mev_count = &info.1.mev_count;
@@ -116,6 +127,7 @@ pub fn searcher_info_to_record_batch(
         atomic_backrun_count_builder.append_option(mev_count.atomic_backrun_count);
         liquidation_count_builder.append_option(mev_count.liquidation_count);
         searcher_tx_count_builder.append_option(mev_count.searcher_tx_count);
+        nft_arb_count_builder.append_option(mev_count.nft_arb_count);
 
         let pnl = &info.1.pnl;
         pnl_total_builder.append_value(pnl.total);
@@ -126,6 +138,7 @@ pub fn searcher_info_to_record_batch(
         pnl_atomic_backrun_builder.append_option(pnl.atomic_backrun);
         pnl_liquidation_builder.append_option(pnl.liquidation);
         pnl_searcher_tx_builder.append_option(pnl.searcher_tx);
+        pnl_nft_arb_builder.append_option(pnl.nft_arb);
 
         let gas_bids = &info.1.gas_bids;
         gas_bids_total_builder.append_value(gas_bids.total);
@@ -136,6 +149,10 @@ pub fn searcher_info_to_record_batch(
         gas_bids_atomic_backrun_builder.append_option(gas_bids.atomic_backrun);
         gas_bids_liquidation_builder.append_option(gas_bids.liquidation);
         gas_bids_searcher_tx_builder.append_option(gas_bids.searcher_tx);
+        gas_bids_nft_arb_builder.append_option(gas_bids.nft_arb);
+
+        failed_attempt_count_builder.append_value(info.1.failed_attempt_count);
+        wasted_gas_total_builder.append_value(info.1.wasted_gas.total);
     }
 
     let schema = Schema::new(vec![
@@ -156,6 +173,7 @@ pub fn searcher_info_to_record_batch(
         Field::new("atomic_backrun_count", DataType::UInt64, true),
         Field::new("liquidation_count", DataType::UInt64, true),
         Field::new("searcher_tx_count", DataType::UInt64, true),
+        Field::new("nft_arb_count", DataType::UInt64, true),
         Field::new("pnl_total", DataType::Float64, false),
         Field::new("pnl_sandwich", DataType::Float64, true),
         Field::new("pnl_cex_dex", DataType::Float64, true),
@@ -164,6 +182,7 @@ pub fn searcher_info_to_record_batch(
         Field::new("pnl_atomic_backrun", DataType::Float64, true),
         Field::new("pnl_liquidation", DataType::Float64, true),
         Field::new("pnl_searcher_tx", DataType::Float64, true),
+        Field::new("pnl_nft_arb", DataType::Float64, true),
         Field::new("gas_bids_total", DataType::Float64, false),
         Field::new("gas_bids_sandwich", DataType::Float64, true),
         Field::new("gas_bids_cex_dex", DataType::Float64, true),
@@ -172,6 +191,9 @@ pub fn searcher_info_to_record_batch(
         Field::new("gas_bids_atomic_backrun", DataType::Float64, true),
         Field::new("gas_bids_liquidation", DataType::Float64, true),
         Field::new("gas_bids_searcher_tx", DataType::Float64, true),
+        Field::new("gas_bids_nft_arb", DataType::Float64, true),
+        Field::new("failed_attempt_count", DataType::UInt64, false),
+        Field::new("wasted_gas_total", DataType::Float64, false),
     ]);
 
     RecordBatch::try_new(
@@ -190,6 +212,7 @@ pub fn searcher_info_to_record_batch(
             Arc::new(atomic_backrun_count_builder.finish()),
             Arc::new(liquidation_count_builder.finish()),
             Arc::new(searcher_tx_count_builder.finish()),
+            Arc::new(nft_arb_count_builder.finish()),
             Arc::new(pnl_total_builder.finish()),
             Arc::new(pnl_sandwich_builder.finish()),
             Arc::new(pnl_cex_dex_builder.finish()),
@@ -198,6 +221,7 @@ pub fn searcher_info_to_record_batch(
             Arc::new(pnl_atomic_backrun_builder.finish()),
             Arc::new(pnl_liquidation_builder.finish()),
             Arc::new(pnl_searcher_tx_builder.finish()),
+            Arc::new(pnl_nft_arb_builder.finish()),
             Arc::new(gas_bids_total_builder.finish()),
             Arc::new(gas_bids_sandwich_builder.finish()),
             Arc::new(gas_bids_cex_dex_builder.finish()),
@@ -206,6 +230,9 @@ pub fn searcher_info_to_record_batch(
             Arc::new(gas_bids_atomic_backrun_builder.finish()),
             Arc::new(gas_bids_liquidation_builder.finish()),
             Arc::new(gas_bids_searcher_tx_builder.finish()),
+            Arc::new(gas_bids_nft_arb_builder.finish()),
+            Arc::new(failed_attempt_count_builder.finish()),
+            Arc::new(wasted_gas_total_builder.finish()),
         ],
     )
 }
@@ -0,0 +1,131 @@
+use std::sync::Arc;
+
+use arrow::{
+    array::Array,
+    datatypes::{DataType, Field, Schema},
+    error::ArrowError,
+    record_batch::RecordBatch,
+};
+use brontes_types::{
+    normalized_actions::{
+        Action, NormalizedBurn, NormalizedLiquidation, NormalizedMint, NormalizedSwap,
+        NormalizedTransfer,
+    },
+    BlockTree,
+};
+use itertools::Itertools;
+
+use super::{
+    normalized_actions::{
+        burns::get_normalized_burn_list_array, gas_details::get_gas_details_array,
+        liquidations::get_normalized_liquidation_list_array, mints::get_normalized_mint_list_array,
+        swaps::get_normalized_swap_list_array, transfers::get_normalized_transfer_list_array,
+    },
+    utils::{
+        build_record_batch, build_string_array, build_uint64_array, get_string_array_from_owned,
+    },
+};
+
+/// The normalized actions of a single transaction
+struct TxActions<'a> {
+    block_number: u64,
+    tx_hash:      String,
+    tx_idx:       u64,
+    from:         String,
+    to:           Option<String>,
+    swaps:        Vec<&'a NormalizedSwap>,
+    mints:        Vec<&'a NormalizedMint>,
+    burns:        Vec<NormalizedBurn>,
+    transfers:    Vec<NormalizedTransfer>,
+    liquidations: Vec<&'a NormalizedLiquidation>,
+}
+
+/// One row per transaction with the classified swaps, mints, burns, transfers
+/// and liquidations it contains
+pub fn normalized_actions_to_record_batch(
+    trees: &[Arc<BlockTree<Action>>],
+) -> Result<RecordBatch, ArrowError> {
+    let mut txes = Vec::new();
+    let mut gas_details = Vec::new();
+
+    for tree in trees {
+        for root in &tree.tx_roots {
+            let mut tx = TxActions {
+                block_number: tree.header.number,
+                tx_hash:      root.tx_hash.to_string(),
+                tx_idx:       root.position as u64,
+                from:         root.get_from_address().to_string(),
+                to:           root.try_get_to_address().map(|to| to.to_string()),
+                swaps:        vec![],
+                mints:        vec![],
+                burns:        vec![],
+                transfers:    vec![],
+                liquidations: vec![],
+            };
+
+            for action in root.data_store.0.iter().flatten().flatten() {
+                match action {
+                    Action::Swap(swap) => tx.swaps.push(swap),
+                    Action::SwapWithFee(swap) => tx.swaps.push(&swap.swap),
+                    Action::Mint(mint) => tx.mints.push(mint),
+                    Action::Burn(burn) => tx.burns.push(burn.clone()),
+                    Action::Transfer(transfer) => tx.transfers.push(transfer.clone()),
+                    Action::Liquidation(liquidation) => tx.liquidations.push(liquidation),
+                    _ => continue,
+                }
+            }
+
+            gas_details.push(root.gas_details);
+            txes.push(tx);
+        }
+    }
+
+    let block_number_array = build_uint64_array(txes.iter().map(|tx| tx.block_number).collect());
+    let tx_hash_array = build_string_array(txes.iter().map(|tx| tx.tx_hash.clone()).collect());
+    let tx_idx_array = build_uint64_array(txes.iter().map(|tx| tx.tx_idx).collect());
+    let from_array = build_string_array(txes.iter().map(|tx| tx.from.clone()).collect());
+    let to_array =
+        get_string_array_from_owned(txes.iter().map(|tx| tx.to.as_deref()).collect_vec());
+    let gas_details_array = get_gas_details_array(gas_details);
+    let swaps_array =
+        get_normalized_swap_list_array(txes.iter().map(|tx| tx.swaps.clone()).collect());
+    let mints_array =
+        get_normalized_mint_list_array(txes.iter().map(|tx| tx.mints.clone()).collect());
+    let burns_array = get_normalized_burn_list_array(txes.iter().map(|tx| &tx.burns).collect());
+    let transfers_array =
+        get_normalized_transfer_list_array(txes.iter().map(|tx| &tx.transfers).collect());
+    let liquidations_array = get_normalized_liquidation_list_array(
+        txes.iter().map(|tx| tx.liquidations.clone()).collect(),
+    );
+
+    let schema = Schema::new(vec![
+        Field::new("block_number", DataType::UInt64, false),
+        Field::new("tx_hash", DataType::Utf8, false),
+        Field::new("tx_idx", DataType::UInt64, false),
+        Field::new("from", DataType::Utf8, false),
+        Field::new("to", DataType::Utf8, true),
+        Field::new("gas_details", gas_details_array.data_type().clone(), false),
+        Field::new("swaps", swaps_array.data_type().clone(), true),
+        Field::new("mints", mints_array.data_type().clone(), true),
+        Field::new("burns", burns_array.data_type().clone(), true),
+        Field::new("transfers", transfers_array.data_type().clone(), true),
+        Field::new("liquidations", liquidations_array.data_type().clone(), true),
+    ]);
+
+    build_record_batch(
+        schema,
+        vec![
+            Arc::new(block_number_array),
+            Arc::new(tx_hash_array),
+            Arc::new(tx_idx_array),
+            Arc::new(from_array),
+            Arc::new(to_array),
+            Arc::new(gas_details_array),
+            Arc::new(swaps_array),
+            Arc::new(mints_array),
+            Arc::new(burns_array),
+            Arc::new(transfers_array),
+            Arc::new(liquidations_array),
+        ],
+    )
+}
@@ -0,0 +1,522 @@
+use std::sync::Arc;
+
+use alloy_primitives::Address;
+use brontes_types::{
+    db::{
+        address_metadata::AddressMetadata,
+        address_to_protocol_info::ProtocolInfo,
+        block_analysis::BlockAnalysis,
+        builder::{BuilderInfo, BuilderInfoWithAddress},
+        cex::{quotes::CexPriceMap, trades::CexTradeMap},
+        cex_dex_inventory::DailyCexDexInventory,
+        dex::{DexQuoteWithIndex, DexQuotes},
+        lvr::BlockLvr,
+        metadata::Metadata,
+        mev_block::MevBlockWithClassified,
+        normalized_actions::ClassifiedBlock,
+        run_checkpoint::RunCheckpoint,
+        searcher::{JoinedSearcherInfo, SearcherInfo},
+        token_info::TokenInfoWithAddress,
+        traits::{DBWriter, LibmdbxReader, ProtocolCreatedRange},
+    },
+    mev::{BlockFailedAttempts, Bundle, MevBlock},
+    normalized_actions::Action,
+    pair::Pair,
+    structured_trace::TxTrace,
+    traits::TracingProvider,
+    BlockTree, FastHashMap, Protocol,
+};
+use indicatif::ProgressBar;
+
+use super::SqliteSink;
+use crate::{
+    clickhouse::ClickhouseHandle,
+    libmdbx::{LibmdbxInit, StateToInitialize},
+    Tables,
+};
+
+/// Copies the writes that go to ClickHouse into a sqlite file. Without a sink
+/// every write goes straight to the inner db
+#[derive(Clone)]
+pub struct SqliteMiddleware<I: DBWriter> {
+    pub sink: Option<SqliteSink>,
+    inner:    I,
+}
+
+impl<I: DBWriter> SqliteMiddleware<I> {
+    pub fn new(sink: Option<SqliteSink>, inner: I) -> Self {
+        Self { sink, inner }
+    }
+}
+
+impl<I: DBWriter + Send + Sync> DBWriter for SqliteMiddleware<I> {
+    type Inner = I;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    async fn write_block_analysis(&self, block_analysis: BlockAnalysis) -> eyre::Result<()> {
+        if let Some(sink) = &self.sink {
+            sink.block_analysis(block_analysis.clone())?;
+        }
+
+        self.inner().write_block_analysis(block_analysis).await
+    }
+
+    async fn write_dex_quotes(
+        &self,
+        block_number: u64,
+        quotes: Option<DexQuotes>,
+    ) -> eyre::Result<()> {
+        if let Some(sink) = &self.sink {
+            sink.write_dex_quotes(block_number, quotes.clone())?;
+        }
+
+        self.inner().write_dex_quotes(block_number, quotes).await
+    }
+
+    async fn write_token_info(
+        &self,
+        address: Address,
+        decimals: u8,
+        symbol: String,
+    ) -> eyre::Result<()> {
+        if let Some(sink) = &self.sink {
+            sink.write_token_info(address, decimals, symbol.clone())?;
+        }
+
+        self.inner()
+            .write_token_info(address, decimals, symbol)
+            .await
+    }
+
+    async fn save_mev_blocks(
+        &self,
+        block_number: u64,
+        block: MevBlock,
+        mev: Vec<Bundle>,
+    ) -> eyre::Result<()> {
+        if let Some(sink) = &self.sink {
+            sink.save_mev_blocks(block.clone(), mev.clone())?;
+        }
+
+        self.inner().save_mev_blocks(block_number, block, mev).await
+    }
+
+    async fn insert_pool(
+        &self,
+        block: u64,
+        address: Address,
+        tokens: &[Address],
+        curve_lp_token: Option<Address>,
+        classifier_name: Protocol,
+    ) -> eyre::Result<()> {
+        if let Some(sink) = &self.sink {
+            sink.insert_pool(block, address, tokens, curve_lp_token, classifier_name)?;
+        }
+
+        self.inner()
+            .insert_pool(block, address, tokens, curve_lp_token, classifier_name)
+            .await
+    }
+
+    async fn insert_tree(&self, tree: BlockTree<Action>) -> eyre::Result<()> {
+        if let Some(sink) = &self.sink {
+            sink.insert_tree(&tree)?;
+        }
+
+        self.inner().insert_tree(tree).await
+    }
+
+    async fn write_searcher_info(
+        &self,
+        eoa_address: Address,
+        contract_address: Option<Address>,
+        eoa_info: SearcherInfo,
+        contract_info: Option<SearcherInfo>,
+    ) -> eyre::Result<()> {
+        if let Some(sink) = &self.sink {
+            sink.write_searcher_info(JoinedSearcherInfo::new_eoa(eoa_address, eoa_info.clone()))?;
+            if let Some(contract_address) = contract_address {
+                sink.write_searcher_info(JoinedSearcherInfo::new_contract(
+                    contract_address,
+                    contract_info.clone().unwrap_or_default(),
+                ))?;
+            }
+        }
+
+        self.inner()
+            .write_searcher_info(eoa_address, contract_address, eoa_info, contract_info)
+            .await
+    }
+
+    async fn write_searcher_eoa_info(
+        &self,
+        searcher_eoa: Address,
+        searcher_info: SearcherInfo,
+    ) -> eyre::Result<()> {
+        if let Some(sink) = &self.sink {
+            sink.write_searcher_info(JoinedSearcherInfo::new_eoa(
+                searcher_eoa,
+                searcher_info.clone(),
+            ))?;
+        }
+
+        self.inner()
+            .write_searcher_eoa_info(searcher_eoa, searcher_info)
+            .await
+    }
+
+    async fn write_searcher_contract_info(
+        &self,
+        searcher_contract: Address,
+        searcher_info: SearcherInfo,
+    ) -> eyre::Result<()> {
+        if let Some(sink) = &self.sink {
+            sink.write_searcher_info(JoinedSearcherInfo::new_contract(
+                searcher_contract,
+                searcher_info.clone(),
+            ))?;
+        }
+
+        self.inner()
+            .write_searcher_contract_info(searcher_contract, searcher_info)
+            .await
+    }
+
+    async fn write_builder_info(
+        &self,
+        builder_coinbase_addr: Address,
+        builder_info: BuilderInfo,
+    ) -> eyre::Result<()> {
+        if let Some(sink) = &self.sink {
+            sink.write_builder_info(BuilderInfoWithAddress::new_with_address(
+                builder_coinbase_addr,
+                builder_info.clone(),
+            ))?;
+        }
+
+        self.inner()
+            .write_builder_info(builder_coinbase_addr, builder_info)
+            .await
+    }
+
+    async fn save_traces(&self, block: u64, traces: Vec<TxTrace>) -> eyre::Result<()> {
+        if let Some(sink) = &self.sink {
+            sink.save_traces(&traces)?;
+        }
+
+        self.inner().save_traces(block, traces).await
+    }
+}
+
+impl<I: LibmdbxInit> LibmdbxInit for SqliteMiddleware<I> {
+    async fn initialize_table<T: brontes_types::traits::TracingProvider, CH: ClickhouseHandle>(
+        &'static self,
+        clickhouse: &'static CH,
+        tracer: std::sync::Arc<T>,
+        tables: crate::Tables,
+        clear_tables: bool,
+        block_range: Option<(u64, u64)>, // inclusive of start only
+        progress_bar: Arc<Vec<(Tables, ProgressBar)>>,
+        metrics: bool,
+    ) -> eyre::Result<()> {
+        self.inner
+            .initialize_table(
+                clickhouse,
+                tracer,
+                tables,
+                clear_tables,
+                block_range,
+                progress_bar,
+                metrics,
+            )
+            .await
+    }
+
+    fn get_db_range(&self) -> eyre::Result<(u64, u64)> {
+        self.inner.get_db_range()
+    }
+
+    async fn initialize_table_arbitrary<
+        T: brontes_types::traits::TracingProvider,
+        CH: ClickhouseHandle,
+    >(
+        &'static self,
+        clickhouse: &'static CH,
+        tracer: std::sync::Arc<T>,
+        tables: crate::Tables,
+        block_range: Vec<u64>,
+        progress_bar: Arc<Vec<(Tables, ProgressBar)>>,
+        metrics: bool,
+    ) -> eyre::Result<()> {
+        self.inner
+            .initialize_table_arbitrary(
+                clickhouse,
+                tracer,
+                tables,
+                block_range,
+                progress_bar,
+                metrics,
+            )
+            .await
+    }
+
+    async fn initialize_full_range_tables<T: TracingProvider, CH: ClickhouseHandle>(
+        &'static self,
+        clickhouse: &'static CH,
+        tracer: Arc<T>,
+        metrics: bool,
+    ) -> eyre::Result<()> {
+        self.inner
+            .initialize_full_range_tables(clickhouse, tracer, metrics)
+            .await
+    }
+
+    fn state_to_initialize(
+        &self,
+        start_block: u64,
+        end_block: u64,
+    ) -> eyre::Result<StateToInitialize> {
+        self.inner.state_to_initialize(start_block, end_block)
+    }
+}
+
+impl<I: LibmdbxInit> LibmdbxReader for SqliteMiddleware<I> {
+    fn get_most_recent_block(&self) -> eyre::Result<u64> {
+        self.inner.get_most_recent_block()
+    }
+
+    fn has_dex_quotes(&self, block_num: u64) -> eyre::Result<bool> {
+        self.inner.has_dex_quotes(block_num)
+    }
+
+    fn get_cex_trades(
+        &self,
+        block: u64,
+    ) -> eyre::Result<brontes_types::db::cex::trades::CexTradeMap> {
+        self.inner.get_cex_trades(block)
+    }
+
+    fn get_metadata_no_dex_price(
+        &self,
+        block_num: u64,
+        quote_asset: Address,
+    ) -> eyre::Result<Metadata> {
+        self.inner.get_metadata_no_dex_price(block_num, quote_asset)
+    }
+
+    fn try_fetch_searcher_eoa_info(
+        &self,
+        searcher_eoa: Address,
+    ) -> eyre::Result<Option<SearcherInfo>> {
+        self.inner.try_fetch_searcher_eoa_info(searcher_eoa)
+    }
+
+    fn try_fetch_searcher_eoa_infos(
+        &self,
+        searcher_eoa: Vec<Address>,
+    ) -> eyre::Result<FastHashMap<Address, SearcherInfo>> {
+        self.inner.try_fetch_searcher_eoa_infos(searcher_eoa)
+    }
+
+    fn try_fetch_searcher_contract_infos(
+        &self,
+        searcher_eoa: Vec<Address>,
+    ) -> eyre::Result<FastHashMap<Address, SearcherInfo>> {
+        self.inner.try_fetch_searcher_contract_infos(searcher_eoa)
+    }
+
+    fn try_fetch_searcher_contract_info(
+        &self,
+        searcher_eoa: Address,
+    ) -> eyre::Result<Option<SearcherInfo>> {
+        self.inner.try_fetch_searcher_contract_info(searcher_eoa)
+    }
+
+    fn fetch_all_searcher_eoa_info(&self) -> eyre::Result<Vec<(Address, SearcherInfo)>> {
+        self.inner.fetch_all_searcher_eoa_info()
+    }
+
+    fn fetch_all_searcher_contract_info(&self) -> eyre::Result<Vec<(Address, SearcherInfo)>> {
+        self.inner.fetch_all_searcher_contract_info()
+    }
+
+    fn fetch_all_searcher_info(
+        &self,
+    ) -> eyre::Result<(Vec<(Address, SearcherInfo)>, Vec<(Address, SearcherInfo)>)> {
+        self.inner.fetch_all_searcher_info()
+    }
+
+    fn try_fetch_builder_info(
+        &self,
+        builder_coinbase_addr: Address,
+    ) -> eyre::Result<Option<BuilderInfo>> {
+        self.inner.try_fetch_builder_info(builder_coinbase_addr)
+    }
+
+    fn fetch_all_builder_info(&self) -> eyre::Result<Vec<(Address, BuilderInfo)>> {
+        self.inner.fetch_all_builder_info()
+    }
+
+    fn try_fetch_mev_blocks(
+        &self,
+        start_block: Option<u64>,
+        end_block: u64,
+    ) -> eyre::Result<Vec<MevBlockWithClassified>> {
+        self.inner.try_fetch_mev_blocks(start_block, end_block)
+    }
+
+    fn fetch_all_mev_blocks(
+        &self,
+        start_block: Option<u64>,
+    ) -> eyre::Result<Vec<MevBlockWithClassified>> {
+        self.inner.fetch_all_mev_blocks(start_block)
+    }
+
+    fn try_fetch_block_analysis(&self, block_num: u64) -> eyre::Result<Option<BlockAnalysis>> {
+        self.inner.try_fetch_block_analysis(block_num)
+    }
+
+    fn try_fetch_block_analyses(
+        &self,
+        start_block: Option<u64>,
+        end_block: u64,
+    ) -> eyre::Result<Vec<BlockAnalysis>> {
+        self.inner.try_fetch_block_analyses(start_block, end_block)
+    }
+
+    fn try_fetch_classified_block(&self, block_num: u64) -> eyre::Result<Option<ClassifiedBlock>> {
+        self.inner.try_fetch_classified_block(block_num)
+    }
+
+    fn try_fetch_classified_blocks(
+        &self,
+        start_block: Option<u64>,
+        end_block: u64,
+    ) -> eyre::Result<Vec<ClassifiedBlock>> {
+        self.inner
+            .try_fetch_classified_blocks(start_block, end_block)
+    }
+
+    fn try_fetch_block_lvrs(
+        &self,
+        start_block: Option<u64>,
+        end_block: u64,
+    ) -> eyre::Result<Vec<BlockLvr>> {
+        self.inner.try_fetch_block_lvrs(start_block, end_block)
+    }
+
+    fn try_fetch_failed_attempts(
+        &self,
+        start_block: Option<u64>,
+        end_block: u64,
+    ) -> eyre::Result<Vec<BlockFailedAttempts>> {
+        self.inner.try_fetch_failed_attempts(start_block, end_block)
+    }
+
+    fn try_fetch_cex_dex_inventories(
+        &self,
+        start_day: Option<u64>,
+        end_day: u64,
+    ) -> eyre::Result<Vec<DailyCexDexInventory>> {
+        self.inner.try_fetch_cex_dex_inventories(start_day, end_day)
+    }
+
+    fn try_fetch_run_checkpoint(&self, run_id: u64) -> eyre::Result<Option<RunCheckpoint>> {
+        self.inner.try_fetch_run_checkpoint(run_id)
+    }
+
+    fn latest_run_checkpoint_id(&self) -> eyre::Result<Option<u64>> {
+        self.inner.latest_run_checkpoint_id()
+    }
+
+    fn get_metadata(&self, block_num: u64, quote_asset: Address) -> eyre::Result<Metadata> {
+        self.inner.get_metadata(block_num, quote_asset)
+    }
+
+    fn try_fetch_address_metadata(
+        &self,
+        address: Address,
+    ) -> eyre::Result<Option<AddressMetadata>> {
+        self.inner.try_fetch_address_metadata(address)
+    }
+
+    fn try_fetch_address_metadatas(
+        &self,
+        address: Vec<Address>,
+    ) -> eyre::Result<FastHashMap<Address, AddressMetadata>> {
+        self.inner.try_fetch_address_metadatas(address)
+    }
+
+    fn fetch_all_address_metadata(&self) -> eyre::Result<Vec<(Address, AddressMetadata)>> {
+        self.inner.fetch_all_address_metadata()
+    }
+
+    fn get_dex_quotes(&self, block: u64) -> eyre::Result<DexQuotes> {
+        self.inner.get_dex_quotes(block)
+    }
+
+    fn try_fetch_dex_quotes_range(
+        &self,
+        start_block: u64,
+        end_block: u64,
+    ) -> eyre::Result<Vec<(u64, DexQuoteWithIndex)>> {
+        self.inner
+            .try_fetch_dex_quotes_range(start_block, end_block)
+    }
+
+    fn try_fetch_cex_trades_range(
+        &self,
+        start_block: u64,
+        end_block: u64,
+    ) -> eyre::Result<Vec<(u64, CexTradeMap)>> {
+        self.inner
+            .try_fetch_cex_trades_range(start_block, end_block)
+    }
+
+    fn try_fetch_cex_quotes_range(
+        &self,
+        start_block: u64,
+        end_block: u64,
+    ) -> eyre::Result<Vec<(u64, CexPriceMap)>> {
+        self.inner
+            .try_fetch_cex_quotes_range(start_block, end_block)
+    }
+
+    fn fetch_all_token_info(&self) -> eyre::Result<Vec<TokenInfoWithAddress>> {
+        self.inner.fetch_all_token_info()
+    }
+
+    fn fetch_all_protocol_info(&self) -> eyre::Result<Vec<(Address, ProtocolInfo)>> {
+        self.inner.fetch_all_protocol_info()
+    }
+
+    fn try_fetch_token_info(&self, address: Address) -> eyre::Result<TokenInfoWithAddress> {
+        self.inner.try_fetch_token_info(address)
+    }
+
+    fn protocols_created_before(
+        &self,
+        start_block: u64,
+    ) -> eyre::Result<FastHashMap<(Address, Protocol), Pair>> {
+        self.inner.protocols_created_before(start_block)
+    }
+
+    fn protocols_created_range(
+        &self,
+        start_block: u64,
+        end_block: u64,
+    ) -> eyre::Result<ProtocolCreatedRange> {
+        self.inner.protocols_created_range(start_block, end_block)
+    }
+
+    fn get_protocol_details(&self, address: Address) -> eyre::Result<ProtocolInfo> {
+        self.inner.get_protocol_details(address)
+    }
+
+    fn load_trace(&self, block_num: u64) -> eyre::Result<Vec<TxTrace>> {
+        self.inner.load_trace(block_num)
+    }
+}
@@ -0,0 +1,7 @@
+mod middleware;
+mod row;
+mod schema;
+mod writer;
+
+pub use middleware::*;
+pub use writer::*;
@@ -0,0 +1,367 @@
+use std::fmt::Display;
+
+use rusqlite::types::Value;
+use serde::{
+    ser::{self, Impossible, SerializeStruct},
+    Serialize, Serializer,
+};
+
+/// A row of one of the ClickHouse row types. The columns are named after the
+/// fields the type serializes, which are the ClickHouse column names
+#[derive(Debug, Clone)]
+pub struct SqlRow {
+    pub columns: Vec<(&'static str, Value)>,
+}
+
+impl SqlRow {
+    pub fn new<T: Serialize>(row: &T, run_id: Option<u64>) -> Result<Self, SqlSerializeError> {
+        let mut columns = row.serialize(RowSerializer)?;
+        if let Some(run_id) = run_id {
+            columns.push(("run_id", Value::Integer(run_id as i64)));
+        }
+
+        Ok(Self { columns })
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum SqlSerializeError {
+    #[error("{0}")]
+    Custom(String),
+    #[error("rows have to serialize as a struct")]
+    NotAStruct,
+    /// the value doesn't fit a single sqlite value and is stored as json
+    #[error("compound value")]
+    Compound,
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+}
+
+impl ser::Error for SqlSerializeError {
+    fn custom<T: Display>(msg: T) -> Self {
+        Self::Custom(msg.to_string())
+    }
+}
+
+macro_rules! reject {
+    ($err:ident, $($fn_name:ident($($arg:ty),*)),* $(,)?) => {
+        $(
+            fn $fn_name(self, $(_: $arg),*) -> Result<Self::Ok, Self::Error> {
+                Err(SqlSerializeError::$err)
+            }
+        )*
+    };
+}
+
+/// Splits a struct into its columns
+struct RowSerializer;
+
+impl Serializer for RowSerializer {
+    type Error = SqlSerializeError;
+    type Ok = Vec<(&'static str, Value)>;
+    type SerializeMap = Impossible<Self::Ok, Self::Error>;
+    type SerializeSeq = Impossible<Self::Ok, Self::Error>;
+    type SerializeStruct = StructColumns;
+    type SerializeStructVariant = Impossible<Self::Ok, Self::Error>;
+    type SerializeTuple = Impossible<Self::Ok, Self::Error>;
+    type SerializeTupleStruct = Impossible<Self::Ok, Self::Error>;
+    type SerializeTupleVariant = Impossible<Self::Ok, Self::Error>;
+
+    reject!(
+        NotAStruct,
+        serialize_bool(bool),
+        serialize_i8(i8),
+        serialize_i16(i16),
+        serialize_i32(i32),
+        serialize_i64(i64),
+        serialize_u8(u8),
+        serialize_u16(u16),
+        serialize_u32(u32),
+        serialize_u64(u64),
+        serialize_f32(f32),
+        serialize_f64(f64),
+        serialize_char(char),
+        serialize_str(&str),
+        serialize_bytes(&[u8]),
+        serialize_none(),
+        serialize_unit(),
+        serialize_unit_struct(&'static str),
+        serialize_unit_variant(&'static str, u32, &'static str),
+    );
+
+    fn serialize_some<T: ?Sized + Serialize>(self, value: &T) -> Result<Self::Ok, Self::Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_struct<T: ?Sized + Serialize>(
+        self,
+        _: &'static str,
+        value: &T,
+    ) -> Result<Self::Ok, Self::Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: ?Sized + Serialize>(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: &T,
+    ) -> Result<Self::Ok, Self::Error> {
+        Err(SqlSerializeError::NotAStruct)
+    }
+
+    fn serialize_seq(self, _: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
+        Err(SqlSerializeError::NotAStruct)
+    }
+
+    fn serialize_tuple(self, _: usize) -> Result<Self::SerializeTuple, Self::Error> {
+        Err(SqlSerializeError::NotAStruct)
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeTupleStruct, Self::Error> {
+        Err(SqlSerializeError::NotAStruct)
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeTupleVariant, Self::Error> {
+        Err(SqlSerializeError::NotAStruct)
+    }
+
+    fn serialize_map(self, _: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
+        Err(SqlSerializeError::NotAStruct)
+    }
+
+    fn serialize_struct(
+        self,
+        _: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeStruct, Self::Error> {
+        Ok(StructColumns { columns: Vec::with_capacity(len) })
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeStructVariant, Self::Error> {
+        Err(SqlSerializeError::NotAStruct)
+    }
+}
+
+struct StructColumns {
+    columns: Vec<(&'static str, Value)>,
+}
+
+impl SerializeStruct for StructColumns {
+    type Error = SqlSerializeError;
+    type Ok = Vec<(&'static str, Value)>;
+
+    fn serialize_field<T: ?Sized + Serialize>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Self::Error> {
+        // arrays, tuples and nested columns are stored as json text
+        let value = match value.serialize(ColumnSerializer) {
+            Ok(value) => value,
+            Err(SqlSerializeError::Compound) => Value::Text(serde_json::to_string(value)?),
+            Err(e) => return Err(e),
+        };
+        self.columns.push((key, value));
+
+        Ok(())
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        Ok(self.columns)
+    }
+}
+
+/// Serializes a single column value. Integers that don't fit an i64 are
+/// stored as text to keep their precision
+struct ColumnSerializer;
+
+impl Serializer for ColumnSerializer {
+    type Error = SqlSerializeError;
+    type Ok = Value;
+    type SerializeMap = Impossible<Self::Ok, Self::Error>;
+    type SerializeSeq = Impossible<Self::Ok, Self::Error>;
+    type SerializeStruct = Impossible<Self::Ok, Self::Error>;
+    type SerializeStructVariant = Impossible<Self::Ok, Self::Error>;
+    type SerializeTuple = Impossible<Self::Ok, Self::Error>;
+    type SerializeTupleStruct = Impossible<Self::Ok, Self::Error>;
+    type SerializeTupleVariant = Impossible<Self::Ok, Self::Error>;
+
+    fn serialize_bool(self, v: bool) -> Result<Self::Ok, Self::Error> {
+        Ok(Value::Integer(v as i64))
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<Self::Ok, Self::Error> {
+        Ok(Value::Integer(v as i64))
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<Self::Ok, Self::Error> {
+        Ok(Value::Integer(v as i64))
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<Self::Ok, Self::Error> {
+        Ok(Value::Integer(v as i64))
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<Self::Ok, Self::Error> {
+        Ok(Value::Integer(v))
+    }
+
+    fn serialize_i128(self, v: i128) -> Result<Self::Ok, Self::Error> {
+        Ok(i64::try_from(v)
+            .map(Value::Integer)
+            .unwrap_or_else(|_| Value::Text(v.to_string())))
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<Self::Ok, Self::Error> {
+        Ok(Value::Integer(v as i64))
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<Self::Ok, Self::Error> {
+        Ok(Value::Integer(v as i64))
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<Self::Ok, Self::Error> {
+        Ok(Value::Integer(v as i64))
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<Self::Ok, Self::Error> {
+        Ok(i64::try_from(v)
+            .map(Value::Integer)
+            .unwrap_or_else(|_| Value::Text(v.to_string())))
+    }
+
+    fn serialize_u128(self, v: u128) -> Result<Self::Ok, Self::Error> {
+        Ok(i64::try_from(v)
+            .map(Value::Integer)
+            .unwrap_or_else(|_| Value::Text(v.to_string())))
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<Self::Ok, Self::Error> {
+        Ok(Value::Real(v as f64))
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<Self::Ok, Self::Error> {
+        Ok(Value::Real(v))
+    }
+
+    fn serialize_char(self, v: char) -> Result<Self::Ok, Self::Error> {
+        Ok(Value::Text(v.to_string()))
+    }
+
+    fn serialize_str(self, v: &str) -> Result<Self::Ok, Self::Error> {
+        Ok(Value::Text(v.to_string()))
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<Self::Ok, Self::Error> {
+        Ok(Value::Blob(v.to_vec()))
+    }
+
+    fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
+        Ok(Value::Null)
+    }
+
+    fn serialize_some<T: ?Sized + Serialize>(self, value: &T) -> Result<Self::Ok, Self::Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<Self::Ok, Self::Error> {
+        Ok(Value::Null)
+    }
+
+    fn serialize_unit_struct(self, _: &'static str) -> Result<Self::Ok, Self::Error> {
+        Ok(Value::Null)
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        variant: &'static str,
+    ) -> Result<Self::Ok, Self::Error> {
+        Ok(Value::Text(variant.to_string()))
+    }
+
+    fn serialize_newtype_struct<T: ?Sized + Serialize>(
+        self,
+        _: &'static str,
+        value: &T,
+    ) -> Result<Self::Ok, Self::Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: ?Sized + Serialize>(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: &T,
+    ) -> Result<Self::Ok, Self::Error> {
+        Err(SqlSerializeError::Compound)
+    }
+
+    fn serialize_seq(self, _: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
+        Err(SqlSerializeError::Compound)
+    }
+
+    fn serialize_tuple(self, _: usize) -> Result<Self::SerializeTuple, Self::Error> {
+        Err(SqlSerializeError::Compound)
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeTupleStruct, Self::Error> {
+        Err(SqlSerializeError::Compound)
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeTupleVariant, Self::Error> {
+        Err(SqlSerializeError::Compound)
+    }
+
+    fn serialize_map(self, _: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
+        Err(SqlSerializeError::Compound)
+    }
+
+    fn serialize_struct(
+        self,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeStruct, Self::Error> {
+        Err(SqlSerializeError::Compound)
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeStructVariant, Self::Error> {
+        Err(SqlSerializeError::Compound)
+    }
+}
@@ -0,0 +1,494 @@
+use itertools::Itertools;
+
+use super::row::SqlRow;
+
+/// The ClickHouse DDL of every table the sink writes. The `brontes_api` tables
+/// aren't created by brontes, so their DDL lives next to the sink
+const TABLE_DDLS: [&str; 20] = [
+    include_str!("../clickhouse/tables/dex_price_mapping.sql"),
+    include_str!("../clickhouse/tables/block_analysis.sql"),
+    include_str!("../clickhouse/tables/token_info.sql"),
+    include_str!("../clickhouse/tables/tree.sql"),
+    include_str!("../clickhouse/tables/run_id.sql"),
+    include_str!("../clickhouse/tables/pools.sql"),
+    include_str!("../clickhouse/tables/mev_blocks.sql"),
+    include_str!("../clickhouse/tables/bundle_header.sql"),
+    include_str!("../clickhouse/tables/searcher_tx.sql"),
+    include_str!("../clickhouse/tables/cex_dex_quotes.sql"),
+    include_str!("../clickhouse/tables/cex_dex.sql"),
+    include_str!("../clickhouse/tables/liquidations.sql"),
+    include_str!("../clickhouse/tables/jit_sandwich.sql"),
+    include_str!("../clickhouse/tables/jit.sql"),
+    include_str!("../clickhouse/tables/sandwich.sql"),
+    include_str!("../clickhouse/tables/atomic_arbs.sql"),
+    include_str!("../clickhouse/tables/nft_arbs.sql"),
+    include_str!("tables/searcher_info.sql"),
+    include_str!("tables/builder_info.sql"),
+    include_str!("tables/tx_traces.sql"),
+];
+
+/// Parses the schemas of all tables the sink writes
+pub fn table_schemas() -> eyre::Result<Vec<TableSchema>> {
+    TABLE_DDLS
+        .iter()
+        .map(|ddl| TableSchema::parse(ddl))
+        .collect()
+}
+
+/// A sqlite table with the columns and sort key of its ClickHouse table
+#[derive(Debug, Clone, PartialEq)]
+pub struct TableSchema {
+    /// `<database>_<table>`, sqlite has no databases to group tables in
+    pub name:    String,
+    pub columns: Vec<Column>,
+    /// the ClickHouse sort key, rows with the same key replace each other
+    pub key:     Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Column {
+    pub name:        String,
+    /// the sqlite type affinity of the column, empty for integers that don't
+    /// fit an i64 so they keep the type they were written with
+    pub sql_type:    &'static str,
+    pub sql_default: Option<&'static str>,
+}
+
+impl TableSchema {
+    /// Reads the table name, the columns and the sort key of a ClickHouse
+    /// `CREATE TABLE` statement. `Nested` columns are split into a column per
+    /// field the way ClickHouse inserts them
+    pub fn parse(ddl: &str) -> eyre::Result<Self> {
+        let ddl = ddl
+            .lines()
+            .map(|line| line.split("--").next().unwrap_or_default())
+            .join("\n");
+
+        let (_, rest) = ddl
+            .split_once("CREATE TABLE")
+            .ok_or_else(|| eyre::eyre!("ddl has no CREATE TABLE"))?;
+        let name = rest
+            .split_whitespace()
+            .next()
+            .ok_or_else(|| eyre::eyre!("ddl has no table name"))?
+            .replace('.', "_");
+
+        let open = rest
+            .find('(')
+            .ok_or_else(|| eyre::eyre!("{name} has no column list"))?;
+        let close = matching_paren(rest, open)
+            .ok_or_else(|| eyre::eyre!("{name} has an unclosed column list"))?;
+
+        let columns = parse_columns(&rest[open + 1..close], None)?;
+        let key = parse_sort_key(&rest[close + 1..])
+            .ok_or_else(|| eyre::eyre!("{name} has no ORDER BY"))?;
+        if let Some(missing) = key
+            .iter()
+            .find(|key| !columns.iter().any(|column| column.name == **key))
+        {
+            eyre::bail!("sort key column {missing} of {name} isn't a column");
+        }
+
+        Ok(Self { name, columns, key })
+    }
+
+    pub fn create_table_sql(&self) -> String {
+        let columns = self
+            .columns
+            .iter()
+            .map(|column| {
+                let mut def = format!("\"{}\" {}", column.name, column.sql_type);
+                if let Some(default) = column.sql_default {
+                    def.push_str(&format!(" DEFAULT {default}"));
+                }
+                def
+            })
+            .join(", ");
+        let key = self.key.iter().map(|key| format!("\"{key}\"")).join(", ");
+
+        format!("CREATE TABLE IF NOT EXISTS \"{}\" ({columns}, PRIMARY KEY ({key}));", self.name)
+    }
+
+    /// Rows replace the stored row with the same sort key, so writing a block
+    /// again doesn't duplicate its rows
+    pub fn insert_sql(&self, row: &SqlRow) -> eyre::Result<String> {
+        if let Some((name, _)) = row
+            .columns
+            .iter()
+            .find(|(name, _)| !self.columns.iter().any(|column| column.name == *name))
+        {
+            eyre::bail!("{} has no column {name}", self.name);
+        }
+
+        let columns = row
+            .columns
+            .iter()
+            .map(|(name, _)| format!("\"{name}\""))
+            .join(", ");
+        let params = (1..=row.columns.len()).map(|i| format!("?{i}")).join(", ");
+
+        Ok(format!("INSERT OR REPLACE INTO \"{}\" ({columns}) VALUES ({params})", self.name))
+    }
+}
+
+fn parse_columns(list: &str, nested_in: Option<&str>) -> eyre::Result<Vec<Column>> {
+    let mut columns = vec![];
+
+    for def in split_top_level(list) {
+        let def = def.trim();
+        if def.is_empty() {
+            continue
+        }
+
+        let (name, rest) = match def.strip_prefix('`') {
+            Some(quoted) => quoted
+                .split_once('`')
+                .ok_or_else(|| eyre::eyre!("unclosed column name in {def}"))?,
+            None => def
+                .split_once(char::is_whitespace)
+                .ok_or_else(|| eyre::eyre!("column {def} has no type"))?,
+        };
+        let name = match nested_in {
+            Some(parent) => format!("{parent}.{name}"),
+            None => name.to_string(),
+        };
+        let (column_type, default) = match rest.split_once(" DEFAULT ") {
+            Some((column_type, default)) => (column_type.trim(), Some(default.trim())),
+            None => (rest.trim(), None),
+        };
+
+        if let Some(fields) = column_type.strip_prefix("Nested") {
+            let fields = fields.trim();
+            let close = matching_paren(fields, 0)
+                .ok_or_else(|| eyre::eyre!("unclosed Nested of {name}"))?;
+            // every field of a nested column is an array
+            columns.extend(
+                parse_columns(&fields[1..close], Some(&name))?
+                    .into_iter()
+                    .map(|field| Column { sql_type: "TEXT", ..field }),
+            );
+            continue
+        }
+
+        columns.push(Column {
+            sql_type: sql_type(column_type),
+            sql_default: default.and_then(sql_default),
+            name,
+        });
+    }
+
+    Ok(columns)
+}
+
+/// Maps a ClickHouse type to the sqlite type affinity of the values the row
+/// serializer writes for it
+fn sql_type(column_type: &str) -> &'static str {
+    let mut column_type = column_type.trim();
+    for wrapper in ["Nullable(", "LowCardinality("] {
+        if let Some(inner) = column_type
+            .strip_prefix(wrapper)
+            .and_then(|inner| inner.strip_suffix(')'))
+        {
+            column_type = inner.trim();
+        }
+    }
+
+    match column_type {
+        t if ["Array", "Tuple", "Map"].iter().any(|c| t.starts_with(c)) => "TEXT",
+        t if t.starts_with("String") || t.starts_with("FixedString") => "TEXT",
+        t if t.starts_with("Float") => "REAL",
+        "UInt128" | "UInt256" | "Int128" | "Int256" => "",
+        t if t.starts_with("UInt") || t.starts_with("Int") || t.starts_with("DateTime") => {
+            "INTEGER"
+        }
+        "Bool" | "bool" => "INTEGER",
+        _ => "",
+    }
+}
+
+fn sql_default(default: &str) -> Option<&'static str> {
+    (default == "now()").then_some("(unixepoch())")
+}
+
+fn parse_sort_key(rest: &str) -> Option<Vec<String>> {
+    let (_, key) = rest.split_once("ORDER BY")?;
+    let key = key.trim_start();
+    let key = match key.strip_prefix('(') {
+        Some(inner) => inner.split_once(')')?.0,
+        None => key.split_whitespace().next()?,
+    };
+
+    Some(
+        key.split(',')
+            .map(|column| column.trim().trim_matches('`').to_string())
+            .filter(|column| !column.is_empty())
+            .collect(),
+    )
+}
+
+/// Index of the `)` closing the `(` at `open`
+fn matching_paren(s: &str, open: usize) -> Option<usize> {
+    let mut depth = 0usize;
+    for (i, c) in s.char_indices().skip_while(|(i, _)| *i < open) {
+        match c {
+            '(' => depth += 1,
+            ')' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(i)
+                }
+            }
+            _ => {}
+        }
+    }
+
+    None
+}
+
+/// Splits on the commas that aren't inside parentheses
+fn split_top_level(list: &str) -> Vec<&str> {
+    let mut parts = vec![];
+    let (mut depth, mut start) = (0usize, 0);
+    for (i, c) in list.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => {
+                parts.push(&list[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(&list[start..]);
+
+    parts
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::Address;
+    use brontes_types::{
+        db::{
+            address_to_protocol_info::ProtocolInfoClickhouse,
+            builder::BuilderInfoWithAddress,
+            searcher::{JoinedSearcherInfo, SearcherInfo},
+            token_info::{TokenInfo, TokenInfoWithAddress},
+        },
+        mev::{AtomicArb, BundleHeader, MevBlock, Sandwich},
+        structured_trace::TxTrace,
+        Protocol,
+    };
+    use rusqlite::{params_from_iter, types::Value, Connection};
+    use serde::Serialize;
+
+    use super::*;
+    use crate::sqlite::table_names::*;
+
+    fn schema(table: &str) -> TableSchema {
+        table_schemas()
+            .unwrap()
+            .into_iter()
+            .find(|schema| schema.name == table)
+            .unwrap()
+    }
+
+    fn insert(conn: &Connection, schema: &TableSchema, row: &SqlRow) {
+        conn.execute(
+            &schema.insert_sql(row).unwrap(),
+            params_from_iter(row.columns.iter().map(|(_, value)| value)),
+        )
+        .unwrap();
+    }
+
+    fn stored_rows(conn: &Connection, table: &str, row: &SqlRow) -> Vec<Vec<Value>> {
+        let columns = row
+            .columns
+            .iter()
+            .map(|(name, _)| format!("\"{name}\""))
+            .join(", ");
+        let mut stmt = conn
+            .prepare(&format!("SELECT {columns} FROM \"{table}\""))
+            .unwrap();
+
+        stmt.query_map([], |stored| {
+            (0..row.columns.len())
+                .map(|i| stored.get::<_, Value>(i))
+                .collect()
+        })
+        .unwrap()
+        .collect::<Result<_, _>>()
+        .unwrap()
+    }
+
+    /// Writes the row twice into a new table and checks it's stored once with
+    /// the values it was written with
+    fn assert_round_trip<T: Serialize>(table: &str, row: &T, run_id: Option<u64>) {
+        let schema = schema(table);
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(&schema.create_table_sql()).unwrap();
+
+        let row = SqlRow::new(row, run_id).unwrap();
+        insert(&conn, &schema, &row);
+        insert(&conn, &schema, &row);
+
+        let values = row
+            .columns
+            .iter()
+            .map(|(_, value)| value.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(stored_rows(&conn, table, &row), vec![values], "{table}");
+    }
+
+    #[test]
+    fn test_parse_ddl() {
+        let schema = TableSchema::parse(
+            "CREATE TABLE db.example ON CLUSTER eth_cluster0
+            (
+                -- the block
+                `block_number` UInt64,
+                hash String,
+                `amount` Nullable(UInt256),
+                `price` Float64,
+                `pair` Tuple(String, String),
+                `swaps` Nested (
+                    `pool` LowCardinality(String),
+                    `amounts` Tuple(UInt256, UInt256),
+                ),
+                `last_updated` UInt64 DEFAULT now()
+            )
+            ENGINE = ReplicatedReplacingMergeTree('/clickhouse/db/example', '{replica}')
+            ORDER BY (`block_number`, hash)",
+        )
+        .unwrap();
+
+        let column = |name: &str, sql_type, sql_default| Column {
+            name: name.to_string(),
+            sql_type,
+            sql_default,
+        };
+        assert_eq!(schema.name, "db_example");
+        assert_eq!(
+            schema.columns,
+            vec![
+                column("block_number", "INTEGER", None),
+                column("hash", "TEXT", None),
+                column("amount", "", None),
+                column("price", "REAL", None),
+                column("pair", "TEXT", None),
+                column("swaps.pool", "TEXT", None),
+                column("swaps.amounts", "TEXT", None),
+                column("last_updated", "INTEGER", Some("(unixepoch())")),
+            ]
+        );
+        assert_eq!(schema.key, vec!["block_number", "hash"]);
+    }
+
+    #[test]
+    fn test_parse_rejects_unknown_sort_key() {
+        let ddl = "CREATE TABLE db.example (`a` UInt64) ENGINE = MergeTree ORDER BY b";
+        assert!(TableSchema::parse(ddl).is_err());
+        assert!(TableSchema::parse("SELECT 1").is_err());
+    }
+
+    #[test]
+    fn test_every_table_has_a_schema() {
+        let names = table_schemas()
+            .unwrap()
+            .into_iter()
+            .map(|schema| schema.name)
+            .collect::<Vec<_>>();
+
+        for table in [
+            RUN_ID,
+            DEX_PRICE_MAPPING,
+            BLOCK_ANALYSIS,
+            TOKEN_INFO,
+            TREE,
+            POOLS,
+            MEV_BLOCKS,
+            BUNDLE_HEADER,
+            SEARCHER_TX,
+            CEX_DEX_QUOTES,
+            CEX_DEX,
+            LIQUIDATIONS,
+            JIT_SANDWICH,
+            JIT,
+            SANDWICHES,
+            ATOMIC_ARBS,
+            NFT_ARBS,
+            SEARCHER_INFO,
+            BUILDER_INFO,
+            TX_TRACES,
+        ] {
+            assert!(names.iter().any(|name| name == table), "{table}");
+        }
+
+        let atomic_arbs = schema(ATOMIC_ARBS);
+        assert_eq!(atomic_arbs.key, vec!["block_number", "tx_hash"]);
+        assert!(atomic_arbs
+            .columns
+            .iter()
+            .any(|column| column.name == "swaps.trace_idx" && column.sql_type == "TEXT"));
+    }
+
+    #[test]
+    fn test_rows_round_trip() {
+        assert_round_trip(MEV_BLOCKS, &MevBlock::default(), Some(1));
+        assert_round_trip(BUNDLE_HEADER, &BundleHeader::default(), Some(1));
+        assert_round_trip(ATOMIC_ARBS, &AtomicArb::default(), Some(1));
+        assert_round_trip(SANDWICHES, &Sandwich::default(), Some(1));
+        assert_round_trip(
+            TOKEN_INFO,
+            &TokenInfoWithAddress {
+                address: Address::with_last_byte(1),
+                inner:   TokenInfo { symbol: "WETH".to_string(), decimals: 18 },
+            },
+            None,
+        );
+        assert_round_trip(
+            POOLS,
+            &ProtocolInfoClickhouse::new(
+                18_000_000,
+                Address::with_last_byte(2),
+                &[Address::with_last_byte(1), Address::with_last_byte(3)],
+                None,
+                Protocol::UniswapV2,
+            ),
+            None,
+        );
+        assert_round_trip(
+            SEARCHER_INFO,
+            &JoinedSearcherInfo::new_eoa(Address::with_last_byte(4), SearcherInfo::default()),
+            None,
+        );
+        assert_round_trip(BUILDER_INFO, &BuilderInfoWithAddress::default(), None);
+        assert_round_trip(TX_TRACES, &TxTrace::default(), None);
+    }
+
+    #[test]
+    fn test_rerun_replaces_rows() {
+        let schema = schema(MEV_BLOCKS);
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(&schema.create_table_sql()).unwrap();
+
+        let block = MevBlock { block_number: 18_000_000, ..Default::default() };
+        insert(&conn, &schema, &SqlRow::new(&block, Some(1)).unwrap());
+        insert(&conn, &schema, &SqlRow::new(&block, Some(2)).unwrap());
+
+        let run_ids = conn
+            .prepare("SELECT run_id FROM mev_mev_blocks")
+            .unwrap()
+            .query_map([], |row| row.get::<_, i64>(0))
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(run_ids, vec![2]);
+    }
+
+    #[test]
+    fn test_unknown_column_is_rejected() {
+        let row = SqlRow { columns: vec![("not_a_column", Value::Integer(1))] };
+        assert!(schema(TOKEN_INFO).insert_sql(&row).is_err());
+    }
+}
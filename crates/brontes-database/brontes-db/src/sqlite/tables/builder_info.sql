@@ -0,0 +1,14 @@
+CREATE TABLE brontes_api.builder_info ON CLUSTER eth_cluster0
+(
+    `address` String,
+    `name` Nullable(String),
+    `fund` Nullable(String),
+    `pub_keys` Array(String),
+    `searchers_eoas` Array(String),
+    `searchers_contracts` Array(String),
+    `ultrasound_relay_collateral_address` Nullable(String),
+    `last_updated` UInt64 DEFAULT now()
+)
+ENGINE = ReplicatedReplacingMergeTree('/clickhouse/eth_cluster0/tables/all/brontes_api/builder_info', '{replica}', `last_updated`)
+PRIMARY KEY (`address`)
+ORDER BY (`address`)
@@ -0,0 +1,50 @@
+CREATE TABLE brontes_api.searcher_info ON CLUSTER eth_cluster0
+(
+    `address` String,
+    `eoa_or_contract` UInt8,
+    `fund` String,
+    `config_labels` Array(String),
+    `builder` Nullable(String),
+    `mev` Tuple(
+        `bundle_count` UInt64,
+        `sandwich_count` Nullable(UInt64),
+        `cex_dex_trade_count` Nullable(UInt64),
+        `cex_dex_quote_count` Nullable(UInt64),
+        `cex_dex_rfq_count` Nullable(UInt64),
+        `jit_cex_dex_count` Nullable(UInt64),
+        `jit_count` Nullable(UInt64),
+        `jit_sandwich_count` Nullable(UInt64),
+        `atomic_backrun_count` Nullable(UInt64),
+        `liquidation_count` Nullable(UInt64),
+        `searcher_tx_count` Nullable(UInt64),
+        `nft_arb_count` Nullable(UInt64)
+    ),
+    `pnl` Tuple(
+        `total` Float64,
+        `sandwich` Nullable(Float64),
+        `cex_dex_quotes` Nullable(Float64),
+        `cex_dex_trades` Nullable(Float64),
+        `jit` Nullable(Float64),
+        `jit_sandwich` Nullable(Float64),
+        `atomic_backrun` Nullable(Float64),
+        `liquidation` Nullable(Float64),
+        `searcher_tx` Nullable(Float64),
+        `nft_arb` Nullable(Float64)
+    ),
+    `gas_bids` Tuple(
+        `total` Float64,
+        `sandwich` Nullable(Float64),
+        `cex_dex_quotes` Nullable(Float64),
+        `cex_dex_trades` Nullable(Float64),
+        `jit` Nullable(Float64),
+        `jit_sandwich` Nullable(Float64),
+        `atomic_backrun` Nullable(Float64),
+        `liquidation` Nullable(Float64),
+        `searcher_tx` Nullable(Float64),
+        `nft_arb` Nullable(Float64)
+    ),
+    `last_updated` UInt64 DEFAULT now()
+)
+ENGINE = ReplicatedReplacingMergeTree('/clickhouse/eth_cluster0/tables/all/brontes_api/searcher_info', '{replica}', `last_updated`)
+PRIMARY KEY (`address`)
+ORDER BY (`address`)
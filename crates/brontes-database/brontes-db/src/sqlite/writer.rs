@@ -0,0 +1,303 @@
+use std::{
+    path::Path,
+    time::{Duration, Instant},
+};
+
+use alloy_primitives::Address;
+use brontes_types::{
+    db::{
+        address_to_protocol_info::ProtocolInfoClickhouse,
+        block_analysis::BlockAnalysis,
+        builder::BuilderInfoWithAddress,
+        dex::{DexQuotes, DexQuotesWithBlockNumber},
+        normalized_actions::TransactionRoot,
+        searcher::JoinedSearcherInfo,
+        token_info::{TokenInfo, TokenInfoWithAddress},
+    },
+    mev::{Bundle, BundleData, MevBlock},
+    normalized_actions::Action,
+    structured_trace::TxTrace,
+    BlockTree, FastHashMap, Protocol,
+};
+use reth_tasks::shutdown::GracefulShutdown;
+use rusqlite::{params_from_iter, Connection};
+use serde::Serialize;
+use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
+
+use super::{
+    row::SqlRow,
+    schema::{table_schemas, TableSchema},
+};
+
+/// Max inserts written per sqlite transaction
+const MAX_INSERTS_PER_TX: usize = 1_000;
+
+/// The ClickHouse tables as `<database>_<table>`, sqlite has no databases to
+/// group them in
+pub mod table_names {
+    pub const RUN_ID: &str = "brontes_run_id";
+    pub const DEX_PRICE_MAPPING: &str = "brontes_dex_price_mapping";
+    pub const BLOCK_ANALYSIS: &str = "brontes_block_analysis";
+    pub const TOKEN_INFO: &str = "brontes_token_info";
+    pub const TREE: &str = "brontes_tree";
+    pub const POOLS: &str = "ethereum_pools";
+    pub const MEV_BLOCKS: &str = "mev_mev_blocks";
+    pub const BUNDLE_HEADER: &str = "mev_bundle_header";
+    pub const SEARCHER_TX: &str = "mev_searcher_tx";
+    pub const CEX_DEX_QUOTES: &str = "mev_cex_dex_quotes";
+    pub const CEX_DEX: &str = "mev_cex_dex";
+    pub const LIQUIDATIONS: &str = "mev_liquidations";
+    pub const JIT_SANDWICH: &str = "mev_jit_sandwich";
+    pub const JIT: &str = "mev_jit";
+    pub const SANDWICHES: &str = "mev_sandwiches";
+    pub const ATOMIC_ARBS: &str = "mev_atomic_arbs";
+    pub const NFT_ARBS: &str = "mev_nft_arbs";
+    pub const SEARCHER_INFO: &str = "brontes_api_searcher_info";
+    pub const BUILDER_INFO: &str = "brontes_api_builder_info";
+    pub const TX_TRACES: &str = "brontes_api_tx_traces";
+}
+
+use table_names::*;
+
+#[derive(Debug)]
+struct SqliteInsert {
+    table: &'static str,
+    rows:  Vec<SqlRow>,
+}
+
+/// Writes the rows brontes sends to ClickHouse into a local sqlite file
+/// instead. The rows are written on their own thread, this is only the handle
+/// to queue them
+#[derive(Debug, Clone)]
+pub struct SqliteSink {
+    pub run_id: u64,
+    tx:         UnboundedSender<SqliteInsert>,
+}
+
+impl SqliteSink {
+    /// Opens or creates the sqlite file and its tables, registers the run in
+    /// its `brontes_run_id` table and starts the writer, which flushes the
+    /// queued rows on shutdown. Without a run id the last run id in the file
+    /// is incremented
+    pub fn spawn<P: AsRef<Path>>(
+        path: P,
+        run_id: Option<u64>,
+        shutdown: GracefulShutdown,
+    ) -> eyre::Result<Self> {
+        let conn = Connection::open(path)?;
+        // lets the file be queried while brontes is writing to it
+        conn.pragma_update(None, "journal_mode", "WAL")?;
+        conn.pragma_update(None, "synchronous", "NORMAL")?;
+        let schemas = table_schemas()?;
+        for schema in &schemas {
+            conn.execute_batch(&schema.create_table_sql())?;
+        }
+
+        let run_id = match run_id {
+            Some(run_id) => run_id,
+            None => conn.query_row(
+                "SELECT COALESCE(MAX(run_id), 0) + 1 FROM brontes_run_id",
+                [],
+                |row| row.get::<_, i64>(0),
+            )? as u64,
+        };
+        conn.execute(
+            "INSERT OR REPLACE INTO brontes_run_id (run_id, last_updated) VALUES (?1, unixepoch())",
+            [run_id as i64],
+        )?;
+
+        let (tx, rx) = unbounded_channel();
+        let schemas = schemas
+            .into_iter()
+            .map(|schema| (schema.name.clone(), schema))
+            .collect();
+        SqliteWriter { conn, rx, schemas }.run(shutdown);
+
+        Ok(Self { run_id, tx })
+    }
+
+    pub fn save_mev_blocks(&self, block: MevBlock, mev: Vec<Bundle>) -> eyre::Result<()> {
+        self.send(MEV_BLOCKS, &[block], true)?;
+
+        let (bundle_headers, bundle_data): (Vec<_>, Vec<_>) = mev
+            .into_iter()
+            .map(|bundle| (bundle.header, bundle.data))
+            .unzip();
+        self.send(BUNDLE_HEADER, &bundle_headers, true)?;
+
+        bundle_data.into_iter().try_for_each(|data| match data {
+            BundleData::Sandwich(s) => self.send(SANDWICHES, &[s], true),
+            BundleData::AtomicArb(s) => self.send(ATOMIC_ARBS, &[s], true),
+            BundleData::JitSandwich(s) => self.send(JIT_SANDWICH, &[s], true),
+            BundleData::Jit(s) => self.send(JIT, &[s], true),
+            BundleData::CexDex(s) => self.send(CEX_DEX, &[s], true),
+            BundleData::CexDexQuote(s) => self.send(CEX_DEX_QUOTES, &[s], true),
+            BundleData::Liquidation(s) => self.send(LIQUIDATIONS, &[s], true),
+            BundleData::Unknown(s) => self.send(SEARCHER_TX, &[s], true),
+            BundleData::NftArb(s) => self.send(NFT_ARBS, &[s], true),
+        })
+    }
+
+    pub fn write_dex_quotes(&self, block_num: u64, quotes: Option<DexQuotes>) -> eyre::Result<()> {
+        let Some(quotes) = quotes else { return Ok(()) };
+
+        self.send(
+            DEX_PRICE_MAPPING,
+            &DexQuotesWithBlockNumber::new_with_block(block_num, quotes),
+            false,
+        )
+    }
+
+    pub fn insert_tree(&self, tree: &BlockTree<Action>) -> eyre::Result<()> {
+        let roots = tree
+            .tx_roots
+            .iter()
+            .map(|root| (root, tree.header.number).into())
+            .collect::<Vec<TransactionRoot>>();
+
+        self.send(TREE, &roots, true)
+    }
+
+    pub fn write_token_info(
+        &self,
+        address: Address,
+        decimals: u8,
+        symbol: String,
+    ) -> eyre::Result<()> {
+        let data = TokenInfoWithAddress { address, inner: TokenInfo { symbol, decimals } };
+
+        self.send(TOKEN_INFO, &[data], false)
+    }
+
+    pub fn insert_pool(
+        &self,
+        block: u64,
+        address: Address,
+        tokens: &[Address],
+        curve_lp_token: Option<Address>,
+        classifier_name: Protocol,
+    ) -> eyre::Result<()> {
+        let data =
+            ProtocolInfoClickhouse::new(block, address, tokens, curve_lp_token, classifier_name);
+
+        self.send(POOLS, &[data], false)
+    }
+
+    pub fn block_analysis(&self, block_analysis: BlockAnalysis) -> eyre::Result<()> {
+        self.send(BLOCK_ANALYSIS, &[block_analysis], true)
+    }
+
+    pub fn write_searcher_info(&self, info: JoinedSearcherInfo) -> eyre::Result<()> {
+        self.send(SEARCHER_INFO, &[info], false)
+    }
+
+    pub fn write_builder_info(&self, info: BuilderInfoWithAddress) -> eyre::Result<()> {
+        self.send(BUILDER_INFO, &[info], false)
+    }
+
+    pub fn save_traces(&self, traces: &[TxTrace]) -> eyre::Result<()> {
+        self.send(TX_TRACES, traces, false)
+    }
+
+    /// Queues the rows for the table. Rows of the tables ClickHouse stores per
+    /// run get the `run_id` column appended
+    fn send<T: Serialize>(
+        &self,
+        table: &'static str,
+        rows: &[T],
+        with_run_id: bool,
+    ) -> eyre::Result<()> {
+        if rows.is_empty() {
+            return Ok(())
+        }
+
+        let run_id = with_run_id.then_some(self.run_id);
+        let rows = rows
+            .iter()
+            .map(|row| SqlRow::new(row, run_id))
+            .collect::<Result<Vec<_>, _>>()?;
+        self.tx.send(SqliteInsert { table, rows })?;
+
+        Ok(())
+    }
+}
+
+struct SqliteWriter {
+    conn:    Connection,
+    rx:      UnboundedReceiver<SqliteInsert>,
+    schemas: FastHashMap<String, TableSchema>,
+}
+
+impl SqliteWriter {
+    fn run(self, shutdown: GracefulShutdown) {
+        // we do this to avoid main tokio runtime load
+        std::thread::spawn(move || {
+            tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .unwrap()
+                .block_on(async move {
+                    self.run_until_shutdown(shutdown).await;
+                });
+        });
+    }
+
+    async fn run_until_shutdown(mut self, shutdown: GracefulShutdown) {
+        let mut shutdown = std::pin::pin!(shutdown);
+        let graceful_guard = loop {
+            tokio::select! {
+                insert = self.rx.recv() => match insert {
+                    Some(insert) => self.write_queued(insert),
+                    None => break None,
+                },
+                guard = &mut shutdown => break Some(guard),
+            }
+        };
+
+        // if we go 1s without a message, we assume shutdown was complete
+        let mut last_message = Instant::now();
+        while last_message.elapsed() < Duration::from_secs(1) {
+            if let Ok(insert) = self.rx.try_recv() {
+                self.write_queued(insert);
+                last_message = Instant::now();
+            } else {
+                tokio::time::sleep(Duration::from_millis(50)).await;
+            }
+        }
+        // we do this so doesn't get instant dropped by compiler
+        tracing::trace!(was_shutdown = graceful_guard.is_some());
+        drop(graceful_guard)
+    }
+
+    /// Writes the insert together with the ones queued behind it
+    fn write_queued(&mut self, insert: SqliteInsert) {
+        let mut inserts = vec![insert];
+        while inserts.len() < MAX_INSERTS_PER_TX {
+            let Ok(insert) = self.rx.try_recv() else { break };
+            inserts.push(insert);
+        }
+
+        if let Err(e) = self.write(inserts) {
+            tracing::error!(target: "brontes", error=%e, "error writing to sqlite");
+        }
+    }
+
+    fn write(&mut self, inserts: Vec<SqliteInsert>) -> eyre::Result<()> {
+        let tx = self.conn.transaction()?;
+
+        for SqliteInsert { table, rows } in inserts {
+            let schema = self
+                .schemas
+                .get(table)
+                .ok_or_else(|| eyre::eyre!("no schema for {table}"))?;
+
+            for row in rows {
+                let mut stmt = tx.prepare_cached(&schema.insert_sql(&row)?)?;
+                stmt.execute(params_from_iter(row.columns.into_iter().map(|(_, value)| value)))?;
+            }
+        }
+        tx.commit()?;
+
+        Ok(())
+    }
+}
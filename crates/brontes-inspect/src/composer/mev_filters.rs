@@ -114,4 +114,5 @@ define_mev_precedence!(
     Unknown, SearcherTx, AtomicArb, CexDexQuotes,CexDexTrades  => Sandwich;
     Unknown, SearcherTx, AtomicArb, Jit, CexDexQuotes, CexDexTrades=> JitCexDex;
     Unknown, SearcherTx, AtomicArb, CexDexQuotes, CexDexTrades, Jit, Sandwich => JitSandwich;
+    Unknown, SearcherTx, AtomicArb, CexDexQuotes, CexDexTrades => NftArb;
 );
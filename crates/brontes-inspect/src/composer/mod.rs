@@ -27,9 +27,9 @@
 //! let composer = Composer::new(&orchestra, tree, metadata);
 //! // Future execution of the composer to process MEV data
 //! ```
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
 
-use alloy_primitives::Address;
+use alloy_primitives::{Address, B256};
 use brontes_types::{
     db::{block_analysis::BlockAnalysis, traits::LibmdbxReader},
     mev::Mev,
@@ -43,7 +43,7 @@ mod mev_filters;
 mod utils;
 use brontes_types::{
     db::metadata::Metadata,
-    mev::{Bundle, MevBlock, MevType, PossibleMevCollection},
+    mev::{BlockFailedAttempts, Bundle, MevBlock, MevType, PossibleMev, PossibleMevCollection},
     normalized_actions::Action,
     tree::BlockTree,
 };
@@ -66,6 +66,9 @@ pub struct ComposerResults {
     /// all txes with coinbase.transfers that weren't classified
     pub possible_mev_txes: PossibleMevCollection,
     pub block_analysis:    BlockAnalysis,
+    /// reverted searcher txes that competed with the classified mev, empty
+    /// unless the failed attempts inspector is enabled
+    pub failed_attempts:   BlockFailedAttempts,
 }
 
 pub fn run_block_inspection<DB: LibmdbxReader>(
@@ -76,46 +79,83 @@ pub fn run_block_inspection<DB: LibmdbxReader>(
     let this_data = data.get_most_recent_block().clone();
     let BlockData { metadata, tree } = this_data;
 
-    let (possible_mev_txes, classified_mev) = run_inspectors(orchestra, data);
+    let (possible_mev_txes, inspector_results) = run_inspectors(orchestra, data);
+    let classified_mev = inspector_results
+        .into_iter()
+        .flatten()
+        .flatten()
+        .collect::<Vec<_>>();
 
     let possible_arbs = possible_mev_txes.clone();
 
     let quote_token = orchestra[0].get_quote_token();
 
-    let (block_details, mev_details) =
-        on_orchestra_resolution(tree, possible_mev_txes, metadata, classified_mev, quote_token, db);
+    let (block_details, mev_details, _) = on_orchestra_resolution(
+        tree.clone(),
+        possible_mev_txes,
+        metadata.clone(),
+        classified_mev,
+        quote_token,
+        db,
+    );
 
     let block_analysis = BlockAnalysis::new(&block_details, &mev_details);
+    let failed_attempts = orchestra
+        .iter()
+        .find_map(|inspector| inspector.attribute_failed_attempts(&tree, &metadata, &mev_details))
+        .unwrap_or_else(|| BlockFailedAttempts {
+            block_number: metadata.block_num,
+            attempts:     vec![],
+        });
 
-    ComposerResults { block_details, mev_details, possible_mev_txes: possible_arbs, block_analysis }
+    ComposerResults {
+        block_details,
+        mev_details,
+        possible_mev_txes: possible_arbs,
+        block_analysis,
+        failed_attempts,
+    }
 }
 
+/// Runs the inspectors in parallel, returning the bundles of each inspector in
+/// the order of `orchestra`, `None` if the inspector's block window isn't
+/// satisfied yet.
 fn run_inspectors(
     orchestra: &[&dyn Inspector<Result = Vec<Bundle>>],
     data: MultiBlockData,
-) -> (PossibleMevCollection, Vec<Bundle>) {
+) -> (PossibleMevCollection, Vec<Option<Vec<Bundle>>>) {
     let this_data = data.get_most_recent_block().clone();
     let BlockData { metadata, tree } = this_data;
-    let mut possible_mev_txes =
+    let possible_mev_txes =
         DiscoveryInspector::new(DISCOVERY_PRIORITY_FEE_MULTIPLIER).find_possible_mev(tree.clone());
 
     let results = orchestra
         .par_iter()
-        .flat_map(|inspector| {
+        .map(|inspector| {
             let window = inspector.block_window();
             // not sufficient size yet
             if data.blocks < window {
-                return vec![]
+                return None
             };
             let data = data.split_to_size(window);
             let span =
                 span!(Level::ERROR, "Inspector", inspector = %inspector.get_id(),block=&metadata.block_num);
 
-            span.in_scope(|| inspector.inspect_block(data))
+            Some(span.in_scope(|| inspector.inspect_block(data)))
         })
         .collect::<Vec<_>>();
 
-    results.iter().for_each(|bundle| {
+    (
+        remove_classified_from_possible(possible_mev_txes, results.iter().flatten().flatten()),
+        results,
+    )
+}
+
+fn remove_classified_from_possible<'a>(
+    mut possible_mev_txes: HashMap<B256, PossibleMev>,
+    results: impl Iterator<Item = &'a Bundle>,
+) -> PossibleMevCollection {
+    results.for_each(|bundle| {
         bundle
             .data
             .mev_transaction_hashes()
@@ -131,7 +171,58 @@ fn run_inspectors(
         .0
         .sort_by(|a, b| a.tx_idx.cmp(&b.tx_idx));
 
-    (possible_mev_collection, results)
+    possible_mev_collection
+}
+
+/// A bundle that was removed in favour of a dominant bundle by the
+/// `MEV_DEDUPLICATION_FILTER`.
+#[derive(Debug, Clone)]
+pub struct DedupDecision {
+    pub dominant_type: MevType,
+    pub dominant_tx:   B256,
+    pub removed_type:  MevType,
+    pub removed_tx:    B256,
+}
+
+/// The intermediate results of a block inspection, used to explain why a
+/// transaction was or wasn't classified as mev.
+#[derive(Debug)]
+pub struct InspectionExplanation {
+    /// the bundles each inspector returned, `None` if the inspector's block
+    /// window wasn't satisfied
+    pub inspector_results: Vec<(String, Option<Vec<Bundle>>)>,
+    pub dedup_decisions:   Vec<DedupDecision>,
+    pub mev_details:       Vec<Bundle>,
+}
+
+/// Runs the same inspection as [`run_block_inspection`], keeping the per
+/// inspector results and the deduplication decisions.
+pub fn explain_block_inspection<DB: LibmdbxReader>(
+    orchestra: &[&dyn Inspector<Result = Vec<Bundle>>],
+    data: MultiBlockData,
+    db: &'static DB,
+) -> InspectionExplanation {
+    let BlockData { metadata, tree } = data.get_most_recent_block().clone();
+
+    let (possible_mev_txes, inspector_results) = run_inspectors(orchestra, data);
+    let classified_mev = inspector_results
+        .iter()
+        .flatten()
+        .flatten()
+        .cloned()
+        .collect_vec();
+
+    let quote_token = orchestra[0].get_quote_token();
+    let (_, mev_details, dedup_decisions) =
+        on_orchestra_resolution(tree, possible_mev_txes, metadata, classified_mev, quote_token, db);
+
+    let inspector_results = orchestra
+        .iter()
+        .map(|inspector| inspector.get_id().to_string())
+        .zip(inspector_results)
+        .collect_vec();
+
+    InspectionExplanation { inspector_results, dedup_decisions, mev_details }
 }
 
 fn on_orchestra_resolution<DB: LibmdbxReader>(
@@ -141,8 +232,9 @@ fn on_orchestra_resolution<DB: LibmdbxReader>(
     orchestra_data: Vec<Bundle>,
     quote_token: Address,
     db: &'static DB,
-) -> (MevBlock, Vec<Bundle>) {
+) -> (MevBlock, Vec<Bundle>, Vec<DedupDecision>) {
     let mut sorted_mev = sort_mev_by_type(orchestra_data);
+    let mut dedup_decisions = Vec::new();
 
     MEV_COMPOSABILITY_FILTER
         .iter()
@@ -159,6 +251,7 @@ fn on_orchestra_resolution<DB: LibmdbxReader>(
                 extra_filter_fn,
                 subordinate_mev_type,
                 &mut sorted_mev,
+                &mut dedup_decisions,
             );
         },
     );
@@ -184,7 +277,7 @@ fn on_orchestra_resolution<DB: LibmdbxReader>(
     // keep order
     filtered_bundles.sort_by(|a, b| a.header.tx_index.cmp(&b.header.tx_index));
 
-    (header, filtered_bundles)
+    (header, filtered_bundles, dedup_decisions)
 }
 
 fn deduplicate_mev<DB: LibmdbxReader>(
@@ -194,6 +287,7 @@ fn deduplicate_mev<DB: LibmdbxReader>(
     extra_filter_function: &FilterFn,
     subordinate_mev_types: &[MevType],
     sorted_mev: &mut FastHashMap<MevType, Vec<Bundle>>,
+    decisions: &mut Vec<DedupDecision>,
 ) {
     let Some(dominant_mev_list) = sorted_mev.get(dominant_mev_type) else { return };
 
@@ -206,16 +300,26 @@ fn deduplicate_mev<DB: LibmdbxReader>(
             let Some(sub_mev_list) = sorted_mev.get(&sub_mev_type) else {
                 continue;
             };
+            let removed = try_deduping_mev(
+                tree.clone(),
+                Box::new(db),
+                dominate_mev,
+                sub_mev_list,
+                extra_filter_function,
+                &hashes,
+            )
+            .collect_vec();
+
+            decisions.extend(removed.iter().unique().map(|&index| DedupDecision {
+                dominant_type: *dominant_mev_type,
+                dominant_tx:   dominate_mev.header.tx_hash,
+                removed_type:  sub_mev_type,
+                removed_tx:    sub_mev_list[index].header.tx_hash,
+            }));
             indexes.extend(
-                try_deduping_mev(
-                    tree.clone(),
-                    Box::new(db),
-                    dominate_mev,
-                    sub_mev_list,
-                    extra_filter_function,
-                    &hashes,
-                )
-                .zip(vec![sub_mev_type].into_iter().cycle()),
+                removed
+                    .into_iter()
+                    .zip(vec![sub_mev_type].into_iter().cycle()),
             )
         }
     }
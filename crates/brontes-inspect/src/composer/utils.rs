@@ -3,7 +3,7 @@ use std::sync::Arc;
 use alloy_primitives::{Address, FixedBytes};
 use brontes_types::{
     db::{builder::BuilderInfo, metadata::Metadata, traits::LibmdbxReader},
-    mev::{Bundle, Mev, MevBlock, MevCount, MevType, PossibleMevCollection},
+    mev::{Bundle, Mev, MevBlock, MevCount, MevType, PossibleMevCollection, QuoteAssetPnl},
     normalized_actions::Action,
     tree::BlockTree,
     FastHashMap, GasDetails, ToFloatNearest, ToScaledRational, TreeSearchBuilder,
@@ -51,6 +51,14 @@ pub(crate) fn build_mev_header<DB: LibmdbxReader>(
         .unwrap()
         .and_then(|b| b.name);
 
+    // same as the usd profit, we ignore searcher txes as they are not mev
+    let quote_pnl = QuoteAssetPnl::aggregate(
+        orchestra_data
+            .iter()
+            .filter(|bundle| bundle.mev_type() != MevType::SearcherTx)
+            .flat_map(|bundle| bundle.header.quote_pnl.iter()),
+    );
+
     MevBlock {
         block_hash: metadata.block_hash.into(),
         block_number: metadata.block_num,
@@ -78,6 +86,7 @@ pub(crate) fn build_mev_header<DB: LibmdbxReader>(
         proposer_mev_reward,
         proposer_profit_usd,
         total_mev_profit_usd,
+        quote_pnl,
         possible_mev,
     }
 }
@@ -191,6 +200,7 @@ fn update_mev_count(mev_count: &mut MevCount, mev_type: MevType, count: u64) {
         MevType::AtomicArb => mev_count.atomic_backrun_count = Some(count),
         MevType::Liquidation => mev_count.liquidation_count = Some(count),
         MevType::SearcherTx => mev_count.searcher_tx_count = Some(count),
+        MevType::NftArb => mev_count.nft_arb_count = Some(count),
         MevType::Unknown => (),
     }
 }
@@ -422,6 +432,17 @@ pub(crate) fn pre_process(tree: Arc<BlockTree<Action>>) -> BlockPreprocessing {
                 let address = root.get_from_address();
                 let gas_details_item = &root.gas_details;
 
+                // the gas of a user operation is paid by its bundle tx, only its coinbase
+                // transfers are not already accounted for
+                if root.is_user_op() {
+                    return (
+                        gas_details_by_address,
+                        total_gas_used,
+                        total_priority_fee,
+                        total_bribe + gas_details_item.coinbase_transfer.unwrap_or(0),
+                    )
+                }
+
                 gas_details_by_address
                     .entry(address)
                     .and_modify(|existing: &mut GasDetails| existing.merge(gas_details_item))
@@ -59,7 +59,9 @@
 
 pub mod composer;
 pub mod discovery;
+pub mod lvr;
 pub mod mev_inspectors;
+pub mod victim_loss;
 use brontes_metrics::inspectors::OutlierMetrics;
 use mev_inspectors::searcher_activity::SearcherActivity;
 pub use mev_inspectors::*;
@@ -67,7 +69,9 @@ pub use mev_inspectors::*;
 #[cfg(feature = "tests")]
 pub mod test_utils;
 
-use alloy_primitives::Address;
+use std::fmt;
+
+use alloy_primitives::{Address, TxHash};
 use atomic_arb::AtomicArbInspector;
 use brontes_types::{
     db::{
@@ -75,14 +79,16 @@ use brontes_types::{
         metadata::Metadata,
         traits::LibmdbxReader,
     },
-    mev::{Bundle, BundleData},
+    mev::{BlockFailedAttempts, Bundle, BundleData},
     normalized_actions::Action,
     tree::BlockTree,
     MultiBlockData,
 };
 use cex_dex::{markout::CexDexMarkoutInspector, quotes::CexDexQuotesInspector};
+use failed_attempts::FailedAttemptInspector;
 use jit::JitCexDex;
 use liquidations::LiquidationInspector;
+use nft_arb::NftArbInspector;
 use sandwich::SandwichInspector;
 
 use crate::jit::jit_liquidity::JitInspector;
@@ -98,6 +104,62 @@ pub trait Inspector: Send + Sync {
     fn get_id(&self) -> &str;
     fn inspect_block(&self, data: MultiBlockData) -> Self::Result;
     fn get_quote_token(&self) -> Address;
+    /// Inspects `tx` of the most recent block the same way [`inspect_block`]
+    /// does and returns why it didn't produce a bundle for it, `None` if it
+    /// did or if the inspector doesn't report its reasons, which is the
+    /// default.
+    ///
+    /// [`inspect_block`]: Inspector::inspect_block
+    fn reject_reason(&self, _data: MultiBlockData, _tx: TxHash) -> Option<RejectReason> {
+        None
+    }
+    /// Attributes the reverted transactions of the block to the bundles that
+    /// were kept after composition. Only implemented by the
+    /// [`FailedAttemptInspector`], `None` for every other inspector.
+    fn attribute_failed_attempts(
+        &self,
+        _tree: &BlockTree<Action>,
+        _metadata: &Metadata,
+        _bundles: &[Bundle],
+    ) -> Option<BlockFailedAttempts> {
+        None
+    }
+}
+
+/// Why an inspector didn't produce a bundle for a transaction
+#[derive(Debug, Clone, PartialEq)]
+pub enum RejectReason {
+    /// the transaction isn't part of the inspected block
+    NotInBlock,
+    /// the transaction info couldn't be loaded from the database
+    MissingTxInfo,
+    /// the transaction has none of the actions the inspector looks for
+    NoRelevantActions,
+    /// the actions don't form the pattern of the mev type
+    PatternMismatch(&'static str),
+    /// the transaction was dismissed by one of the inspector's filters
+    Filtered(&'static str),
+    /// the transaction needs to come from a known searcher of the mev type
+    NotSearcher,
+    /// the prices needed to value the transaction are missing
+    MissingPricing,
+    /// the mev didn't meet the profit requirement of the inspector
+    Unprofitable { profit_usd: f64 },
+}
+
+impl fmt::Display for RejectReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotInBlock => write!(f, "transaction isn't in the inspected block"),
+            Self::MissingTxInfo => write!(f, "transaction info couldn't be loaded"),
+            Self::NoRelevantActions => write!(f, "no actions the inspector looks for"),
+            Self::PatternMismatch(reason) => write!(f, "pattern mismatch: {reason}"),
+            Self::Filtered(reason) => write!(f, "filtered: {reason}"),
+            Self::NotSearcher => write!(f, "not a known searcher of this mev type"),
+            Self::MissingPricing => write!(f, "missing prices to value the transaction"),
+            Self::Unprofitable { profit_usd } => write!(f, "unprofitable: ${profit_usd:.2}"),
+        }
+    }
 }
 
 #[derive(
@@ -112,6 +174,8 @@ pub enum Inspectors {
     SearcherActivity,
     CexDexMarkout,
     JitCexDex,
+    NftArb,
+    FailedAttempts,
 }
 
 type DynMevInspector = &'static (dyn Inspector<Result = Vec<Bundle>> + 'static);
@@ -120,6 +184,7 @@ impl Inspectors {
     pub fn init_mev_inspector<DB: LibmdbxReader>(
         &self,
         quote_token: Address,
+        extra_quote_tokens: &[Address],
         db: &'static DB,
         cex_exchanges: &[CexExchange],
         trade_config: CexDexTradeConfig,
@@ -127,31 +192,39 @@ impl Inspectors {
     ) -> DynMevInspector {
         match &self {
             Self::AtomicArb => {
-                static_object(AtomicArbInspector::new(quote_token, db, metrics)) as DynMevInspector
+                static_object(AtomicArbInspector::new(quote_token, extra_quote_tokens, db, metrics))
+                    as DynMevInspector
             }
             Self::Jit => {
-                static_object(JitInspector::new(quote_token, db, metrics)) as DynMevInspector
+                static_object(JitInspector::new(quote_token, extra_quote_tokens, db, metrics))
+                    as DynMevInspector
             }
 
             Self::CexDex => static_object(CexDexQuotesInspector::new(
                 quote_token,
+                extra_quote_tokens,
                 db,
                 cex_exchanges,
                 trade_config.quote_offset_from_block_us,
                 metrics,
             )) as DynMevInspector,
             Self::Sandwich => {
-                static_object(SandwichInspector::new(quote_token, db, metrics)) as DynMevInspector
-            }
-            Self::Liquidations => {
-                static_object(LiquidationInspector::new(quote_token, db, metrics))
+                static_object(SandwichInspector::new(quote_token, extra_quote_tokens, db, metrics))
                     as DynMevInspector
             }
+            Self::Liquidations => static_object(LiquidationInspector::new(
+                quote_token,
+                extra_quote_tokens,
+                db,
+                metrics,
+            )) as DynMevInspector,
             Self::SearcherActivity => {
-                static_object(SearcherActivity::new(quote_token, db, metrics)) as DynMevInspector
+                static_object(SearcherActivity::new(quote_token, extra_quote_tokens, db, metrics))
+                    as DynMevInspector
             }
             Self::CexDexMarkout => static_object(CexDexMarkoutInspector::new(
                 quote_token,
+                extra_quote_tokens,
                 db,
                 cex_exchanges,
                 trade_config,
@@ -160,13 +233,21 @@ impl Inspectors {
             Self::JitCexDex => static_object(JitCexDex {
                 cex_dex: CexDexMarkoutInspector::new(
                     quote_token,
+                    extra_quote_tokens,
                     db,
                     cex_exchanges,
                     trade_config,
                     metrics.clone(),
                 ),
-                jit:     JitInspector::new(quote_token, db, metrics),
+                jit:     JitInspector::new(quote_token, extra_quote_tokens, db, metrics),
             }) as DynMevInspector,
+            Self::NftArb => {
+                static_object(NftArbInspector::new(quote_token, extra_quote_tokens, db, metrics))
+                    as DynMevInspector
+            }
+            Self::FailedAttempts => {
+                static_object(FailedAttemptInspector::new(quote_token, db)) as DynMevInspector
+            }
         }
     }
 }
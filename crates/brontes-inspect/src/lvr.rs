@@ -0,0 +1,260 @@
+//! Loss versus rebalancing (LVR) of liquidity providers.
+//!
+//! For every swap against a pool, the output the pool paid is valued at the
+//! cex price over the trade config's time window and compared against what the
+//! pool received. The difference is what the liquidity providers lost compared
+//! to a portfolio that made the same trade at the cex price.
+
+use std::sync::Arc;
+
+use alloy_primitives::{Address, B256};
+use brontes_database::libmdbx::LibmdbxReader;
+use brontes_types::{
+    db::{
+        cex::{trades::CexDexTradeConfig, CexExchange},
+        dex::PriceAt,
+        lvr::{BlockLvr, PoolLvr},
+        metadata::Metadata,
+    },
+    normalized_actions::{Action, NormalizedSwap},
+    pair::Pair,
+    BlockTree, FastHashMap, ToFloatNearest, TreeSearchBuilder,
+};
+use malachite::{num::basic::traits::Zero, Rational};
+
+use crate::shared_utils::SharedInspectorUtils;
+
+pub struct LvrCalculator<'db, DB: LibmdbxReader> {
+    utils:         SharedInspectorUtils<'db, DB>,
+    trade_config:  CexDexTradeConfig,
+    cex_exchanges: Vec<CexExchange>,
+}
+
+impl<'db, DB: LibmdbxReader> LvrCalculator<'db, DB> {
+    pub fn new(
+        quote: Address,
+        db: &'db DB,
+        cex_exchanges: &[CexExchange],
+        trade_config: CexDexTradeConfig,
+    ) -> Self {
+        Self {
+            utils: SharedInspectorUtils::new(quote, &[], db, None),
+            trade_config,
+            cex_exchanges: cex_exchanges.to_vec(),
+        }
+    }
+
+    /// Calculates the lvr of every pool swapped against in the block.
+    /// `metadata` needs both the dex quotes and the cex trades of the block's
+    /// time window.
+    pub fn calculate_block_lvr(
+        &self,
+        tree: &BlockTree<Action>,
+        metadata: Arc<Metadata>,
+    ) -> BlockLvr {
+        let search = TreeSearchBuilder::default().with_action(Action::is_swap);
+        let mut pools: FastHashMap<Address, PoolLvr> = FastHashMap::default();
+
+        for root in &tree.tx_roots {
+            for swap in root.collect(&search).into_iter().map(Action::force_swap) {
+                let (token0, token1) = if swap.token_in.address < swap.token_out.address {
+                    (swap.token_in.address, swap.token_out.address)
+                } else {
+                    (swap.token_out.address, swap.token_in.address)
+                };
+                let pool = pools.entry(swap.pool).or_insert_with(|| PoolLvr {
+                    pool: swap.pool,
+                    protocol: swap.protocol,
+                    token0,
+                    token1,
+                    swap_count: 0,
+                    unpriced_swaps: 0,
+                    volume_usd: 0.0,
+                    lvr_usd: 0.0,
+                });
+                pool.swap_count += 1;
+
+                // `position` is the on chain tx index, which is what dex quotes are
+                // keyed by. User operations split out of one `handleOps` tx share
+                // it, so they're all priced against the quotes of their bundle
+                match self.swap_lvr(&swap, root.position, root.tx_hash, &metadata) {
+                    Some((volume_usd, lvr_usd)) => {
+                        pool.volume_usd += volume_usd;
+                        pool.lvr_usd += lvr_usd;
+                    }
+                    None => pool.unpriced_swaps += 1,
+                }
+            }
+        }
+
+        let mut pools = pools.into_values().collect::<Vec<_>>();
+        pools.sort_by_key(|pool| pool.pool);
+
+        BlockLvr {
+            block_number: metadata.block_num,
+            block_timestamp: metadata.block_timestamp,
+            pools,
+        }
+    }
+
+    /// Returns the usd volume and lvr of the swap, or `None` if either side
+    /// can't be priced
+    fn swap_lvr(
+        &self,
+        swap: &NormalizedSwap,
+        tx_index: usize,
+        tx_hash: B256,
+        metadata: &Arc<Metadata>,
+    ) -> Option<(f64, f64)> {
+        if swap.amount_in == Rational::ZERO || swap.amount_out == Rational::ZERO {
+            return None
+        }
+
+        // the price is taken regardless of the cex volume in the window, the
+        // rebalancing trade is assumed to be fillable at the window's vwap
+        let cex_price = metadata
+            .cex_trades
+            .as_ref()?
+            .calculate_time_window_vwam(
+                self.trade_config,
+                &self.cex_exchanges,
+                Pair(swap.token_in.address, swap.token_out.address),
+                &swap.amount_out,
+                metadata.microseconds_block_timestamp(),
+                true,
+                swap,
+                tx_hash,
+            )?
+            .global
+            .price_maker;
+        if cex_price == Rational::ZERO {
+            return None
+        }
+
+        // cex value of what the pool paid out less what it received, both in
+        // token in
+        let lvr_token_in = &swap.amount_out * cex_price - &swap.amount_in;
+
+        let volume_usd = self.utils.get_token_value_dex(
+            tx_index,
+            PriceAt::Before,
+            swap.token_in.address,
+            &swap.amount_in,
+            metadata,
+        )?;
+        let lvr_usd = self.utils.get_token_value_dex(
+            tx_index,
+            PriceAt::Before,
+            swap.token_in.address,
+            &lvr_token_in,
+            metadata,
+        )?;
+
+        Some((volume_usd.to_float(), lvr_usd.to_float()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use brontes_core::get_db_handle;
+    use brontes_types::{
+        constants::{USDC_ADDRESS, USDT_ADDRESS, WETH_ADDRESS},
+        db::{cex::trades::CexTradeMap, metadata::BlockMetadata, token_info::TokenInfoWithAddress},
+        tree::{root::NodeData, Node, Root},
+        GasDetails, Protocol,
+    };
+    use reth_primitives::Header;
+
+    use super::*;
+
+    fn swap(pool: Address, token_in: Address, token_out: Address, amount: u64) -> Action {
+        Action::Swap(NormalizedSwap {
+            protocol: Protocol::UniswapV2,
+            pool,
+            token_in: TokenInfoWithAddress { address: token_in, ..Default::default() },
+            token_out: TokenInfoWithAddress { address: token_out, ..Default::default() },
+            amount_in: Rational::from(amount),
+            amount_out: Rational::from(amount),
+            ..Default::default()
+        })
+    }
+
+    fn root(position: usize, swaps: Vec<Action>) -> Root<Action> {
+        let mut data_store = NodeData(vec![]);
+        let mut head = Node::new(0, Address::ZERO, vec![]);
+        head.data = data_store.add(swaps);
+
+        Root {
+            head,
+            position,
+            tx_hash: B256::with_last_byte(position as u8),
+            private: false,
+            gas_details: GasDetails::default(),
+            total_msg_value_transfers: vec![],
+            data_store,
+            user_op: None,
+        }
+    }
+
+    #[brontes_macros::test]
+    async fn test_calculate_block_lvr() {
+        let db = get_db_handle(tokio::runtime::Handle::current()).await;
+        let calculator = LvrCalculator::new(
+            USDT_ADDRESS,
+            db,
+            &[CexExchange::Binance],
+            CexDexTradeConfig::default(),
+        );
+
+        let (pool_a, pool_b) = (Address::with_last_byte(0xb), Address::with_last_byte(0xa));
+        let mut tree = BlockTree::new(Header::default(), 2);
+        tree.tx_roots = vec![
+            root(
+                0,
+                vec![
+                    swap(pool_a, WETH_ADDRESS, USDC_ADDRESS, 1),
+                    swap(pool_b, USDC_ADDRESS, USDT_ADDRESS, 1),
+                ],
+            ),
+            // the opposite direction and a swap that moved nothing
+            root(1, vec![swap(pool_a, USDC_ADDRESS, WETH_ADDRESS, 1)]),
+            root(2, vec![swap(pool_a, USDC_ADDRESS, WETH_ADDRESS, 0)]),
+        ];
+
+        let metadata = Metadata {
+            block_metadata: BlockMetadata {
+                block_num: 1,
+                block_timestamp: 12,
+                ..Default::default()
+            },
+            cex_trades: Some(CexTradeMap::default()),
+            ..Default::default()
+        };
+        let block_lvr = calculator.calculate_block_lvr(&tree, Arc::new(metadata));
+
+        assert_eq!(block_lvr.block_number, 1);
+        assert_eq!(block_lvr.block_timestamp, 12);
+
+        // pools are sorted by address and their tokens don't depend on the
+        // direction of the first swap
+        let pools = block_lvr
+            .pools
+            .iter()
+            .map(|pool| (pool.pool, pool.token0, pool.token1, pool.swap_count))
+            .collect::<Vec<_>>();
+        let ordered = |a: Address, b: Address| (a.min(b), a.max(b));
+        let (usdc_usdt, usdc_weth) =
+            (ordered(USDC_ADDRESS, USDT_ADDRESS), ordered(USDC_ADDRESS, WETH_ADDRESS));
+        assert_eq!(
+            pools,
+            vec![(pool_b, usdc_usdt.0, usdc_usdt.1, 1), (pool_a, usdc_weth.0, usdc_weth.1, 3)]
+        );
+
+        // without cex trades or dex quotes none of the swaps can be priced
+        for pool in &block_lvr.pools {
+            assert_eq!(pool.unpriced_swaps, pool.swap_count);
+            assert_eq!(pool.volume_usd, 0.0);
+            assert_eq!(pool.lvr_usd, 0.0);
+        }
+    }
+}
@@ -15,10 +15,11 @@ use brontes_types::{
 };
 use itertools::Itertools;
 use malachite::{num::basic::traits::Zero, Rational};
-use reth_primitives::{Address, B256};
+use reth_primitives::{Address, TxHash, B256};
 
 use crate::{
-    shared_utils::SharedInspectorUtils, BlockTree, Inspector, Metadata, MAX_PROFIT, MIN_PROFIT,
+    shared_utils::SharedInspectorUtils, BlockTree, Inspector, Metadata, RejectReason, MAX_PROFIT,
+    MIN_PROFIT,
 };
 
 const MAX_PRICE_DIFF: Rational = Rational::const_from_unsigneds(99, 100);
@@ -29,8 +30,13 @@ pub struct AtomicArbInspector<'db, DB: LibmdbxReader> {
 }
 
 impl<'db, DB: LibmdbxReader> AtomicArbInspector<'db, DB> {
-    pub fn new(quote: Address, db: &'db DB, metrics: Option<OutlierMetrics>) -> Self {
-        Self { utils: SharedInspectorUtils::new(quote, db, metrics) }
+    pub fn new(
+        quote: Address,
+        extra_quotes: &[Address],
+        db: &'db DB,
+        metrics: Option<OutlierMetrics>,
+    ) -> Self {
+        Self { utils: SharedInspectorUtils::new(quote, extra_quotes, db, metrics) }
     }
 }
 
@@ -55,12 +61,7 @@ impl<DB: LibmdbxReader> Inspector for AtomicArbInspector<'_, DB> {
 
         let execution = || {
             tree.clone()
-                .collect_all(TreeSearchBuilder::default().with_actions([
-                    Action::is_swap,
-                    Action::is_transfer,
-                    Action::is_eth_transfer,
-                    Action::is_nested_action,
-                ]))
+                .collect_all(Self::search_args())
                 .t_full_map(|(tree, v)| {
                     let (tx_hashes, v): (Vec<_>, Vec<_>) = v.unzip();
                     (
@@ -84,14 +85,9 @@ impl<DB: LibmdbxReader> Inspector for AtomicArbInspector<'_, DB> {
                             .collect_vec(),
                         info,
                         metadata.clone(),
-                        actions
-                            .into_iter()
-                            .split_actions::<(Vec<_>, Vec<_>, Vec<_>), _>((
-                                Action::try_swaps_merged,
-                                Action::try_transfer,
-                                Action::try_eth_transfer,
-                            )),
+                        Self::split_actions(actions),
                     )
+                    .ok()
                 })
                 .collect::<Vec<_>>()
         };
@@ -101,16 +97,62 @@ impl<DB: LibmdbxReader> Inspector for AtomicArbInspector<'_, DB> {
             .map(|m| m.run_inspector(MevType::AtomicArb, execution))
             .unwrap_or_else(&execution)
     }
+
+    fn reject_reason(&self, data: MultiBlockData, tx: TxHash) -> Option<RejectReason> {
+        let BlockData { metadata, tree } = data.get_most_recent_block();
+        if tree.get_root(tx).is_none() {
+            return Some(RejectReason::NotInBlock)
+        }
+        let Some(info) = tree.get_tx_info(tx, self.utils.db) else {
+            return Some(RejectReason::MissingTxInfo)
+        };
+        let actions = self
+            .utils
+            .flatten_nested_actions_default(tree.clone().collect(&tx, Self::search_args()))
+            .collect::<Vec<_>>();
+
+        self.process_swaps(
+            data.per_block_data
+                .iter()
+                .map(|inner| inner.tree.clone())
+                .collect_vec(),
+            info,
+            metadata.clone(),
+            Self::split_actions(actions),
+        )
+        .err()
+    }
 }
 
 impl<DB: LibmdbxReader> AtomicArbInspector<'_, DB> {
+    fn search_args() -> TreeSearchBuilder<Action> {
+        TreeSearchBuilder::default().with_actions([
+            Action::is_swap,
+            Action::is_transfer,
+            Action::is_eth_transfer,
+            Action::is_nested_action,
+        ])
+    }
+
+    fn split_actions(
+        actions: Vec<Action>,
+    ) -> (Vec<NormalizedSwap>, Vec<NormalizedTransfer>, Vec<NormalizedEthTransfer>) {
+        actions
+            .into_iter()
+            .split_actions::<(Vec<_>, Vec<_>, Vec<_>), _>((
+                Action::try_swaps_merged,
+                Action::try_transfer,
+                Action::try_eth_transfer,
+            ))
+    }
+
     fn process_swaps(
         &self,
         trees: Vec<Arc<BlockTree<Action>>>,
         info: TxInfo,
         metadata: Arc<Metadata>,
         data: (Vec<NormalizedSwap>, Vec<NormalizedTransfer>, Vec<NormalizedEthTransfer>),
-    ) -> Option<Bundle> {
+    ) -> Result<Bundle, RejectReason> {
         tracing::trace!(?info, "trying atomic");
         let (mut swaps, transfers, eth_transfers) = data;
         let mev_addresses: FastHashSet<Address> = info.collect_address_set_for_accounting();
@@ -123,7 +165,12 @@ impl<DB: LibmdbxReader> AtomicArbInspector<'_, DB> {
 
         swaps.extend(self.utils.try_create_swaps(&transfers, ignore_addresses));
 
-        let possible_arb_type = self.is_possible_arb(&swaps)?;
+        if swaps.is_empty() {
+            return Err(RejectReason::NoRelevantActions)
+        }
+        let possible_arb_type = self
+            .is_possible_arb(&swaps)
+            .ok_or(RejectReason::PatternMismatch("the swaps don't form an arbitrage"))?;
 
         let account_deltas = transfers
             .into_iter()
@@ -179,28 +226,48 @@ impl<DB: LibmdbxReader> AtomicArbInspector<'_, DB> {
 
         let requirement_multiplier = if has_dex_price { 1 } else { 2 };
 
-        let profit = match possible_arb_type {
-            AtomicArbType::Triangle => (is_profitable
-                || self.process_triangle_arb(&info, requirement_multiplier))
-            .then_some(profit),
-            AtomicArbType::CrossPair(jump_index) => (is_profitable
-                || self.is_stable_arb(&swaps, jump_index)
-                || self.is_cross_pair_or_stable_arb(&info, requirement_multiplier))
-            .then_some(profit),
-
-            AtomicArbType::StablecoinArb => (is_profitable
-                || self.is_cross_pair_or_stable_arb(&info, requirement_multiplier))
-            .then_some(profit),
-            AtomicArbType::LongTail => (self.is_long_tail(&info, requirement_multiplier)
-                && is_profitable
-                || self.is_long_tail(&info, requirement_multiplier) & !has_dex_price)
-                .then_some(profit),
-        }?;
+        let is_arb = match possible_arb_type {
+            AtomicArbType::Triangle => {
+                is_profitable || self.process_triangle_arb(&info, requirement_multiplier)
+            }
+            AtomicArbType::CrossPair(jump_index) => {
+                is_profitable
+                    || self.is_stable_arb(&swaps, jump_index)
+                    || self.is_cross_pair_or_stable_arb(&info, requirement_multiplier)
+            }
+            AtomicArbType::StablecoinArb => {
+                is_profitable || self.is_cross_pair_or_stable_arb(&info, requirement_multiplier)
+            }
+            AtomicArbType::LongTail => {
+                self.is_long_tail(&info, requirement_multiplier) && is_profitable
+                    || self.is_long_tail(&info, requirement_multiplier) & !has_dex_price
+            }
+        };
+        if !is_arb {
+            return Err(if has_dex_price {
+                RejectReason::Unprofitable { profit_usd: profit.to_float() }
+            } else {
+                RejectReason::MissingPricing
+            })
+        }
 
         // given we have a atomic arb now, we will go and try to find the trigger
         // transaction that lead to this arb.
+        let tree = trees.last().ok_or(RejectReason::NotInBlock)?.clone();
         let trigger_tx = self.find_trigger_tx(&info, trees, &swaps);
 
+        // the user whose transaction was backrun might be refunded part of the
+        // arb through an order flow auction
+        let users = tree
+            .tx_roots
+            .iter()
+            .filter(|root| root.tx_hash == trigger_tx)
+            .map(|root| root.get_from_address())
+            .collect::<FastHashSet<_>>();
+        let refunds =
+            self.utils
+                .find_ofa_refunds(&tree, &[info.tx_hash], &mev_addresses, &users, &metadata);
+
         let backrun = AtomicArb {
             block_number: metadata.block_num,
             trigger_tx,
@@ -208,10 +275,11 @@ impl<DB: LibmdbxReader> AtomicArbInspector<'_, DB> {
             gas_details: info.gas_details,
             swaps,
             arb_type: possible_arb_type,
+            refunds: refunds.clone(),
         };
         let data = BundleData::AtomicArb(backrun);
 
-        let header = self.utils.build_bundle_header(
+        let mut header = self.utils.build_bundle_header(
             vec![account_deltas],
             vec![info.tx_hash],
             &info,
@@ -230,8 +298,15 @@ impl<DB: LibmdbxReader> AtomicArbInspector<'_, DB> {
                 )
             },
         );
+        self.utils.apply_ofa_refunds(
+            &mut header,
+            &refunds,
+            &[info.tx_hash],
+            tree.header.beneficiary,
+            &metadata,
+        );
 
-        Some(Bundle { header, data })
+        Ok(Bundle { header, data })
     }
 
     /// goes back through the tree until it finds a transaction that occurred
@@ -437,7 +512,7 @@ pub fn is_stable_pair(token_in: &str, token_out: &str) -> bool {
 #[cfg(test)]
 mod tests {
     use alloy_primitives::hex;
-    use brontes_types::constants::USDT_ADDRESS;
+    use brontes_types::{constants::USDT_ADDRESS, mev::BundleData};
 
     use crate::{
         test_utils::{InspectorTestUtils, InspectorTxRunConfig, USDC_ADDRESS, WETH_ADDRESS},
@@ -745,4 +820,36 @@ mod tests {
 
         inspector_util.run_inspector(config, None).await.unwrap();
     }
+
+    #[brontes_macros::test]
+    async fn test_ofa_refunds() {
+        let inspector_util = InspectorTestUtils::new(USDC_ADDRESS, 0.5).await;
+
+        // mev-share backruns refunding the user through the builder
+        let mut refunds = 0;
+        for block in 18_500_000..18_500_020 {
+            let bundles = inspector_util
+                .inspect_block(Inspectors::AtomicArb, block)
+                .await
+                .unwrap();
+
+            for bundle in bundles {
+                let BundleData::AtomicArb(arb) = &bundle.data else { continue };
+                if arb.refunds.is_empty() {
+                    continue
+                }
+                refunds += arb.refunds.len();
+
+                let refund_usd = arb.refunds.iter().map(|r| r.usd_value).sum::<f64>();
+                assert!((bundle.header.refund_usd - refund_usd).abs() < 1e-6);
+                for refund in &arb.refunds {
+                    assert!(refund.amount > 0.0);
+                    assert_ne!(refund.payer, refund.recipient);
+                    assert_ne!(refund.recipient, bundle.header.eoa);
+                }
+            }
+        }
+
+        assert!(refunds > 0, "no order flow auction refunds found in the block range");
+    }
 }
@@ -57,13 +57,14 @@ pub struct CexDexMarkoutInspector<'db, DB: LibmdbxReader> {
 impl<'db, DB: LibmdbxReader> CexDexMarkoutInspector<'db, DB> {
     pub fn new(
         quote: Address,
+        extra_quotes: &[Address],
         db: &'db DB,
         cex_exchanges: &[CexExchange],
         trade_config: CexDexTradeConfig,
         metrics: Option<OutlierMetrics>,
     ) -> Self {
         Self {
-            utils: SharedInspectorUtils::new(quote, db, metrics),
+            utils: SharedInspectorUtils::new(quote, extra_quotes, db, metrics),
             trade_config,
             cex_exchanges: cex_exchanges.to_owned(),
         }
@@ -86,18 +86,20 @@ impl<'db, DB: LibmdbxReader> CexDexQuotesInspector<'db, DB> {
     /// # Arguments
     ///
     /// * `quote` - The address of the quote asset
+    /// * `extra_quotes` - Additional quote assets to denominate profit in
     /// * `db` - Database reader to our local libmdbx database
     /// * `cex_exchanges` - List of centralized exchanges to consider for
     ///   arbitrage.
     pub fn new(
         quote: Address,
+        extra_quotes: &[Address],
         db: &'db DB,
         cex_exchanges: &[CexExchange],
         quotes_fetch_offset: u64,
         metrics: Option<OutlierMetrics>,
     ) -> Self {
         Self {
-            utils:                SharedInspectorUtils::new(quote, db, metrics),
+            utils:                SharedInspectorUtils::new(quote, extra_quotes, db, metrics),
             _quotes_fetch_offset: quotes_fetch_offset,
             _cex_exchanges:       cex_exchanges.to_owned(),
         }
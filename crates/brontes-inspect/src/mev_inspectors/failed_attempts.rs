@@ -0,0 +1,295 @@
+//! Attributes the reverted transactions of known searchers to the opportunity
+//! they competed for.
+//!
+//! Reverted transactions aren't classified, so an attempt is matched to a
+//! landed bundle by the pools and tokens it called into. The gas it burned is
+//! recorded as wasted for the searcher and the opportunity.
+//!
+//! The attribution needs the bundles kept after composition, so the inspector
+//! finds no bundles of its own and instead runs once the composer resolved the
+//! block, see [`Inspector::attribute_failed_attempts`].
+
+use std::cmp::Reverse;
+
+use brontes_database::libmdbx::LibmdbxReader;
+use brontes_types::{
+    db::metadata::Metadata,
+    mev::{BlockFailedAttempts, Bundle, FailedMevAttempt, Mev, MevType},
+    normalized_actions::Action,
+    tree::{BlockTree, RevertedTx},
+    FastHashSet, MultiBlockData, ToFloatNearest, TreeSearchBuilder,
+};
+use itertools::Itertools;
+use reth_primitives::Address;
+
+use crate::Inspector;
+
+pub struct FailedAttemptInspector<'db, DB: LibmdbxReader> {
+    quote: Address,
+    db:    &'db DB,
+}
+
+/// The pools and tokens a landed bundle traded against
+struct Opportunity<'a> {
+    bundle: &'a Bundle,
+    pools:  FastHashSet<Address>,
+    tokens: FastHashSet<Address>,
+}
+
+impl<DB: LibmdbxReader> Inspector for FailedAttemptInspector<'_, DB> {
+    type Result = Vec<Bundle>;
+
+    fn get_id(&self) -> &str {
+        "FailedAttempts"
+    }
+
+    fn get_quote_token(&self) -> Address {
+        self.quote
+    }
+
+    fn inspect_block(&self, _data: MultiBlockData) -> Self::Result {
+        vec![]
+    }
+
+    fn attribute_failed_attempts(
+        &self,
+        tree: &BlockTree<Action>,
+        metadata: &Metadata,
+        bundles: &[Bundle],
+    ) -> Option<BlockFailedAttempts> {
+        Some(self.attribute_attempts(tree, metadata, bundles))
+    }
+}
+
+impl<'db, DB: LibmdbxReader> FailedAttemptInspector<'db, DB> {
+    pub fn new(quote: Address, db: &'db DB) -> Self {
+        Self { quote, db }
+    }
+
+    /// Matches the reverted transactions of known searchers against the
+    /// bundles that landed in the block
+    pub fn attribute_attempts(
+        &self,
+        tree: &BlockTree<Action>,
+        metadata: &Metadata,
+        bundles: &[Bundle],
+    ) -> BlockFailedAttempts {
+        let opportunities = bundles
+            .iter()
+            .filter(|bundle| {
+                !matches!(bundle.header.mev_type, MevType::SearcherTx | MevType::Unknown)
+            })
+            .map(|bundle| Self::opportunity(tree, bundle))
+            .filter(|opportunity| !opportunity.pools.is_empty())
+            .collect_vec();
+
+        let attempts = if opportunities.is_empty() {
+            vec![]
+        } else {
+            tree.reverted_txs
+                .iter()
+                .filter_map(|tx| self.try_attribute(tx, &opportunities, metadata))
+                .collect()
+        };
+
+        BlockFailedAttempts { block_number: metadata.block_num, attempts }
+    }
+
+    fn opportunity<'a>(tree: &BlockTree<Action>, bundle: &'a Bundle) -> Opportunity<'a> {
+        let hashes = bundle.data.mev_transaction_hashes();
+        let search =
+            TreeSearchBuilder::default().with_actions([Action::is_swap, Action::is_liquidation]);
+
+        let mut pools = FastHashSet::default();
+        let mut tokens = FastHashSet::default();
+        for action in tree
+            .tx_roots
+            .iter()
+            .filter(|root| hashes.contains(&root.tx_hash))
+            .flat_map(|root| root.collect(&search))
+        {
+            if let Action::Liquidation(liquidation) = action {
+                pools.insert(liquidation.pool);
+                tokens
+                    .extend([liquidation.collateral_asset.address, liquidation.debt_asset.address]);
+            } else {
+                let swap = action.force_swap();
+                pools.insert(swap.pool);
+                tokens.extend([swap.token_in.address, swap.token_out.address]);
+            }
+        }
+
+        Opportunity { bundle, pools, tokens }
+    }
+
+    fn try_attribute(
+        &self,
+        tx: &RevertedTx,
+        opportunities: &[Opportunity<'_>],
+        metadata: &Metadata,
+    ) -> Option<FailedMevAttempt> {
+        let (eoa_info, contract_info) =
+            self.db.try_fetch_searcher_info(tx.from, Some(tx.to)).ok()?;
+        if eoa_info.is_none() && contract_info.is_none() {
+            return None
+        }
+
+        // the opportunity sharing the most pools and tokens with the attempt, ties
+        // go to the bundle that landed closest to it
+        let opportunity = opportunities
+            .iter()
+            .filter_map(|opportunity| {
+                let pools = tx
+                    .touched
+                    .iter()
+                    .filter(|address| opportunity.pools.contains(address))
+                    .count();
+                if pools == 0 {
+                    return None
+                }
+                let tokens = tx
+                    .touched
+                    .iter()
+                    .filter(|address| opportunity.tokens.contains(address))
+                    .count();
+                let distance = tx.tx_index.abs_diff(opportunity.bundle.header.tx_index);
+
+                Some((opportunity, (pools + tokens, Reverse(distance))))
+            })
+            .max_by_key(|(_, score)| *score)
+            .map(|(opportunity, _)| opportunity)?;
+
+        let wasted_gas_usd = metadata
+            .get_gas_price_usd(tx.gas_details.gas_paid(), self.quote)
+            .to_float();
+
+        Some(FailedMevAttempt {
+            tx_hash: tx.tx_hash,
+            tx_index: tx.tx_index,
+            eoa: tx.from,
+            mev_contract: contract_info.map(|_| tx.to),
+            opportunity_tx_hash: opportunity.bundle.header.tx_hash,
+            mev_type: opportunity.bundle.header.mev_type,
+            gas_details: tx.gas_details,
+            wasted_gas_usd,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::B256;
+    use brontes_core::get_db_handle;
+    use brontes_database::libmdbx::tables::SearcherEOAs;
+    use brontes_types::{
+        constants::{USDC_ADDRESS, WETH_ADDRESS},
+        db::{metadata::BlockMetadata, searcher::SearcherInfo, token_info::TokenInfoWithAddress},
+        mev::{AtomicArb, BundleData, BundleHeader},
+        normalized_actions::NormalizedSwap,
+        tree::{root::NodeData, Node, Root},
+        GasDetails,
+    };
+    use reth_db::DatabaseError;
+    use reth_primitives::Header;
+
+    use super::*;
+
+    fn token(address: Address) -> TokenInfoWithAddress {
+        TokenInfoWithAddress { address, ..Default::default() }
+    }
+
+    /// A transaction swapping WETH for USDC on the pool
+    fn swap_root(tx_hash: B256, pool: Address) -> Root<Action> {
+        let mut data_store = NodeData(vec![]);
+        let mut head = Node::new(0, pool, vec![]);
+        head.data = data_store.add(vec![Action::Swap(NormalizedSwap {
+            pool,
+            token_in: token(WETH_ADDRESS),
+            token_out: token(USDC_ADDRESS),
+            ..Default::default()
+        })]);
+
+        Root {
+            head,
+            position: 0,
+            tx_hash,
+            private: false,
+            gas_details: GasDetails::default(),
+            total_msg_value_transfers: vec![],
+            data_store,
+            user_op: None,
+        }
+    }
+
+    fn arb(tx_hash: B256, tx_index: u64) -> Bundle {
+        Bundle {
+            header: BundleHeader {
+                tx_hash,
+                tx_index,
+                mev_type: MevType::AtomicArb,
+                ..Default::default()
+            },
+            data:   BundleData::AtomicArb(AtomicArb { tx_hash, ..Default::default() }),
+        }
+    }
+
+    fn reverted(tx_index: u64, from: Address, touched: Vec<Address>) -> RevertedTx {
+        RevertedTx {
+            tx_hash: B256::with_last_byte(tx_index as u8),
+            tx_index,
+            from,
+            to: Address::with_last_byte(0xcc),
+            gas_details: GasDetails::default(),
+            touched,
+        }
+    }
+
+    #[brontes_macros::test]
+    async fn test_attribute_failed_attempts() {
+        let db = get_db_handle(tokio::runtime::Handle::current()).await;
+        let (searcher, unknown) = (Address::repeat_byte(0xfa), Address::repeat_byte(0xfb));
+        db.db
+            .update_db(|tx| {
+                tx.put::<SearcherEOAs>(searcher, SearcherInfo::default())?;
+                Ok::<_, DatabaseError>(())
+            })
+            .unwrap()
+            .unwrap();
+
+        let (pool_a, pool_b) = (Address::with_last_byte(0xa), Address::with_last_byte(0xb));
+        let (arb_a, arb_b) = (B256::repeat_byte(0xa), B256::repeat_byte(0xb));
+
+        let mut tree = BlockTree::new(Header::default(), 2);
+        tree.tx_roots = vec![swap_root(arb_a, pool_a), swap_root(arb_b, pool_b)];
+        tree.reverted_txs = vec![
+            reverted(1, searcher, vec![pool_a, WETH_ADDRESS]),
+            // shares as much with both, goes to the closest bundle
+            reverted(18, searcher, vec![pool_a, pool_b]),
+            // not a known searcher
+            reverted(2, unknown, vec![pool_a]),
+            // tokens alone don't make it an attempt at the opportunity
+            reverted(3, searcher, vec![WETH_ADDRESS, USDC_ADDRESS]),
+        ];
+
+        let metadata = Metadata {
+            block_metadata: BlockMetadata { block_num: 1, ..Default::default() },
+            ..Default::default()
+        };
+        let failed = FailedAttemptInspector::new(USDC_ADDRESS, db).attribute_attempts(
+            &tree,
+            &metadata,
+            &[arb(arb_a, 5), arb(arb_b, 20)],
+        );
+
+        assert_eq!(failed.block_number, 1);
+        let attributed = failed
+            .attempts
+            .iter()
+            .map(|attempt| (attempt.tx_index, attempt.opportunity_tx_hash))
+            .collect_vec();
+        assert_eq!(attributed, vec![(1, arb_a), (18, arb_b)]);
+        assert!(failed.attempts.iter().all(|attempt| attempt.eoa == searcher
+            && attempt.mev_contract.is_none()
+            && attempt.mev_type == MevType::AtomicArb));
+    }
+}
@@ -10,18 +10,23 @@ use brontes_types::{
 };
 use itertools::multizip;
 use malachite::{num::basic::traits::Zero, Rational};
-use reth_primitives::{b256, Address};
+use reth_primitives::{b256, Address, TxHash};
 
 use super::{MAX_PROFIT, MIN_PROFIT};
-use crate::{shared_utils::SharedInspectorUtils, Inspector, Metadata};
+use crate::{shared_utils::SharedInspectorUtils, Inspector, Metadata, RejectReason};
 
 pub struct LiquidationInspector<'db, DB: LibmdbxReader> {
     utils: SharedInspectorUtils<'db, DB>,
 }
 
 impl<'db, DB: LibmdbxReader> LiquidationInspector<'db, DB> {
-    pub fn new(quote: Address, db: &'db DB, metrics: Option<OutlierMetrics>) -> Self {
-        Self { utils: SharedInspectorUtils::new(quote, db, metrics) }
+    pub fn new(
+        quote: Address,
+        extra_quotes: &[Address],
+        db: &'db DB,
+        metrics: Option<OutlierMetrics>,
+    ) -> Self {
+        Self { utils: SharedInspectorUtils::new(quote, extra_quotes, db, metrics) }
     }
 }
 
@@ -41,16 +46,7 @@ impl<DB: LibmdbxReader> Inspector for LiquidationInspector<'_, DB> {
         let BlockData { metadata, tree } = block;
 
         let ex = || {
-            let (tx, liq): (Vec<_>, Vec<_>) = tree
-                .clone()
-                .collect_all(TreeSearchBuilder::default().with_actions([
-                    Action::is_swap,
-                    Action::is_liquidation,
-                    Action::is_transfer,
-                    Action::is_eth_transfer,
-                    Action::is_aggregator,
-                ]))
-                .unzip();
+            let (tx, liq): (Vec<_>, Vec<_>) = tree.clone().collect_all(Self::search_args()).unzip();
             let tx_info = tree.get_tx_info_batch(&tx, self.utils.db);
 
             multizip((liq, tx_info))
@@ -62,6 +58,7 @@ impl<DB: LibmdbxReader> Inspector for LiquidationInspector<'_, DB> {
                         .collect::<Vec<_>>();
 
                     self.calculate_liquidation(info, metadata.clone(), actions)
+                        .ok()
                 })
                 .collect::<Vec<_>>()
         };
@@ -70,15 +67,42 @@ impl<DB: LibmdbxReader> Inspector for LiquidationInspector<'_, DB> {
             .map(|m| m.run_inspector(MevType::Liquidation, ex))
             .unwrap_or_else(ex)
     }
+
+    fn reject_reason(&self, data: MultiBlockData, tx: TxHash) -> Option<RejectReason> {
+        let BlockData { metadata, tree } = data.get_most_recent_block();
+        if tree.get_root(tx).is_none() {
+            return Some(RejectReason::NotInBlock)
+        }
+        let Some(info) = tree.get_tx_info(tx, self.utils.db) else {
+            return Some(RejectReason::MissingTxInfo)
+        };
+        let actions = self
+            .utils
+            .flatten_nested_actions_default(tree.clone().collect(&tx, Self::search_args()))
+            .collect::<Vec<_>>();
+
+        self.calculate_liquidation(info, metadata.clone(), actions)
+            .err()
+    }
 }
 
 impl<DB: LibmdbxReader> LiquidationInspector<'_, DB> {
+    fn search_args() -> TreeSearchBuilder<Action> {
+        TreeSearchBuilder::default().with_actions([
+            Action::is_swap,
+            Action::is_liquidation,
+            Action::is_transfer,
+            Action::is_eth_transfer,
+            Action::is_aggregator,
+        ])
+    }
+
     fn calculate_liquidation(
         &self,
         info: TxInfo,
         metadata: Arc<Metadata>,
         actions: Vec<Action>,
-    ) -> Option<Bundle> {
+    ) -> Result<Bundle, RejectReason> {
         let (swaps, liqs): (Vec<_>, Vec<_>) = actions
             .clone()
             .into_iter()
@@ -86,7 +110,7 @@ impl<DB: LibmdbxReader> LiquidationInspector<'_, DB> {
 
         if liqs.is_empty() {
             tracing::debug!("no liquidation events");
-            return None
+            return Err(RejectReason::NoRelevantActions)
         }
 
         let mev_addresses: FastHashSet<Address> = info.collect_address_set_for_accounting();
@@ -152,7 +176,7 @@ impl<DB: LibmdbxReader> LiquidationInspector<'_, DB> {
             gas_details:         info.gas_details,
         };
 
-        Some(Bundle { header, data: BundleData::Liquidation(new_liquidation) })
+        Ok(Bundle { header, data: BundleData::Liquidation(new_liquidation) })
     }
 }
 
@@ -1,8 +1,10 @@
 pub mod atomic_arb;
 pub mod cex_dex;
+pub mod failed_attempts;
 
 pub mod jit;
 pub mod liquidations;
+pub mod nft_arb;
 pub mod sandwich;
 pub mod searcher_activity;
 pub mod shared_utils;
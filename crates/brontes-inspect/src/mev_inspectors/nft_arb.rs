@@ -0,0 +1,231 @@
+use std::sync::Arc;
+
+use brontes_database::libmdbx::LibmdbxReader;
+use brontes_metrics::inspectors::OutlierMetrics;
+use brontes_types::{
+    db::dex::PriceAt,
+    mev::{Bundle, BundleData, MevType, NftArb},
+    normalized_actions::{
+        accounting::{ActionAccounting, NftId},
+        Action, NormalizedEthTransfer, NormalizedNftTrade, NormalizedNftTransfer,
+        NormalizedTransfer,
+    },
+    BlockData, FastHashSet, IntoZip, MultiBlockData, ToFloatNearest, TreeBase, TreeCollector,
+    TreeSearchBuilder, TxInfo,
+};
+use itertools::Itertools;
+use malachite::{num::basic::traits::Zero, Rational};
+use reth_primitives::Address;
+
+use crate::{shared_utils::SharedInspectorUtils, Inspector, Metadata, MAX_PROFIT, MIN_PROFIT};
+
+/// Finds transactions that buy a nft on one marketplace and sell the same
+/// token id on another, all within the same transaction
+pub struct NftArbInspector<'db, DB: LibmdbxReader> {
+    utils: SharedInspectorUtils<'db, DB>,
+}
+
+impl<'db, DB: LibmdbxReader> NftArbInspector<'db, DB> {
+    pub fn new(
+        quote: Address,
+        extra_quotes: &[Address],
+        db: &'db DB,
+        metrics: Option<OutlierMetrics>,
+    ) -> Self {
+        Self { utils: SharedInspectorUtils::new(quote, extra_quotes, db, metrics) }
+    }
+}
+
+impl<DB: LibmdbxReader> Inspector for NftArbInspector<'_, DB> {
+    type Result = Vec<Bundle>;
+
+    fn get_id(&self) -> &str {
+        "NftArb"
+    }
+
+    fn get_quote_token(&self) -> Address {
+        self.utils.quote
+    }
+
+    fn inspect_block(&self, data: MultiBlockData) -> Self::Result {
+        let BlockData { metadata, tree } = data.get_most_recent_block();
+
+        let execution = || {
+            tree.clone()
+                .collect_all(TreeSearchBuilder::default().with_actions([
+                    Action::is_nft_trade,
+                    Action::is_nft_transfer,
+                    Action::is_transfer,
+                    Action::is_eth_transfer,
+                    Action::is_nested_action,
+                ]))
+                .t_full_map(|(tree, v)| {
+                    let (tx_hashes, v): (Vec<_>, Vec<_>) = v.unzip();
+                    (
+                        tree.get_tx_info_batch(&tx_hashes, self.utils.db),
+                        v.into_iter().map(|v| {
+                            self.utils
+                                .flatten_nested_actions(v.into_iter(), &|action| {
+                                    action.is_nft_trade()
+                                        || action.is_nft_transfer()
+                                        || action.is_transfer()
+                                        || action.is_eth_transfer()
+                                })
+                                .collect::<Vec<_>>()
+                        }),
+                    )
+                })
+                .into_zip()
+                .filter_map(|(info, actions)| {
+                    let info = info??;
+                    let actions = actions?;
+
+                    self.process_trades(
+                        info,
+                        metadata.clone(),
+                        actions
+                            .into_iter()
+                            .split_actions::<(Vec<_>, Vec<_>, Vec<_>, Vec<_>), _>((
+                                Action::try_nft_trade,
+                                Action::try_nft_transfer,
+                                Action::try_transfer,
+                                Action::try_eth_transfer,
+                            )),
+                    )
+                })
+                .collect::<Vec<_>>()
+        };
+
+        self.utils
+            .get_metrics()
+            .map(|m| m.run_inspector(MevType::NftArb, execution))
+            .unwrap_or_else(&execution)
+    }
+}
+
+impl<DB: LibmdbxReader> NftArbInspector<'_, DB> {
+    fn process_trades(
+        &self,
+        info: TxInfo,
+        metadata: Arc<Metadata>,
+        data: (
+            Vec<NormalizedNftTrade>,
+            Vec<NormalizedNftTransfer>,
+            Vec<NormalizedTransfer>,
+            Vec<NormalizedEthTransfer>,
+        ),
+    ) -> Option<Bundle> {
+        let (trades, nft_transfers, transfers, eth_transfers) = data;
+        if trades.len() < 2 {
+            return None
+        }
+
+        let mev_addresses: FastHashSet<Address> = info.collect_address_set_for_accounting();
+        let flipped = Self::flipped_nft_ids(&trades, &mev_addresses);
+        if flipped.is_empty()
+            || !Self::sold_everything_bought(&flipped, nft_transfers, &mev_addresses)
+        {
+            return None
+        }
+
+        tracing::trace!(?info, ?flipped, "found nft arb");
+
+        let trades = trades
+            .into_iter()
+            .filter(|trade| trade.nft_ids().any(|id| flipped.contains(&id)))
+            .collect_vec();
+
+        let account_deltas = transfers
+            .into_iter()
+            .map(Action::from)
+            .chain(eth_transfers.into_iter().map(Action::from))
+            .chain(info.get_total_eth_value().iter().cloned().map(Action::from))
+            .account_for_actions();
+
+        let gas_used = info.gas_details.gas_paid();
+        let gas_used_usd = metadata.get_gas_price_usd(gas_used, self.utils.quote);
+
+        let rev = self.utils.get_deltas_usd(
+            info.tx_index,
+            PriceAt::Average,
+            &mev_addresses,
+            &account_deltas,
+            metadata.clone(),
+            false,
+        );
+        let mut has_dex_price = rev.is_some();
+        let mut profit = rev.map(|rev| rev - &gas_used_usd).unwrap_or_default();
+
+        if profit >= MAX_PROFIT || profit <= MIN_PROFIT {
+            has_dex_price = false;
+            profit = Rational::ZERO;
+        }
+
+        let nft_arb = NftArb {
+            tx_hash: info.tx_hash,
+            block_number: metadata.block_num,
+            trades,
+            gas_details: info.gas_details,
+        };
+
+        let header = self.utils.build_bundle_header(
+            vec![account_deltas],
+            vec![info.tx_hash],
+            &info,
+            profit.to_float(),
+            &[info.gas_details],
+            metadata.clone(),
+            MevType::NftArb,
+            !has_dex_price,
+            |this, token, amount| {
+                this.get_token_value_dex(
+                    info.tx_index as usize,
+                    PriceAt::Average,
+                    token,
+                    &amount,
+                    &metadata,
+                )
+            },
+        );
+
+        Some(Bundle { header, data: BundleData::NftArb(nft_arb) })
+    }
+
+    /// Nft ids that the searcher both bought and sold in the transaction
+    fn flipped_nft_ids(
+        trades: &[NormalizedNftTrade],
+        mev_addresses: &FastHashSet<Address>,
+    ) -> FastHashSet<NftId> {
+        let bought = trades
+            .iter()
+            .filter(|trade| mev_addresses.contains(&trade.buyer))
+            .flat_map(NormalizedNftTrade::nft_ids)
+            .collect::<FastHashSet<_>>();
+
+        trades
+            .iter()
+            .filter(|trade| mev_addresses.contains(&trade.seller))
+            .flat_map(NormalizedNftTrade::nft_ids)
+            .filter(|id| bought.contains(id))
+            .collect()
+    }
+
+    /// An arb doesn't hold on to inventory, so none of the flipped ids can be
+    /// left with the searcher once the transaction is done
+    fn sold_everything_bought(
+        flipped: &FastHashSet<NftId>,
+        nft_transfers: Vec<NormalizedNftTransfer>,
+        mev_addresses: &FastHashSet<Address>,
+    ) -> bool {
+        let nft_deltas = nft_transfers
+            .into_iter()
+            .map(Action::from)
+            .account_for_nft_actions();
+
+        mev_addresses
+            .iter()
+            .filter_map(|address| nft_deltas.get(address))
+            .flatten()
+            .all(|(id, delta)| !flipped.contains(id) || delta <= &Rational::ZERO)
+    }
+}
@@ -41,8 +41,13 @@ pub struct SandwichInspector<'db, DB: LibmdbxReader> {
 }
 
 impl<'db, DB: LibmdbxReader> SandwichInspector<'db, DB> {
-    pub fn new(quote: Address, db: &'db DB, metrics: Option<OutlierMetrics>) -> Self {
-        Self { utils: SharedInspectorUtils::new(quote, db, metrics) }
+    pub fn new(
+        quote: Address,
+        extra_quotes: &[Address],
+        db: &'db DB,
+        metrics: Option<OutlierMetrics>,
+    ) -> Self {
+        Self { utils: SharedInspectorUtils::new(quote, extra_quotes, db, metrics) }
     }
 }
 
@@ -399,9 +404,15 @@ impl<DB: LibmdbxReader> SandwichInspector<'_, DB> {
         }
         bundle_hashes.push(backrun_info.tx_hash);
 
-        let header = self.utils.build_bundle_header(
+        let victims: FastHashSet<Address> =
+            victim_info.iter().flatten().map(|info| info.eoa).collect();
+        let refunds =
+            self.utils
+                .find_ofa_refunds(&tree, &bundle_hashes, &mev_addresses, &victims, &metadata);
+
+        let mut header = self.utils.build_bundle_header(
             vec![searcher_deltas],
-            bundle_hashes,
+            bundle_hashes.clone(),
             &backrun_info,
             profit_usd.to_float(),
             &gas_details,
@@ -418,6 +429,13 @@ impl<DB: LibmdbxReader> SandwichInspector<'_, DB> {
                 )
             },
         );
+        self.utils.apply_ofa_refunds(
+            &mut header,
+            &refunds,
+            &bundle_hashes,
+            tree.header.beneficiary,
+            &metadata,
+        );
 
         let victim_swaps = victim_swaps.into_iter().map(|(s, _)| s).collect_vec();
 
@@ -432,6 +450,8 @@ impl<DB: LibmdbxReader> SandwichInspector<'_, DB> {
             backrun_tx_hash: backrun_info.tx_hash,
             backrun_swaps: back_run_swaps,
             backrun_gas_details: backrun_info.gas_details,
+            refunds,
+            victim_losses: vec![],
         };
         tracing::debug!("{:#?}\n{:#?}", header, sandwich);
 
@@ -1529,4 +1549,28 @@ mod tests {
 
         inspector_util.run_inspector(config, None).await.unwrap();
     }
+
+    #[brontes_macros::test]
+    async fn test_ofa_refunds() {
+        let inspector_util = InspectorTestUtils::new(USDC_ADDRESS, 1.0).await;
+
+        for block in 18_500_000..18_500_020 {
+            let bundles = inspector_util
+                .inspect_block(Inspectors::Sandwich, block)
+                .await
+                .unwrap();
+
+            for bundle in bundles {
+                let BundleData::Sandwich(sandwich) = &bundle.data else { continue };
+                let refund_usd = sandwich.refunds.iter().map(|r| r.usd_value).sum::<f64>();
+                assert!((bundle.header.refund_usd - refund_usd).abs() < 1e-6);
+
+                for refund in &sandwich.refunds {
+                    assert!(refund.amount > 0.0);
+                    assert_ne!(refund.recipient, bundle.header.eoa);
+                    assert_ne!(Some(refund.recipient), bundle.header.mev_contract);
+                }
+            }
+        }
+    }
 }
@@ -4,14 +4,15 @@ use alloy_primitives::{Address, FixedBytes};
 use brontes_database::libmdbx::LibmdbxReader;
 use brontes_metrics::inspectors::OutlierMetrics;
 use brontes_types::{
+    constants::{ETH_ADDRESS, WETH_ADDRESS},
     db::{
         dex::{BlockPrice, PriceAt},
         metadata::Metadata,
         token_info::TokenInfoWithAddress,
     },
     mev::{
-        AddressBalanceDeltas, Bundle, BundleHeader, Mev, MevType, TokenBalanceDelta,
-        TransactionAccounting,
+        AddressBalanceDeltas, Bundle, BundleHeader, Mev, MevType, OfaRefund, QuoteAssetPnl,
+        TokenBalanceDelta, TransactionAccounting,
     },
     normalized_actions::{
         Action, NormalizedAggregator, NormalizedBatch, NormalizedFlashLoan, NormalizedSwap,
@@ -19,7 +20,8 @@ use brontes_types::{
     },
     pair::Pair,
     utils::ToFloatNearest,
-    ActionIter, FastHashMap, FastHashSet, GasDetails, TxInfo,
+    ActionIter, BlockTree, FastHashMap, FastHashSet, GasDetails, ToScaledRational,
+    TreeSearchBuilder, TxInfo,
 };
 use itertools::Itertools;
 use malachite::{
@@ -33,17 +35,33 @@ use reth_primitives::TxHash;
 
 const CONNECTION_TH: usize = 2;
 const LOW_LIQ_TH: Rational = Rational::const_from_unsigned(50_000u64);
+/// Address metadata label of contracts that pay out order flow auction refunds
+pub const OFA_REFUND_LABEL: &str = "ofa-refund";
 
 #[derive(Debug)]
 pub struct SharedInspectorUtils<'db, DB: LibmdbxReader> {
-    pub(crate) quote: Address,
-    pub(crate) db:    &'db DB,
-    pub metrics:      Option<OutlierMetrics>,
+    pub(crate) quote:        Address,
+    /// additional quote assets that bundle profit is also denominated in
+    pub(crate) extra_quotes: Vec<Address>,
+    pub(crate) db:           &'db DB,
+    pub metrics:             Option<OutlierMetrics>,
 }
 
 impl<'db, DB: LibmdbxReader> SharedInspectorUtils<'db, DB> {
-    pub fn new(quote_address: Address, db: &'db DB, metrics: Option<OutlierMetrics>) -> Self {
-        SharedInspectorUtils { quote: quote_address, db, metrics }
+    pub fn new(
+        quote_address: Address,
+        extra_quotes: &[Address],
+        db: &'db DB,
+        metrics: Option<OutlierMetrics>,
+    ) -> Self {
+        let extra_quotes = extra_quotes
+            .iter()
+            .copied()
+            .filter(|quote| quote != &quote_address)
+            .unique()
+            .collect();
+
+        SharedInspectorUtils { quote: quote_address, extra_quotes, db, metrics }
     }
 }
 type TokenDeltas = FastHashMap<Address, Rational>;
@@ -326,6 +344,9 @@ impl<DB: LibmdbxReader> SharedInspectorUtils<'_, DB> {
             .or_else(|| info.get_searcher_eao_info().map(|f| f.fund))
             .unwrap_or_default();
 
+        let quote_pnl =
+            self.get_quote_pnl(info.tx_index as usize, profit_usd, bribe_usd, &metadata);
+
         BundleHeader {
             block_number: metadata.block_num,
             tx_index: info.tx_index,
@@ -338,6 +359,8 @@ impl<DB: LibmdbxReader> SharedInspectorUtils<'_, DB> {
             mev_type,
             no_pricing_calculated,
             balance_deltas,
+            quote_pnl,
+            refund_usd: 0.0,
         }
     }
 
@@ -380,6 +403,9 @@ impl<DB: LibmdbxReader> SharedInspectorUtils<'_, DB> {
             .or_else(|| info.get_searcher_eao_info().map(|f| f.fund))
             .unwrap_or_default();
 
+        let quote_pnl =
+            self.get_quote_pnl(info.tx_index as usize, profit_usd, bribe_usd, &metadata);
+
         BundleHeader {
             block_number: metadata.block_num,
             tx_index: info.tx_index,
@@ -392,7 +418,215 @@ impl<DB: LibmdbxReader> SharedInspectorUtils<'_, DB> {
             mev_type,
             no_pricing_calculated,
             balance_deltas,
+            quote_pnl,
+            refund_usd: 0.0,
+        }
+    }
+
+    /// Denominates the usd profit & revenue of a bundle in each of the extra
+    /// quote assets. The pricer only prices the primary quote asset, so an
+    /// extra quote asset other than ETH is only resolved in blocks where its
+    /// pair with the primary quote asset was priced, it's left out of the
+    /// result otherwise.
+    pub fn get_quote_pnl(
+        &self,
+        tx_index: usize,
+        profit_usd: f64,
+        bribe_usd: f64,
+        metadata: &Arc<Metadata>,
+    ) -> Vec<QuoteAssetPnl> {
+        self.extra_quotes
+            .iter()
+            .filter_map(|quote_asset| {
+                let price = self
+                    .get_quote_asset_price(tx_index, *quote_asset, metadata)?
+                    .to_float();
+                if price == 0.0 {
+                    return None
+                }
+
+                Some(QuoteAssetPnl {
+                    quote_asset: *quote_asset,
+                    profit:      profit_usd / price,
+                    revenue:     (profit_usd + bribe_usd) / price,
+                })
+            })
+            .collect()
+    }
+
+    /// Gets the price of the given quote asset in terms of our primary quote
+    /// asset. For ETH we use the same price that is used to price gas, so that
+    /// the bribe is denominated exactly.
+    fn get_quote_asset_price(
+        &self,
+        tx_index: usize,
+        quote_asset: Address,
+        metadata: &Arc<Metadata>,
+    ) -> Option<Rational> {
+        if quote_asset == self.quote {
+            return Some(Rational::ONE)
+        }
+
+        if quote_asset == WETH_ADDRESS || quote_asset == ETH_ADDRESS {
+            let eth_price = metadata.get_eth_price(self.quote);
+            return (eth_price != Rational::ZERO).then_some(eth_price)
         }
+
+        let pair = Pair(quote_asset, self.quote);
+        let dex_quotes = metadata.dex_quotes.as_ref()?;
+
+        dex_quotes
+            .price_at_or_before(pair, tx_index)
+            .map(|price| price.get_price(PriceAt::Average))
+            .or_else(|| dex_quotes.price_for_block(pair, BlockPrice::Average))
+    }
+
+    /// Finds the ETH and WETH refunds paid to the `users` whose transactions
+    /// the bundle backran. Refunds are picked up when paid by the searcher in
+    /// or after the bundle, by the block builder as MEV-Share and MEV Blocker
+    /// do, or by a contract labelled as an order flow auction refund contract.
+    pub fn find_ofa_refunds(
+        &self,
+        tree: &BlockTree<Action>,
+        bundle_txes: &[TxHash],
+        searcher_addresses: &FastHashSet<Address>,
+        users: &FastHashSet<Address>,
+        metadata: &Arc<Metadata>,
+    ) -> Vec<OfaRefund> {
+        let builder = tree.header.beneficiary;
+        let Some(first_bundle_tx) = tree
+            .tx_roots
+            .iter()
+            .position(|root| bundle_txes.contains(&root.tx_hash))
+        else {
+            return vec![]
+        };
+        let eth_price = metadata.get_eth_price(self.quote);
+
+        tree.tx_roots
+            .iter()
+            .skip(first_bundle_tx)
+            .flat_map(|root| {
+                let transfers = root
+                    .collect(
+                        &TreeSearchBuilder::default()
+                            .with_actions([Action::is_eth_transfer, Action::is_transfer]),
+                    )
+                    .into_iter()
+                    .filter_map(|action| match action {
+                        Action::EthTransfer(transfer) if !transfer.coinbase_transfer => Some((
+                            transfer.from,
+                            transfer.to,
+                            transfer.value.to_scaled_rational(18),
+                        )),
+                        Action::Transfer(transfer) if transfer.token.address == WETH_ADDRESS => {
+                            Some((transfer.from, transfer.to, transfer.amount))
+                        }
+                        _ => None,
+                    })
+                    .filter(|(_, to, _)| users.contains(to) && !searcher_addresses.contains(to))
+                    .collect_vec();
+                if transfers.is_empty() {
+                    return vec![]
+                }
+
+                let from = root.get_from_address();
+                let is_searcher_tx =
+                    bundle_txes.contains(&root.tx_hash) || searcher_addresses.contains(&from);
+                // outside of the bundle and builder payments, only the called contract can pay
+                // refunds so its labels are only looked up once per transaction
+                let refund_contract = root.try_get_to_address().filter(|to| {
+                    !is_searcher_tx && from != builder && self.is_ofa_refund_contract(*to)
+                });
+                let is_payer = |payer: &Address| {
+                    if is_searcher_tx {
+                        searcher_addresses.contains(payer)
+                    } else if from == builder {
+                        *payer == builder
+                    } else {
+                        refund_contract == Some(*payer)
+                    }
+                };
+
+                transfers
+                    .into_iter()
+                    .filter(|(from, ..)| is_payer(from))
+                    .map(|(from, to, amount)| OfaRefund {
+                        tx_hash:   root.tx_hash,
+                        payer:     from,
+                        recipient: to,
+                        usd_value: (&amount * &eth_price).to_float(),
+                        amount:    amount.to_float(),
+                    })
+                    .collect_vec()
+            })
+            .collect()
+    }
+
+    fn is_ofa_refund_contract(&self, address: Address) -> bool {
+        self.db
+            .try_fetch_address_metadata(address)
+            .ok()
+            .flatten()
+            .is_some_and(|meta| meta.labels.iter().any(|label| label == OFA_REFUND_LABEL))
+    }
+
+    /// Records the refunds in the bundle header. Refunds paid in the bundle
+    /// are already part of the searcher's balance deltas and builder refunds
+    /// are paid out of the bribe, so only refunds the searcher pays after the
+    /// bundle are deducted from the profit. Refunds paid outside of the
+    /// bundle are added to the balance deltas.
+    pub fn apply_ofa_refunds(
+        &self,
+        header: &mut BundleHeader,
+        refunds: &[OfaRefund],
+        bundle_txes: &[TxHash],
+        builder: Address,
+        metadata: &Arc<Metadata>,
+    ) {
+        if refunds.is_empty() {
+            return
+        }
+        header.refund_usd = refunds.iter().map(|refund| refund.usd_value).sum();
+
+        let eth = self
+            .db
+            .try_fetch_token_info(ETH_ADDRESS)
+            .ok()
+            .unwrap_or_default();
+        for (tx_hash, refunds) in &refunds
+            .iter()
+            .filter(|refund| !bundle_txes.contains(&refund.tx_hash))
+            .group_by(|refund| refund.tx_hash)
+        {
+            let mut address_deltas = Vec::new();
+            for refund in refunds {
+                if refund.payer != builder && !header.no_pricing_calculated {
+                    header.profit_usd -= refund.usd_value;
+                }
+                for (address, sign) in [(refund.payer, -1.0), (refund.recipient, 1.0)] {
+                    address_deltas.push(AddressBalanceDeltas {
+                        address,
+                        name: self.fetch_address_name(address),
+                        token_deltas: vec![TokenBalanceDelta {
+                            token:     eth.clone(),
+                            amount:    sign * refund.amount,
+                            usd_value: sign * refund.usd_value,
+                        }],
+                    });
+                }
+            }
+            header
+                .balance_deltas
+                .push(TransactionAccounting { tx_hash, address_deltas });
+        }
+
+        header.quote_pnl = self.get_quote_pnl(
+            header.tx_index as usize,
+            header.profit_usd,
+            header.bribe_usd,
+            metadata,
+        );
     }
 
     pub fn get_full_block_price(
@@ -796,15 +1030,120 @@ impl<DB: LibmdbxReader> SharedInspectorUtils<'_, DB> {
 
 #[cfg(test)]
 pub mod test {
-    use brontes_core::LibmdbxReadWriter;
+    use std::sync::Arc;
+
+    use alloy_primitives::{Address, TxHash};
+    use brontes_core::{get_db_handle, LibmdbxReadWriter};
     use brontes_types::{
         constants::{USDC_ADDRESS, USDT_ADDRESS, WETH_ADDRESS},
+        db::{
+            dex::{DexPrices, DexQuotes},
+            metadata::{BlockMetadata, Metadata},
+        },
+        mev::{BundleHeader, OfaRefund, QuoteAssetPnl},
         normalized_actions::NormalizedSwap,
+        pair::Pair,
+        FastHashMap,
     };
     use malachite::Rational;
 
     use super::SharedInspectorUtils;
 
+    fn usdc_price(price: u64) -> Option<FastHashMap<Pair, DexPrices>> {
+        let price = DexPrices {
+            pre_state: Rational::from(price),
+            post_state: Rational::from(price),
+            ..Default::default()
+        };
+
+        Some(
+            [(Pair(USDC_ADDRESS, USDT_ADDRESS), price)]
+                .into_iter()
+                .collect(),
+        )
+    }
+
+    #[brontes_macros::test]
+    async fn test_quote_pnl() {
+        let db = get_db_handle(tokio::runtime::Handle::current()).await;
+        let utils = SharedInspectorUtils::new(
+            USDT_ADDRESS,
+            &[USDC_ADDRESS, WETH_ADDRESS, USDT_ADDRESS],
+            db,
+            None,
+        );
+        // the primary quote asset is never an extra quote asset
+        assert_eq!(utils.extra_quotes, vec![USDC_ADDRESS, WETH_ADDRESS]);
+
+        let metadata = Arc::new(Metadata {
+            block_metadata: BlockMetadata {
+                eth_prices: Rational::from(2_000),
+                ..Default::default()
+            },
+            dex_quotes: Some(DexQuotes(vec![usdc_price(2), None, usdc_price(4)])),
+            ..Default::default()
+        });
+
+        // no USDC price at the bundle, so the last price before it is used
+        assert_eq!(
+            utils.get_quote_pnl(1, 100.0, 20.0, &metadata),
+            vec![
+                QuoteAssetPnl { quote_asset: USDC_ADDRESS, profit: 50.0, revenue: 60.0 },
+                QuoteAssetPnl { quote_asset: WETH_ADDRESS, profit: 0.05, revenue: 0.06 },
+            ]
+        );
+        assert_eq!(
+            utils.get_quote_pnl(2, 100.0, 20.0, &metadata),
+            vec![
+                QuoteAssetPnl { quote_asset: USDC_ADDRESS, profit: 25.0, revenue: 30.0 },
+                QuoteAssetPnl { quote_asset: WETH_ADDRESS, profit: 0.05, revenue: 0.06 },
+            ]
+        );
+
+        // without extra quote assets nothing changes
+        let utils = SharedInspectorUtils::new(USDT_ADDRESS, &[], db, None);
+        assert!(utils.get_quote_pnl(2, 100.0, 20.0, &metadata).is_empty());
+    }
+
+    #[brontes_macros::test]
+    async fn test_apply_ofa_refunds() {
+        let db = get_db_handle(tokio::runtime::Handle::current()).await;
+        let utils = SharedInspectorUtils::new(USDT_ADDRESS, &[], db, None);
+        let metadata = Arc::new(Metadata::default());
+
+        let (bundle_tx, later_tx) = (TxHash::with_last_byte(1), TxHash::with_last_byte(2));
+        let (searcher, builder, user) =
+            (Address::with_last_byte(1), Address::with_last_byte(2), Address::with_last_byte(3));
+        let refund = |tx_hash, payer, usd_value: f64| OfaRefund {
+            tx_hash,
+            payer,
+            recipient: user,
+            amount: usd_value / 2_000.0,
+            usd_value,
+        };
+
+        let mut header = BundleHeader { profit_usd: 100.0, ..Default::default() };
+        utils.apply_ofa_refunds(
+            &mut header,
+            &[
+                refund(bundle_tx, searcher, 10.0),
+                refund(later_tx, searcher, 20.0),
+                refund(later_tx, builder, 5.0),
+            ],
+            &[bundle_tx],
+            builder,
+            &metadata,
+        );
+
+        assert_eq!(header.refund_usd, 35.0);
+        // in bundle refunds are already in the deltas and builders pay out of the bribe
+        assert_eq!(header.profit_usd, 80.0);
+        // refunds paid after the bundle are added to its balance deltas
+        assert_eq!(header.balance_deltas.len(), 1);
+        assert_eq!(header.balance_deltas[0].tx_hash, later_tx);
+        assert_eq!(header.balance_deltas[0].address_deltas.len(), 4);
+    }
+
     #[test]
     pub fn test_multi_hop_cex_merge_swap() {
         let address0 = alloy_primitives::address!("76F36d497b51e48A288f03b4C1d7461e92247d5e");
@@ -45,6 +45,7 @@ impl InspectorBenchUtils {
             .map(|i| {
                 i.init_mev_inspector(
                     self.quote_address,
+                    &[],
                     self.classifier_inspector.libmdbx,
                     &[CexExchange::Binance],
                     CexDexTradeConfig::default(),
@@ -96,6 +97,7 @@ impl InspectorBenchUtils {
     ) -> Result<(), InspectorTestUtilsError> {
         let inspector = inspector_type.init_mev_inspector(
             self.quote_address,
+            &[],
             self.classifier_inspector.libmdbx,
             &[CexExchange::Binance],
             CexDexTradeConfig::default(),
@@ -159,6 +161,7 @@ impl InspectorBenchUtils {
     ) -> Result<(), InspectorTestUtilsError> {
         let inspector = inspector_type.init_mev_inspector(
             self.quote_address,
+            &[],
             self.classifier_inspector.libmdbx,
             &[CexExchange::Binance],
             CexDexTradeConfig::default(),
@@ -213,6 +216,7 @@ impl InspectorBenchUtils {
     ) -> Result<(), InspectorTestUtilsError> {
         let inspector = inspector.init_mev_inspector(
             self.quote_address,
+            &[],
             self.classifier_inspector.libmdbx,
             &[CexExchange::Binance],
             CexDexTradeConfig::default(),
@@ -260,6 +264,7 @@ impl InspectorBenchUtils {
             .map(|i| {
                 i.init_mev_inspector(
                     self.quote_address,
+                    &[],
                     self.classifier_inspector.libmdbx,
                     &[CexExchange::Binance],
                     CexDexTradeConfig::default(),
@@ -319,6 +324,7 @@ impl InspectorBenchUtils {
             .map(|i| {
                 i.init_mev_inspector(
                     self.quote_address,
+                    &[],
                     self.classifier_inspector.libmdbx,
                     &[CexExchange::Binance],
                     CexDexTradeConfig::default(),
@@ -159,6 +159,7 @@ impl InspectorTestUtils {
 
         let inspector = config.expected_mev_type.init_mev_inspector(
             self.quote_address,
+            &[],
             self.classifier_inspector.libmdbx,
             &[
                 CexExchange::Binance,
@@ -248,6 +249,7 @@ impl InspectorTestUtils {
 
         let inspector = config.expected_mev_type.init_mev_inspector(
             self.quote_address,
+            &[],
             self.classifier_inspector.libmdbx,
             &[
                 CexExchange::Binance,
@@ -298,6 +300,39 @@ impl InspectorTestUtils {
         Ok(())
     }
 
+    /// Runs the inspector over the whole block with dex pricing, returning
+    /// every bundle it found.
+    pub async fn inspect_block(
+        &self,
+        inspector: Inspectors,
+        block: u64,
+    ) -> Result<Vec<Bundle>, InspectorTestUtilsError> {
+        let (tree, quotes) = self.get_block_tree_with_pricing(block, vec![]).await?;
+
+        let mut metadata = self
+            .classifier_inspector
+            .get_metadata(block, false)
+            .await
+            .unwrap_or_default();
+        if metadata.dex_quotes.is_none() {
+            metadata.dex_quotes = quotes;
+        }
+
+        let inspector = inspector.init_mev_inspector(
+            self.quote_address,
+            &[],
+            self.classifier_inspector.libmdbx,
+            &[CexExchange::Binance],
+            CexDexTradeConfig::default(),
+            None,
+        );
+
+        let data = BlockData { metadata: metadata.into(), tree: tree.into() };
+        let multi = MultiBlockData { per_block_data: vec![data], blocks: 1 };
+
+        Ok(SharedInspectorUtils::<LibmdbxReadWriter>::dedup_bundles(inspector.inspect_block(multi)))
+    }
+
     pub async fn run_composer(
         &self,
         config: ComposerRunConfig,
@@ -364,6 +399,7 @@ impl InspectorTestUtils {
             .map(|i| {
                 i.init_mev_inspector(
                     self.quote_address,
+                    &[],
                     self.classifier_inspector.libmdbx,
                     &[CexExchange::Binance],
                     CexDexTradeConfig::default(),
@@ -0,0 +1,271 @@
+//! Counterfactual loss of sandwich victims.
+//!
+//! The block is replayed with the frontruns and the backrun of the sandwich
+//! left out, while every other transaction, including the ones landing between
+//! the victims, still executes. The transfers into the first pool and out of
+//! the last pool of each victim are then compared against the landed swaps,
+//! which gives the exact input the victim overpaid, or the output it missed,
+//! because of the frontruns.
+//!
+//! The replay needs direct access to the reth db, so the losses aren't part of
+//! the run pipeline. They are filled in on the stored sandwiches afterwards by
+//! `brontes db victim-loss`.
+
+use std::sync::Arc;
+
+use alloy_primitives::{Address, Log, TxHash};
+use alloy_sol_macro::sol;
+use alloy_sol_types::SolEvent;
+use brontes_database::libmdbx::LibmdbxReader;
+use brontes_types::{
+    db::{dex::PriceAt, metadata::Metadata, token_info::TokenInfoWithAddress},
+    mev::{Sandwich, VictimLossAmount},
+    traits::SimulatedTx,
+    ToFloatNearest, ToScaledRational,
+};
+use malachite::{num::basic::traits::Zero, Rational};
+
+use crate::shared_utils::SharedInspectorUtils;
+
+sol! {
+    event Transfer(address indexed from, address indexed to, uint256 value);
+}
+
+pub struct VictimLossCalculator<'db, DB: LibmdbxReader> {
+    utils: SharedInspectorUtils<'db, DB>,
+}
+
+impl<'db, DB: LibmdbxReader> VictimLossCalculator<'db, DB> {
+    pub fn new(quote: Address, db: &'db DB) -> Self {
+        Self { utils: SharedInspectorUtils::new(quote, &[], db, None) }
+    }
+
+    /// The transactions to leave out of the replay and the victims to simulate
+    /// in it. Every frontrun is left out, not only the first, as the later
+    /// ones target victims of their own.
+    pub fn replayed_txs(sandwich: &Sandwich) -> (Vec<TxHash>, Vec<TxHash>) {
+        let mut skip = sandwich.frontrun_tx_hash.clone();
+        skip.push(sandwich.backrun_tx_hash);
+
+        let victims = sandwich
+            .victim_swaps_tx_hashes
+            .iter()
+            .flatten()
+            .copied()
+            .collect();
+
+        (skip, victims)
+    }
+
+    /// Calculates the loss of every victim of the sandwich. `simulated` are
+    /// the victim transactions replayed without the sandwich, `tx_index` is
+    /// the index of the first frontrun, where the victims are priced at.
+    pub fn calculate_victim_losses(
+        &self,
+        sandwich: &Sandwich,
+        tx_index: usize,
+        simulated: &[SimulatedTx],
+        metadata: &Arc<Metadata>,
+    ) -> Vec<VictimLossAmount> {
+        sandwich
+            .victim_swaps_tx_hashes
+            .iter()
+            .flatten()
+            .zip(sandwich.victim_swaps.iter())
+            .filter_map(|(tx_hash, swaps)| {
+                let sim = simulated
+                    .iter()
+                    .find(|sim| &sim.tx_hash == tx_hash && sim.success)?;
+                let (first, last) = (swaps.first()?, swaps.last()?);
+
+                // exact input swaps move the same amount in, so only the output
+                // changes and vice versa for exact output swaps
+                let amount_in =
+                    Self::transferred(&sim.logs, &first.token_in, first.from, first.pool);
+                let amount_out =
+                    Self::transferred(&sim.logs, &last.token_out, last.pool, last.recipient);
+                if amount_in == Rational::ZERO && amount_out == Rational::ZERO {
+                    return None
+                }
+
+                // a missing transfer means the token moved as native eth, which
+                // we can't compare against
+                let extra_amount_in = Self::excess(&first.amount_in, &amount_in);
+                let lost_amount_out = Self::excess(&amount_out, &last.amount_out);
+                let amount_lost_usd =
+                    self.value(tx_index, &first.token_in, &extra_amount_in, metadata)
+                        + self.value(tx_index, &last.token_out, &lost_amount_out, metadata);
+
+                Some(VictimLossAmount {
+                    tx_hash:         *tx_hash,
+                    token_in:        first.token_in.clone(),
+                    extra_amount_in: extra_amount_in.to_float(),
+                    token_out:       last.token_out.clone(),
+                    lost_amount_out: lost_amount_out.to_float(),
+                    amount_lost_usd: amount_lost_usd.to_float(),
+                })
+            })
+            .collect()
+    }
+
+    /// Sum of the `token` transfers from `from` to `to` in the logs
+    fn transferred(
+        logs: &[Log],
+        token: &TokenInfoWithAddress,
+        from: Address,
+        to: Address,
+    ) -> Rational {
+        logs.iter()
+            .filter(|log| log.address == token.address)
+            .filter_map(|log| Transfer::decode_log_data(&log.data, false).ok())
+            .filter(|transfer| transfer.from == from && transfer.to == to)
+            .map(|transfer| transfer.value.to_scaled_rational(token.decimals))
+            .fold(Rational::ZERO, |acc, amount| acc + amount)
+    }
+
+    /// How much `larger` exceeds `smaller`, zero if it doesn't or if either
+    /// amount is missing
+    fn excess(larger: &Rational, smaller: &Rational) -> Rational {
+        if larger > smaller && smaller > &Rational::ZERO {
+            larger - smaller
+        } else {
+            Rational::ZERO
+        }
+    }
+
+    fn value(
+        &self,
+        tx_index: usize,
+        token: &TokenInfoWithAddress,
+        amount: &Rational,
+        metadata: &Arc<Metadata>,
+    ) -> Rational {
+        if amount == &Rational::ZERO {
+            return Rational::ZERO
+        }
+
+        self.utils
+            .get_token_value_dex(tx_index, PriceAt::Before, token.address, amount, metadata)
+            .unwrap_or(Rational::ZERO)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use alloy_primitives::{Address, Log, TxHash, U256};
+    use alloy_sol_types::SolEvent;
+    use brontes_core::get_db_handle;
+    use brontes_types::{
+        constants::USDT_ADDRESS,
+        db::{
+            metadata::Metadata,
+            token_info::{TokenInfo, TokenInfoWithAddress},
+        },
+        mev::Sandwich,
+        normalized_actions::NormalizedSwap,
+        traits::SimulatedTx,
+    };
+    use malachite::Rational;
+
+    use super::{Transfer, VictimLossCalculator};
+
+    fn token(byte: u8) -> TokenInfoWithAddress {
+        TokenInfoWithAddress {
+            address: Address::with_last_byte(byte),
+            inner:   TokenInfo { decimals: 0, symbol: format!("T{byte}") },
+        }
+    }
+
+    fn transfer(token: &TokenInfoWithAddress, from: Address, to: Address, value: u64) -> Log {
+        Log {
+            address: token.address,
+            data:    Transfer { from, to, value: U256::from(value) }.encode_log_data(),
+        }
+    }
+
+    #[test]
+    fn test_replayed_txs() {
+        let sandwich = Sandwich {
+            frontrun_tx_hash: vec![TxHash::with_last_byte(1), TxHash::with_last_byte(3)],
+            victim_swaps_tx_hashes: vec![
+                vec![TxHash::with_last_byte(2)],
+                vec![TxHash::with_last_byte(4), TxHash::with_last_byte(5)],
+            ],
+            backrun_tx_hash: TxHash::with_last_byte(6),
+            ..Default::default()
+        };
+
+        let (skip, victims) =
+            VictimLossCalculator::<brontes_core::LibmdbxReadWriter>::replayed_txs(&sandwich);
+        assert_eq!(
+            skip,
+            vec![TxHash::with_last_byte(1), TxHash::with_last_byte(3), TxHash::with_last_byte(6)]
+        );
+        assert_eq!(
+            victims,
+            vec![TxHash::with_last_byte(2), TxHash::with_last_byte(4), TxHash::with_last_byte(5)]
+        );
+    }
+
+    #[brontes_macros::test]
+    async fn test_calculate_victim_losses() {
+        let db = get_db_handle(tokio::runtime::Handle::current()).await;
+        let calculator = VictimLossCalculator::new(USDT_ADDRESS, db);
+        let metadata = Arc::new(Metadata::default());
+
+        let (token_in, token_out) = (token(1), token(2));
+        let (pool, victim) = (Address::with_last_byte(10), Address::with_last_byte(11));
+        let swap = |amount_in: u64, amount_out: u64| NormalizedSwap {
+            from: victim,
+            recipient: victim,
+            pool,
+            token_in: token_in.clone(),
+            token_out: token_out.clone(),
+            amount_in: Rational::from(amount_in),
+            amount_out: Rational::from(amount_out),
+            ..Default::default()
+        };
+        let simulated = |tx_hash, success, amount_in, amount_out| SimulatedTx {
+            tx_hash,
+            success,
+            gas_used: 0,
+            logs: vec![
+                transfer(&token_in, victim, pool, amount_in),
+                // transfers between other parties don't count towards the victim
+                transfer(&token_out, pool, Address::with_last_byte(12), 1_000),
+                transfer(&token_out, pool, victim, amount_out),
+            ],
+        };
+
+        let (exact_in, exact_out, reverted) =
+            (TxHash::with_last_byte(1), TxHash::with_last_byte(2), TxHash::with_last_byte(3));
+        let sandwich = Sandwich {
+            victim_swaps_tx_hashes: vec![vec![exact_in], vec![exact_out, reverted]],
+            victim_swaps: vec![vec![swap(100, 90)], vec![swap(110, 50)], vec![swap(100, 50)]],
+            ..Default::default()
+        };
+
+        let losses = calculator.calculate_victim_losses(
+            &sandwich,
+            0,
+            &[
+                simulated(exact_in, true, 100, 95),
+                simulated(exact_out, true, 100, 50),
+                simulated(reverted, false, 100, 80),
+            ],
+            &metadata,
+        );
+
+        assert_eq!(losses.len(), 2);
+        assert_eq!(losses[0].tx_hash, exact_in);
+        assert_eq!(losses[0].extra_amount_in, 0.0);
+        assert_eq!(losses[0].lost_amount_out, 5.0);
+        assert_eq!(losses[1].tx_hash, exact_out);
+        assert_eq!(losses[1].extra_amount_in, 10.0);
+        assert_eq!(losses[1].lost_amount_out, 0.0);
+        // there are no dex quotes to price the loss at
+        assert_eq!(losses[0].amount_lost_usd, 0.0);
+    }
+}
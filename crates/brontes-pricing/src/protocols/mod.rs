@@ -52,6 +52,8 @@ impl LoadState for Protocol {
                 | Self::SushiSwapV3
                 | Self::PancakeSwapV2
                 | Self::PancakeSwapV3
+                | Self::UniswapV2Fork
+                | Self::UniswapV3Fork
         )
     }
 
@@ -64,15 +66,15 @@ impl LoadState for Protocol {
         fp: PairWithFirstPoolHop,
     ) -> Result<PoolFetchSuccess, PoolFetchError> {
         match self {
-            Self::UniswapV2 | Self::SushiSwapV2 | Self::PancakeSwapV2 => {
-                let (pool, res) = if let Ok(pool) =
+            Self::UniswapV2 | Self::SushiSwapV2 | Self::PancakeSwapV2 | Self::UniswapV2Fork => {
+                let (mut pool, res) = if let Ok(pool) =
                     UniswapV2Pool::new_load_on_block(address, provider.clone(), block_number - 1)
                         .await
                 {
                     (pool, LoadResult::Ok)
                 } else {
                     (
-                        UniswapV2Pool::new_load_on_block(address, provider, block_number)
+                        UniswapV2Pool::new_load_on_block(address, provider.clone(), block_number)
                             .await
                             .map_err(|e| {
                                 debug!(?pool_pair,protocol=%self, %block_number, pool_address=?address, err=%e, "lazy load failed");
@@ -82,6 +84,14 @@ impl LoadState for Protocol {
                     )
                 };
 
+                // forks don't necessarily share the fee of the template, it's configured per
+                // factory
+                if matches!(self, Self::UniswapV2Fork) {
+                    if let Err(e) = pool.load_fork_fee(&provider, block_number).await {
+                        debug!(pool_address=?address, err=%e, "failed to load fork fee, pricing at the mid price");
+                    }
+                }
+
                 Ok((
                     block_number,
                     address,
@@ -92,7 +102,7 @@ impl LoadState for Protocol {
                     res,
                 ))
             }
-            Self::UniswapV3 | Self::SushiSwapV3 | Self::PancakeSwapV3 => {
+            Self::UniswapV3 | Self::SushiSwapV3 | Self::PancakeSwapV3 | Self::UniswapV3Fork => {
                 let (pool, res) = if let Ok(pool) =
                     UniswapV3Pool::new_from_address(address, block_number - 1, provider.clone())
                         .await
@@ -8,7 +8,10 @@ use alloy_rlp::{RlpDecodable, RlpEncodable};
 use alloy_sol_macro::sol;
 use alloy_sol_types::SolEvent;
 use async_trait::async_trait;
-use brontes_types::{normalized_actions::Action, traits::TracingProvider, ToScaledRational};
+use brontes_types::{
+    normalized_actions::Action, queries::make_call_request, traits::TracingProvider,
+    uniswap_forks::UniswapForkConfig, ToScaledRational,
+};
 use malachite::{
     num::{arithmetic::traits::Pow, basic::traits::Zero},
     Natural, Rational,
@@ -31,6 +34,7 @@ sol!(
         );
         function token0() external view returns (address);
         function token1() external view returns (address);
+        function factory() external view returns (address);
         function swap(uint256 amount0Out, uint256 amount1Out, address to, bytes calldata data);
         event Sync(uint112 reserve0, uint112 reserve1);
     }
@@ -59,6 +63,7 @@ pub struct UniswapV2Pool {
     pub token_b_decimals: u8,
     pub reserve_0:        u128,
     pub reserve_1:        u128,
+    /// Fee charged on the amount in, in bps
     pub fee:              u32,
 }
 
@@ -90,7 +95,14 @@ impl UpdatableProtocol for UniswapV2Pool {
     //Calculates base/quote, meaning the price of base token per quote (ie.
     // exchange rate is X base per 1 quote)
     fn calculate_price(&self, base_token: Address) -> Result<Rational, ArithmeticError> {
-        self.calculate_price_64_x_64(base_token)
+        let price = self.calculate_price_64_x_64(base_token)?;
+        if self.fee == 0 {
+            return Ok(price)
+        }
+
+        // what selling the base token at the margin yields once the fee is taken
+        Ok(price
+            * Rational::from_naturals(Natural::from(10_000 - self.fee), Natural::from(10_000u32)))
     }
 
     fn tokens(&self) -> Vec<Address> {
@@ -186,6 +198,29 @@ impl UniswapV2Pool {
         self.fee
     }
 
+    /// Sets the fee of a fork pair to the one configured for the factory that
+    /// deployed it. Pairs of forks that aren't configured keep a zero fee and
+    /// are priced at the mid price, like the pairs of the template
+    pub async fn load_fork_fee<M: TracingProvider>(
+        &mut self,
+        middleware: &Arc<M>,
+        block: u64,
+    ) -> Result<(), AmmError> {
+        let factory = make_call_request(
+            IUniswapV2Pair::factoryCall {},
+            middleware,
+            self.address,
+            Some(block),
+        )
+        .await?
+        ._0;
+        if let Some(fork) = UniswapForkConfig::get().fork(&factory) {
+            self.fee = fork.fee_bps;
+        }
+
+        Ok(())
+    }
+
     pub fn data_is_populated(&self) -> bool {
         !(self.token_a.is_zero()
             || self.token_b.is_zero()
@@ -233,3 +268,40 @@ impl UniswapV2Pool {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pool(fee: u32) -> UniswapV2Pool {
+        UniswapV2Pool::new(
+            Address::with_last_byte(1),
+            Address::with_last_byte(2),
+            18,
+            Address::with_last_byte(3),
+            18,
+            1_000_000,
+            2_000_000,
+            fee,
+        )
+    }
+
+    #[test]
+    fn test_calculate_price_takes_the_fork_fee() {
+        let token_a = Address::with_last_byte(2);
+        let token_b = Address::with_last_byte(3);
+
+        // 2_000_000 / 1_000_000 at the mid price
+        assert_eq!(pool(0).calculate_price(token_a).unwrap(), Rational::from(2));
+        // 2 * (1 - 0.0025)
+        assert_eq!(
+            pool(25).calculate_price(token_a).unwrap(),
+            Rational::from_naturals(Natural::from(1995u32), Natural::from(1000u32))
+        );
+        // 0.5 * (1 - 0.0025)
+        assert_eq!(
+            pool(25).calculate_price(token_b).unwrap(),
+            Rational::from_naturals(Natural::from(3990u32), Natural::from(8000u32))
+        );
+    }
+}
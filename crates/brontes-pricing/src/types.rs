@@ -202,6 +202,8 @@ impl PoolUpdate {
             || self.action.is_batch()
             || self.action.is_aggregator()
             || self.action.is_eth_transfer()
+            || self.action.is_nft_transfer()
+            || self.action.is_nft_trade()
         {
             return None
         }
@@ -1,6 +1,7 @@
 use alloy_primitives::{hex, Address};
 
 pub const USDT_ADDRESS_STRING: &str = "0xdAC17F958D2ee523a2206206994597C13D831ec7";
+pub const WETH_ADDRESS_STRING: &str = "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2";
 
 pub const ETH_ADDRESS: Address = Address::new(hex!("EeeeeEeeeEeEeeEeEeEeeEEEeeeeEeeeeeeeEEeE"));
 pub const DOLA_ADDRESS: Address = Address::new(hex!("865377367054516e17014ccded1e7d814edc9ce4"));
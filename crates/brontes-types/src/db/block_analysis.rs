@@ -3,12 +3,19 @@ use crate::serde_utils::address;
 use alloy_primitives::Address;
 use clickhouse::Row;
 use itertools::Itertools;
+use redefined::Redefined;
 use reth_primitives::TxHash;
+use rkyv::{Archive, Deserialize as rDeserialize, Serialize as rSerialize};
 use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
 use crate::serde_utils::vec_address;
 use crate::{
-    db::{searcher::Fund, token_info::TokenInfoWithAddress},
+    db::{
+        redefined_types::primitives::{AddressRedefined, TxHashRedefined},
+        searcher::Fund,
+        token_info::TokenInfoWithAddress,
+    },
+    implement_table_value_codecs_with_zc,
     mev::{Bundle, BundleData, Mev, MevBlock, MevType},
     pair::Pair,
     serde_utils::{
@@ -18,7 +25,8 @@ use crate::{
 };
 
 #[serde_as]
-#[derive(Debug, Clone, Serialize, Deserialize, Row)]
+#[derive(Debug, Clone, Serialize, Deserialize, Row, Redefined)]
+#[redefined_attr(derive(Debug, PartialEq, Clone, Serialize, rSerialize, rDeserialize, Archive))]
 pub struct BlockAnalysis {
     pub block_number: u64,
     pub eth_price: f64,
@@ -35,9 +43,11 @@ pub struct BlockAnalysis {
     pub all_top_searcher_revenue_amt: Option<f64>,
     pub all_searcher_count: u64,
     #[serde(with = "option_fund")]
+    #[redefined(same_fields)]
     pub all_top_fund_profit: Option<Fund>,
     pub all_top_fund_profit_amt: Option<f64>,
     #[serde(with = "option_fund")]
+    #[redefined(same_fields)]
     pub all_top_fund_revenue: Option<Fund>,
     pub all_top_fund_revenue_amt: Option<f64>,
     pub all_fund_count: u64,
@@ -52,9 +62,11 @@ pub struct BlockAnalysis {
     pub all_most_arbed_pair_revenue: TokenPairDetails,
     pub all_most_arbed_pair_revenue_amt: Option<f64>,
     #[serde(with = "option_protocol")]
+    #[redefined(same_fields)]
     pub all_most_arbed_dex_profit: Option<Protocol>,
     pub all_most_arbed_dex_profit_amt: Option<f64>,
     #[serde(with = "option_protocol")]
+    #[redefined(same_fields)]
     pub all_most_arbed_dex_revenue: Option<Protocol>,
     pub all_most_arbed_dex_revenue_amt: Option<f64>,
     #[serde(with = "option_txhash")]
@@ -98,18 +110,22 @@ pub struct BlockAnalysis {
     pub atomic_mev_contract_all_revenue_amt: Vec<f64>,
     pub atomic_mev_contract_count:           u64,
     #[serde(with = "option_fund")]
+    #[redefined(same_fields)]
     pub atomic_top_fund_profit:              Option<Fund>,
     pub atomic_top_fund_profit_amt:          Option<f64>,
     #[serde(with = "option_fund")]
+    #[redefined(same_fields)]
     pub atomic_top_fund_revenue:             Option<Fund>,
     pub atomic_top_fund_revenue_amt:         Option<f64>,
     #[serde(rename = "atomic_fund_all.profit")]
     #[serde(with = "vec_fund")]
+    #[redefined(same_fields)]
     pub atomic_fund_all_profit:              Vec<Fund>,
     #[serde(rename = "atomic_fund_all.profit_amt")]
     pub atomic_fund_all_profit_amt:          Vec<f64>,
     #[serde(rename = "atomic_fund_all.revenue")]
     #[serde(with = "vec_fund")]
+    #[redefined(same_fields)]
     pub atomic_fund_all_revenue:             Vec<Fund>,
     #[serde(rename = "atomic_fund_all.revenue_amt")]
     pub atomic_fund_all_revenue_amt:         Vec<f64>,
@@ -125,9 +141,11 @@ pub struct BlockAnalysis {
     pub atomic_most_arbed_pair_revenue:      TokenPairDetails,
     pub atomic_most_arbed_pair_revenue_amt:  Option<f64>,
     #[serde(with = "option_protocol")]
+    #[redefined(same_fields)]
     pub atomic_most_arbed_dex_profit:        Option<Protocol>,
     pub atomic_most_arbed_dex_profit_amt:    Option<f64>,
     #[serde(with = "option_protocol")]
+    #[redefined(same_fields)]
     pub atomic_most_arbed_dex_revenue:       Option<Protocol>,
     pub atomic_most_arbed_dex_revenue_amt:   Option<f64>,
     #[serde(with = "vec_address")]
@@ -150,11 +168,13 @@ pub struct BlockAnalysis {
     pub atomic_arbed_pair_all_revenue_amt:   Vec<f64>,
     #[serde(rename = "atomic_arbed_dex_all.profit")]
     #[serde(with = "vec_protocol")]
+    #[redefined(same_fields)]
     pub atomic_arbed_dex_all_profit:         Vec<Protocol>,
     #[serde(rename = "atomic_arbed_dex_all.profit_amt")]
     pub atomic_arbed_dex_all_profit_amt:     Vec<f64>,
     #[serde(rename = "atomic_arbed_dex_all.revenue")]
     #[serde(with = "vec_protocol")]
+    #[redefined(same_fields)]
     pub atomic_arbed_dex_all_revenue:        Vec<Protocol>,
     #[serde(rename = "atomic_arbed_dex_all.revenue_amt")]
     pub atomic_arbed_dex_all_revenue_amt:    Vec<f64>,
@@ -199,18 +219,22 @@ pub struct BlockAnalysis {
     pub sandwich_mev_contract_all_revenue_amt: Vec<f64>,
     pub sandwich_mev_contract_count:           u64,
     #[serde(with = "option_fund")]
+    #[redefined(same_fields)]
     pub sandwich_top_fund_profit:              Option<Fund>,
     pub sandwich_top_fund_profit_amt:          Option<f64>,
     #[serde(with = "option_fund")]
+    #[redefined(same_fields)]
     pub sandwich_top_fund_revenue:             Option<Fund>,
     pub sandwich_top_fund_revenue_amt:         Option<f64>,
     #[serde(rename = "sandwich_fund_all.profit")]
     #[serde(with = "vec_fund")]
+    #[redefined(same_fields)]
     pub sandwich_fund_all_profit:              Vec<Fund>,
     #[serde(rename = "sandwich_fund_all.profit_amt")]
     pub sandwich_fund_all_profit_amt:          Vec<f64>,
     #[serde(rename = "sandwich_fund_all.revenue")]
     #[serde(with = "vec_fund")]
+    #[redefined(same_fields)]
     pub sandwich_fund_all_revenue:             Vec<Fund>,
     #[serde(rename = "sandwich_fund_all.revenue_amt")]
     pub sandwich_fund_all_revenue_amt:         Vec<f64>,
@@ -226,9 +250,11 @@ pub struct BlockAnalysis {
     pub sandwich_most_arbed_pair_revenue:      TokenPairDetails,
     pub sandwich_most_arbed_pair_revenue_amt:  Option<f64>,
     #[serde(with = "option_protocol")]
+    #[redefined(same_fields)]
     pub sandwich_most_arbed_dex_profit:        Option<Protocol>,
     pub sandwich_most_arbed_dex_profit_amt:    Option<f64>,
     #[serde(with = "option_protocol")]
+    #[redefined(same_fields)]
     pub sandwich_most_arbed_dex_revenue:       Option<Protocol>,
     pub sandwich_most_arbed_dex_revenue_amt:   Option<f64>,
     #[serde(with = "vec_address")]
@@ -251,11 +277,13 @@ pub struct BlockAnalysis {
     pub sandwich_arbed_pair_all_revenue_amt:   Vec<f64>,
     #[serde(rename = "sandwich_arbed_dex_all.profit")]
     #[serde(with = "vec_protocol")]
+    #[redefined(same_fields)]
     pub sandwich_arbed_dex_all_profit:         Vec<Protocol>,
     #[serde(rename = "sandwich_arbed_dex_all.profit_amt")]
     pub sandwich_arbed_dex_all_profit_amt:     Vec<f64>,
     #[serde(rename = "sandwich_arbed_dex_all.revenue")]
     #[serde(with = "vec_protocol")]
+    #[redefined(same_fields)]
     pub sandwich_arbed_dex_all_revenue:        Vec<Protocol>,
     #[serde(rename = "sandwich_arbed_dex_all.revenue_amt")]
     pub sandwich_arbed_dex_all_revenue_amt:    Vec<f64>,
@@ -300,18 +328,22 @@ pub struct BlockAnalysis {
     pub jit_mev_contract_all_revenue_amt: Vec<f64>,
     pub jit_mev_contract_count:           u64,
     #[serde(with = "option_fund")]
+    #[redefined(same_fields)]
     pub jit_top_fund_profit:              Option<Fund>,
     pub jit_top_fund_profit_amt:          Option<f64>,
     #[serde(with = "option_fund")]
+    #[redefined(same_fields)]
     pub jit_top_fund_revenue:             Option<Fund>,
     pub jit_top_fund_revenue_amt:         Option<f64>,
     #[serde(rename = "jit_fund_all.profit")]
     #[serde(with = "vec_fund")]
+    #[redefined(same_fields)]
     pub jit_fund_all_profit:              Vec<Fund>,
     #[serde(rename = "jit_fund_all.profit_amt")]
     pub jit_fund_all_profit_amt:          Vec<f64>,
     #[serde(rename = "jit_fund_all.revenue")]
     #[serde(with = "vec_fund")]
+    #[redefined(same_fields)]
     pub jit_fund_all_revenue:             Vec<Fund>,
     #[serde(rename = "jit_fund_all.revenue_amt")]
     pub jit_fund_all_revenue_amt:         Vec<f64>,
@@ -327,9 +359,11 @@ pub struct BlockAnalysis {
     pub jit_most_arbed_pair_revenue:      TokenPairDetails,
     pub jit_most_arbed_pair_revenue_amt:  Option<f64>,
     #[serde(with = "option_protocol")]
+    #[redefined(same_fields)]
     pub jit_most_arbed_dex_profit:        Option<Protocol>,
     pub jit_most_arbed_dex_profit_amt:    Option<f64>,
     #[serde(with = "option_protocol")]
+    #[redefined(same_fields)]
     pub jit_most_arbed_dex_revenue:       Option<Protocol>,
     pub jit_most_arbed_dex_revenue_amt:   Option<f64>,
     #[serde(with = "vec_address")]
@@ -352,11 +386,13 @@ pub struct BlockAnalysis {
     pub jit_arbed_pair_all_revenue_amt:   Vec<f64>,
     #[serde(rename = "jit_arbed_dex_all.profit")]
     #[serde(with = "vec_protocol")]
+    #[redefined(same_fields)]
     pub jit_arbed_dex_all_profit:         Vec<Protocol>,
     #[serde(rename = "jit_arbed_dex_all.profit_amt")]
     pub jit_arbed_dex_all_profit_amt:     Vec<f64>,
     #[serde(rename = "jit_arbed_dex_all.revenue")]
     #[serde(with = "vec_protocol")]
+    #[redefined(same_fields)]
     pub jit_arbed_dex_all_revenue:        Vec<Protocol>,
     #[serde(rename = "jit_arbed_dex_all.revenue_amt")]
     pub jit_arbed_dex_all_revenue_amt:    Vec<f64>,
@@ -401,18 +437,22 @@ pub struct BlockAnalysis {
     pub jit_sandwich_mev_contract_all_revenue_amt: Vec<f64>,
     pub jit_sandwich_mev_contract_count:           u64,
     #[serde(with = "option_fund")]
+    #[redefined(same_fields)]
     pub jit_sandwich_top_fund_profit:              Option<Fund>,
     pub jit_sandwich_top_fund_profit_amt:          Option<f64>,
     #[serde(with = "option_fund")]
+    #[redefined(same_fields)]
     pub jit_sandwich_top_fund_revenue:             Option<Fund>,
     pub jit_sandwich_top_fund_revenue_amt:         Option<f64>,
     #[serde(rename = "jit_sandwich_fund_all.profit")]
     #[serde(with = "vec_fund")]
+    #[redefined(same_fields)]
     pub jit_sandwich_fund_all_profit:              Vec<Fund>,
     #[serde(rename = "jit_sandwich_fund_all.profit_amt")]
     pub jit_sandwich_fund_all_profit_amt:          Vec<f64>,
     #[serde(rename = "jit_sandwich_fund_all.revenue")]
     #[serde(with = "vec_fund")]
+    #[redefined(same_fields)]
     pub jit_sandwich_fund_all_revenue:             Vec<Fund>,
     #[serde(rename = "jit_sandwich_fund_all.revenue_amt")]
     pub jit_sandwich_fund_all_revenue_amt:         Vec<f64>,
@@ -428,9 +468,11 @@ pub struct BlockAnalysis {
     pub jit_sandwich_most_arbed_pair_revenue:      TokenPairDetails,
     pub jit_sandwich_most_arbed_pair_revenue_amt:  Option<f64>,
     #[serde(with = "option_protocol")]
+    #[redefined(same_fields)]
     pub jit_sandwich_most_arbed_dex_profit:        Option<Protocol>,
     pub jit_sandwich_most_arbed_dex_profit_amt:    Option<f64>,
     #[serde(with = "option_protocol")]
+    #[redefined(same_fields)]
     pub jit_sandwich_most_arbed_dex_revenue:       Option<Protocol>,
     pub jit_sandwich_most_arbed_dex_revenue_amt:   Option<f64>,
     #[serde(with = "vec_address")]
@@ -453,11 +495,13 @@ pub struct BlockAnalysis {
     pub jit_sandwich_arbed_pair_all_revenue_amt:   Vec<f64>,
     #[serde(rename = "jit_sandwich_arbed_dex_all.profit")]
     #[serde(with = "vec_protocol")]
+    #[redefined(same_fields)]
     pub jit_sandwich_arbed_dex_all_profit:         Vec<Protocol>,
     #[serde(rename = "jit_sandwich_arbed_dex_all.profit_amt")]
     pub jit_sandwich_arbed_dex_all_profit_amt:     Vec<f64>,
     #[serde(rename = "jit_sandwich_arbed_dex_all.revenue")]
     #[serde(with = "vec_protocol")]
+    #[redefined(same_fields)]
     pub jit_sandwich_arbed_dex_all_revenue:        Vec<Protocol>,
     #[serde(rename = "jit_sandwich_arbed_dex_all.revenue_amt")]
     pub jit_sandwich_arbed_dex_all_revenue_amt:    Vec<f64>,
@@ -502,18 +546,22 @@ pub struct BlockAnalysis {
     pub cex_dex_mev_contract_all_revenue_amt: Vec<f64>,
     pub cex_dex_mev_contract_count:           u64,
     #[serde(with = "option_fund")]
+    #[redefined(same_fields)]
     pub cex_dex_top_fund_profit:              Option<Fund>,
     pub cex_dex_top_fund_profit_amt:          Option<f64>,
     #[serde(with = "option_fund")]
+    #[redefined(same_fields)]
     pub cex_dex_top_fund_revenue:             Option<Fund>,
     pub cex_dex_top_fund_revenue_amt:         Option<f64>,
     #[serde(rename = "cex_dex_fund_all.profit")]
     #[serde(with = "vec_fund")]
+    #[redefined(same_fields)]
     pub cex_dex_fund_all_profit:              Vec<Fund>,
     #[serde(rename = "cex_dex_fund_all.profit_amt")]
     pub cex_dex_fund_all_profit_amt:          Vec<f64>,
     #[serde(rename = "cex_dex_fund_all.revenue")]
     #[serde(with = "vec_fund")]
+    #[redefined(same_fields)]
     pub cex_dex_fund_all_revenue:             Vec<Fund>,
     #[serde(rename = "cex_dex_fund_all.revenue_amt")]
     pub cex_dex_fund_all_revenue_amt:         Vec<f64>,
@@ -529,9 +577,11 @@ pub struct BlockAnalysis {
     pub cex_dex_most_arbed_pair_revenue:      TokenPairDetails,
     pub cex_dex_most_arbed_pair_revenue_amt:  Option<f64>,
     #[serde(with = "option_protocol")]
+    #[redefined(same_fields)]
     pub cex_dex_most_arbed_dex_profit:        Option<Protocol>,
     pub cex_dex_most_arbed_dex_profit_amt:    Option<f64>,
     #[serde(with = "option_protocol")]
+    #[redefined(same_fields)]
     pub cex_dex_most_arbed_dex_revenue:       Option<Protocol>,
     pub cex_dex_most_arbed_dex_revenue_amt:   Option<f64>,
     #[serde(with = "vec_address")]
@@ -554,11 +604,13 @@ pub struct BlockAnalysis {
     pub cex_dex_arbed_pair_all_revenue_amt:   Vec<f64>,
     #[serde(rename = "cex_dex_arbed_dex_all.profit")]
     #[serde(with = "vec_protocol")]
+    #[redefined(same_fields)]
     pub cex_dex_arbed_dex_all_profit:         Vec<Protocol>,
     #[serde(rename = "cex_dex_arbed_dex_all.profit_amt")]
     pub cex_dex_arbed_dex_all_profit_amt:     Vec<f64>,
     #[serde(rename = "cex_dex_arbed_dex_all.revenue")]
     #[serde(with = "vec_protocol")]
+    #[redefined(same_fields)]
     pub cex_dex_arbed_dex_all_revenue:        Vec<Protocol>,
     #[serde(rename = "cex_dex_arbed_dex_all.revenue_amt")]
     pub cex_dex_arbed_dex_all_revenue_amt:    Vec<f64>,
@@ -603,18 +655,22 @@ pub struct BlockAnalysis {
     pub liquidation_mev_contract_all_revenue_amt: Vec<f64>,
     pub liquidation_mev_contract_count:           u64,
     #[serde(with = "option_fund")]
+    #[redefined(same_fields)]
     pub liquidation_top_fund_profit:              Option<Fund>,
     pub liquidation_top_fund_profit_amt:          Option<f64>,
     #[serde(with = "option_fund")]
+    #[redefined(same_fields)]
     pub liquidation_top_fund_revenue:             Option<Fund>,
     pub liquidation_top_fund_revenue_amt:         Option<f64>,
     #[serde(rename = "liquidation_fund_all.profit")]
     #[serde(with = "vec_fund")]
+    #[redefined(same_fields)]
     pub liquidation_fund_all_profit:              Vec<Fund>,
     #[serde(rename = "liquidation_fund_all.profit_amt")]
     pub liquidation_fund_all_profit_amt:          Vec<f64>,
     #[serde(rename = "liquidation_fund_all.revenue")]
     #[serde(with = "vec_fund")]
+    #[redefined(same_fields)]
     pub liquidation_fund_all_revenue:             Vec<Fund>,
     #[serde(rename = "liquidation_fund_all.revenue_amt")]
     pub liquidation_fund_all_revenue_amt:         Vec<f64>,
@@ -653,6 +709,8 @@ pub struct BlockAnalysis {
     pub proposer_profit_eth:    Option<f64>,
 }
 
+implement_table_value_codecs_with_zc!(BlockAnalysisRedefined);
+
 impl BlockAnalysis {
     #[rustfmt::skip]
     pub fn new(block: &MevBlock, bundles: &[Bundle]) -> Self {
@@ -2183,7 +2241,8 @@ impl<A, B, C, D> TupleTwoVecUnzip<A, B, C, D> for (Vec<(A, B)>, Vec<(C, D)>) {
     }
 }
 
-#[derive(Default, Debug, Clone, Hash, PartialEq, Eq)]
+#[derive(Default, Debug, Clone, Hash, PartialEq, Eq, Redefined)]
+#[redefined_attr(derive(Debug, PartialEq, Clone, Serialize, rSerialize, rDeserialize, Archive))]
 pub struct TokenPairDetails {
     pub address0: Address,
     pub symbol0:  String,
@@ -2240,7 +2299,8 @@ impl From<(TokenInfoWithAddress, TokenInfoWithAddress)> for TokenPairDetails {
     }
 }
 
-#[derive(Default, Debug, Clone, Hash, PartialEq, Eq)]
+#[derive(Default, Debug, Clone, Hash, PartialEq, Eq, Redefined)]
+#[redefined_attr(derive(Debug, PartialEq, Clone, Serialize, rSerialize, rDeserialize, Archive))]
 pub struct SingleTokenDetails {
     pub address: Address,
     pub symbol:  String,
@@ -6,7 +6,8 @@ use redefined::self_convert_redefined;
 use serde::Deserialize;
 use strum::Display;
 
-use crate::constants::*;
+use super::CexFeeSchedule;
+use crate::{constants::*, pair::Pair};
 
 #[derive(
     Copy,
@@ -43,6 +44,14 @@ pub enum CexExchange {
     OptimisticVWAP,
     #[default]
     Unknown,
+    // stored rows hold the archived discriminant of the exchange, new venues
+    // have to be appended so that they keep decoding to the same exchange
+    /// Binance USDⓈ-M perpetual futures
+    BinanceUsdm,
+    /// Bybit linear (USDT / USDC margined) perpetual futures
+    BybitLinear,
+    /// OKX perpetual swaps
+    OkexSwap,
 }
 
 self_convert_redefined!(CexExchange);
@@ -77,24 +86,36 @@ impl CexExchange {
             CexExchange::Average => "c.exchange = ''",
             CexExchange::VWAP => "c.exchange = ''",
             CexExchange::OptimisticVWAP => "c.exchange = ''",
+            CexExchange::BinanceUsdm => "c.exchange = 'binance-futures'",
+            CexExchange::BybitLinear => "c.exchange = 'bybit-linear'",
+            CexExchange::OkexSwap => "c.exchange = 'okex-swap'",
         }
     }
 }
 
+/// Maps the exchange names of the cex database. `binance-futures` and
+/// `okex-swap` used to map to their spot exchange, they now map to the
+/// perpetual venues. This only applies to newly downloaded data: the symbols
+/// and the most liquid venues per pair are resolved on download, and the
+/// trades and quotes are only fetched for the spot exchange names, so rows
+/// stored before keep their spot exchange.
 impl From<&str> for CexExchange {
     fn from(value: &str) -> Self {
         let val = value.to_lowercase();
         let value = val.as_str();
         match value {
-            "binance" | "binance-futures" => CexExchange::Binance,
+            "binance" => CexExchange::Binance,
+            "binance-futures" | "binanceusdm" | "binance-usdm" => CexExchange::BinanceUsdm,
             "bitmex" | "Bitmex" => CexExchange::Bitmex,
             "deribit" | "Deribit" => CexExchange::Deribit,
-            "okex" | "Okex" | "okex-swap" => CexExchange::Okex,
+            "okex" | "Okex" => CexExchange::Okex,
+            "okex-swap" | "okexswap" | "okx-swap" => CexExchange::OkexSwap,
             "coinbase" | "Coinbase" => CexExchange::Coinbase,
             "kraken" | "Kraken" => CexExchange::Kraken,
             "bybit-spot" | "bybitspot" | "BybitSpot" | "Bybit-Spot" | "Bybit_Spot" | "bybit" => {
                 CexExchange::BybitSpot
             }
+            "bybit-linear" | "bybitlinear" => CexExchange::BybitLinear,
             "kucoin" | "Kucoin" => CexExchange::Kucoin,
             "upbit" | "Upbit" => CexExchange::Upbit,
             "huobi" | "Huobi" => CexExchange::Huobi,
@@ -205,18 +226,26 @@ impl CexExchange {
             CexExchange::Upbit => {
                 vec![WETH_ADDRESS, WBTC_ADDRESS, LINK_ADDRESS, EURT_ADDRESS, UNI_TOKEN]
             }
+            CexExchange::BinanceUsdm | CexExchange::BybitLinear => vec![USDT_ADDRESS, USDC_ADDRESS],
+            CexExchange::OkexSwap => vec![USDT_ADDRESS, USDC_ADDRESS, WETH_ADDRESS, WBTC_ADDRESS],
 
             _ => vec![],
         }
     }
 
-    /// Returns the maker & taker fees by exchange
+    /// Returns the maker & taker fees of the pair on this exchange. Fees
+    /// configured in the [`CexFeeSchedule`] take precedence over the default
+    /// fees of [`CexExchange::fees`]
+    pub fn fees_for_pair(&self, pair: &Pair) -> (Rational, Rational) {
+        CexFeeSchedule::get()
+            .fees(self, pair)
+            .unwrap_or_else(|| self.fees())
+    }
+
+    /// Returns the default maker & taker fees by exchange
     /// Assumes best possible fee structure e.g Binanace VIP 9 for example
     /// Does not account for special market maker rebate programs or special
-    /// pairs
-    ///
-    /// TODO: Account for special fee pairs & stableswap rates
-    /// TODO: Account for futures & spot fee deltas
+    /// pairs, these can be configured through the [`CexFeeSchedule`]
     pub fn fees(&self) -> (Rational, Rational) {
         match self {
             CexExchange::Binance => (
@@ -270,6 +299,18 @@ impl CexExchange {
                 Rational::from_sci_string("0").unwrap(),
                 Rational::from_sci_string("0.0003").unwrap(),
             ),
+            CexExchange::BinanceUsdm => (
+                Rational::from_sci_string("0").unwrap(),
+                Rational::from_sci_string("0.00017").unwrap(),
+            ),
+            CexExchange::BybitLinear => (
+                Rational::from_sci_string("0").unwrap(),
+                Rational::from_sci_string("0.0003").unwrap(),
+            ),
+            CexExchange::OkexSwap => (
+                Rational::from_sci_string("-0.00005").unwrap(),
+                Rational::from_sci_string("0.0002").unwrap(),
+            ),
             CexExchange::Average => {
                 unreachable!("Cannot get fees for cross exchange average quote")
             }
@@ -0,0 +1,231 @@
+//! Maker & taker fee schedules declared in `config/cex_fee_config.toml`.
+//!
+//! Exchanges pick one of their configured tiers and can override the fees of
+//! single pairs, e.g. zero fee stable pairs. Anything that isn't configured
+//! falls back onto the best tier fees of [`CexExchange::fees`].
+use std::{
+    path::{Path, PathBuf},
+    sync::OnceLock,
+};
+
+use alloy_primitives::Address;
+use malachite::{num::conversion::traits::FromSciString, Rational};
+use serde::Deserialize;
+use tracing::error;
+
+use super::CexExchange;
+use crate::{pair::Pair, FastHashMap};
+
+const CEX_FEE_CONFIG_FILE: &str = "config/cex_fee_config.toml";
+
+/// Env var pointing at the fee config file, overrides the config file of the
+/// current directory
+pub const CEX_FEE_CONFIG_ENV: &str = "BRONTES_CEX_FEE_CONFIG";
+
+static CEX_FEE_SCHEDULE: OnceLock<CexFeeSchedule> = OnceLock::new();
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FeeRates {
+    pub maker: Rational,
+    pub taker: Rational,
+}
+
+#[derive(Debug, Default)]
+struct ExchangeFees {
+    tier:  Option<FeeRates>,
+    pairs: FastHashMap<Pair, FeeRates>,
+}
+
+#[derive(Debug, Default)]
+pub struct CexFeeSchedule {
+    exchanges: FastHashMap<CexExchange, ExchangeFees>,
+}
+
+impl CexFeeSchedule {
+    /// Loads the fee schedule from the given file. Has to be called before the
+    /// first [`CexFeeSchedule::get`] to take effect.
+    pub fn init(config_path: &Path) -> eyre::Result<()> {
+        CEX_FEE_SCHEDULE
+            .set(Self::load(config_path)?)
+            .map_err(|_| eyre::eyre!("cex fee schedule is already loaded"))
+    }
+
+    /// The fee schedule set through [`CexFeeSchedule::init`], otherwise loaded
+    /// from `$BRONTES_CEX_FEE_CONFIG` or `config/cex_fee_config.toml` of the
+    /// current directory. Failing to load the file logs an error and uses the
+    /// default fees for every exchange.
+    pub fn get() -> &'static Self {
+        CEX_FEE_SCHEDULE.get_or_init(|| {
+            let config_path = std::env::var_os(CEX_FEE_CONFIG_ENV)
+                .map(PathBuf::from)
+                .unwrap_or_else(|| PathBuf::from(CEX_FEE_CONFIG_FILE));
+
+            Self::load(&config_path).unwrap_or_else(|e| {
+                error!(
+                    target: "brontes_types::cex", error=%e, path=?config_path,
+                    "failed to load cex fee config"
+                );
+                Self::default()
+            })
+        })
+    }
+
+    pub fn load(config_path: &Path) -> eyre::Result<Self> {
+        Self::from_toml(&std::fs::read_to_string(config_path)?)
+    }
+
+    pub fn from_toml(config: &str) -> eyre::Result<Self> {
+        let config: FastHashMap<String, ExchangeFeeConfig> = toml::from_str(config)?;
+
+        let exchanges = config
+            .into_iter()
+            .map(|(exchange_name, config)| {
+                let exchange = CexExchange::from(exchange_name.as_str());
+                if exchange == CexExchange::Unknown {
+                    eyre::bail!("unknown exchange {exchange_name} in cex fee config")
+                }
+
+                let tier = config
+                    .tier
+                    .as_ref()
+                    .map(|tier| {
+                        config.tiers.get(tier).ok_or_else(|| {
+                            eyre::eyre!("tier {tier} of {exchange_name} has no fees configured")
+                        })
+                    })
+                    .transpose()?
+                    .map(FeeRatesConfig::parse)
+                    .transpose()?;
+
+                let mut pairs = FastHashMap::default();
+                for pair_config in config.pairs {
+                    // pair overrides that are specific to another tier don't apply
+                    if pair_config.tier.is_some() && pair_config.tier != config.tier {
+                        continue
+                    }
+
+                    let pair = Pair(pair_config.tokens[0], pair_config.tokens[1]);
+                    let rates = pair_config.fees.parse()?;
+                    pairs.insert(pair.flip(), rates.clone());
+                    pairs.insert(pair, rates);
+                }
+
+                Ok((exchange, ExchangeFees { tier, pairs }))
+            })
+            .collect::<eyre::Result<_>>()?;
+
+        Ok(Self { exchanges })
+    }
+
+    /// The configured maker & taker fees of the pair on the exchange, pair
+    /// overrides take precedence over the tier of the exchange
+    pub fn fees(&self, exchange: &CexExchange, pair: &Pair) -> Option<(Rational, Rational)> {
+        let fees = self.exchanges.get(exchange)?;
+
+        fees.pairs
+            .get(pair)
+            .or(fees.tier.as_ref())
+            .map(|rates| (rates.maker.clone(), rates.taker.clone()))
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ExchangeFeeConfig {
+    /// The tier of `tiers` the exchange is charged at
+    tier:  Option<String>,
+    #[serde(default)]
+    tiers: FastHashMap<String, FeeRatesConfig>,
+    #[serde(default)]
+    pairs: Vec<PairFeeConfig>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PairFeeConfig {
+    tokens: [Address; 2],
+    /// Only apply the override when the exchange is charged at this tier
+    tier:   Option<String>,
+    #[serde(flatten)]
+    fees:   FeeRatesConfig,
+}
+
+/// Fees are strings so they can be parsed into exact rationals
+#[derive(Debug, Deserialize)]
+struct FeeRatesConfig {
+    maker: String,
+    taker: String,
+}
+
+impl FeeRatesConfig {
+    fn parse(&self) -> eyre::Result<FeeRates> {
+        let parse = |fee: &str| {
+            Rational::from_sci_string(fee).ok_or_else(|| eyre::eyre!("invalid fee {fee}"))
+        };
+
+        Ok(FeeRates { maker: parse(&self.maker)?, taker: parse(&self.taker)? })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::{USDC_ADDRESS, USDT_ADDRESS, WETH_ADDRESS};
+
+    #[test]
+    fn test_load_fee_schedule() {
+        let config = format!(
+            r#"
+            [Binance]
+            tier = "vip1"
+
+            [Binance.tiers.vip1]
+            maker = "0.0009"
+            taker = "0.001"
+
+            [[Binance.pairs]]
+            tokens = ["{USDC_ADDRESS:?}", "{USDT_ADDRESS:?}"]
+            maker = "0"
+            taker = "0"
+
+            [[Binance.pairs]]
+            tokens = ["{WETH_ADDRESS:?}", "{USDT_ADDRESS:?}"]
+            tier = "vip9"
+            maker = "0"
+            taker = "0"
+            "#
+        );
+        let schedule = CexFeeSchedule::from_toml(&config).unwrap();
+        let tier = Some((
+            Rational::from_sci_string("0.0009").unwrap(),
+            Rational::from_sci_string("0.001").unwrap(),
+        ));
+
+        assert_eq!(
+            schedule.fees(&CexExchange::Binance, &Pair(USDT_ADDRESS, USDC_ADDRESS)),
+            Some((Rational::from(0), Rational::from(0)))
+        );
+        assert_eq!(schedule.fees(&CexExchange::Binance, &Pair(WETH_ADDRESS, USDT_ADDRESS)), tier);
+        assert_eq!(schedule.fees(&CexExchange::Okex, &Pair(WETH_ADDRESS, USDT_ADDRESS)), None);
+    }
+
+    #[test]
+    fn test_perpetual_venues_are_separate() {
+        assert_eq!(CexExchange::from("binance"), CexExchange::Binance);
+        assert_eq!(CexExchange::from("binance-futures"), CexExchange::BinanceUsdm);
+        assert_eq!(CexExchange::from("okex"), CexExchange::Okex);
+        assert_eq!(CexExchange::from("okex-swap"), CexExchange::OkexSwap);
+    }
+
+    #[test]
+    fn test_perpetual_venues_keep_stored_discriminants() {
+        // stored cex rows hold the archived discriminant, the perpetual venues are
+        // appended so that the rows written before them still decode the same
+        let discriminant = |exchange: CexExchange| rkyv::to_bytes::<_, 8>(&exchange).unwrap()[0];
+
+        assert_eq!(discriminant(CexExchange::Binance), 0);
+        assert_eq!(discriminant(CexExchange::Okex), 3);
+        assert_eq!(discriminant(CexExchange::BybitSpot), 6);
+        assert_eq!(discriminant(CexExchange::Unknown), 16);
+        assert_eq!(discriminant(CexExchange::BinanceUsdm), 17);
+        assert_eq!(discriminant(CexExchange::OkexSwap), 19);
+    }
+}
@@ -1,10 +1,12 @@
 mod best_cex_per_pair;
 mod cex_symbols;
 mod exchanges;
+mod fees;
 
 pub use best_cex_per_pair::*;
 pub use cex_symbols::*;
 pub use exchanges::*;
+pub use fees::*;
 
 pub mod quotes;
 pub mod trades;
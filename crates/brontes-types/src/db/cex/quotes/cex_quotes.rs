@@ -121,6 +121,35 @@ impl CexPriceMap {
             })
     }
 
+    /// Mid price of the pair before fees, taken from the quote closest to the
+    /// timestamp on the most liquid exchange that quotes the pair directly.
+    pub fn get_mid_price(&self, pair: &Pair, timestamp: u64) -> Option<Rational> {
+        if pair.0 == pair.1 {
+            return Some(Rational::ONE)
+        }
+
+        self.most_liquid_ex
+            .get(pair)
+            .or_else(|| self.most_liquid_ex.get(&pair.flip()))?
+            .iter()
+            .find_map(|exchange| {
+                let quotes = self.quotes.get(exchange)?;
+                let (quotes, direction) = quotes
+                    .get(pair)
+                    .map(|quotes| (quotes, Direction::Sell))
+                    .or_else(|| {
+                        quotes
+                            .get(&pair.flip())
+                            .map(|quotes| (quotes, Direction::Buy))
+                    })?;
+
+                let index = quotes.partition_point(|q| q.timestamp <= timestamp);
+                let quote = quotes.get(index.saturating_sub(1))?;
+
+                Some(quote.adjust_for_direction(direction).avg())
+            })
+    }
+
     pub fn get_quote_at(
         &self,
         pair: &Pair,
@@ -173,7 +202,7 @@ impl CexPriceMap {
                 let closest_quote = adjusted_quotes.get(index.saturating_sub(1))?;
                 let adjusted_quote = closest_quote.adjust_for_direction(direction);
 
-                let fees = exchange.fees();
+                let fees = exchange.fees_for_pair(pair);
 
                 let fee_adjusted_maker = (
                     &adjusted_quote.price.0 * (Rational::ONE - &fees.0),
@@ -315,7 +344,7 @@ impl CexPriceMap {
                     let volume_weighted_bid = volume_price.0 / &cumulative_bbo.0;
                     let volume_weighted_ask = volume_price.1 / &cumulative_bbo.1;
 
-                    let fees = exchange.fees();
+                    let fees = exchange.fees_for_pair(pair);
 
                     let fee_adjusted_maker = (
                         &volume_weighted_bid * (Rational::ONE - &fees.0),
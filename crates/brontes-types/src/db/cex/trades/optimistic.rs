@@ -297,7 +297,7 @@ impl<'a> SortedTrades<'a> {
         let mut global_end_time = 0;
 
         for trade in trades_used {
-            let (m_fee, t_fee) = trade.exchange.fees();
+            let (m_fee, t_fee) = trade.exchange.fees_for_pair(&pair);
 
             let weight = if config.use_block_time_weights_vwap {
                 calculate_weight(
@@ -289,7 +289,7 @@ impl<'a> TimeWindowTrades<'a> {
                 // See explanation of trade representation in the book
                 let adjusted_trade = trade.adjust_for_direction(trade_data.direction);
 
-                let (m_fee, t_fee) = trade.exchange.fees();
+                let (m_fee, t_fee) = trade.exchange.fees_for_pair(&pair);
 
                 let (
                     vxp_maker,
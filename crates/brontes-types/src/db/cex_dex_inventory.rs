@@ -0,0 +1,239 @@
+use alloy_primitives::Address;
+use redefined::Redefined;
+use rkyv::{Archive, Deserialize as rDeserialize, Serialize as rSerialize};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    db::redefined_types::primitives::AddressRedefined, implement_table_value_codecs_with_zc,
+};
+
+pub const SECONDS_PER_DAY: u64 = 86_400;
+
+/// Positions smaller than this are treated as closed, so float dust from
+/// repeated partial unwinds doesn't keep them open
+const DUST: f64 = 1e-9;
+
+/// The inventory of every labelled cex-dex searcher at the end of a day
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize, Redefined)]
+#[redefined_attr(derive(Debug, PartialEq, Clone, Serialize, rSerialize, rDeserialize, Archive))]
+pub struct DailyCexDexInventory {
+    /// days since the unix epoch
+    pub day:        u64,
+    /// last block of the day that was accounted for
+    pub last_block: u64,
+    pub searchers:  Vec<SearcherInventory>,
+}
+
+implement_table_value_codecs_with_zc!(DailyCexDexInventoryRedefined);
+
+impl DailyCexDexInventory {
+    /// The state carried over into the next day. Open positions are kept while
+    /// the daily pnl and counters are reset
+    pub fn roll_over(&self, day: u64) -> Self {
+        Self {
+            day,
+            last_block: self.last_block,
+            searchers: self
+                .searchers
+                .iter()
+                .filter(|searcher| !searcher.positions.is_empty())
+                .map(|searcher| SearcherInventory {
+                    searcher:           searcher.searcher,
+                    positions:          searcher.positions.clone(),
+                    realized_pnl_usd:   0.0,
+                    unrealized_pnl_usd: searcher.unrealized_pnl_usd,
+                    markout_pnl_usd:    0.0,
+                    tx_count:           0,
+                })
+                .collect(),
+        }
+    }
+
+    pub fn searcher_mut(&mut self, searcher: Address) -> &mut SearcherInventory {
+        let idx = match self.searchers.iter().position(|s| s.searcher == searcher) {
+            Some(idx) => idx,
+            None => {
+                self.searchers.push(SearcherInventory::new(searcher));
+                self.searchers.len() - 1
+            }
+        };
+
+        &mut self.searchers[idx]
+    }
+}
+
+/// A searcher's open positions and the pnl it made over the day
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Redefined)]
+#[redefined_attr(derive(Debug, PartialEq, Clone, Serialize, rSerialize, rDeserialize, Archive))]
+pub struct SearcherInventory {
+    /// The address holding the inventory, the mev contract if the searcher
+    /// uses one otherwise the eoa
+    pub searcher:           Address,
+    pub positions:          Vec<InventoryPosition>,
+    /// pnl locked in by reducing positions over the day
+    pub realized_pnl_usd:   f64,
+    /// pnl of the open positions marked to the cex mid price at the end of the
+    /// day
+    pub unrealized_pnl_usd: f64,
+    /// sum of the per transaction markout profit of the day's cex-dex bundles
+    pub markout_pnl_usd:    f64,
+    pub tx_count:           u64,
+}
+
+impl SearcherInventory {
+    pub fn new(searcher: Address) -> Self {
+        Self {
+            searcher,
+            positions: vec![],
+            realized_pnl_usd: 0.0,
+            unrealized_pnl_usd: 0.0,
+            markout_pnl_usd: 0.0,
+            tx_count: 0,
+        }
+    }
+
+    /// Books a token delta at the given usd price, closed positions are
+    /// dropped from the inventory
+    pub fn apply_delta(&mut self, token: Address, amount: f64, price_usd: f64) {
+        if amount == 0.0 || !amount.is_finite() || !price_usd.is_finite() {
+            return
+        }
+
+        let idx = match self.positions.iter().position(|p| p.token == token) {
+            Some(idx) => idx,
+            None => {
+                self.positions.push(InventoryPosition::new(token));
+                self.positions.len() - 1
+            }
+        };
+
+        self.realized_pnl_usd += self.positions[idx].apply(amount, price_usd);
+        if self.positions[idx].is_closed() {
+            self.positions.swap_remove(idx);
+        }
+        self.update_unrealized();
+    }
+
+    /// Marks the position of the token to the given usd price
+    pub fn mark(&mut self, token: Address, price_usd: f64) {
+        if !price_usd.is_finite() {
+            return
+        }
+
+        if let Some(position) = self.positions.iter_mut().find(|p| p.token == token) {
+            position.mark_price_usd = price_usd;
+            self.update_unrealized();
+        }
+    }
+
+    fn update_unrealized(&mut self) {
+        self.unrealized_pnl_usd = self
+            .positions
+            .iter()
+            .map(InventoryPosition::unrealized_pnl_usd)
+            .sum();
+    }
+}
+
+/// A long or short position in a token, accounted at its average cost
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Redefined)]
+#[redefined_attr(derive(Debug, PartialEq, Clone, Serialize, rSerialize, rDeserialize, Archive))]
+pub struct InventoryPosition {
+    pub token:          Address,
+    /// signed amount held, negative for a short
+    pub amount:         f64,
+    /// average usd price the open amount was acquired at
+    pub avg_price_usd:  f64,
+    /// latest cex mid price the position was marked at
+    pub mark_price_usd: f64,
+}
+
+impl InventoryPosition {
+    pub fn new(token: Address) -> Self {
+        Self { token, amount: 0.0, avg_price_usd: 0.0, mark_price_usd: 0.0 }
+    }
+
+    /// Applies a signed delta at the given price, returning the pnl realized by
+    /// reducing the position. A delta that flips the position opens the
+    /// remainder at the given price
+    pub fn apply(&mut self, amount: f64, price_usd: f64) -> f64 {
+        self.mark_price_usd = price_usd;
+
+        if self.amount == 0.0 || self.amount.signum() == amount.signum() {
+            let total = self.amount + amount;
+            self.avg_price_usd = (self.avg_price_usd * self.amount + price_usd * amount) / total;
+            self.amount = total;
+            return 0.0
+        }
+
+        let closed = amount.abs().min(self.amount.abs()) * self.amount.signum();
+        let realized = closed * (price_usd - self.avg_price_usd);
+        let prev_sign = self.amount.signum();
+        self.amount += amount;
+
+        if self.is_closed() {
+            self.amount = 0.0;
+            self.avg_price_usd = 0.0;
+        } else if self.amount.signum() != prev_sign {
+            self.avg_price_usd = price_usd;
+        }
+
+        realized
+    }
+
+    pub fn unrealized_pnl_usd(&self) -> f64 {
+        self.amount * (self.mark_price_usd - self.avg_price_usd)
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.amount.abs() < DUST
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_average_cost_pnl() {
+        let token = Address::repeat_byte(1);
+        let mut inventory = SearcherInventory::new(Address::ZERO);
+
+        inventory.apply_delta(token, 2.0, 100.0);
+        inventory.apply_delta(token, 2.0, 200.0);
+        assert_eq!(inventory.positions[0].avg_price_usd, 150.0);
+
+        // sell half above the average cost
+        inventory.apply_delta(token, -2.0, 250.0);
+        assert_eq!(inventory.realized_pnl_usd, 200.0);
+        assert_eq!(inventory.unrealized_pnl_usd, 200.0);
+
+        inventory.mark(token, 100.0);
+        assert_eq!(inventory.unrealized_pnl_usd, -100.0);
+
+        // flip short, the remainder opens at the fill price
+        inventory.apply_delta(token, -3.0, 120.0);
+        assert_eq!(inventory.realized_pnl_usd, 140.0);
+        assert_eq!(inventory.positions[0].amount, -1.0);
+        assert_eq!(inventory.positions[0].avg_price_usd, 120.0);
+
+        inventory.apply_delta(token, 1.0, 110.0);
+        assert_eq!(inventory.realized_pnl_usd, 150.0);
+        assert!(inventory.positions.is_empty());
+        assert_eq!(inventory.unrealized_pnl_usd, 0.0);
+    }
+
+    #[test]
+    fn test_roll_over_keeps_open_positions() {
+        let mut day = DailyCexDexInventory { day: 1, last_block: 10, searchers: vec![] };
+        day.searcher_mut(Address::ZERO)
+            .apply_delta(Address::repeat_byte(1), 1.0, 10.0);
+        day.searcher_mut(Address::repeat_byte(2)).markout_pnl_usd = 5.0;
+
+        let next = day.roll_over(2);
+        assert_eq!(next.day, 2);
+        assert_eq!(next.searchers.len(), 1);
+        assert_eq!(next.searchers[0].positions, day.searchers[0].positions);
+        assert_eq!(next.searchers[0].markout_pnl_usd, 0.0);
+    }
+}
@@ -11,6 +11,12 @@ macro_rules! implement_table_value_codecs_with_zc {
 
         impl alloy_rlp::Decodable for $table_value {
             fn decode(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+                // the root is read from the end of the buffer, so a truncated
+                // value would be read out of bounds
+                if buf.len() < std::mem::size_of::<paste::paste!([<Archived $table_value>])>() {
+                    return Err(alloy_rlp::Error::InputTooShort)
+                }
+
                 let archived: &paste::paste!([<Archived $table_value>]) =
                 unsafe { rkyv::archived_root::<Self>(&buf[..]) };
 
@@ -35,9 +41,8 @@ macro_rules! implement_table_value_codecs_with_zc {
 
         impl reth_db::table::Decompress for $table_value {
             fn decompress<B: AsRef<[u8]>>(value: B) -> Result<Self, reth_db::DatabaseError> {
-                let binding = value.as_ref().to_vec();
-
-                let encoded_decompressed = zstd::decode_all(&*binding).unwrap();
+                let encoded_decompressed = zstd::decode_all(value.as_ref())
+                    .map_err(|_| reth_db::DatabaseError::Decode)?;
                 let buf = &mut encoded_decompressed.as_slice();
 
                 alloy_rlp::Decodable::decode(buf).map_err(|_| reth_db::DatabaseError::Decode)
@@ -0,0 +1,153 @@
+use alloy_primitives::Address;
+use redefined::Redefined;
+use rkyv::{Archive, Deserialize as rDeserialize, Serialize as rSerialize};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    db::redefined_types::primitives::AddressRedefined, implement_table_value_codecs_with_zc,
+    FastHashMap, Protocol,
+};
+
+const SECONDS_PER_DAY: u64 = 86_400;
+
+/// The loss versus rebalancing of every priced pool that was swapped against
+/// in a block
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize, Redefined)]
+#[redefined_attr(derive(Debug, PartialEq, Clone, Serialize, rSerialize, rDeserialize, Archive))]
+pub struct BlockLvr {
+    pub block_number:    u64,
+    pub block_timestamp: u64,
+    pub pools:           Vec<PoolLvr>,
+}
+
+impl BlockLvr {
+    /// days since the unix epoch
+    pub fn day(&self) -> u64 {
+        self.block_timestamp / SECONDS_PER_DAY
+    }
+}
+
+/// The loss of a pool's liquidity providers against a portfolio that is
+/// rebalanced at the cex price, for the swaps of a single block
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Redefined)]
+#[redefined_attr(derive(Debug, PartialEq, Clone, Serialize, rSerialize, rDeserialize, Archive))]
+pub struct PoolLvr {
+    pub pool:           Address,
+    #[redefined(same_fields)]
+    pub protocol:       Protocol,
+    pub token0:         Address,
+    pub token1:         Address,
+    pub swap_count:     u64,
+    /// swaps without a cex or dex price, excluded from the volume and lvr
+    pub unpriced_swaps: u64,
+    pub volume_usd:     f64,
+    /// value the pool gave up versus trading at the cex price. Positive values
+    /// are a loss for the liquidity providers
+    pub lvr_usd:        f64,
+}
+
+implement_table_value_codecs_with_zc!(BlockLvrRedefined);
+
+/// The loss versus rebalancing of a pool aggregated over a day
+#[derive(Debug, Clone, PartialEq)]
+pub struct DailyPoolLvr {
+    pub day:            u64,
+    pub pool:           Address,
+    pub protocol:       Protocol,
+    pub token0:         Address,
+    pub token1:         Address,
+    pub blocks:         u64,
+    pub swap_count:     u64,
+    pub unpriced_swaps: u64,
+    pub volume_usd:     f64,
+    pub lvr_usd:        f64,
+}
+
+/// Aggregates the per block lvr of each pool by day, sorted by day and pool
+pub fn aggregate_daily_lvr(blocks: &[BlockLvr]) -> Vec<DailyPoolLvr> {
+    let mut daily: FastHashMap<(u64, Address), DailyPoolLvr> = FastHashMap::default();
+
+    for block in blocks {
+        let day = block.day();
+        for pool in &block.pools {
+            let entry = daily
+                .entry((day, pool.pool))
+                .or_insert_with(|| DailyPoolLvr {
+                    day,
+                    pool: pool.pool,
+                    protocol: pool.protocol,
+                    token0: pool.token0,
+                    token1: pool.token1,
+                    blocks: 0,
+                    swap_count: 0,
+                    unpriced_swaps: 0,
+                    volume_usd: 0.0,
+                    lvr_usd: 0.0,
+                });
+            entry.blocks += 1;
+            entry.swap_count += pool.swap_count;
+            entry.unpriced_swaps += pool.unpriced_swaps;
+            entry.volume_usd += pool.volume_usd;
+            entry.lvr_usd += pool.lvr_usd;
+        }
+    }
+
+    let mut daily = daily.into_values().collect::<Vec<_>>();
+    daily.sort_by_key(|entry| (entry.day, entry.pool));
+    daily
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::Address;
+
+    use super::*;
+
+    fn pool_lvr(pool: Address, lvr_usd: f64) -> PoolLvr {
+        PoolLvr {
+            pool,
+            protocol: Protocol::UniswapV2,
+            token0: Address::ZERO,
+            token1: Address::repeat_byte(1),
+            swap_count: 2,
+            unpriced_swaps: 1,
+            volume_usd: 100.0,
+            lvr_usd,
+        }
+    }
+
+    #[test]
+    fn test_aggregate_daily_lvr() {
+        let pool = Address::repeat_byte(2);
+        let blocks = vec![
+            BlockLvr {
+                block_number:    1,
+                block_timestamp: 10,
+                pools:           vec![pool_lvr(pool, 1.0)],
+            },
+            BlockLvr {
+                block_number:    2,
+                block_timestamp: 22,
+                pools:           vec![pool_lvr(pool, 2.5)],
+            },
+            BlockLvr {
+                block_number:    3,
+                block_timestamp: SECONDS_PER_DAY + 1,
+                pools:           vec![pool_lvr(pool, -1.0)],
+            },
+        ];
+
+        let daily = aggregate_daily_lvr(&blocks);
+        assert_eq!(daily.len(), 2);
+
+        assert_eq!(daily[0].day, 0);
+        assert_eq!(daily[0].blocks, 2);
+        assert_eq!(daily[0].swap_count, 4);
+        assert_eq!(daily[0].unpriced_swaps, 2);
+        assert_eq!(daily[0].volume_usd, 200.0);
+        assert_eq!(daily[0].lvr_usd, 3.5);
+
+        assert_eq!(daily[1].day, 1);
+        assert_eq!(daily[1].lvr_usd, -1.0);
+    }
+}
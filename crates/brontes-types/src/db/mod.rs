@@ -9,17 +9,21 @@ pub mod block_analysis;
 pub mod block_times;
 pub mod builder;
 pub mod cex;
+pub mod cex_dex_inventory;
 
 pub mod clickhouse;
 pub mod clickhouse_serde;
 pub mod codecs;
 pub mod dex;
 pub mod initialized_state;
+pub mod lvr;
 pub mod metadata;
 pub mod mev_block;
 pub mod normalized_actions;
 pub mod pool_creation_block;
 pub mod redefined_types;
+pub mod run_checkpoint;
+pub mod schema_version;
 pub mod searcher;
 pub mod token_info;
 pub mod traces;
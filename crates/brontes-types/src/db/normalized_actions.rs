@@ -1,10 +1,32 @@
 use alloy_primitives::Address;
 use clickhouse::DbRow;
 use itertools::MultiUnzip;
+use redefined::{self_convert_redefined, Redefined};
 use reth_primitives::B256;
+use rkyv::{Archive, Deserialize as rDeserialize, Serialize as rSerialize};
 use serde::{ser::SerializeStruct, Deserialize, Serialize};
 
-use crate::{normalized_actions::Action, GasDetails, Node, Root};
+use crate::{
+    db::{
+        redefined_types::{
+            malachite::RationalRedefined,
+            primitives::{AddressRedefined, B256Redefined, U256Redefined},
+        },
+        token_info::TokenInfoWithAddressRedefined,
+        traces::TransactionTraceWithLogsRedefined,
+    },
+    implement_table_value_codecs_with_zc,
+    normalized_actions::{
+        Action, NormalizedAggregator, NormalizedBatchRedefined, NormalizedBurnRedefined,
+        NormalizedCollectRedefined, NormalizedEthTransferRedefined, NormalizedFlashLoan,
+        NormalizedLiquidationRedefined, NormalizedMintRedefined, NormalizedNewPoolRedefined,
+        NormalizedNftTradeRedefined, NormalizedNftTransferRedefined,
+        NormalizedPoolConfigUpdateRedefined, NormalizedSwapRedefined,
+        NormalizedSwapWithFeeRedefined, NormalizedTransferRedefined,
+        SelfdestructWithIndexRedefined,
+    },
+    BlockTree, GasDetails, Node, Protocol, Root,
+};
 
 #[derive(Debug, Clone)]
 pub struct TransactionRoot {
@@ -140,7 +162,7 @@ impl From<(&Node, &[Option<Vec<Action>>])> for TraceNode {
     }
 }
 
-#[derive(Debug, Clone, Copy, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, rSerialize, rDeserialize, Archive)]
 pub enum ActionKind {
     Swap,
     SwapWithFee,
@@ -158,6 +180,8 @@ pub enum ActionKind {
     PoolConfigUpdate,
     Aggregator,
     Revert,
+    NftTransfer,
+    NftTrade,
 }
 
 impl From<&Action> for ActionKind {
@@ -178,6 +202,8 @@ impl From<&Action> for ActionKind {
             Action::NewPool(_) => ActionKind::NewPool,
             Action::PoolConfigUpdate(_) => ActionKind::PoolConfigUpdate,
             Action::Aggregator(_) => ActionKind::Aggregator,
+            Action::NftTransfer(_) => ActionKind::NftTransfer,
+            Action::NftTrade(_) => ActionKind::NftTrade,
             Action::Revert => ActionKind::Revert,
         }
     }
@@ -192,6 +218,138 @@ impl Serialize for ActionKind {
     }
 }
 
+self_convert_redefined!(ActionKind);
+
+/// The pruned classified transaction trees of a block, as persisted in
+/// libmdbx. Mirrors the clickhouse `tx_roots` rows.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize, Redefined)]
+#[redefined_attr(derive(Debug, PartialEq, Clone, Serialize, rSerialize, rDeserialize, Archive))]
+pub struct ClassifiedBlock {
+    pub block_number: u64,
+    pub tx_roots:     Vec<ClassifiedTxRoot>,
+}
+
+impl From<&BlockTree<Action>> for ClassifiedBlock {
+    fn from(tree: &BlockTree<Action>) -> Self {
+        let block_number = tree.header.number;
+        Self {
+            block_number,
+            tx_roots: tree
+                .tx_roots
+                .iter()
+                .map(|root| TransactionRoot::from((root, block_number)).into())
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize, Redefined)]
+#[redefined_attr(derive(Debug, PartialEq, Clone, Serialize, rSerialize, rDeserialize, Archive))]
+pub struct ClassifiedTxRoot {
+    pub tx_hash:      B256,
+    pub tx_idx:       u64,
+    pub from_address: Address,
+    pub to_address:   Option<Address>,
+    #[redefined(same_fields)]
+    pub gas_details:  GasDetails,
+    pub trace_nodes:  Vec<ClassifiedTraceNode>,
+}
+
+impl From<TransactionRoot> for ClassifiedTxRoot {
+    fn from(root: TransactionRoot) -> Self {
+        Self {
+            tx_hash:      root.tx_hash,
+            tx_idx:       root.tx_idx as u64,
+            from_address: root.from_address,
+            to_address:   root.to_address,
+            gas_details:  root.gas_details,
+            trace_nodes:  root.trace_nodes.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize, Redefined)]
+#[redefined_attr(derive(Debug, PartialEq, Clone, Serialize, rSerialize, rDeserialize, Archive))]
+pub struct ClassifiedTraceNode {
+    pub trace_idx:     u64,
+    pub trace_address: Vec<u64>,
+    #[redefined(same_fields)]
+    pub action_kind:   Option<ActionKind>,
+    pub action:        Option<Action>,
+}
+
+impl From<TraceNode> for ClassifiedTraceNode {
+    fn from(node: TraceNode) -> Self {
+        Self {
+            trace_idx:     node.trace_idx,
+            trace_address: node.trace_address,
+            action_kind:   node.action_kind,
+            action:        node.action,
+        }
+    }
+}
+
+/// Flash loans and aggregators nest the actions they wrap, so the archived
+/// layout of [`Action`] is recursive. rkyv can't derive the bounds of a
+/// recursive type, which is why these are written out instead of generated
+#[derive(Debug, PartialEq, Clone, Serialize, rSerialize, rDeserialize, Archive, Redefined)]
+#[redefined(Action)]
+pub enum ActionRedefined {
+    Swap(NormalizedSwapRedefined),
+    SwapWithFee(NormalizedSwapWithFeeRedefined),
+    FlashLoan(NormalizedFlashLoanRedefined),
+    Batch(NormalizedBatchRedefined),
+    Transfer(NormalizedTransferRedefined),
+    Mint(NormalizedMintRedefined),
+    Burn(NormalizedBurnRedefined),
+    Collect(NormalizedCollectRedefined),
+    Liquidation(NormalizedLiquidationRedefined),
+    SelfDestruct(SelfdestructWithIndexRedefined),
+    EthTransfer(NormalizedEthTransferRedefined),
+    NewPool(NormalizedNewPoolRedefined),
+    PoolConfigUpdate(NormalizedPoolConfigUpdateRedefined),
+    Aggregator(NormalizedAggregatorRedefined),
+    NftTransfer(NormalizedNftTransferRedefined),
+    NftTrade(NormalizedNftTradeRedefined),
+    Unclassified(TransactionTraceWithLogsRedefined),
+    Revert,
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, rSerialize, rDeserialize, Archive, Redefined)]
+#[redefined(NormalizedFlashLoan)]
+#[archive(bound(serialize = "__S: rkyv::ser::ScratchSpace + rkyv::ser::Serializer"))]
+pub struct NormalizedFlashLoanRedefined {
+    pub protocol:          Protocol,
+    pub trace_index:       u64,
+    pub from:              AddressRedefined,
+    pub pool:              AddressRedefined,
+    pub receiver_contract: AddressRedefined,
+    pub assets:            Vec<TokenInfoWithAddressRedefined>,
+    pub amounts:           Vec<RationalRedefined>,
+    pub aave_mode:         Option<(Vec<U256Redefined>, AddressRedefined)>,
+    #[omit_bounds]
+    pub child_actions:     Vec<ActionRedefined>,
+    pub repayments:        Vec<NormalizedTransferRedefined>,
+    pub fees_paid:         Vec<RationalRedefined>,
+    pub msg_value:         U256Redefined,
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, rSerialize, rDeserialize, Archive, Redefined)]
+#[redefined(NormalizedAggregator)]
+#[archive(bound(serialize = "__S: rkyv::ser::ScratchSpace + rkyv::ser::Serializer"))]
+pub struct NormalizedAggregatorRedefined {
+    pub protocol:      Protocol,
+    pub trace_index:   u64,
+    pub from:          AddressRedefined,
+    pub to:            AddressRedefined,
+    pub recipient:     AddressRedefined,
+    #[omit_bounds]
+    pub child_actions: Vec<ActionRedefined>,
+    pub msg_value:     U256Redefined,
+}
+
+implement_table_value_codecs_with_zc!(ClassifiedBlockRedefined);
+
 #[cfg(test)]
 pub mod test {
     use std::sync::Arc;
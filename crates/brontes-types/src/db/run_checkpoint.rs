@@ -0,0 +1,118 @@
+use redefined::Redefined;
+use rkyv::{Archive, Deserialize as rDeserialize, Serialize as rSerialize};
+use serde::{Deserialize, Serialize};
+
+use crate::implement_table_value_codecs_with_zc;
+
+/// Progress of every range executor of a historical run, used to resume the
+/// run after a crash
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize, Redefined)]
+#[redefined_attr(derive(Debug, PartialEq, Clone, Serialize, rSerialize, rDeserialize, Archive))]
+pub struct RunCheckpoint {
+    pub run_id: u64,
+    /// indexed by the chunk id
+    pub chunks: Vec<ChunkCheckpoint>,
+}
+
+implement_table_value_codecs_with_zc!(RunCheckpointRedefined);
+
+impl RunCheckpoint {
+    pub fn new(run_id: u64, ranges: &[(u64, u64)]) -> Self {
+        Self {
+            run_id,
+            chunks: ranges
+                .iter()
+                .map(|(start_block, end_block)| ChunkCheckpoint::new(*start_block, *end_block))
+                .collect(),
+        }
+    }
+
+    pub fn commit(&mut self, chunk_id: usize, block: u64) {
+        if let Some(chunk) = self.chunks.get_mut(chunk_id) {
+            chunk.commit(block)
+        }
+    }
+
+    /// The chunks that still have blocks left to process
+    pub fn remaining(&self) -> impl Iterator<Item = (usize, &ChunkCheckpoint)> + '_ {
+        self.chunks
+            .iter()
+            .enumerate()
+            .filter(|(_, chunk)| !chunk.is_finished())
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.remaining().next().is_none()
+    }
+}
+
+/// The progress of a single range executor. Blocks can finish out of order, so
+/// the blocks committed past the first uncommitted block are tracked
+/// separately to not process them twice when resuming
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize, Redefined)]
+#[redefined_attr(derive(Debug, PartialEq, Clone, Serialize, rSerialize, rDeserialize, Archive))]
+pub struct ChunkCheckpoint {
+    pub start_block:     u64,
+    /// exclusive, matching the blocks the range executor fetches
+    pub end_block:       u64,
+    /// every block before this one is committed
+    pub next_block:      u64,
+    pub committed_ahead: Vec<u64>,
+}
+
+impl ChunkCheckpoint {
+    pub fn new(start_block: u64, end_block: u64) -> Self {
+        Self { start_block, end_block, next_block: start_block, committed_ahead: vec![] }
+    }
+
+    pub fn commit(&mut self, block: u64) {
+        if block < self.next_block || self.committed_ahead.contains(&block) {
+            return
+        }
+        self.committed_ahead.push(block);
+        self.committed_ahead.sort_unstable();
+
+        while self.committed_ahead.first() == Some(&self.next_block) {
+            self.committed_ahead.remove(0);
+            self.next_block += 1;
+        }
+    }
+
+    /// The last block of the unbroken run of committed blocks from the start
+    /// of the chunk
+    pub fn last_committed_block(&self) -> Option<u64> {
+        (self.next_block > self.start_block).then(|| self.next_block - 1)
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.next_block >= self.end_block
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_out_of_order_commits() {
+        let mut checkpoint = RunCheckpoint::new(0, &[(10, 15), (15, 20)]);
+
+        checkpoint.commit(0, 12);
+        checkpoint.commit(0, 11);
+        assert_eq!(checkpoint.chunks[0].last_committed_block(), None);
+        assert_eq!(checkpoint.chunks[0].committed_ahead, vec![11, 12]);
+
+        checkpoint.commit(0, 10);
+        assert_eq!(checkpoint.chunks[0].last_committed_block(), Some(12));
+        assert!(checkpoint.chunks[0].committed_ahead.is_empty());
+
+        // committing twice is a no-op
+        checkpoint.commit(0, 11);
+        assert_eq!(checkpoint.chunks[0].next_block, 13);
+
+        checkpoint.commit(0, 13);
+        checkpoint.commit(0, 14);
+        assert!(checkpoint.chunks[0].is_finished());
+        assert_eq!(checkpoint.remaining().map(|(id, _)| id).collect::<Vec<_>>(), vec![1]);
+    }
+}
@@ -0,0 +1,21 @@
+use redefined::Redefined;
+use rkyv::{Archive, Deserialize as rDeserialize, Serialize as rSerialize};
+use serde::{Deserialize, Serialize};
+
+use crate::implement_table_value_codecs_with_zc;
+
+/// The version of the archived layout the values of a table are stored in.
+/// Bumped by every migration that is run over the table
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Redefined)]
+#[redefined_attr(derive(Debug, PartialEq, Clone, Serialize, rSerialize, rDeserialize, Archive))]
+pub struct SchemaVersion {
+    pub version: u32,
+}
+
+implement_table_value_codecs_with_zc!(SchemaVersionRedefined);
+
+impl SchemaVersion {
+    pub fn new(version: u32) -> Self {
+        Self { version }
+    }
+}
@@ -12,7 +12,7 @@ use strum::AsRefStr;
 use crate::{
     db::redefined_types::primitives::AddressRedefined,
     implement_table_value_codecs_with_zc,
-    mev::{BundleHeader, MevCount, MevType},
+    mev::{BundleHeader, FailedMevAttempt, MevCount, MevType},
     serde_utils::{addresss, option_addresss, vec_address},
 };
 
@@ -20,31 +20,39 @@ use crate::{
 #[redefined_attr(derive(Debug, PartialEq, Clone, Serialize, rSerialize, rDeserialize, Archive))]
 pub struct SearcherInfo {
     #[serde(default)]
-    pub name:              Option<String>,
+    pub name:                 Option<String>,
     #[redefined(same_fields)]
     #[serde(default)]
-    pub fund:              Fund,
+    pub fund:                 Fund,
     #[redefined(same_fields)]
     #[serde(default)]
-    pub mev_count:         MevCount,
+    pub mev_count:            MevCount,
     #[redefined(same_fields)]
     #[serde(default)]
-    pub pnl:               TollByType,
+    pub pnl:                  TollByType,
     #[redefined(same_fields)]
     #[serde(default)]
-    pub gas_bids:          TollByType,
+    pub gas_bids:             TollByType,
     /// If the searcher is vertically integrated, this will contain the
     /// corresponding builder's information.
     #[serde(with = "option_addresss")]
     #[serde(default)]
-    pub builder:           Option<Address>,
+    pub builder:              Option<Address>,
     #[redefined(same_fields)]
     #[serde(default)]
     #[serde(rename = "mev_types")]
-    pub config_labels:     Vec<MevType>,
+    pub config_labels:        Vec<MevType>,
     #[serde(with = "vec_address")]
     #[serde(default)]
-    pub sibling_searchers: Vec<Address>,
+    pub sibling_searchers:    Vec<Address>,
+    /// Reverted transactions that competed for an opportunity that another
+    /// bundle landed
+    #[serde(default)]
+    pub failed_attempt_count: u64,
+    /// Gas burned by the failed attempts
+    #[redefined(same_fields)]
+    #[serde(default)]
+    pub wasted_gas:           TollByType,
 }
 
 impl SearcherInfo {
@@ -74,6 +82,7 @@ impl SearcherInfo {
             MevType::AtomicArb => self.mev_count.atomic_backrun_count,
             MevType::Liquidation => self.mev_count.liquidation_count,
             MevType::SearcherTx => self.mev_count.searcher_tx_count,
+            MevType::NftArb => self.mev_count.nft_arb_count,
             MevType::Unknown => None,
         }
     }
@@ -145,6 +154,7 @@ impl SearcherInfo {
                 self.pnl.liquidation,
                 self.gas_bids.liquidation,
             ),
+            ("NftArb", self.mev_count.nft_arb_count, self.pnl.nft_arb, self.gas_bids.nft_arb),
         ]
         .into_iter()
         .filter_map(|(mev_type, count, pnl, gas_bid)| {
@@ -172,6 +182,50 @@ impl SearcherInfo {
         self.mev_count.increment_count(header.mev_type);
         self.gas_bids.account_gas(header);
     }
+
+    pub fn update_with_failed_attempt(&mut self, attempt: &FailedMevAttempt) {
+        self.failed_attempt_count += 1;
+        self.wasted_gas
+            .account_wasted_gas(attempt.mev_type, attempt.wasted_gas_usd);
+    }
+
+    /// The labels of the searcher without any of its activity
+    pub fn labels_only(&self) -> Self {
+        Self {
+            name: self.name.clone(),
+            fund: self.fund,
+            builder: self.builder,
+            config_labels: self.config_labels.clone(),
+            sibling_searchers: self.sibling_searchers.clone(),
+            ..Default::default()
+        }
+    }
+
+    /// Adds the activity of `other` on top of this one. Used to combine the
+    /// infos of db partitions that cover disjoint block ranges
+    pub fn accumulate(&mut self, other: SearcherInfo) {
+        self.name = other.name.or(self.name.take());
+        if !other.fund.is_none() {
+            self.fund = other.fund;
+        }
+        self.builder = other.builder.or(self.builder.take());
+        for mev_type in other.config_labels {
+            if !self.config_labels.contains(&mev_type) {
+                self.config_labels.push(mev_type);
+            }
+        }
+        for sibling in other.sibling_searchers {
+            if !self.sibling_searchers.contains(&sibling) {
+                self.sibling_searchers.push(sibling);
+            }
+        }
+
+        self.mev_count.add_counts(&other.mev_count);
+        self.pnl.add_toll(&other.pnl);
+        self.gas_bids.add_toll(&other.gas_bids);
+        self.failed_attempt_count += other.failed_attempt_count;
+        self.wasted_gas.add_toll(&other.wasted_gas);
+    }
 }
 
 implement_table_value_codecs_with_zc!(SearcherInfoRedefined);
@@ -199,6 +253,7 @@ pub struct TollByType {
     pub atomic_backrun: Option<f64>,
     pub liquidation:    Option<f64>,
     pub searcher_tx:    Option<f64>,
+    pub nft_arb:        Option<f64>,
 }
 
 self_convert_redefined!(TollByType);
@@ -242,6 +297,9 @@ impl TollByType {
             MevType::SearcherTx => {
                 self.searcher_tx = Some(self.searcher_tx.unwrap_or_default().add(header.profit_usd))
             }
+            MevType::NftArb => {
+                self.nft_arb = Some(self.nft_arb.unwrap_or_default().add(header.profit_usd))
+            }
             _ => (),
         }
     }
@@ -284,9 +342,49 @@ impl TollByType {
             MevType::SearcherTx => {
                 self.searcher_tx = Some(self.searcher_tx.unwrap_or_default().add(header.bribe_usd))
             }
+            MevType::NftArb => {
+                self.nft_arb = Some(self.nft_arb.unwrap_or_default().add(header.bribe_usd))
+            }
             _ => (),
         }
     }
+
+    pub fn account_wasted_gas(&mut self, mev_type: MevType, gas_usd: f64) {
+        self.total += gas_usd;
+        let entry = match mev_type {
+            MevType::CexDexQuotes => &mut self.cex_dex_quotes,
+            MevType::CexDexTrades => &mut self.cex_dex_trades,
+            MevType::Sandwich => &mut self.sandwich,
+            MevType::AtomicArb => &mut self.atomic_backrun,
+            MevType::Jit => &mut self.jit,
+            MevType::JitSandwich => &mut self.jit_sandwich,
+            MevType::Liquidation => &mut self.liquidation,
+            MevType::SearcherTx => &mut self.searcher_tx,
+            MevType::NftArb => &mut self.nft_arb,
+            _ => return,
+        };
+        *entry = Some(entry.unwrap_or_default().add(gas_usd));
+    }
+
+    pub fn add_toll(&mut self, other: &TollByType) {
+        fn sum(a: Option<f64>, b: Option<f64>) -> Option<f64> {
+            match (a, b) {
+                (Some(a), Some(b)) => Some(a + b),
+                (a, b) => a.or(b),
+            }
+        }
+
+        self.total += other.total;
+        self.sandwich = sum(self.sandwich, other.sandwich);
+        self.cex_dex_quotes = sum(self.cex_dex_quotes, other.cex_dex_quotes);
+        self.cex_dex_trades = sum(self.cex_dex_trades, other.cex_dex_trades);
+        self.jit = sum(self.jit, other.jit);
+        self.jit_sandwich = sum(self.jit_sandwich, other.jit_sandwich);
+        self.atomic_backrun = sum(self.atomic_backrun, other.atomic_backrun);
+        self.liquidation = sum(self.liquidation, other.liquidation);
+        self.searcher_tx = sum(self.searcher_tx, other.searcher_tx);
+        self.nft_arb = sum(self.nft_arb, other.nft_arb);
+    }
 }
 
 #[derive(
@@ -2,11 +2,22 @@ use alloy_primitives::Address;
 
 use crate::{
     db::{
-        address_metadata::AddressMetadata, address_to_protocol_info::ProtocolInfo,
-        builder::BuilderInfo, cex::trades::CexTradeMap, dex::DexQuotes, metadata::Metadata,
-        mev_block::MevBlockWithClassified, searcher::SearcherInfo,
+        address_metadata::AddressMetadata,
+        address_to_protocol_info::ProtocolInfo,
+        block_analysis::BlockAnalysis,
+        builder::BuilderInfo,
+        cex::{quotes::CexPriceMap, trades::CexTradeMap},
+        cex_dex_inventory::DailyCexDexInventory,
+        dex::{DexQuoteWithIndex, DexQuotes},
+        lvr::BlockLvr,
+        metadata::Metadata,
+        mev_block::MevBlockWithClassified,
+        normalized_actions::ClassifiedBlock,
+        run_checkpoint::RunCheckpoint,
+        searcher::SearcherInfo,
         token_info::TokenInfoWithAddress,
     },
+    mev::BlockFailedAttempts,
     pair::Pair,
     structured_trace::TxTrace,
     FastHashMap, Protocol,
@@ -107,6 +118,30 @@ pub trait LibmdbxReader: Send + Sync + Unpin + 'static {
 
     fn get_dex_quotes(&self, block: u64) -> eyre::Result<DexQuotes>;
 
+    /// returns the dex quotes of every tx in the inclusive block range along
+    /// with the block they belong to
+    fn try_fetch_dex_quotes_range(
+        &self,
+        start_block: u64,
+        end_block: u64,
+    ) -> eyre::Result<Vec<(u64, DexQuoteWithIndex)>>;
+
+    fn try_fetch_cex_trades_range(
+        &self,
+        start_block: u64,
+        end_block: u64,
+    ) -> eyre::Result<Vec<(u64, CexTradeMap)>>;
+
+    fn try_fetch_cex_quotes_range(
+        &self,
+        start_block: u64,
+        end_block: u64,
+    ) -> eyre::Result<Vec<(u64, CexPriceMap)>>;
+
+    fn fetch_all_token_info(&self) -> eyre::Result<Vec<TokenInfoWithAddress>>;
+
+    fn fetch_all_protocol_info(&self) -> eyre::Result<Vec<(Address, ProtocolInfo)>>;
+
     fn try_fetch_token_info(&self, address: Address) -> eyre::Result<TokenInfoWithAddress>;
 
     fn try_fetch_token_decimals(&self, address: Address) -> eyre::Result<u8> {
@@ -124,6 +159,51 @@ pub trait LibmdbxReader: Send + Sync + Unpin + 'static {
         start_block: Option<u64>,
     ) -> eyre::Result<Vec<MevBlockWithClassified>>;
 
+    fn try_fetch_block_analysis(&self, block_num: u64) -> eyre::Result<Option<BlockAnalysis>>;
+
+    fn try_fetch_block_analyses(
+        &self,
+        start_block: Option<u64>,
+        end_block: u64,
+    ) -> eyre::Result<Vec<BlockAnalysis>>;
+
+    /// returns the pruned classified transaction trees of the block
+    fn try_fetch_classified_block(&self, block_num: u64) -> eyre::Result<Option<ClassifiedBlock>>;
+
+    fn try_fetch_classified_blocks(
+        &self,
+        start_block: Option<u64>,
+        end_block: u64,
+    ) -> eyre::Result<Vec<ClassifiedBlock>>;
+
+    /// returns the per pool loss versus rebalancing of the blocks in the range
+    fn try_fetch_block_lvrs(
+        &self,
+        start_block: Option<u64>,
+        end_block: u64,
+    ) -> eyre::Result<Vec<BlockLvr>>;
+
+    /// returns the failed mev attempts of the blocks in the range
+    fn try_fetch_failed_attempts(
+        &self,
+        start_block: Option<u64>,
+        end_block: u64,
+    ) -> eyre::Result<Vec<BlockFailedAttempts>>;
+
+    /// returns the daily inventory snapshots of the cex-dex searchers, keyed
+    /// by days since the unix epoch
+    fn try_fetch_cex_dex_inventories(
+        &self,
+        start_day: Option<u64>,
+        end_day: u64,
+    ) -> eyre::Result<Vec<DailyCexDexInventory>>;
+
+    /// returns the progress of a checkpointed range run
+    fn try_fetch_run_checkpoint(&self, run_id: u64) -> eyre::Result<Option<RunCheckpoint>>;
+
+    /// returns the id of the most recently started checkpointed range run
+    fn latest_run_checkpoint_id(&self) -> eyre::Result<Option<u64>>;
+
     fn protocols_created_before(
         &self,
         start_block: u64,
@@ -4,9 +4,10 @@ use futures::Future;
 use crate::{
     db::{
         address_metadata::AddressMetadata, block_analysis::BlockAnalysis, builder::BuilderInfo,
-        dex::DexQuotes, searcher::SearcherInfo,
+        cex_dex_inventory::DailyCexDexInventory, dex::DexQuotes, lvr::BlockLvr,
+        run_checkpoint::RunCheckpoint, searcher::SearcherInfo,
     },
-    mev::{Bundle, MevBlock},
+    mev::{BlockFailedAttempts, Bundle, MevBlock},
     normalized_actions::Action,
     structured_trace::TxTrace,
     BlockTree, Protocol,
@@ -26,6 +27,46 @@ pub trait DBWriter: Send + Unpin + 'static {
         self.inner().write_block_analysis(block_analysis)
     }
 
+    fn write_block_lvr(
+        &self,
+        block_lvr: BlockLvr,
+    ) -> impl Future<Output = eyre::Result<()>> + Send {
+        self.inner().write_block_lvr(block_lvr)
+    }
+
+    fn write_failed_attempts(
+        &self,
+        attempts: BlockFailedAttempts,
+    ) -> impl Future<Output = eyre::Result<()>> + Send {
+        self.inner().write_failed_attempts(attempts)
+    }
+
+    fn write_cex_dex_inventory(
+        &self,
+        inventory: DailyCexDexInventory,
+    ) -> impl Future<Output = eyre::Result<()>> + Send {
+        self.inner().write_cex_dex_inventory(inventory)
+    }
+
+    fn write_run_checkpoint(
+        &self,
+        checkpoint: RunCheckpoint,
+    ) -> impl Future<Output = eyre::Result<()>> + Send {
+        self.inner().write_run_checkpoint(checkpoint)
+    }
+
+    /// Marks the block of the run's chunk as committed. Only called once
+    /// every write of the block has been issued, so the checkpoint can be
+    /// persisted together with them
+    fn commit_run_checkpoint(
+        &self,
+        run_id: u64,
+        chunk_id: usize,
+        block: u64,
+    ) -> impl Future<Output = eyre::Result<()>> + Send {
+        self.inner().commit_run_checkpoint(run_id, chunk_id, block)
+    }
+
     fn write_dex_quotes(
         &self,
         block_number: u64,
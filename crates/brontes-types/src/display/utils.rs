@@ -105,6 +105,34 @@ pub fn display_sandwich(bundle: &Bundle, f: &mut fmt::Formatter) -> fmt::Result
                 if let Some(gas_details) = victim_gas_details {
                     gas_details.pretty_print_with_spaces(f, 16)?;
                 }
+
+                // Victim loss against the counterfactual without the frontrun
+                if let Some(loss) = sandwich_data
+                    .victim_losses
+                    .iter()
+                    .find(|loss| &loss.tx_hash == tx_hash)
+                {
+                    writeln!(f, "          - {}:", "Counterfactual Loss".bright_blue())?;
+                    if loss.extra_amount_in != 0.0 {
+                        writeln!(
+                            f,
+                            "                - Extra Input: {} {}",
+                            loss.extra_amount_in, loss.token_in.symbol
+                        )?;
+                    }
+                    if loss.lost_amount_out != 0.0 {
+                        writeln!(
+                            f,
+                            "                - Lost Output: {} {}",
+                            loss.lost_amount_out, loss.token_out.symbol
+                        )?;
+                    }
+                    writeln!(
+                        f,
+                        "                - Loss (USD): {}",
+                        format!("${:.2}", loss.amount_lost_usd).bright_red()
+                    )?;
+                }
             }
         }
     }
@@ -457,6 +485,82 @@ pub fn display_atomic_backrun(bundle: &Bundle, f: &mut fmt::Formatter) -> fmt::R
     Ok(())
 }
 
+pub fn display_nft_arb(bundle: &Bundle, f: &mut fmt::Formatter) -> fmt::Result {
+    let ascii_header = indoc! {r#"
+
+         _   _  __ _        ___       _
+        | \ | |/ _| |      / _ \     | |
+        |  \| | |_| |_    / /_\ \_ __| |__
+        | . ` |  _| __|   |  _  | '__| '_ \
+        | |\  | | | |_    | | | | |  | |_) |
+        \_| \_/_|  \__|   \_| |_/_|  |_.__/
+
+    "#};
+
+    let nft_arb_data = match &bundle.data {
+        BundleData::NftArb(data) => data,
+        _ => panic!("Wrong bundle type"),
+    };
+
+    for line in ascii_header.lines() {
+        writeln!(f, "{}", line.bright_magenta())?;
+    }
+
+    // Tx details
+    writeln!(f, "{}: \n", "Transaction Details".bold().underline().bright_yellow())?;
+    writeln!(f, "   - Tx Index: {}", bundle.header.tx_index.to_string().bold())?;
+    writeln!(f, "   - EOA: {}", bundle.header.eoa)?;
+
+    match bundle.header.mev_contract {
+        Some(contract) => {
+            writeln!(f, "   - Mev Contract: {}", contract)?;
+        }
+        None => {
+            writeln!(f, "   - Mev Contract: None")?;
+        }
+    }
+
+    writeln!(f, "   - Etherscan: {}", format_etherscan_url(&bundle.header.tx_hash))?;
+
+    // Trades Section
+    writeln!(f, "\n - {}", "Trades:".bright_blue())?;
+    for (i, trade) in nft_arb_data.trades.iter().enumerate() {
+        writeln!(f, "    {}: {}", format!(" - {}", i + 1).green(), trade)?;
+        writeln!(f, "         - Seller: {:?}", trade.seller)?;
+        writeln!(f, "         - Buyer: {:?}", trade.buyer)?;
+    }
+
+    writeln!(f, " - {}:", "Gas Details".bright_blue())?;
+    nft_arb_data.gas_details.pretty_print_with_spaces(f, 8)?;
+
+    // Profitability Section
+    writeln!(f, "\n{}\n", "Profitability".bright_yellow().underline())?;
+    writeln!(
+        f,
+        " - {}: {}",
+        "Bundle Profit (USD)".bright_white(),
+        format_profit(bundle.header.profit_usd)
+            .to_string()
+            .bright_white()
+    )?;
+    writeln!(
+        f,
+        " - {}: {}",
+        "Bribe (USD)".bright_white(),
+        format_bribe(bundle.header.bribe_usd)
+            .to_string()
+            .bright_red()
+    )?;
+
+    bundle
+        .header
+        .balance_deltas
+        .iter()
+        .for_each(|tx_delta| writeln!(f, "{}", tx_delta).expect("Failed to write balance deltas"));
+
+    Ok(())
+}
+
 pub fn display_liquidation(bundle: &Bundle, f: &mut fmt::Formatter) -> fmt::Result {
     let ascii_header = indoc! {r#"
 
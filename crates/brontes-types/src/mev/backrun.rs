@@ -14,7 +14,7 @@ use rkyv::{Archive, Deserialize as rDeserialize, Serialize as rSerialize};
 use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
 
-use super::{Mev, MevType};
+use super::{Mev, MevType, OfaRefund, OfaRefundRedefined};
 use crate::{
     db::redefined_types::primitives::B256Redefined,
     normalized_actions::{ClickhouseVecNormalizedSwap, NormalizedSwap, NormalizedSwapRedefined},
@@ -33,6 +33,9 @@ pub struct AtomicArb {
     pub gas_details:  GasDetails,
     #[redefined(same_fields)]
     pub arb_type:     AtomicArbType,
+    /// Refunds paid back to the user whose transaction was backrun
+    #[serde(default)]
+    pub refunds:      Vec<OfaRefund>,
 }
 /// Represents the different types of atomic arb
 /// A triangle arb is a simple arb that goes from token A -> B -> C -> A
@@ -104,7 +107,7 @@ impl Serialize for AtomicArb {
     where
         S: Serializer,
     {
-        let mut ser_struct = serializer.serialize_struct("AtomicArb", 37)?;
+        let mut ser_struct = serializer.serialize_struct("AtomicArb", 42)?;
         ser_struct.serialize_field("tx_hash", &format!("{:?}", self.tx_hash))?;
         ser_struct.serialize_field("block_number", &self.block_number)?;
         ser_struct.serialize_field("trigger_tx", &format!("{:?}", self.trigger_tx))?;
@@ -129,6 +132,38 @@ impl Serialize for AtomicArb {
         );
         ser_struct.serialize_field("gas_details", &gas_details)?;
         ser_struct.serialize_field("arb_type", &self.arb_type.to_string())?;
+        ser_struct.serialize_field(
+            "refunds.tx_hash",
+            &self
+                .refunds
+                .iter()
+                .map(|r| format!("{:?}", r.tx_hash))
+                .collect::<Vec<_>>(),
+        )?;
+        ser_struct.serialize_field(
+            "refunds.payer",
+            &self
+                .refunds
+                .iter()
+                .map(|r| format!("{:?}", r.payer))
+                .collect::<Vec<_>>(),
+        )?;
+        ser_struct.serialize_field(
+            "refunds.recipient",
+            &self
+                .refunds
+                .iter()
+                .map(|r| format!("{:?}", r.recipient))
+                .collect::<Vec<_>>(),
+        )?;
+        ser_struct.serialize_field(
+            "refunds.amount",
+            &self.refunds.iter().map(|r| r.amount).collect::<Vec<_>>(),
+        )?;
+        ser_struct.serialize_field(
+            "refunds.usd_value",
+            &self.refunds.iter().map(|r| r.usd_value).collect::<Vec<_>>(),
+        )?;
         ser_struct.end()
     }
 }
@@ -148,5 +183,10 @@ impl DbRow for AtomicArb {
         "swaps.amount_out",
         "gas_details",
         "arb_type",
+        "refunds.tx_hash",
+        "refunds.payer",
+        "refunds.recipient",
+        "refunds.amount",
+        "refunds.usd_value",
     ];
 }
@@ -14,7 +14,7 @@ use rkyv::{Archive, Deserialize as rDeser, Serialize as rSer};
 use serde::{ser::SerializeStruct, Deserialize, Serialize};
 use serde_with::serde_as;
 
-use super::MevType;
+use super::{MevType, QuoteAssetPnl, QuoteAssetPnlRedefined};
 use crate::{
     db::redefined_types::primitives::{AddressRedefined, B256Redefined},
     display::utils::formate_etherscan_address_url,
@@ -57,6 +57,9 @@ pub struct MevBlock {
     pub proposer_mev_reward:         Option<u128>,
     pub proposer_profit_usd:         Option<f64>,
     pub total_mev_profit_usd:        f64,
+    // Total MEV profit & revenue denominated in the additional quote assets
+    #[serde(default)]
+    pub quote_pnl:                   Vec<QuoteAssetPnl>,
     pub possible_mev:                PossibleMevCollection,
 }
 
@@ -93,6 +96,17 @@ impl fmt::Display for MevBlock {
             "Total MEV Profit (USD):".purple().bold(),
             format_profit(self.total_mev_profit_usd)
         )?;
+        for pnl in &self.quote_pnl {
+            writeln!(
+                f,
+                "  - {} {:.6} (Revenue: {:.6})",
+                format!("Total MEV Profit ({:?}):", pnl.quote_asset)
+                    .purple()
+                    .bold(),
+                pnl.profit,
+                pnl.revenue
+            )?;
+        }
 
         writeln!(f, "  - {}", "Gas:".bold().underline())?;
         writeln!(
@@ -199,6 +213,7 @@ pub struct MevCount {
     pub atomic_backrun_count: Option<u64>,
     pub liquidation_count:    Option<u64>,
     pub searcher_tx_count:    Option<u64>,
+    pub nft_arb_count:        Option<u64>,
 }
 
 impl MevCount {
@@ -231,9 +246,36 @@ impl MevCount {
             MevType::JitCexDex => {
                 self.jit_cex_dex_count = Some(self.jit_cex_dex_count.unwrap_or_default().add(1))
             }
+            MevType::NftArb => {
+                self.nft_arb_count = Some(self.nft_arb_count.unwrap_or_default().add(1))
+            }
             _ => {}
         }
     }
+
+    /// Adds the counts of `other`, used to combine the counts of disjoint
+    /// block ranges
+    pub fn add_counts(&mut self, other: &MevCount) {
+        fn sum(a: Option<u64>, b: Option<u64>) -> Option<u64> {
+            match (a, b) {
+                (Some(a), Some(b)) => Some(a + b),
+                (a, b) => a.or(b),
+            }
+        }
+
+        self.bundle_count += other.bundle_count;
+        self.sandwich_count = sum(self.sandwich_count, other.sandwich_count);
+        self.cex_dex_trade_count = sum(self.cex_dex_trade_count, other.cex_dex_trade_count);
+        self.cex_dex_quote_count = sum(self.cex_dex_quote_count, other.cex_dex_quote_count);
+        self.cex_dex_rfq_count = sum(self.cex_dex_rfq_count, other.cex_dex_rfq_count);
+        self.jit_cex_dex_count = sum(self.jit_cex_dex_count, other.jit_cex_dex_count);
+        self.jit_count = sum(self.jit_count, other.jit_count);
+        self.jit_sandwich_count = sum(self.jit_sandwich_count, other.jit_sandwich_count);
+        self.atomic_backrun_count = sum(self.atomic_backrun_count, other.atomic_backrun_count);
+        self.liquidation_count = sum(self.liquidation_count, other.liquidation_count);
+        self.searcher_tx_count = sum(self.searcher_tx_count, other.searcher_tx_count);
+        self.nft_arb_count = sum(self.nft_arb_count, other.nft_arb_count);
+    }
 }
 self_convert_redefined!(MevCount);
 
@@ -268,6 +310,9 @@ impl fmt::Display for MevCount {
         if let Some(count) = self.searcher_tx_count {
             writeln!(f, "    - Searcher TXs: {}", count.to_string().bold())?;
         }
+        if let Some(count) = self.nft_arb_count {
+            writeln!(f, "    - Nft Arb: {}", count.to_string().bold())?;
+        }
 
         Ok(())
     }
@@ -358,7 +403,7 @@ impl Serialize for MevBlock {
     where
         S: serde::Serializer,
     {
-        let mut ser_struct = serializer.serialize_struct("MevBlock", 33)?;
+        let mut ser_struct = serializer.serialize_struct("MevBlock", 37)?;
 
         ser_struct.serialize_field("block_hash", &format!("{:?}", self.block_hash))?;
         ser_struct.serialize_field("block_number", &self.block_number)?;
@@ -396,6 +441,10 @@ impl Serialize for MevBlock {
             "mev_count.liquidation_count",
             &vec![self.mev_count.liquidation_count.unwrap_or_default()],
         )?;
+        ser_struct.serialize_field(
+            "mev_count.nft_arb_count",
+            &vec![self.mev_count.nft_arb_count.unwrap_or_default()],
+        )?;
 
         ser_struct.serialize_field("eth_price", &self.eth_price)?;
         ser_struct.serialize_field("total_gas_used", &self.total_gas_used)?;
@@ -427,6 +476,31 @@ impl Serialize for MevBlock {
         ser_struct.serialize_field("proposer_profit_usd", &self.proposer_profit_usd)?;
         ser_struct.serialize_field("total_mev_profit_usd", &self.total_mev_profit_usd)?;
 
+        ser_struct.serialize_field(
+            "quote_pnl.quote_asset",
+            &self
+                .quote_pnl
+                .iter()
+                .map(|pnl| format!("{:?}", pnl.quote_asset))
+                .collect::<Vec<_>>(),
+        )?;
+        ser_struct.serialize_field(
+            "quote_pnl.profit",
+            &self
+                .quote_pnl
+                .iter()
+                .map(|pnl| pnl.profit)
+                .collect::<Vec<_>>(),
+        )?;
+        ser_struct.serialize_field(
+            "quote_pnl.revenue",
+            &self
+                .quote_pnl
+                .iter()
+                .map(|pnl| pnl.revenue)
+                .collect::<Vec<_>>(),
+        )?;
+
         let mut possible_tx_hashes = Vec::new();
         let mut possible_tx_idxes = Vec::new();
         let mut possible_gas_coinbases = Vec::new();
@@ -515,6 +589,7 @@ impl DbRow for MevBlock {
         "mev_count.jit_sandwich_count",
         "mev_count.atomic_backrun_count",
         "mev_count.liquidation_count",
+        "mev_count.nft_arb_count",
         "eth_price",
         "total_gas_used",
         "total_priority_fee",
@@ -534,6 +609,9 @@ impl DbRow for MevBlock {
         "proposer_mev_reward",
         "proposer_profit_usd",
         "total_mev_profit_usd",
+        "quote_pnl.quote_asset",
+        "quote_pnl.profit",
+        "quote_pnl.revenue",
         "possible_mev.tx_hash",
         "possible_mev.tx_idx",
         "possible_mev.gas_details.coinbase_transfer",
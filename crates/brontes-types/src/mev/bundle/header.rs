@@ -51,6 +51,56 @@ pub struct BundleHeader {
     // if we generated this arb without pricing
     pub no_pricing_calculated: bool,
     pub balance_deltas:        Vec<TransactionAccounting>,
+    /// Profit & revenue denominated in each of the additional quote assets
+    #[serde(default)]
+    pub quote_pnl:             Vec<QuoteAssetPnl>,
+    /// Value refunded to the users whose transactions were backrun. Already
+    /// deducted from `profit_usd`
+    #[serde(default)]
+    pub refund_usd:            f64,
+}
+
+/// Profit & revenue denominated in a quote asset other than the primary (USD)
+/// quote asset of the run.
+#[serde_as]
+#[derive(Debug, Deserialize, Row, PartialEq, Clone, Default, Serialize, Redefined)]
+#[redefined_attr(derive(Debug, PartialEq, Clone, Serialize, rSerialize, rDeserialize, Archive))]
+pub struct QuoteAssetPnl {
+    pub quote_asset: Address,
+    pub profit:      f64,
+    pub revenue:     f64,
+}
+
+/// A refund of part of the mev back to the user whose transaction was
+/// backrun. Paid either by the searcher, in or after the bundle, or by the
+/// builder through an order flow auction (MEV-Share `refundRecipient`, MEV
+/// Blocker rebates)
+#[serde_as]
+#[derive(Debug, Deserialize, Row, PartialEq, Clone, Default, Serialize, Redefined)]
+#[redefined_attr(derive(Debug, PartialEq, Clone, Serialize, rSerialize, rDeserialize, Archive))]
+pub struct OfaRefund {
+    /// Transaction the refund was paid in
+    pub tx_hash:   B256,
+    pub payer:     Address,
+    pub recipient: Address,
+    /// Amount of ETH, or WETH, refunded
+    pub amount:    f64,
+    pub usd_value: f64,
+}
+
+impl QuoteAssetPnl {
+    /// Sums the pnl of each quote asset across the given sets
+    pub fn aggregate<'a>(pnls: impl Iterator<Item = &'a QuoteAssetPnl>) -> Vec<QuoteAssetPnl> {
+        pnls.fold(Vec::<QuoteAssetPnl>::new(), |mut acc, pnl| {
+            if let Some(existing) = acc.iter_mut().find(|p| p.quote_asset == pnl.quote_asset) {
+                existing.profit += pnl.profit;
+                existing.revenue += pnl.revenue;
+            } else {
+                acc.push(pnl.clone());
+            }
+            acc
+        })
+    }
 }
 
 #[serde_as]
@@ -124,7 +174,7 @@ impl Serialize for BundleHeader {
     where
         S: serde::Serializer,
     {
-        let mut ser_struct = serializer.serialize_struct("BundleHeader", 12)?;
+        let mut ser_struct = serializer.serialize_struct("BundleHeader", 18)?;
 
         ser_struct.serialize_field("block_number", &self.block_number)?;
         ser_struct.serialize_field("tx_index", &self.tx_index)?;
@@ -193,6 +243,20 @@ impl Serialize for BundleHeader {
             .collect_vec();
         ser_struct.serialize_field("balance_deltas.token_deltas", &balance_deltas_token_deltas)?;
 
+        let quote_pnl_assets = self
+            .quote_pnl
+            .iter()
+            .map(|pnl| format!("{:?}", pnl.quote_asset))
+            .collect_vec();
+        ser_struct.serialize_field("quote_pnl.quote_asset", &quote_pnl_assets)?;
+
+        let quote_pnl_profits = self.quote_pnl.iter().map(|pnl| pnl.profit).collect_vec();
+        ser_struct.serialize_field("quote_pnl.profit", &quote_pnl_profits)?;
+
+        let quote_pnl_revenues = self.quote_pnl.iter().map(|pnl| pnl.revenue).collect_vec();
+        ser_struct.serialize_field("quote_pnl.revenue", &quote_pnl_revenues)?;
+        ser_struct.serialize_field("refund_usd", &self.refund_usd)?;
+
         ser_struct.end()
     }
 }
@@ -213,5 +277,9 @@ impl DbRow for BundleHeader {
         "balance_deltas.address",
         "balance_deltas.name",
         "balance_deltas.token_deltas",
+        "quote_pnl.quote_asset",
+        "quote_pnl.profit",
+        "quote_pnl.revenue",
+        "refund_usd",
     ];
 }
@@ -0,0 +1,125 @@
+use alloy_primitives::Address;
+use itertools::Itertools;
+use redefined::Redefined;
+use reth_primitives::B256;
+use rkyv::{Archive, Deserialize as rDeserialize, Serialize as rSerialize};
+use serde::{Deserialize, Serialize};
+
+use super::MevType;
+use crate::{
+    db::redefined_types::primitives::*, implement_table_value_codecs_with_zc, FastHashMap,
+    GasDetails,
+};
+
+/// A reverted transaction from a known searcher that competed for the same
+/// opportunity as a bundle that landed in the block
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize, Redefined)]
+#[redefined_attr(derive(Debug, PartialEq, Clone, Serialize, rSerialize, rDeserialize, Archive))]
+pub struct FailedMevAttempt {
+    pub tx_hash:             B256,
+    pub tx_index:            u64,
+    pub eoa:                 Address,
+    pub mev_contract:        Option<Address>,
+    /// Tx hash of the landed bundle the attempt competed with
+    pub opportunity_tx_hash: B256,
+    #[redefined(same_fields)]
+    pub mev_type:            MevType,
+    #[redefined(same_fields)]
+    pub gas_details:         GasDetails,
+    pub wasted_gas_usd:      f64,
+}
+
+/// The failed mev attempts of a block
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize, Redefined)]
+#[redefined_attr(derive(Debug, PartialEq, Clone, Serialize, rSerialize, rDeserialize, Archive))]
+pub struct BlockFailedAttempts {
+    pub block_number: u64,
+    pub attempts:     Vec<FailedMevAttempt>,
+}
+
+implement_table_value_codecs_with_zc!(BlockFailedAttemptsRedefined);
+
+/// The gas burned by the failed attempts on a single opportunity
+#[derive(Debug, Clone, PartialEq)]
+pub struct OpportunityWastedGas {
+    pub opportunity_tx_hash: B256,
+    pub mev_type:            MevType,
+    pub attempts:            u64,
+    pub searchers:           u64,
+    pub wasted_gas_usd:      f64,
+}
+
+impl BlockFailedAttempts {
+    pub fn is_empty(&self) -> bool {
+        self.attempts.is_empty()
+    }
+
+    /// Gas burned per searcher eoa
+    pub fn wasted_gas_by_searcher(&self) -> FastHashMap<Address, f64> {
+        self.attempts
+            .iter()
+            .fold(FastHashMap::default(), |mut acc, attempt| {
+                *acc.entry(attempt.eoa).or_default() += attempt.wasted_gas_usd;
+                acc
+            })
+    }
+
+    /// Gas burned per landed opportunity, sorted by the opportunity's tx hash
+    pub fn wasted_gas_by_opportunity(&self) -> Vec<OpportunityWastedGas> {
+        self.attempts
+            .iter()
+            .into_group_map_by(|attempt| attempt.opportunity_tx_hash)
+            .into_iter()
+            .map(|(opportunity_tx_hash, attempts)| OpportunityWastedGas {
+                opportunity_tx_hash,
+                mev_type: attempts[0].mev_type,
+                attempts: attempts.len() as u64,
+                searchers: attempts.iter().map(|attempt| attempt.eoa).unique().count() as u64,
+                wasted_gas_usd: attempts.iter().map(|attempt| attempt.wasted_gas_usd).sum(),
+            })
+            .sorted_by_key(|opportunity| opportunity.opportunity_tx_hash)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn attempt(eoa: Address, opportunity: B256, wasted_gas_usd: f64) -> FailedMevAttempt {
+        FailedMevAttempt {
+            eoa,
+            opportunity_tx_hash: opportunity,
+            mev_type: MevType::AtomicArb,
+            wasted_gas_usd,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_wasted_gas_by_opportunity() {
+        let (searcher_a, searcher_b) = (Address::repeat_byte(1), Address::repeat_byte(2));
+        let (first, second) = (B256::repeat_byte(1), B256::repeat_byte(2));
+        let block = BlockFailedAttempts {
+            block_number: 1,
+            attempts:     vec![
+                attempt(searcher_a, first, 1.0),
+                attempt(searcher_a, first, 2.0),
+                attempt(searcher_b, first, 0.5),
+                attempt(searcher_b, second, 4.0),
+            ],
+        };
+
+        let opportunities = block.wasted_gas_by_opportunity();
+        assert_eq!(opportunities.len(), 2);
+        assert_eq!(opportunities[0].opportunity_tx_hash, first);
+        assert_eq!(opportunities[0].attempts, 3);
+        assert_eq!(opportunities[0].searchers, 2);
+        assert_eq!(opportunities[0].wasted_gas_usd, 3.5);
+        assert_eq!(opportunities[1].wasted_gas_usd, 4.0);
+
+        let by_searcher = block.wasted_gas_by_searcher();
+        assert_eq!(by_searcher[&searcher_a], 3.0);
+        assert_eq!(by_searcher[&searcher_b], 4.5);
+    }
+}
@@ -185,6 +185,8 @@ pub fn compose_sandwich_jit(mev: Vec<Bundle>) -> Option<Bundle> {
         balance_deltas:        classified_sandwich.balance_deltas,
         bribe_usd:             classified_sandwich.bribe_usd,
         no_pricing_calculated: classified_sandwich.no_pricing_calculated,
+        quote_pnl:             classified_sandwich.quote_pnl,
+        refund_usd:            classified_sandwich.refund_usd,
     };
 
     Some(Bundle { header: new_classified, data: BundleData::JitSandwich(jit_sand) })
@@ -16,6 +16,11 @@ pub mod block;
 pub use block::*;
 pub mod searcher_tx;
 pub use searcher_tx::*;
+pub mod nft_arb;
+pub use nft_arb::*;
 
 pub mod cex_dex_quotes;
 pub use cex_dex_quotes::*;
+
+pub mod failed_attempt;
+pub use failed_attempt::*;
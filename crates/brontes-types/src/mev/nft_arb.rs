@@ -0,0 +1,109 @@
+use std::fmt::Debug;
+
+use ::clickhouse::DbRow;
+use ::serde::ser::{SerializeStruct, Serializer};
+use ahash::HashSet;
+#[allow(unused)]
+use clickhouse::fixed_string::FixedString;
+use redefined::Redefined;
+use reth_primitives::B256;
+use rkyv::{Archive, Deserialize as rDeserialize, Serialize as rSerialize};
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+
+use super::{Mev, MevType};
+use crate::{
+    db::redefined_types::primitives::B256Redefined,
+    normalized_actions::{
+        ClickhouseVecNormalizedNftTrade, NormalizedNftTrade, NormalizedNftTradeRedefined,
+    },
+    GasDetails, Protocol,
+};
+
+/// Buys and sells of the same nft ids across one or more marketplaces within a
+/// single transaction
+#[serde_as]
+#[derive(Debug, Deserialize, PartialEq, Clone, Default, Redefined)]
+#[redefined_attr(derive(Debug, PartialEq, Clone, Serialize, rSerialize, rDeserialize, Archive))]
+pub struct NftArb {
+    pub tx_hash:      B256,
+    pub block_number: u64,
+    /// The trades of the arb in order of execution
+    pub trades:       Vec<NormalizedNftTrade>,
+    #[redefined(same_fields)]
+    pub gas_details:  GasDetails,
+}
+
+impl Mev for NftArb {
+    fn total_gas_paid(&self) -> u128 {
+        self.gas_details.gas_paid()
+    }
+
+    fn total_priority_fee_paid(&self, base_fee: u128) -> u128 {
+        self.gas_details.priority_fee(base_fee) * self.gas_details.gas_used
+    }
+
+    fn bribe(&self) -> u128 {
+        self.gas_details.coinbase_transfer.unwrap_or(0)
+    }
+
+    fn mev_transaction_hashes(&self) -> Vec<B256> {
+        vec![self.tx_hash]
+    }
+
+    fn mev_type(&self) -> MevType {
+        MevType::NftArb
+    }
+
+    fn protocols(&self) -> HashSet<Protocol> {
+        self.trades.iter().map(|trade| trade.protocol).collect()
+    }
+}
+
+impl Serialize for NftArb {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut ser_struct = serializer.serialize_struct("NftArb", 12)?;
+        ser_struct.serialize_field("tx_hash", &format!("{:?}", self.tx_hash))?;
+        ser_struct.serialize_field("block_number", &self.block_number)?;
+        let trades: ClickhouseVecNormalizedNftTrade = self
+            .trades
+            .clone()
+            .try_into()
+            .map_err(serde::ser::Error::custom)?;
+        ser_struct.serialize_field("trades.trace_idx", &trades.trace_index)?;
+        ser_struct.serialize_field("trades.marketplace", &trades.marketplace)?;
+        ser_struct.serialize_field("trades.buyer", &trades.buyer)?;
+        ser_struct.serialize_field("trades.seller", &trades.seller)?;
+        ser_struct.serialize_field("trades.collection", &trades.collection)?;
+        ser_struct.serialize_field("trades.token_ids", &trades.token_ids)?;
+        ser_struct.serialize_field("trades.payment_token", &trades.payment_token)?;
+        ser_struct.serialize_field("trades.price", &trades.price)?;
+        let gas_details = (
+            self.gas_details.coinbase_transfer,
+            self.gas_details.priority_fee,
+            self.gas_details.gas_used,
+            self.gas_details.effective_gas_price,
+        );
+        ser_struct.serialize_field("gas_details", &gas_details)?;
+        ser_struct.end()
+    }
+}
+
+impl DbRow for NftArb {
+    const COLUMN_NAMES: &'static [&'static str] = &[
+        "tx_hash",
+        "block_number",
+        "trades.trace_idx",
+        "trades.marketplace",
+        "trades.buyer",
+        "trades.seller",
+        "trades.collection",
+        "trades.token_ids",
+        "trades.payment_token",
+        "trades.price",
+        "gas_details",
+    ];
+}
@@ -3,16 +3,18 @@ use std::fmt::Debug;
 use ::clickhouse::DbRow;
 use ::serde::ser::{SerializeStruct, Serializer};
 use ahash::HashSet;
-use malachite::Rational;
 use redefined::Redefined;
-use reth_primitives::{Address, B256};
+use reth_primitives::B256;
 use rkyv::{Archive, Deserialize as rDeserialize, Serialize as rSerialize};
 use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
 
-use super::{Mev, MevType};
+use super::{Mev, MevType, OfaRefund, OfaRefundRedefined};
 use crate::{
-    db::{redefined_types::primitives::*, token_info::TokenInfoWithAddress},
+    db::{
+        redefined_types::primitives::*,
+        token_info::{TokenInfoWithAddress, TokenInfoWithAddressRedefined},
+    },
     normalized_actions::*,
     ClickhouseVecGasDetails, Protocol,
 };
@@ -88,17 +90,30 @@ pub struct Sandwich {
     /// Gas details for each backrunning transaction.
     #[redefined(same_fields)]
     pub backrun_gas_details:      GasDetails,
+    /// Refunds paid back to the victims
+    #[serde(default)]
+    pub refunds:                  Vec<OfaRefund>,
+    /// Loss of each victim, measured by re-executing the victim transactions
+    /// without the frontruns. The pipeline doesn't simulate the counterfactual,
+    /// so this stays empty until `brontes db victim-loss` is run over the block
+    #[serde(default)]
+    pub victim_losses:            Vec<VictimLossAmount>,
 }
 
-/// calcuation for the loss per user
-#[derive(Debug, Deserialize, PartialEq, Clone, Default)]
+/// The loss of a victim compared to executing on the state before the
+/// frontrun. Exact input swaps lose output, exact output swaps pay extra input
+#[derive(Debug, Deserialize, Serialize, PartialEq, Clone, Default, Redefined)]
+#[redefined_attr(derive(Debug, PartialEq, Clone, Serialize, rSerialize, rDeserialize, Archive))]
 pub struct VictimLossAmount {
-    pub tx_hash:           B256,
-    pub vicitim_eoa:       Address,
-    pub token:             TokenInfoWithAddress,
-    pub token_amount_lost: Rational,
-    /// is zero if we don't have a price for the given token
-    pub amount_lost_usd:   Rational,
+    pub tx_hash:         B256,
+    pub token_in:        TokenInfoWithAddress,
+    /// `token_in` paid on top of what the victim would have paid
+    pub extra_amount_in: f64,
+    pub token_out:       TokenInfoWithAddress,
+    /// `token_out` the victim would have received on top of what it got
+    pub lost_amount_out: f64,
+    /// is zero if we don't have a price for the tokens
+    pub amount_lost_usd: f64,
 }
 
 impl Mev for Sandwich {
@@ -166,7 +181,7 @@ impl Serialize for Sandwich {
     where
         S: Serializer,
     {
-        let mut ser_struct = serializer.serialize_struct("Sandwich", 35)?;
+        let mut ser_struct = serializer.serialize_struct("Sandwich", 57)?;
         ser_struct.serialize_field("block_number", &self.block_number)?;
 
         // frontrun
@@ -282,6 +297,88 @@ impl Serialize for Sandwich {
             &vec![self.backrun_gas_details.effective_gas_price],
         )?;
 
+        ser_struct.serialize_field(
+            "refunds.tx_hash",
+            &self
+                .refunds
+                .iter()
+                .map(|r| format!("{:?}", r.tx_hash))
+                .collect::<Vec<_>>(),
+        )?;
+        ser_struct.serialize_field(
+            "refunds.payer",
+            &self
+                .refunds
+                .iter()
+                .map(|r| format!("{:?}", r.payer))
+                .collect::<Vec<_>>(),
+        )?;
+        ser_struct.serialize_field(
+            "refunds.recipient",
+            &self
+                .refunds
+                .iter()
+                .map(|r| format!("{:?}", r.recipient))
+                .collect::<Vec<_>>(),
+        )?;
+        ser_struct.serialize_field(
+            "refunds.amount",
+            &self.refunds.iter().map(|r| r.amount).collect::<Vec<_>>(),
+        )?;
+        ser_struct.serialize_field(
+            "refunds.usd_value",
+            &self.refunds.iter().map(|r| r.usd_value).collect::<Vec<_>>(),
+        )?;
+
+        ser_struct.serialize_field(
+            "victim_losses.tx_hash",
+            &self
+                .victim_losses
+                .iter()
+                .map(|l| format!("{:?}", l.tx_hash))
+                .collect::<Vec<_>>(),
+        )?;
+        ser_struct.serialize_field(
+            "victim_losses.token_in",
+            &self
+                .victim_losses
+                .iter()
+                .map(|l| (format!("{:?}", l.token_in.address), l.token_in.symbol.clone()))
+                .collect::<Vec<_>>(),
+        )?;
+        ser_struct.serialize_field(
+            "victim_losses.extra_amount_in",
+            &self
+                .victim_losses
+                .iter()
+                .map(|l| l.extra_amount_in)
+                .collect::<Vec<_>>(),
+        )?;
+        ser_struct.serialize_field(
+            "victim_losses.token_out",
+            &self
+                .victim_losses
+                .iter()
+                .map(|l| (format!("{:?}", l.token_out.address), l.token_out.symbol.clone()))
+                .collect::<Vec<_>>(),
+        )?;
+        ser_struct.serialize_field(
+            "victim_losses.lost_amount_out",
+            &self
+                .victim_losses
+                .iter()
+                .map(|l| l.lost_amount_out)
+                .collect::<Vec<_>>(),
+        )?;
+        ser_struct.serialize_field(
+            "victim_losses.amount_lost_usd",
+            &self
+                .victim_losses
+                .iter()
+                .map(|l| l.amount_lost_usd)
+                .collect::<Vec<_>>(),
+        )?;
+
         ser_struct.end()
     }
 }
@@ -333,5 +430,16 @@ impl DbRow for Sandwich {
         "backrun_gas_details.priority_fee",
         "backrun_gas_details.gas_used",
         "backrun_gas_details.effective_gas_price",
+        "refunds.tx_hash",
+        "refunds.payer",
+        "refunds.recipient",
+        "refunds.amount",
+        "refunds.usd_value",
+        "victim_losses.tx_hash",
+        "victim_losses.token_in",
+        "victim_losses.extra_amount_in",
+        "victim_losses.token_out",
+        "victim_losses.lost_amount_out",
+        "victim_losses.amount_lost_usd",
     ];
 }
@@ -1,7 +1,7 @@
 use std::{collections::hash_map::Entry, hash::Hash};
 
-use alloy_primitives::Address;
-use malachite::Rational;
+use alloy_primitives::{Address, U256};
+use malachite::{num::basic::traits::Zero, Rational};
 
 use super::{comparison::ActionComparison, Action};
 use crate::FastHashMap;
@@ -9,17 +9,31 @@ use crate::FastHashMap;
 pub type TokenDeltas = FastHashMap<Address, Rational>;
 pub type AddressDeltas = FastHashMap<Address, TokenDeltas>;
 
+/// (collection, token id) of an ERC721 or ERC1155 token
+pub type NftId = (Address, U256);
+pub type NftDeltas = FastHashMap<Address, FastHashMap<NftId, Rational>>;
+
 /// apply's the given actions token deltas to the map;
 pub trait TokenAccounting {
     fn apply_token_deltas(&self, delta_map: &mut AddressDeltas);
 }
 
+/// apply's the given actions nft id deltas to the map. Kept apart from
+/// [`TokenAccounting`] as token ids of the same collection are not fungible
+pub trait NftAccounting {
+    fn apply_nft_deltas(&self, delta_map: &mut NftDeltas);
+}
+
 /// For a given Vector of actions, will go through and apply the token deltas,
 /// de-duping them as it is doing the calculations
 pub trait ActionAccounting {
     /// for a given list of actions, this will dedup the actions and then apply
     /// the token deltas for it
     fn account_for_actions(self) -> AddressDeltas;
+
+    /// for a given list of actions, this will apply the nft id deltas of
+    /// every nft transfer
+    fn account_for_nft_actions(self) -> NftDeltas;
 }
 
 fn accounting_calc(accounting: &mut Accounting, next: Action) {
@@ -54,6 +68,13 @@ impl<IT: Iterator<Item = Action>> ActionAccounting for IT {
 
         accounting.delta_map
     }
+
+    fn account_for_nft_actions(self) -> NftDeltas {
+        let mut delta_map = NftDeltas::default();
+        self.for_each(|action| action.apply_nft_deltas(&mut delta_map));
+
+        delta_map
+    }
 }
 
 /// Holds all accounting info.
@@ -89,7 +110,49 @@ pub fn apply_delta<K: PartialEq + Hash + Eq>(
     }
 }
 
+pub fn apply_nft_delta(address: Address, id: NftId, amount: Rational, delta_map: &mut NftDeltas) {
+    *delta_map
+        .entry(address)
+        .or_default()
+        .entry(id)
+        .or_insert(Rational::ZERO) += amount;
+}
+
 #[cfg(test)]
 pub mod test {
-    // todo: add tests
+    use alloy_primitives::{Address, U256};
+    use malachite::Rational;
+
+    use super::ActionAccounting;
+    use crate::normalized_actions::{Action, NftStandard, NormalizedNftTransfer};
+
+    #[test]
+    fn test_nft_round_trip_nets_out() {
+        let searcher = Address::with_last_byte(1);
+        let collection = Address::with_last_byte(2);
+        let transfer = |from, to| {
+            Action::NftTransfer(NormalizedNftTransfer {
+                standard: NftStandard::Erc721,
+                collection,
+                from,
+                to,
+                token_id: U256::from(7),
+                amount: U256::from(1),
+                ..Default::default()
+            })
+        };
+
+        let deltas = vec![
+            transfer(Address::with_last_byte(3), searcher),
+            transfer(searcher, Address::with_last_byte(4)),
+        ]
+        .into_iter()
+        .account_for_nft_actions();
+
+        assert_eq!(deltas[&searcher][&(collection, U256::from(7))], Rational::from(0));
+        assert_eq!(
+            deltas[&Address::with_last_byte(4)][&(collection, U256::from(7))],
+            Rational::from(1)
+        );
+    }
 }
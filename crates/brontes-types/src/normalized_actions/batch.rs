@@ -2,14 +2,21 @@ use std::fmt::Debug;
 
 use alloy_primitives::{Address, U256};
 use clickhouse::Row;
+use redefined::Redefined;
+use rkyv::{Archive, Deserialize as rDeserialize, Serialize as rSerialize};
 use serde::{Deserialize, Serialize};
 
-use super::accounting::{apply_delta, AddressDeltas, TokenAccounting};
+use super::{
+    accounting::{apply_delta, AddressDeltas, TokenAccounting},
+    NormalizedSwapRedefined,
+};
 pub use super::{Action, NormalizedSwap};
-use crate::Protocol;
+use crate::{db::redefined_types::primitives::*, Protocol};
 
-#[derive(Debug, Default, Serialize, Clone, Row, PartialEq, Eq, Deserialize)]
+#[derive(Debug, Default, Serialize, Clone, Row, PartialEq, Eq, Deserialize, Redefined)]
+#[redefined_attr(derive(Debug, PartialEq, Clone, Serialize, rSerialize, rDeserialize, Archive))]
 pub struct NormalizedBatch {
+    #[redefined(same_fields)]
     pub protocol:            Protocol,
     pub trace_index:         u64,
     pub solver:              Address,
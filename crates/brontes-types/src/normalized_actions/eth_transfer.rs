@@ -2,13 +2,16 @@ use std::fmt::Debug;
 
 use alloy_primitives::{Address, U256};
 use clickhouse::Row;
+use redefined::Redefined;
+use rkyv::{Archive, Deserialize as rDeserialize, Serialize as rSerialize};
 use serde::{Deserialize, Serialize};
 
 use super::accounting::{apply_delta, AddressDeltas, TokenAccounting};
 pub use super::{Action, NormalizedSwap};
-use crate::{constants::ETH_ADDRESS, ToScaledRational};
+use crate::{constants::ETH_ADDRESS, db::redefined_types::primitives::*, ToScaledRational};
 
-#[derive(Debug, Default, Serialize, Clone, Row, PartialEq, Eq, Deserialize)]
+#[derive(Debug, Default, Serialize, Clone, Row, PartialEq, Eq, Deserialize, Redefined)]
+#[redefined_attr(derive(Debug, PartialEq, Clone, Serialize, rSerialize, rDeserialize, Archive))]
 pub struct NormalizedEthTransfer {
     pub trace_index:       u64,
     pub from:              Address,
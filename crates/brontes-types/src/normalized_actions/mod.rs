@@ -8,6 +8,7 @@ pub mod lending;
 pub mod liquidation;
 pub mod liquidity;
 pub mod multi_callframe;
+pub mod nft;
 pub mod pool;
 pub mod self_destruct;
 pub mod swaps;
@@ -15,7 +16,7 @@ pub mod transfer;
 use std::fmt::Debug;
 
 use ::clickhouse::DbRow;
-use accounting::{AddressDeltas, TokenAccounting};
+use accounting::{AddressDeltas, NftAccounting, NftDeltas, TokenAccounting};
 pub use aggregator::*;
 use alloy_primitives::{Address, Bytes, Log};
 pub use batch::*;
@@ -26,6 +27,7 @@ pub use lending::*;
 pub use liquidation::*;
 pub use liquidity::*;
 pub use multi_callframe::*;
+pub use nft::*;
 pub use pool::*;
 use reth_rpc_types::trace::parity::Action as TraceAction;
 pub use self_destruct::*;
@@ -97,6 +99,8 @@ impl NormalizedAction for Action {
             Self::NewPool(p) => p.trace_index,
             Self::PoolConfigUpdate(p) => p.trace_index,
             Self::Aggregator(a) => a.trace_index,
+            Self::NftTransfer(n) => n.trace_index,
+            Self::NftTrade(n) => n.trace_index,
             Self::Revert => unreachable!("no trace index for revert"),
         }
     }
@@ -119,6 +123,8 @@ pub enum Action {
     NewPool(NormalizedNewPool),
     PoolConfigUpdate(NormalizedPoolConfigUpdate),
     Aggregator(NormalizedAggregator),
+    NftTransfer(NormalizedNftTransfer),
+    NftTrade(NormalizedNftTrade),
     Unclassified(TransactionTraceWithLogs),
     Revert,
 }
@@ -141,6 +147,8 @@ impl InsertRow for Action {
             Action::PoolConfigUpdate(_) => todo!(),
             Action::Unclassified(..) | Action::Revert => panic!(),
             Action::Aggregator(_) => NormalizedAggregator::COLUMN_NAMES,
+            Action::NftTransfer(_) => NormalizedNftTransfer::COLUMN_NAMES,
+            Action::NftTrade(_) => NormalizedNftTrade::COLUMN_NAMES,
         }
     }
 }
@@ -163,6 +171,8 @@ impl serde::Serialize for Action {
             Action::Liquidation(c) => c.serialize(serializer),
             Action::SelfDestruct(sd) => sd.serialize(serializer),
             Action::EthTransfer(et) => et.serialize(serializer),
+            Action::NftTransfer(nt) => nt.serialize(serializer),
+            Action::NftTrade(nt) => nt.serialize(serializer),
             Action::Unclassified(trace) => (trace).serialize(serializer),
             action => format!("{:?}", action).serialize(serializer),
             //action => unreachable!("no action serialization for {action:?}"),
@@ -218,9 +228,16 @@ impl Action {
                     from: a.from,
                     ..Default::default()
                 }),
+                Self::NftTrade(n) => (!n.msg_value.is_zero()).then(|| NormalizedEthTransfer {
+                    value: n.msg_value,
+                    to: n.marketplace,
+                    from: n.buyer,
+                    ..Default::default()
+                }),
                 Self::Mint(_) => None,
                 Self::Burn(_) => None,
                 Self::Transfer(_) => None,
+                Self::NftTransfer(_) => None,
                 Self::Collect(_) => None,
                 Self::SelfDestruct(_) => None,
                 Self::EthTransfer(_) => None,
@@ -258,6 +275,8 @@ impl Action {
             Self::NewPool(p) => p.trace_index,
             Self::PoolConfigUpdate(p) => p.trace_index,
             Self::Aggregator(a) => a.trace_index,
+            Self::NftTransfer(n) => n.trace_index,
+            Self::NftTrade(n) => n.trace_index,
             Self::Revert => return None,
         })
     }
@@ -333,6 +352,8 @@ impl Action {
                 reth_rpc_types::trace::parity::Action::Selfdestruct(s) => s.address,
             },
             Action::EthTransfer(t) => t.to,
+            Action::NftTransfer(t) => t.to,
+            Action::NftTrade(t) => t.marketplace,
             Action::NewPool(p) => p.pool_address,
             Action::PoolConfigUpdate(p) => p.pool_address,
             Action::Revert => Address::ZERO,
@@ -359,6 +380,8 @@ impl Action {
                 reth_rpc_types::trace::parity::Action::Selfdestruct(s) => s.address,
             },
             Action::EthTransfer(t) => t.from,
+            Action::NftTransfer(t) => t.from,
+            Action::NftTrade(t) => t.buyer,
             Action::Revert => unreachable!(),
             Action::NewPool(_) => Address::ZERO,
             Action::PoolConfigUpdate(_) => Address::ZERO,
@@ -415,6 +438,14 @@ impl Action {
         matches!(self, Action::Transfer(_))
     }
 
+    pub const fn is_nft_transfer(&self) -> bool {
+        matches!(self, Action::NftTransfer(_))
+    }
+
+    pub const fn is_nft_trade(&self) -> bool {
+        matches!(self, Action::NftTrade(_))
+    }
+
     pub const fn is_collect(&self) -> bool {
         matches!(self, Action::Collect(_))
     }
@@ -448,6 +479,7 @@ impl Action {
             Action::NewPool(p) => p.protocol,
             Action::PoolConfigUpdate(p) => p.protocol,
             Action::Aggregator(a) => a.protocol,
+            Action::NftTrade(n) => n.protocol,
             _ => Protocol::Unknown,
         }
     }
@@ -526,7 +558,9 @@ extra_impls!(
     (FlashLoan, NormalizedFlashLoan),
     (Aggregator, NormalizedAggregator),
     (Batch, NormalizedBatch),
-    (NewPool, NormalizedNewPool)
+    (NewPool, NormalizedNewPool),
+    (NftTransfer, NormalizedNftTransfer),
+    (NftTrade, NormalizedNftTrade)
 );
 
 /// Custom impl for itering over swaps and swap with fee
@@ -578,6 +612,8 @@ impl TokenAccounting for Action {
             Action::SwapWithFee(swap_with_fee) => swap_with_fee.swap.apply_token_deltas(delta_map),
             Action::Collect(collect) => collect.apply_token_deltas(delta_map),
             Action::EthTransfer(eth_transfer) => eth_transfer.apply_token_deltas(delta_map),
+            Action::NftTransfer(nft_transfer) => nft_transfer.apply_token_deltas(delta_map),
+            Action::NftTrade(nft_trade) => nft_trade.apply_token_deltas(delta_map),
             Action::Unclassified(_) => (), /* Potentially no token deltas to apply, adjust as */
             // necessary
             Action::SelfDestruct(_self_destruct) => (),
@@ -587,3 +623,16 @@ impl TokenAccounting for Action {
         }
     }
 }
+
+impl NftAccounting for Action {
+    fn apply_nft_deltas(&self, delta_map: &mut NftDeltas) {
+        match self {
+            Action::NftTransfer(nft_transfer) => nft_transfer.apply_nft_deltas(delta_map),
+            Action::Aggregator(aggregator) => aggregator
+                .child_actions
+                .iter()
+                .for_each(|action| action.apply_nft_deltas(delta_map)),
+            _ => (),
+        }
+    }
+}
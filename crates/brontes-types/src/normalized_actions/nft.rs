@@ -0,0 +1,188 @@
+use std::{
+    fmt,
+    fmt::{Debug, Display},
+};
+
+use alloy_primitives::{Address, U256};
+use clickhouse::Row;
+use colored::Colorize;
+use itertools::Itertools;
+use malachite::Rational;
+use redefined::{self_convert_redefined, Redefined};
+use rkyv::{Archive, Deserialize as rDeserialize, Serialize as rSerialize};
+use serde::{Deserialize, Serialize};
+
+use super::accounting::{
+    apply_nft_delta, AddressDeltas, NftAccounting, NftDeltas, TokenAccounting,
+};
+use crate::{
+    db::{
+        redefined_types::{malachite::*, primitives::*},
+        token_info::{TokenInfoWithAddress, TokenInfoWithAddressRedefined},
+    },
+    rational_to_u256_fraction, Protocol, ToFloatNearest, ToScaledRational,
+};
+
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    Serialize,
+    Deserialize,
+    rSerialize,
+    rDeserialize,
+    Archive,
+)]
+pub enum NftStandard {
+    #[default]
+    Erc721,
+    Erc1155,
+}
+
+self_convert_redefined!(NftStandard);
+
+impl Display for NftStandard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NftStandard::Erc721 => write!(f, "ERC721"),
+            NftStandard::Erc1155 => write!(f, "ERC1155"),
+        }
+    }
+}
+
+/// A single ERC721 or ERC1155 token id moving between two addresses. ERC721
+/// transfers always have an amount of one
+#[derive(Debug, Default, Serialize, Deserialize, Clone, Row, PartialEq, Eq, Redefined)]
+#[redefined_attr(derive(Debug, PartialEq, Clone, Serialize, rSerialize, rDeserialize, Archive))]
+pub struct NormalizedNftTransfer {
+    pub trace_index: u64,
+    #[redefined(same_fields)]
+    pub standard:    NftStandard,
+    pub collection:  Address,
+    pub from:        Address,
+    pub to:          Address,
+    pub token_id:    U256,
+    pub amount:      U256,
+}
+
+impl TokenAccounting for NormalizedNftTransfer {
+    /// Nft ids are accounted for separately through [`NftAccounting`]
+    fn apply_token_deltas(&self, _: &mut AddressDeltas) {}
+}
+
+impl NftAccounting for NormalizedNftTransfer {
+    fn apply_nft_deltas(&self, delta_map: &mut NftDeltas) {
+        let amount = self.amount.to_scaled_rational(0);
+        let id = (self.collection, self.token_id);
+
+        apply_nft_delta(self.from, id, -amount.clone(), delta_map);
+        apply_nft_delta(self.to, id, amount, delta_map);
+    }
+}
+
+impl Display for NormalizedNftTransfer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Transfer {} #{} of {:?} from {:?} to {:?}",
+            self.standard, self.token_id, self.collection, self.from, self.to
+        )
+    }
+}
+
+/// A sale of one or more token ids of a collection on a nft marketplace.
+///
+/// `price` is the total amount of `payment_token` paid by the buyer, including
+/// marketplace and royalty fees. The trade only summarizes the sale, the
+/// payment and nft legs are accounted for by the transfers it contains
+#[derive(Debug, Default, Serialize, Deserialize, Clone, Row, PartialEq, Eq, Redefined)]
+#[redefined_attr(derive(Debug, PartialEq, Clone, Serialize, rSerialize, rDeserialize, Archive))]
+pub struct NormalizedNftTrade {
+    #[redefined(same_fields)]
+    pub protocol:      Protocol,
+    pub trace_index:   u64,
+    /// The marketplace or pair contract the trade was settled by
+    pub marketplace:   Address,
+    pub buyer:         Address,
+    pub seller:        Address,
+    #[redefined(same_fields)]
+    pub standard:      NftStandard,
+    pub collection:    Address,
+    pub token_ids:     Vec<U256>,
+    /// Amount of each token id, always one for ERC721
+    pub amounts:       Vec<U256>,
+    pub payment_token: TokenInfoWithAddress,
+    pub price:         Rational,
+    pub msg_value:     U256,
+}
+
+impl NormalizedNftTrade {
+    /// The (collection, token id) pairs that changed hands
+    pub fn nft_ids(&self) -> impl Iterator<Item = (Address, U256)> + '_ {
+        self.token_ids.iter().map(|id| (self.collection, *id))
+    }
+}
+
+impl TokenAccounting for NormalizedNftTrade {
+    fn apply_token_deltas(&self, _: &mut AddressDeltas) {}
+}
+
+impl Display for NormalizedNftTrade {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let ids = self.token_ids.iter().map(|id| format!("#{id}")).join(", ");
+        let price = format!("{:.4}", self.price.clone().to_float()).red();
+        let protocol: colored::ColoredString = self.protocol.to_string().bold();
+        write!(
+            f,
+            "Buy {} of {:?} for {} {} via {}",
+            ids, self.collection, price, self.payment_token.symbol, protocol
+        )
+    }
+}
+
+pub struct ClickhouseVecNormalizedNftTrade {
+    pub trace_index:   Vec<u64>,
+    pub marketplace:   Vec<String>,
+    pub buyer:         Vec<String>,
+    pub seller:        Vec<String>,
+    pub collection:    Vec<String>,
+    pub token_ids:     Vec<Vec<U256>>,
+    pub payment_token: Vec<(String, String)>,
+    pub price:         Vec<([u8; 32], [u8; 32])>,
+}
+
+impl TryFrom<Vec<NormalizedNftTrade>> for ClickhouseVecNormalizedNftTrade {
+    type Error = eyre::Report;
+
+    fn try_from(value: Vec<NormalizedNftTrade>) -> eyre::Result<Self> {
+        Ok(ClickhouseVecNormalizedNftTrade {
+            trace_index:   value.iter().map(|val| val.trace_index).collect(),
+            marketplace:   value
+                .iter()
+                .map(|val| format!("{:?}", val.marketplace))
+                .collect(),
+            buyer:         value.iter().map(|val| format!("{:?}", val.buyer)).collect(),
+            seller:        value
+                .iter()
+                .map(|val| format!("{:?}", val.seller))
+                .collect(),
+            collection:    value
+                .iter()
+                .map(|val| format!("{:?}", val.collection))
+                .collect(),
+            token_ids:     value.iter().map(|val| val.token_ids.clone()).collect(),
+            payment_token: value
+                .iter()
+                .map(|val| val.payment_token.clickhouse_fmt())
+                .collect(),
+            price:         value
+                .iter()
+                .map(|val| rational_to_u256_fraction(&val.price))
+                .collect::<eyre::Result<Vec<_>>>()?,
+        })
+    }
+}
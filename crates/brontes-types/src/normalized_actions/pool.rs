@@ -1,11 +1,15 @@
 use alloy_primitives::Address;
-use serde::Deserialize;
+use redefined::Redefined;
+use rkyv::{Archive, Deserialize as rDeserialize, Serialize as rSerialize};
+use serde::{Deserialize, Serialize};
 
-use crate::Protocol;
+use crate::{db::redefined_types::primitives::*, Protocol};
 
-#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq, Redefined)]
+#[redefined_attr(derive(Debug, PartialEq, Clone, Serialize, rSerialize, rDeserialize, Archive))]
 pub struct NormalizedNewPool {
     pub trace_index:  u64,
+    #[redefined(same_fields)]
     pub protocol:     Protocol,
     pub pool_address: Address,
     pub tokens:       Vec<Address>,
@@ -28,9 +32,11 @@ impl TryFrom<NormalizedNewPool> for NormalizedPoolConfigUpdate {
     }
 }
 
-#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq, Redefined)]
+#[redefined_attr(derive(Debug, PartialEq, Clone, Serialize, rSerialize, rDeserialize, Archive))]
 pub struct NormalizedPoolConfigUpdate {
     pub trace_index:  u64,
+    #[redefined(same_fields)]
     pub protocol:     Protocol,
     pub pool_address: Address,
     pub tokens:       Vec<Address>,
@@ -1,11 +1,16 @@
 use std::fmt::Debug;
 
 use clickhouse::Row;
+use redefined::Redefined;
 use reth_primitives::{Address, U256};
 use reth_rpc_types::trace::parity::SelfdestructAction;
+use rkyv::{Archive, Deserialize as rDeserialize, Serialize as rSerialize};
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Serialize, Clone, Row, PartialEq, Eq, Deserialize)]
+use crate::db::traces::SelfdestructActionRedefined;
+
+#[derive(Debug, Serialize, Clone, Row, PartialEq, Eq, Deserialize, Redefined)]
+#[redefined_attr(derive(Debug, PartialEq, Clone, Serialize, rSerialize, rDeserialize, Archive))]
 pub struct SelfdestructWithIndex {
     pub trace_index:   u64,
     pub self_destruct: SelfdestructAction,
@@ -27,7 +27,8 @@ use crate::{
     rational_to_u256_fraction, Protocol, ToFloatNearest,
 };
 
-#[derive(Debug, Default, Serialize, Deserialize, Clone, Row, PartialEq, Eq)]
+#[derive(Debug, Default, Serialize, Deserialize, Clone, Row, PartialEq, Eq, Redefined)]
+#[redefined_attr(derive(Debug, PartialEq, Clone, Serialize, rSerialize, rDeserialize, Archive))]
 pub struct NormalizedSwapWithFee {
     pub swap:       NormalizedSwap,
     pub fee_token:  TokenInfoWithAddress,
@@ -103,6 +103,11 @@ utils!(
         Dodo,
         #[default]
         Unknown,
+        UniswapV2Fork,
+        UniswapV3Fork,
+        Seaport,
+        BlurExchange,
+        Sudoswap,
     }
 );
 
@@ -147,6 +152,11 @@ impl Protocol {
             Protocol::PropellerLabsSolver => ("Propeller Labs Solver", ""),
             Protocol::Dodo => ("Dodo", "V1/V2"),
             Protocol::Unknown => ("Unknown", "Unknown"),
+            Protocol::UniswapV2Fork => ("Uniswap", "V2 Fork"),
+            Protocol::UniswapV3Fork => ("Uniswap", "V3 Fork"),
+            Protocol::Seaport => ("Seaport", "V1.5/V1.6"),
+            Protocol::BlurExchange => ("Blur", "Exchange"),
+            Protocol::Sudoswap => ("Sudoswap", "V1"),
         }
     }
 
@@ -173,6 +183,11 @@ impl Protocol {
             "dodov1/v2" => Protocol::Dodo,
             "pancakeswapv2" => Protocol::PancakeSwapV2,
             "pancakeswapv3" => Protocol::PancakeSwapV3,
+            "uniswapv2 fork" => Protocol::UniswapV2Fork,
+            "uniswapv3 fork" => Protocol::UniswapV3Fork,
+            "seaportv1.5/v1.6" => Protocol::Seaport,
+            "blurexchange" => Protocol::BlurExchange,
+            "sudoswapv1" => Protocol::Sudoswap,
             _ => Protocol::Unknown,
         }
     }
@@ -231,6 +246,11 @@ impl fmt::Display for Protocol {
                 Protocol::PropellerLabsSolver => "Propeller Labs",
                 Protocol::Dodo => "Dodo",
                 Protocol::Unknown => "Unknown",
+                Protocol::UniswapV2Fork => "Uni V2 Fork",
+                Protocol::UniswapV3Fork => "Uni V3 Fork",
+                Protocol::Seaport => "Seaport",
+                Protocol::BlurExchange => "Blur",
+                Protocol::Sudoswap => "Sudoswap",
             }
         )
     }
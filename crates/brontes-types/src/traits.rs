@@ -1,4 +1,4 @@
-use alloy_primitives::TxHash;
+use alloy_primitives::{Log as PrimitiveLog, TxHash};
 use alloy_rpc_types::AnyReceiptEnvelope;
 use reth_primitives::{
     Address, BlockId, BlockNumber, BlockNumberOrTag, Bytecode, Bytes, Header, StorageValue, B256,
@@ -9,6 +9,15 @@ use reth_rpc_types::{
 
 use crate::structured_trace::TxTrace;
 
+/// Outcome of a transaction executed in a counterfactual block
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimulatedTx {
+    pub tx_hash:  TxHash,
+    pub success:  bool,
+    pub gas_used: u64,
+    pub logs:     Vec<PrimitiveLog>,
+}
+
 #[async_trait::async_trait]
 #[auto_impl::auto_impl(Box)]
 pub trait TracingProvider: Send + Sync + 'static {
@@ -52,6 +61,17 @@ pub trait TracingProvider: Send + Sync + 'static {
 
     async fn block_and_tx_index(&self, hash: TxHash) -> eyre::Result<(u64, usize)>;
 
+    /// Replays the block in order, leaving out the transactions in `skip`, up
+    /// to and including the last of `txs`. Every other transaction still
+    /// executes, so `txs` run as if only the skipped ones had never been
+    /// included. Returns the outcome of `txs` in block order
+    async fn replay_txs_without(
+        &self,
+        block_number: u64,
+        skip: Vec<TxHash>,
+        txs: Vec<TxHash>,
+    ) -> eyre::Result<Vec<SimulatedTx>>;
+
     // DB Access Methods
     async fn get_storage(
         &self,
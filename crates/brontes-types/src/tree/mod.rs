@@ -16,9 +16,11 @@ mod types;
 #[allow(unused_parens)]
 pub mod util;
 pub use util::*;
+pub mod reverted_tx;
 pub mod root;
 pub mod tx_info;
 pub use node::*;
+pub use reverted_tx::*;
 pub use root::*;
 pub use tx_info::*;
 pub mod search_args;
@@ -35,6 +37,8 @@ pub struct BlockTree<V: NormalizedAction> {
     pub header:               Header,
     pub priority_fee_std_dev: f64,
     pub avg_priority_fee:     f64,
+    /// Transactions that reverted, these don't have a root in the tree
+    pub reverted_txs:         Vec<RevertedTx>,
 }
 
 impl<V: NormalizedAction> BlockTree<V> {
@@ -44,13 +48,12 @@ impl<V: NormalizedAction> BlockTree<V> {
             header,
             priority_fee_std_dev: 0.0,
             avg_priority_fee: 0.0,
+            reverted_txs: vec![],
         }
     }
 
     pub fn tx_must_contain_action(&self, tx_hash: B256, f: impl Fn(&V) -> bool) -> Option<bool> {
-        self.tx_roots
-            .iter()
-            .find(|r| r.tx_hash == tx_hash)
+        self.find_root(tx_hash)
             .map(|root| root.tx_must_contain_action(f))
     }
 
@@ -94,25 +97,25 @@ impl<V: NormalizedAction> BlockTree<V> {
     }
 
     pub fn get_tx_info<DB: LibmdbxReader>(&self, tx_hash: B256, database: &DB) -> Option<TxInfo> {
-        self.tx_roots
-            .iter()
-            .find(|r| r.tx_hash == tx_hash)
-            .and_then(|root| {
-                root.get_tx_info(self.header.number, database)
-                    .map_err(|e| error!(block=%self.header.number,"Database Error: {}", e ))
-                    .ok()
-            })
+        self.find_root(tx_hash).and_then(|root| {
+            root.get_tx_info(self.header.number, database)
+                .map_err(|e| error!(block=%self.header.number,"Database Error: {}", e ))
+                .ok()
+        })
     }
 
     pub fn get_root(&self, tx_hash: B256) -> Option<&Root<V>> {
-        self.tx_roots.iter().find(|r| r.tx_hash == tx_hash)
+        self.find_root(tx_hash)
     }
 
     pub fn get_gas_details(&self, hash: B256) -> Option<&GasDetails> {
-        self.tx_roots
-            .iter()
-            .find(|h| h.tx_hash == hash)
-            .map(|root| &root.gas_details)
+        self.find_root(hash).map(|root| &root.gas_details)
+    }
+
+    /// User operations split out of a `handleOps` bundle are keyed by their
+    /// user op hash, so every hash resolves to a single root.
+    fn find_root(&self, tx_hash: B256) -> Option<&Root<V>> {
+        self.tx_roots.iter().find(|r| r.tx_hash == tx_hash)
     }
 
     pub fn get_prev_tx(&self, hash: B256) -> Option<B256> {
@@ -147,16 +150,20 @@ impl<V: NormalizedAction> BlockTree<V> {
             let mut priority_fees: Vec<f64> = Vec::new();
 
             for tx in &mut this.tx_roots {
+                tx.finalize();
+                // user operations are already accounted for by their bundle tx
+                if tx.is_user_op() {
+                    continue
+                }
+
                 let priority_fee = (tx.gas_details.effective_gas_price
                     - this.header.base_fee_per_gas.unwrap_or_default() as u128)
                     as f64;
                 priority_fees.push(priority_fee);
                 total_priority_fee += priority_fee;
-
-                tx.finalize();
             }
 
-            this.avg_priority_fee = total_priority_fee / this.tx_roots.len() as f64;
+            this.avg_priority_fee = total_priority_fee / priority_fees.len() as f64;
             let std_dev = priority_fees.population_std_dev();
             this.priority_fee_std_dev = std_dev;
         })
@@ -175,7 +182,7 @@ impl<V: NormalizedAction> BlockTree<V> {
         call: TreeSearchBuilder<V>,
     ) -> TreeIterator<V, std::vec::IntoIter<Vec<V>>> {
         self.run_in_span_ref(|this| {
-            if let Some(root) = this.tx_roots.iter().find(|r| r.tx_hash == hash) {
+            if let Some(root) = this.find_root(hash) {
                 TreeIterator::new(this.clone(), root.collect_spans(&call).into_iter())
             } else {
                 TreeIterator::new(this.clone(), vec![].into_iter())
@@ -220,7 +227,7 @@ impl<V: NormalizedAction> BlockTree<V> {
         call: TreeSearchBuilder<V>,
     ) -> TreeIterator<V, std::vec::IntoIter<V>> {
         self.run_in_span_ref(|this| {
-            if let Some(root) = this.tx_roots.iter().find(|r| r.tx_hash == *hash) {
+            if let Some(root) = this.find_root(*hash) {
                 TreeIterator::new(this.clone(), root.collect(&call).into_iter())
             } else {
                 TreeIterator::new(this.clone(), vec![].into_iter())
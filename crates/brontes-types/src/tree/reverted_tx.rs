@@ -0,0 +1,46 @@
+use alloy_primitives::Address;
+use itertools::Itertools;
+use reth_primitives::B256;
+
+use super::GasDetails;
+use crate::structured_trace::{TraceActions, TxTrace};
+
+/// A transaction whose top level call reverted. These aren't classified, so
+/// only the addresses the transaction called into are kept.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RevertedTx {
+    pub tx_hash:     B256,
+    pub tx_index:    u64,
+    pub from:        Address,
+    pub to:          Address,
+    pub gas_details: GasDetails,
+    /// Every address called during the transaction, including the calls that
+    /// were unwound by the revert
+    pub touched:     Vec<Address>,
+}
+
+impl RevertedTx {
+    pub fn from_trace(trace: &TxTrace, base_fee: u128) -> Option<Self> {
+        let root = trace.trace.first()?;
+
+        Some(Self {
+            tx_hash:     trace.tx_hash,
+            tx_index:    trace.tx_index,
+            from:        root.get_from_addr(),
+            to:          root.get_to_address(),
+            gas_details: GasDetails {
+                coinbase_transfer:   None,
+                priority_fee:        trace.effective_price.saturating_sub(base_fee),
+                gas_used:            trace.gas_used,
+                effective_gas_price: trace.effective_price,
+            },
+            touched:     trace
+                .trace
+                .iter()
+                .map(|call| call.get_to_address())
+                .filter(|address| *address != Address::ZERO)
+                .unique()
+                .collect(),
+        })
+    }
+}
@@ -1,6 +1,6 @@
 use std::{fmt, fmt::Display};
 
-use alloy_primitives::TxHash;
+use alloy_primitives::{TxHash, U256};
 use clickhouse::Row;
 use colored::Colorize;
 use itertools::Itertools;
@@ -52,7 +52,13 @@ impl<V: NormalizedAction> NodeData<V> {
 #[derive(Debug, Clone)]
 pub struct Root<V: NormalizedAction> {
     pub head: Node,
+    /// the on chain tx index. User operations split out of a `handleOps`
+    /// bundle share the index of the bundle, they are told apart by
+    /// [`UserOperationDetails::op_index`]
     pub position: usize,
+    /// the on chain tx hash, or the user op hash for a user operation split
+    /// out of a `handleOps` bundle so each operation can be looked up on its
+    /// own
     pub tx_hash: B256,
     pub private: bool,
     pub gas_details: GasDetails,
@@ -60,6 +66,9 @@ pub struct Root<V: NormalizedAction> {
     /// eth transfers
     pub total_msg_value_transfers: Vec<NormalizedEthTransfer>,
     pub data_store: NodeData<V>,
+    /// set when this root is a virtual sub-transaction that was split out of
+    /// an ERC-4337 `handleOps` bundle
+    pub user_op: Option<UserOperationDetails>,
 }
 
 impl<V: NormalizedAction> Root<V> {
@@ -184,6 +193,7 @@ impl<V: NormalizedAction> Root<V> {
                 searcher_eoa_info,
                 None,
                 self.total_msg_value_transfers.clone(),
+                self.user_op,
             ))
         }
 
@@ -202,6 +212,7 @@ impl<V: NormalizedAction> Root<V> {
             searcher_eoa_info,
             searcher_contract_info,
             self.total_msg_value_transfers.clone(),
+            self.user_op,
         ))
     }
 
@@ -289,8 +300,20 @@ impl<V: NormalizedAction> Root<V> {
         self.private
     }
 
+    pub fn is_user_op(&self) -> bool {
+        self.user_op.is_some()
+    }
+
+    /// The hash of the transaction that landed on chain, the bundle tx for a
+    /// user operation
+    pub fn on_chain_tx_hash(&self) -> B256 {
+        self.user_op
+            .map(|op| op.bundle_tx_hash)
+            .unwrap_or(self.tx_hash)
+    }
+
     pub fn label_private_tx(&mut self, metadata: &Metadata) {
-        if metadata.private_flow.contains(&self.tx_hash) {
+        if metadata.private_flow.contains(&self.on_chain_tx_hash()) {
             self.private = true;
         }
     }
@@ -358,6 +381,25 @@ impl Display for GasDetails {
 
 self_convert_redefined!(GasDetails);
 
+/// Details of an ERC-4337 user operation that was executed as part of a
+/// bundler's `EntryPoint.handleOps` transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UserOperationDetails {
+    pub user_op_hash:   B256,
+    /// the smart account that originated the user operation
+    pub sender:         Address,
+    pub paymaster:      Address,
+    pub nonce:          U256,
+    pub entry_point:    Address,
+    /// the eoa that submitted the `handleOps` transaction
+    pub bundler:        Address,
+    /// hash of the `handleOps` transaction
+    pub bundle_tx_hash: B256,
+    /// index of the user operation among the executed operations of the
+    /// bundle
+    pub op_index:       usize,
+}
+
 impl GasDetails {
     pub fn gas_paid(&self) -> u128 {
         let mut gas = self.gas_used * self.effective_gas_price;
@@ -4,7 +4,7 @@ use crate::{
     db::{address_metadata::ContractType, searcher::SearcherInfo},
     mev::MevType,
     normalized_actions::NormalizedEthTransfer,
-    FastHashSet, GasDetails,
+    FastHashSet, GasDetails, UserOperationDetails,
 };
 
 #[derive(Debug, Clone)]
@@ -26,6 +26,10 @@ pub struct TxInfo {
     pub searcher_eoa_info:      Option<SearcherInfo>,
     pub searcher_contract_info: Option<SearcherInfo>,
     pub total_eth_value:        Vec<NormalizedEthTransfer>,
+    /// set when the tx is a user operation that was split out of a
+    /// `handleOps` bundle. In that case `eoa` is the smart account sender,
+    /// `tx_hash` is the user op hash and `tx_index` the index of the bundle
+    pub user_op:                Option<UserOperationDetails>,
 }
 
 impl TxInfo {
@@ -45,6 +49,7 @@ impl TxInfo {
         searcher_eoa_info: Option<SearcherInfo>,
         searcher_contract_info: Option<SearcherInfo>,
         total_eth_value: Vec<NormalizedEthTransfer>,
+        user_op: Option<UserOperationDetails>,
     ) -> Self {
         Self {
             total_eth_value,
@@ -61,6 +66,7 @@ impl TxInfo {
             is_verified_contract,
             searcher_eoa_info,
             searcher_contract_info,
+            user_op,
         }
     }
 
@@ -153,6 +159,10 @@ impl TxInfo {
     pub fn is_cex_dex_call(&self) -> bool {
         self.is_cex_dex_call
     }
+
+    pub fn is_user_op(&self) -> bool {
+        self.user_op.is_some()
+    }
 }
 
 pub fn collect_address_set_for_accounting(tx_infos: &[TxInfo]) -> FastHashSet<Address> {
@@ -0,0 +1,140 @@
+//! Uniswap V2 forks declared in `config/uniswap_fork_config.toml`.
+//!
+//! Fork pools are discovered through their factory's `PairCreated` event and
+//! share the pair template of Uniswap V2, but not necessarily its fee. The
+//! config maps the factories of forks that charge another fee onto the fee
+//! their pairs charge, which pricing takes off the mid price of the pair.
+//! Pairs of factories that aren't configured are priced at the mid price, the
+//! same as the pairs of the template.
+use std::{
+    path::{Path, PathBuf},
+    sync::OnceLock,
+};
+
+use alloy_primitives::Address;
+use serde::Deserialize;
+use tracing::error;
+
+use crate::FastHashMap;
+
+const UNISWAP_FORK_CONFIG_FILE: &str = "config/uniswap_fork_config.toml";
+
+/// Env var pointing at the fork config file, overrides the config file of the
+/// current directory
+pub const UNISWAP_FORK_CONFIG_ENV: &str = "BRONTES_UNISWAP_FORK_CONFIG";
+
+static UNISWAP_FORKS: OnceLock<UniswapForkConfig> = OnceLock::new();
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct UniswapFork {
+    pub name:    String,
+    pub factory: Address,
+    /// Fee charged on the amount in, in bps
+    pub fee_bps: u32,
+}
+
+#[derive(Debug, Default)]
+pub struct UniswapForkConfig {
+    forks: FastHashMap<Address, UniswapFork>,
+}
+
+impl UniswapForkConfig {
+    /// Loads the forks from the given file. Has to be called before the first
+    /// [`UniswapForkConfig::get`] to take effect.
+    pub fn init(config_path: &Path) -> eyre::Result<()> {
+        UNISWAP_FORKS
+            .set(Self::load(config_path)?)
+            .map_err(|_| eyre::eyre!("uniswap fork config is already loaded"))
+    }
+
+    /// The forks set through [`UniswapForkConfig::init`], otherwise loaded
+    /// from `$BRONTES_UNISWAP_FORK_CONFIG` or `config/uniswap_fork_config.toml`
+    /// of the current directory. Failing to load the file logs an error and
+    /// prices the pairs of every fork at the mid price.
+    pub fn get() -> &'static Self {
+        UNISWAP_FORKS.get_or_init(|| {
+            let config_path = std::env::var_os(UNISWAP_FORK_CONFIG_ENV)
+                .map(PathBuf::from)
+                .unwrap_or_else(|| PathBuf::from(UNISWAP_FORK_CONFIG_FILE));
+
+            Self::load(&config_path).unwrap_or_else(|e| {
+                error!(
+                    target: "brontes_types::uniswap_forks", error=%e, path=?config_path,
+                    "failed to load uniswap fork config"
+                );
+                Self::default()
+            })
+        })
+    }
+
+    pub fn load(config_path: &Path) -> eyre::Result<Self> {
+        Self::from_toml(&std::fs::read_to_string(config_path)?)
+    }
+
+    pub fn from_toml(config: &str) -> eyre::Result<Self> {
+        let config: UniswapForkToml = toml::from_str(config)?;
+
+        let forks = config
+            .fork
+            .into_iter()
+            .map(|fork| {
+                if fork.fee_bps >= 10_000 {
+                    eyre::bail!("fee of {} is over 100%: {} bps", fork.name, fork.fee_bps)
+                }
+
+                Ok((fork.factory, fork))
+            })
+            .collect::<eyre::Result<_>>()?;
+
+        Ok(Self { forks })
+    }
+
+    pub fn fork(&self, factory: &Address) -> Option<&UniswapFork> {
+        self.forks.get(factory)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct UniswapForkToml {
+    #[serde(default)]
+    fork: Vec<UniswapFork>,
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::hex;
+
+    use super::*;
+
+    #[test]
+    fn test_load_fork_config() {
+        let config = r#"
+            [[fork]]
+            name = "PancakeSwap"
+            factory = "0x1097053Fd2ea711dad45caCcc45EfF7548fCB362"
+            fee_bps = 25
+
+            [[fork]]
+            name = "LowFee"
+            factory = "0x0000000000000000000000000000000000000001"
+            fee_bps = 20
+        "#;
+        let forks = UniswapForkConfig::from_toml(config).unwrap();
+
+        let pancake = Address::new(hex!("1097053Fd2ea711dad45caCcc45EfF7548fCB362"));
+        assert_eq!(forks.fork(&pancake).unwrap().name, "PancakeSwap");
+        assert_eq!(forks.fork(&Address::with_last_byte(1)).unwrap().fee_bps, 20);
+        assert!(forks.fork(&Address::with_last_byte(2)).is_none());
+    }
+
+    #[test]
+    fn test_reject_fee_over_100_percent() {
+        let config = r#"
+            [[fork]]
+            name = "Broken"
+            factory = "0x0000000000000000000000000000000000000001"
+            fee_bps = 10000
+        "#;
+        assert!(UniswapForkConfig::from_toml(config).is_err());
+    }
+}
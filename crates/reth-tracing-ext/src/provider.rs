@@ -1,13 +1,18 @@
 use std::cmp::min;
 
 use alloy_rpc_types::AnyReceiptEnvelope;
-use brontes_types::{structured_trace::TxTrace, traits::TracingProvider};
+use brontes_types::{
+    structured_trace::TxTrace,
+    traits::{SimulatedTx, TracingProvider},
+};
 use eyre::eyre;
 use reth_primitives::{
-    Address, BlockId, BlockNumber, BlockNumberOrTag, Bytecode, Bytes, Header, StorageValue, TxHash,
-    B256, U256,
+    revm::env::tx_env_with_recovered, Address, BlockHashOrNumber, BlockId, BlockNumber,
+    BlockNumberOrTag, Bytecode, Bytes, Header, StorageValue, TxHash, B256, U256,
+};
+use reth_provider::{
+    BlockIdReader, BlockNumReader, BlockReader, HeaderProvider, TransactionVariant,
 };
-use reth_provider::{BlockIdReader, BlockNumReader, HeaderProvider};
 use reth_revm::{database::StateProviderDatabase, db::CacheDB};
 use reth_rpc::eth::{
     error::{EthApiError, EthResult, RevertError, RpcInvalidTransactionError},
@@ -21,7 +26,7 @@ use revm::{
     primitives::{
         db::DatabaseRef, BlockEnv, CfgEnvWithHandlerCfg, EnvWithHandlerCfg, TransactTo, TxEnv,
     },
-    Database,
+    Database, DatabaseCommit,
 };
 use revm_primitives::ExecutionResult;
 
@@ -107,6 +112,77 @@ impl TracingProvider for TracingClient {
         Ok((tx.block_number.unwrap(), tx.transaction_index.unwrap() as usize))
     }
 
+    async fn replay_txs_without(
+        &self,
+        block_number: u64,
+        skip: Vec<TxHash>,
+        txs: Vec<TxHash>,
+    ) -> eyre::Result<Vec<SimulatedTx>> {
+        let block = self
+            .trace
+            .provider()
+            .block_with_senders(
+                BlockHashOrNumber::Number(block_number),
+                TransactionVariant::WithHash,
+            )?
+            .ok_or_else(|| eyre!("no block found for {block_number}"))?;
+
+        let (cfg, block_env, _) = self
+            .api
+            .evm_env_at(BlockId::Number(BlockNumberOrTag::Number(block_number)))
+            .await?;
+        let state = self
+            .api
+            .state_at(BlockId::Number(BlockNumberOrTag::Number(block_number - 1)))?;
+        let mut db = CacheDB::new(StateProviderDatabase::new(state));
+
+        let mut simulated = Vec::with_capacity(txs.len());
+        for tx in block.into_transactions_ecrecovered() {
+            if simulated.len() == txs.len() {
+                break
+            }
+            if skip.contains(&tx.hash()) {
+                continue
+            }
+
+            let env = EnvWithHandlerCfg::new_with_cfg_env(
+                cfg.clone(),
+                block_env.clone(),
+                tx_env_with_recovered(&tx),
+            );
+            // without the skipped txs a later one can become invalid, e.g. its
+            // nonce or balance came from a frontrun. It is left out of the
+            // replay instead of aborting it, a victim is recorded as failed
+            let res = match self.api.transact(&mut db, env) {
+                Ok((res, _)) => res,
+                Err(e) => {
+                    tracing::debug!(tx_hash=?tx.hash(), err=%e, "tx is invalid without the skipped txs");
+                    if txs.contains(&tx.hash()) {
+                        simulated.push(SimulatedTx {
+                            tx_hash:  tx.hash(),
+                            success:  false,
+                            gas_used: 0,
+                            logs:     vec![],
+                        });
+                    }
+                    continue
+                }
+            };
+            db.commit(res.state);
+
+            if txs.contains(&tx.hash()) {
+                simulated.push(SimulatedTx {
+                    tx_hash:  tx.hash(),
+                    success:  res.result.is_success(),
+                    gas_used: res.result.gas_used(),
+                    logs:     res.result.into_logs(),
+                });
+            }
+        }
+
+        Ok(simulated)
+    }
+
     async fn header_by_number(&self, number: BlockNumber) -> eyre::Result<Option<Header>> {
         self.trace
             .provider()